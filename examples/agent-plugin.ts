@@ -0,0 +1,48 @@
+#!/usr/bin/env bun
+/**
+ * Reference agent plugin for `--agent plugin:examples/agent-plugin.ts` (see src/agents/plugin.ts).
+ *
+ * It answers `describe` and, for `execute`, writes the task prompt to NOTES.md in the
+ * workspace when the task grants `write`, without calling a model. A real plugin would run
+ * its agent where `act` is and report its metrics in the result.
+ */
+
+import { writeFileSync } from 'fs';
+import { join } from 'path';
+
+interface Request {
+  protocol: number;
+  type: 'describe' | 'execute';
+  task?: { id: string; prompt: string; max_iterations: number };
+  workspace?: string;
+  model?: string;
+  permissions?: { read: boolean; write: boolean; execute: boolean; network: boolean; approval: string };
+}
+
+function answer(message: object): void {
+  process.stdout.write(`${JSON.stringify(message)}\n`);
+}
+
+function act(request: Required<Request>): string {
+  if (!request.permissions.write) {
+    return `Not allowed to write; the task was: ${request.task.prompt}`;
+  }
+  writeFileSync(join(request.workspace, 'NOTES.md'), `# ${request.task.id}\n\n${request.task.prompt}\n`);
+  return 'Wrote the task to NOTES.md';
+}
+
+const input = await new Response(Bun.stdin.stream()).text();
+const request = JSON.parse(input) as Request;
+
+if (request.protocol !== 1) {
+  answer({ type: 'error', message: `Unsupported protocol version ${request.protocol}` });
+} else if (request.type === 'describe') {
+  answer({ name: 'reference-plugin', version: '1.0.0', capabilities: ['read', 'write'] });
+} else {
+  try {
+    const output = act(request as Required<Request>);
+    answer({ type: 'result', output, iterations: 1, usage: null, cost: null });
+  } catch (error) {
+    answer({ type: 'error', message: error instanceof Error ? error.message : String(error) });
+  }
+}
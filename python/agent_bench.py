@@ -0,0 +1,163 @@
+"""Python client for agent-bench, for notebooks and analysis scripts.
+
+agent-bench is a TypeScript/Bun program, so there is no native extension to bind to.
+This module talks to the REST API of ``agent-bench serve-api`` (builds with the
+``server`` feature) and returns tasks, runs, and results as Python objects, with no
+CLI calls or result files to parse::
+
+    from agent_bench import Client
+
+    bench = Client("http://127.0.0.1:8787")
+    tasks = bench.tasks(category="bug-fix")
+    run = bench.run(suite="bug-fix", agent="opencode@anthropic/claude-sonnet-4-5", yes=True)
+    finished = bench.wait(run["id"])
+    frame = bench.results_frame(run_id=finished["run_id"])  # needs pandas
+
+Only the standard library is required; ``results_frame`` needs pandas.
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import time
+from typing import Any, Dict, List, Optional
+from urllib.error import HTTPError
+from urllib.parse import urlencode
+from urllib.request import Request, urlopen
+
+__all__ = ["AgentBenchError", "Client"]
+
+DEFAULT_URL = "http://127.0.0.1:8787"
+"""Where ``serve-api`` listens by default."""
+
+FINISHED_STATUSES = ("completed", "failed")
+
+
+class AgentBenchError(Exception):
+    """An API request failed; ``status`` is the HTTP status (None if the server was unreachable)."""
+
+    def __init__(self, message: str, status: Optional[int] = None):
+        super().__init__(message)
+        self.status = status
+
+
+class Client:
+    """Client for one ``agent-bench serve-api`` server.
+
+    :param url: Server URL (default: ``AGENT_BENCH_API_URL``, else the local default)
+    :param token: Bearer token, if the server requires one (default: ``AGENT_BENCH_API_TOKEN``)
+    :param timeout: Seconds to wait for each request
+    """
+
+    def __init__(self, url: Optional[str] = None, token: Optional[str] = None, timeout: float = 30):
+        self.url = (url or os.environ.get("AGENT_BENCH_API_URL") or DEFAULT_URL).rstrip("/")
+        self.token = token if token is not None else os.environ.get("AGENT_BENCH_API_TOKEN")
+        self.timeout = timeout
+
+    def health(self) -> bool:
+        """Whether the server is up."""
+        try:
+            return self._request("GET", "/api/health").get("status") == "ok"
+        except AgentBenchError:
+            return False
+
+    def tasks(self, category: Optional[str] = None, tag: Optional[str] = None) -> List[Dict[str, Any]]:
+        """The task catalog (entries as in ``--format json list``), optionally by category or tag."""
+        tasks = self._request("GET", "/api/tasks")
+        return [
+            task
+            for task in tasks
+            if (category is None or task.get("category") == category)
+            and (tag is None or tag in task.get("tags", []))
+        ]
+
+    def run(
+        self,
+        task: Optional[str] = None,
+        suite: Optional[str] = None,
+        *,
+        filter: Optional[str] = None,
+        agent: Optional[str] = None,
+        model: Optional[str] = None,
+        skip_verify: bool = False,
+        fail_fast: Optional[bool] = None,
+        yes: bool = False,
+    ) -> Dict[str, Any]:
+        """Queue a run of one task or a suite (``"all"`` or a category); returns the queued run.
+
+        ``yes=True`` allows tasks that give the agent unrestricted shell access, like ``run --yes``.
+        """
+        if (task is None) == (suite is None):
+            raise ValueError("Specify exactly one of task or suite")
+        body: Dict[str, Any] = {"skip_verify": skip_verify, "yes": yes}
+        for key, value in (("task", task), ("suite", suite), ("filter", filter), ("agent", agent),
+                           ("model", model), ("fail_fast", fail_fast)):
+            if value is not None:
+                body[key] = value
+        return self._request("POST", "/api/runs", body)
+
+    def runs(self) -> List[Dict[str, Any]]:
+        """Runs started through this server, newest first."""
+        return self._request("GET", "/api/runs")
+
+    def get_run(self, run_id: str) -> Dict[str, Any]:
+        """A run's status, with its results once finished."""
+        return self._request("GET", f"/api/runs/{run_id}")
+
+    def wait(self, run_id: str, poll: float = 5, timeout: Optional[float] = None) -> Dict[str, Any]:
+        """Poll a run until it completes or fails, and return it.
+
+        :raises TimeoutError: If ``timeout`` seconds pass first
+        """
+        deadline = None if timeout is None else time.monotonic() + timeout
+        while True:
+            run = self.get_run(run_id)
+            if run["status"] in FINISHED_STATUSES:
+                return run
+            if deadline is not None and time.monotonic() >= deadline:
+                raise TimeoutError(f"Run {run_id} is still {run['status']}")
+            time.sleep(poll)
+
+    def results(
+        self,
+        task: Optional[str] = None,
+        agent: Optional[str] = None,
+        run_id: Optional[str] = None,
+        limit: Optional[int] = None,
+    ) -> List[Dict[str, Any]]:
+        """Stored results (``BenchmarkResult`` records, see results/README.md), oldest first."""
+        query = {key: value for key, value in (("task", task), ("agent", agent), ("run_id", run_id),
+                                               ("limit", limit)) if value is not None}
+        return self._request("GET", "/api/results" + (f"?{urlencode(query)}" if query else ""))
+
+    def results_frame(self, **query: Any):
+        """Stored results as a pandas DataFrame (same filters as ``results``), with a parsed ``timestamp``."""
+        import pandas as pd
+
+        frame = pd.DataFrame(self.results(**query))
+        if not frame.empty:
+            frame["timestamp"] = pd.to_datetime(frame["timestamp"])
+        return frame
+
+    def _request(self, method: str, path: str, body: Optional[Dict[str, Any]] = None) -> Any:
+        headers = {"Accept": "application/json"}
+        data = None
+        if body is not None:
+            data = json.dumps(body).encode("utf-8")
+            headers["Content-Type"] = "application/json"
+        if self.token:
+            headers["Authorization"] = f"Bearer {self.token}"
+
+        request = Request(self.url + path, data=data, headers=headers, method=method)
+        try:
+            with urlopen(request, timeout=self.timeout) as response:
+                return json.loads(response.read().decode("utf-8"))
+        except HTTPError as error:
+            try:
+                message = json.loads(error.read().decode("utf-8")).get("error", error.reason)
+            except ValueError:
+                message = error.reason
+            raise AgentBenchError(f"{method} {path}: {message}", error.code) from None
+        except OSError as error:
+            raise AgentBenchError(f"Cannot reach agent-bench at {self.url}: {error}") from None
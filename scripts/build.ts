@@ -0,0 +1,62 @@
+/**
+ * Build dist/ with a chosen set of optional features (see src/features.ts).
+ *
+ *   bun run scripts/build.ts                          # every feature
+ *   bun run scripts/build.ts --features sql           # only the listed features
+ *   bun run scripts/build.ts --no-default-features    # local JSON workflow only
+ *
+ * Modules of disabled features are replaced with empty stubs, so their code is not bundled.
+ */
+
+import { parseArgs } from 'util';
+import type { BunPlugin } from 'bun';
+import { FEATURES, type Feature } from '../src/features.js';
+
+/**
+ * Modules that only a feature uses (always loaded through guarded dynamic imports).
+ */
+const FEATURE_MODULES: Record<Feature, RegExp> = {
+  sql: /[\\/]storage[\\/](sqlite|postgres)\.(js|ts)$/,
+  server: /[\\/]server[\\/](api|worker|coordinator|http)\.(js|ts)$/,
+};
+
+const { values } = parseArgs({
+  options: {
+    features: { type: 'string' },
+    'no-default-features': { type: 'boolean', default: false },
+  },
+});
+
+const requested = values.features?.split(',').map(feature => feature.trim()).filter(Boolean)
+  ?? (values['no-default-features'] ? [] : [...FEATURES]);
+const unknown = requested.filter(feature => !(FEATURES as readonly string[]).includes(feature));
+if (unknown.length > 0) {
+  console.error(`Unknown features: ${unknown.join(', ')} (available: ${FEATURES.join(', ')})`);
+  process.exit(1);
+}
+const disabled = FEATURES.filter(feature => !requested.includes(feature));
+
+const stubDisabledFeatures: BunPlugin = {
+  name: 'stub-disabled-features',
+  setup(build) {
+    for (const feature of disabled) {
+      build.onLoad({ filter: FEATURE_MODULES[feature] }, () => ({ contents: 'export {};', loader: 'js' }));
+    }
+  },
+};
+
+const result = await Bun.build({
+  entrypoints: ['src/index.ts', 'src/lib.ts'],
+  outdir: 'dist',
+  target: 'bun',
+  define: { AGENT_BENCH_FEATURES: JSON.stringify(requested.join(',')) },
+  plugins: [stubDisabledFeatures],
+});
+
+if (!result.success) {
+  for (const log of result.logs) {
+    console.error(log);
+  }
+  process.exit(1);
+}
+console.log(`Built dist/ with features: ${requested.length > 0 ? requested.join(', ') : '(none)'}`);
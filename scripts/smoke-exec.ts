@@ -0,0 +1,58 @@
+/**
+ * Cross-platform smoke check of process execution (run in CI on Linux, macOS, and Windows):
+ * PATH lookup of a shim script, argument quoting, shell commands, output spooling, and timeout kills.
+ *
+ *   bun run scripts/smoke-exec.ts
+ */
+
+import { statSync } from 'fs';
+import { chmod, mkdtemp, rm, writeFile } from 'fs/promises';
+import { delimiter, join } from 'path';
+import { tmpdir } from 'os';
+import { exec, findExecutable } from '../src/utils/exec.js';
+
+const isWindows = process.platform === 'win32';
+const failures: string[] = [];
+
+function check(name: string, ok: boolean, detail: string): void {
+  console.log(`${ok ? 'ok  ' : 'FAIL'} ${name}${ok ? '' : `: ${detail}`}`);
+  if (!ok) failures.push(name);
+}
+
+const dir = await mkdtemp(join(tmpdir(), 'agent-bench-smoke-'));
+try {
+  // An agent CLI installed by npm is a .cmd shim on Windows and a script elsewhere
+  if (isWindows) {
+    await writeFile(join(dir, 'smoke-shim.cmd'), '@echo off\r\necho [%~1] [%~2]\r\n');
+  } else {
+    await writeFile(join(dir, 'smoke-shim'), '#!/bin/sh\necho "[$1] [$2]"\n');
+    await chmod(join(dir, 'smoke-shim'), 0o755);
+  }
+  process.env.PATH = `${dir}${delimiter}${process.env.PATH ?? ''}`;
+
+  check('find shim on PATH', findExecutable('smoke-shim') !== null, 'not found');
+
+  const shim = await exec('smoke-shim "two words" plain');
+  check('run shim with quoted arguments', shim.stdout.trim() === '[two words] [plain]', JSON.stringify(shim));
+
+  const shell = await exec('echo one&& echo two', { shell: true });
+  check('shell command', shell.stdout.replace(/\r/g, '').trim() === 'one\ntwo', JSON.stringify(shell));
+
+  const noisy = await exec({ program: process.execPath, args: ['-e', "process.stdout.write('x'.repeat(100000))"] }, { maxOutputBytes: 1000 });
+  check(
+    'large output is spooled to a file',
+    noisy.stdoutFile !== null && noisy.stdout.length < 2000 && statSync(noisy.stdoutFile).size === 100000,
+    JSON.stringify({ ...noisy, stdout: noisy.stdout.length })
+  );
+
+  const sleeper = isWindows ? 'powershell -NoProfile -Command "Start-Sleep 30"' : 'sleep 30';
+  const slow = await exec(sleeper, { timeoutSecs: 1 });
+  check('timeout kills the process', slow.timedOut && slow.durationSecs < 15, JSON.stringify(slow));
+} finally {
+  await rm(dir, { recursive: true, force: true });
+}
+
+if (failures.length > 0) {
+  console.error(`${failures.length} exec check(s) failed`);
+  process.exit(1);
+}
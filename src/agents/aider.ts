@@ -0,0 +1,134 @@
+/**
+ * Aider CLI agent adapter.
+ *
+ * Runs `aider --message <prompt>` once per task. Aider's own git commits are turned off
+ * (`--no-auto-commits`, `--no-dirty-commits`) so the agent's changes stay uncommitted in
+ * the workspace, where the runner diffs them, and its chat and input history go to the
+ * execution's scratch directory instead of the workspace. Task permissions map onto
+ * aider's flags:
+ *
+ *   approval auto / bypass  →  --yes-always (confirm every edit and shell command)
+ *   write: false            →  --dry-run (propose edits without applying them)
+ *   execute: false          →  --no-suggest-shell-commands
+ *   network: false          →  --no-detect-urls (no scraping of URLs in the prompt)
+ *
+ * With approval `ask` nothing is confirmed up front, and aider's prompts go unanswered
+ * (stdin is closed). Metrics come from aider's per-reply `Tokens: ... Cost: ...` lines.
+ */
+
+import { homedir } from 'os';
+import { join } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import type { ExecResult } from '../utils/exec.js';
+import { emptyUsage } from '../evaluator/pricing.js';
+import type { Capabilities } from './permissions.js';
+import { CliAgent, parseTokenCount, type CliCommand, type CliOutput } from './cli.js';
+
+/**
+ * A reply's usage line, e.g.
+ * `Tokens: 4.2k sent, 1.5k cache write, 2.0k cache hit, 312 received. Cost: $0.02 message, $0.05 session.`
+ */
+const TOKENS_LINE = /^Tokens: (.+?)\.(?: Cost: \$([\d.]+) message.*)?$/;
+const TOKEN_PART = /^([\d.,]+[kKmM]?) (sent|received|cache write|cache hit)$/;
+
+/**
+ * Parse aider's output: its replies, and usage summed over its `Tokens:` lines.
+ */
+export function parseAiderOutput(stdout: string): CliOutput {
+  const usage = emptyUsage();
+  let iterations = 0;
+  let cost: number | null = null;
+  const output: string[] = [];
+
+  for (const line of stdout.split('\n')) {
+    const match = line.trim().match(TOKENS_LINE);
+    if (!match) {
+      output.push(line);
+      continue;
+    }
+    iterations++;
+    for (const part of match[1].split(', ')) {
+      const token = part.trim().match(TOKEN_PART);
+      if (!token) continue;
+      const count = parseTokenCount(token[1]);
+      switch (token[2]) {
+        case 'sent':
+          usage.input += count;
+          break;
+        case 'received':
+          usage.output += count;
+          break;
+        case 'cache write':
+          usage.cache_write += count;
+          break;
+        case 'cache hit':
+          usage.cache_read += count;
+          break;
+      }
+    }
+    if (match[2]) {
+      cost = (cost ?? 0) + Number(match[2]);
+    }
+  }
+
+  return { output: output.join('\n').trim(), iterations, usage: iterations > 0 ? usage : null, cost };
+}
+
+/**
+ * Aider CLI adapter.
+ */
+export class AiderAgent extends CliAgent {
+  protected readonly binary = 'aider';
+  protected readonly envVars = ['AIDER_*'];
+
+  name(): string {
+    return 'aider';
+  }
+
+  capabilities(): AgentCapability[] {
+    return ['read', 'write', 'bash', 'web', 'token_reporting'];
+  }
+
+  /**
+   * `~/.aider` (model metadata caches, analytics ID).
+   */
+  protected stateDirs(): string[] {
+    return [join(homedir(), '.aider')];
+  }
+
+  protected buildCommand(task: Task, _workspace: string, capabilities: Capabilities, scratch: string): CliCommand {
+    const args = [
+      '--message', task.prompt,
+      '--model', this.model,
+      '--no-auto-commits',
+      '--no-dirty-commits',
+      '--no-gitignore',
+      '--no-check-update',
+      '--no-show-model-warnings',
+      '--analytics-disable',
+      '--no-pretty',
+      '--no-stream',
+      '--no-fancy-input',
+      '--chat-history-file', join(scratch, 'chat.history.md'),
+      '--input-history-file', join(scratch, 'input.history'),
+    ];
+    if (capabilities.approval !== 'ask') {
+      args.push('--yes-always');
+    }
+    if (!capabilities.write) {
+      args.push('--dry-run');
+    }
+    if (!capabilities.execute) {
+      args.push('--no-suggest-shell-commands');
+    }
+    if (!capabilities.network) {
+      args.push('--no-detect-urls');
+    }
+    args.push(...this.extraArgs);
+    return { program: 'aider', args };
+  }
+
+  protected async parseOutput(result: ExecResult): Promise<CliOutput> {
+    return parseAiderOutput(await this.fullStdout(result));
+  }
+}
@@ -0,0 +1,490 @@
+/**
+ * OpenAI-compatible API agent.
+ *
+ * Talks to a chat completions endpoint directly and runs its own tool loop in the
+ * workspace, so base models can be benchmarked without a vendor CLI. Each model turn is
+ * one iteration: the model gets the tools the task's permissions allow, the harness
+ * executes its tool calls and sends back their results, and the loop ends when the model
+ * answers without calling a tool (or the iteration limit is reached).
+ *
+ *   read_file      read      a file in the workspace
+ *   write_file     write     create or overwrite a file in the workspace
+ *   run_command    execute   a shell command in the workspace (in the configured sandbox)
+ *
+ * Paths outside the workspace are refused. The harness performs the calls itself, so
+ * approval policies do not apply, and `network` is not enforced beyond the sandbox.
+ *
+ * The endpoint is `<PROVIDER>_BASE_URL` (default: the provider's public API, see
+ * `API_BASE_URLS`) and the key `<PROVIDER>_API_KEY`, both read from the agent environment
+ * (`[agent] env` and the leased credential): `api@openrouter/qwen/qwen3-coder` uses
+ * `OPENROUTER_API_KEY`, and `api@local/<model>` with `LOCAL_BASE_URL` any local server.
+ */
+
+import { mkdir, readFile, writeFile } from 'fs/promises';
+import { dirname, relative } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import { emptyUsage, type TokenUsage } from '../evaluator/pricing.js';
+import { AgentError, BenchError, TimeoutError } from '../utils/errors.js';
+import { startProcess, type RunningProcess } from '../utils/exec.js';
+import { resolveWithin } from '../utils/files.js';
+import { logger } from '../utils/logger.js';
+import { disposeSpoolFiles } from '../utils/spool.js';
+import { sandboxCommand } from '../sandbox/index.js';
+import { agentEnvironment, providerEnvVars } from './env.js';
+import { resolveCapabilities, type Capabilities } from './permissions.js';
+import type { Agent, AgentInvocation, AgentResult, IterationListener, StepCallback } from './types.js';
+import { DEFAULT_MAX_ITERATIONS } from './types.js';
+
+/**
+ * Public endpoints of providers with an OpenAI-compatible API.
+ */
+export const API_BASE_URLS: Record<string, string> = {
+  anthropic: 'https://api.anthropic.com/v1',
+  openai: 'https://api.openai.com/v1',
+  openrouter: 'https://openrouter.ai/api/v1',
+  deepseek: 'https://api.deepseek.com/v1',
+  xai: 'https://api.x.ai/v1',
+  groq: 'https://api.groq.com/openai/v1',
+  mistral: 'https://api.mistral.ai/v1',
+  together: 'https://api.together.xyz/v1',
+  ollama: 'http://localhost:11434/v1',
+};
+
+/**
+ * Seconds a `run_command` call may take (less if the agent timeout is closer).
+ */
+export const COMMAND_TIMEOUT_SECS = 120;
+
+/**
+ * Characters of a tool result sent back to the model; longer results keep their start and end.
+ */
+const MAX_TOOL_OUTPUT = 16_000;
+
+const SYSTEM_PROMPT = [
+  'You are a coding agent working in a repository checkout.',
+  'Use the tools to inspect and change files; paths are relative to the repository root.',
+  'When the task is done, reply with a short summary and no tool calls.',
+].join(' ');
+
+/**
+ * A chat message in the OpenAI format.
+ */
+interface ChatMessage {
+  role: 'system' | 'user' | 'assistant' | 'tool';
+  content: string | null;
+  tool_calls?: ToolCall[];
+  tool_call_id?: string;
+}
+
+interface ToolCall {
+  id: string;
+  type: 'function';
+  function: { name: string; arguments: string };
+}
+
+/**
+ * The parts of a chat completion the agent uses.
+ */
+interface ChatCompletion {
+  choices?: { message?: ChatMessage }[];
+  usage?: {
+    prompt_tokens?: number;
+    completion_tokens?: number;
+    prompt_tokens_details?: { cached_tokens?: number };
+  };
+}
+
+/**
+ * Tool definitions, by the capability that enables them.
+ */
+const TOOLS: { capability: keyof Pick<Capabilities, 'read' | 'write' | 'execute'>; definition: object }[] = [
+  {
+    capability: 'read',
+    definition: {
+      type: 'function',
+      function: {
+        name: 'read_file',
+        description: 'Read a text file',
+        parameters: {
+          type: 'object',
+          properties: { path: { type: 'string', description: 'Path relative to the repository root' } },
+          required: ['path'],
+        },
+      },
+    },
+  },
+  {
+    capability: 'write',
+    definition: {
+      type: 'function',
+      function: {
+        name: 'write_file',
+        description: 'Create or overwrite a text file with the given content',
+        parameters: {
+          type: 'object',
+          properties: {
+            path: { type: 'string', description: 'Path relative to the repository root' },
+            content: { type: 'string', description: 'The complete new content of the file' },
+          },
+          required: ['path', 'content'],
+        },
+      },
+    },
+  },
+  {
+    capability: 'execute',
+    definition: {
+      type: 'function',
+      function: {
+        name: 'run_command',
+        description: 'Run a shell command in the repository root and return its exit code and output',
+        parameters: {
+          type: 'object',
+          properties: { command: { type: 'string' } },
+          required: ['command'],
+        },
+      },
+    },
+  },
+];
+
+/**
+ * Endpoint and key for a provider.
+ * @param env Agent environment to read `<PROVIDER>_BASE_URL` and `<PROVIDER>_API_KEY` from
+ * @throws AgentError If the provider has no known endpoint and no base URL is set
+ */
+export function resolveApiEndpoint(provider: string, env: Record<string, string | undefined>): { baseUrl: string; apiKey: string | null } {
+  const prefix = provider.toUpperCase().replace(/[^A-Z0-9]/g, '_');
+  const baseUrl = env[`${prefix}_BASE_URL`] || API_BASE_URLS[provider];
+  if (!baseUrl) {
+    throw new AgentError(`No endpoint for provider "${provider}": set ${prefix}_BASE_URL (and pass it in [agent] env)`, { phase: 'agent' });
+  }
+  return { baseUrl: baseUrl.replace(/\/+$/, ''), apiKey: env[`${prefix}_API_KEY`] || null };
+}
+
+/**
+ * Shorten a tool result to its start and end.
+ */
+function truncate(text: string): string {
+  if (text.length <= MAX_TOOL_OUTPUT) {
+    return text;
+  }
+  const half = MAX_TOOL_OUTPUT / 2;
+  return `${text.slice(0, half)}\n[... ${text.length - MAX_TOOL_OUTPUT} characters omitted ...]\n${text.slice(-half)}`;
+}
+
+/**
+ * State of one execution, across its steps.
+ */
+interface Conversation {
+  task: Task;
+  workspace: string;
+  capabilities: Capabilities;
+  messages: ChatMessage[];
+  iterations: number;
+  usage: TokenUsage;
+  output: string;
+  startTime: number;
+  /** Epoch ms when the agent timeout elapses (null: no timeout) */
+  deadline: number | null;
+}
+
+/**
+ * Agent running its own tool loop against an OpenAI-compatible endpoint.
+ */
+export class ApiAgent implements Agent {
+  private provider: string;
+  private model: string;
+  private maxIterations: number;
+  private iterationListener: IterationListener | null = null;
+  private abort: AbortController | null = null;
+  private running: RunningProcess | null = null;
+
+  /**
+   * @param model Model as `provider/model`; the model ID may contain further slashes (`openrouter/qwen/qwen3-coder`)
+   * @param maxIterations Iteration limit for tasks that do not set `max_iterations`
+   */
+  constructor(model: string, maxIterations: number = DEFAULT_MAX_ITERATIONS) {
+    const slash = model.indexOf('/');
+    if (slash <= 0 || slash === model.length - 1) {
+      throw new AgentError(`Invalid model format: ${model}. Expected "provider/model"`);
+    }
+    this.provider = model.slice(0, slash);
+    this.model = model.slice(slash + 1);
+    this.maxIterations = maxIterations;
+  }
+
+  name(): string {
+    return 'api';
+  }
+
+  capabilities(): AgentCapability[] {
+    return ['read', 'write', 'bash', 'multi_turn', 'token_reporting'];
+  }
+
+  setIterationListener(listener: IterationListener | null): void {
+    this.iterationListener = listener;
+  }
+
+  /**
+   * Stop the running execution: the pending request is aborted and a running command killed.
+   */
+  async cancel(): Promise<void> {
+    if (!this.abort) {
+      return;
+    }
+    logger.debug('Cancelling API agent...');
+    this.abort.abort();
+    await this.running?.kill();
+  }
+
+  async describeInvocation(task: Task): Promise<AgentInvocation> {
+    const { baseUrl } = resolveApiEndpoint(this.provider, agentEnvironment(providerEnvVars(this.provider)));
+    return {
+      argv: null,
+      model: `${this.provider}/${this.model}`,
+      parameters: {
+        base_url: baseUrl,
+        max_iterations: task.max_iterations ?? this.maxIterations,
+        agent_timeout: task.agent_timeout ?? null,
+        tools: TOOLS
+          .filter(tool => resolveCapabilities(task.permissions)[tool.capability])
+          .map(tool => (tool.definition as { function: { name: string } }).function.name),
+      },
+    };
+  }
+
+  async execute(task: Task, workspace: string): Promise<AgentResult> {
+    return await this.executeSteps(task, workspace, [task.prompt], async () => true);
+  }
+
+  async executeSteps(task: Task, workspace: string, prompts: string[], onStep: StepCallback): Promise<AgentResult> {
+    const conversation: Conversation = {
+      task,
+      workspace,
+      capabilities: resolveCapabilities(task.permissions),
+      messages: [{ role: 'system', content: SYSTEM_PROMPT }],
+      iterations: 0,
+      usage: emptyUsage(),
+      output: '',
+      startTime: Date.now(),
+      deadline: task.agent_timeout ? Date.now() + task.agent_timeout * 1000 : null,
+    };
+    const maxIterations = task.max_iterations ?? this.maxIterations;
+    this.abort = new AbortController();
+
+    try {
+      for (let step = 0; step < prompts.length; step++) {
+        conversation.messages.push({ role: 'user', content: prompts[step] });
+        const finished = await this.converse(conversation, maxIterations);
+        if (this.abort.signal.aborted) {
+          logger.warn('Stopping: execution cancelled');
+          break;
+        }
+        if (!finished) {
+          logger.warn(`Stopping: iteration limit of ${maxIterations} reached`);
+          break;
+        }
+        if (!(await onStep(step, this.buildResult(conversation)))) {
+          logger.debug(`Stopping after step ${step + 1}`);
+          break;
+        }
+      }
+      return this.buildResult(conversation);
+    } catch (error) {
+      if (error instanceof BenchError) {
+        throw error;
+      }
+      throw new AgentError(`API agent execution failed: ${error}`, { taskId: task.id, phase: 'agent' });
+    } finally {
+      this.abort = null;
+      this.running = null;
+    }
+  }
+
+  /**
+   * Run model turns until the model stops calling tools.
+   * @returns Whether the model finished (false: the iteration limit was reached or the execution cancelled)
+   */
+  private async converse(conversation: Conversation, maxIterations: number): Promise<boolean> {
+    const tools = TOOLS.filter(tool => conversation.capabilities[tool.capability]).map(tool => tool.definition);
+
+    while (conversation.iterations < maxIterations) {
+      const completion = await this.complete(conversation, tools);
+      if (!completion) {
+        return false;
+      }
+      conversation.iterations++;
+      const cached = completion.usage?.prompt_tokens_details?.cached_tokens ?? 0;
+      conversation.usage.input += (completion.usage?.prompt_tokens ?? 0) - cached;
+      conversation.usage.cache_read += cached;
+      conversation.usage.output += completion.usage?.completion_tokens ?? 0;
+      this.iterationListener?.({
+        iteration: conversation.iterations,
+        tokensUsed: conversation.usage.input + conversation.usage.output,
+        cost: null,
+      });
+
+      const message = completion.choices?.[0]?.message;
+      if (!message) {
+        throw new AgentError('API returned no message', { taskId: conversation.task.id, phase: 'agent' });
+      }
+      conversation.messages.push({ role: 'assistant', content: message.content ?? null, tool_calls: message.tool_calls });
+      if (message.content) {
+        conversation.output = message.content;
+      }
+      if (!message.tool_calls?.length) {
+        return true;
+      }
+
+      for (const call of message.tool_calls) {
+        const result = await this.callTool(conversation, call);
+        conversation.messages.push({ role: 'tool', tool_call_id: call.id, content: truncate(result) });
+      }
+    }
+    return false;
+  }
+
+  /**
+   * Request the next model turn.
+   * @returns The completion, or null if the execution was cancelled
+   * @throws TimeoutError If the agent timeout elapsed
+   * @throws AgentError If the request failed
+   */
+  private async complete(conversation: Conversation, tools: object[]): Promise<ChatCompletion | null> {
+    const { baseUrl, apiKey } = resolveApiEndpoint(this.provider, agentEnvironment(providerEnvVars(this.provider)));
+    const signals = [this.abort!.signal];
+    if (conversation.deadline !== null) {
+      signals.push(AbortSignal.timeout(Math.max(0, conversation.deadline - Date.now())));
+    }
+
+    let response: Response;
+    try {
+      response = await fetch(`${baseUrl}/chat/completions`, {
+        method: 'POST',
+        headers: {
+          'Content-Type': 'application/json',
+          ...(apiKey ? { Authorization: `Bearer ${apiKey}` } : {}),
+        },
+        body: JSON.stringify({
+          model: this.model,
+          messages: conversation.messages,
+          ...(tools.length > 0 ? { tools } : {}),
+        }),
+        signal: AbortSignal.any(signals),
+      });
+    } catch (error) {
+      if (this.abort!.signal.aborted) {
+        return null;
+      }
+      if (conversation.deadline !== null && Date.now() >= conversation.deadline) {
+        throw new TimeoutError(conversation.task.agent_timeout!, { taskId: conversation.task.id, phase: 'agent' });
+      }
+      throw new AgentError(`Cannot reach ${baseUrl}: ${error instanceof Error ? error.message : error}`, {
+        taskId: conversation.task.id,
+        phase: 'agent',
+      });
+    }
+
+    if (!response.ok) {
+      const body = (await response.text().catch(() => '')).slice(0, 500);
+      throw new AgentError(`API request failed with ${response.status} ${response.statusText}${body ? `: ${body}` : ''}`, {
+        taskId: conversation.task.id,
+        phase: 'agent',
+      });
+    }
+    return await response.json() as ChatCompletion;
+  }
+
+  /**
+   * Execute a tool call; failures are reported to the model rather than thrown.
+   */
+  private async callTool(conversation: Conversation, call: ToolCall): Promise<string> {
+    const { workspace, capabilities } = conversation;
+    let args: Record<string, unknown>;
+    try {
+      args = JSON.parse(call.function.arguments || '{}');
+    } catch {
+      return `Error: arguments are not valid JSON: ${call.function.arguments}`;
+    }
+    logger.debug(`Tool call ${call.function.name} ${call.function.arguments}`);
+
+    // Symlinks in the workspace are followed, so they cannot lead outside it
+    const path = async () => {
+      if (typeof args.path !== 'string') {
+        throw new Error('path is required');
+      }
+      const target = await resolveWithin(workspace, args.path);
+      if (!target) {
+        throw new Error(`${args.path} is outside the repository`);
+      }
+      return target;
+    };
+
+    try {
+      switch (call.function.name) {
+        case 'read_file':
+          if (!capabilities.read) break;
+          return await readFile(await path(), 'utf-8');
+        case 'write_file': {
+          if (!capabilities.write) break;
+          const target = await path();
+          await mkdir(dirname(target), { recursive: true });
+          await writeFile(target, typeof args.content === 'string' ? args.content : '');
+          return `Wrote ${relative(workspace, target)}`;
+        }
+        case 'run_command':
+          if (!capabilities.execute) break;
+          if (typeof args.command !== 'string') {
+            throw new Error('command is required');
+          }
+          return await this.runCommand(conversation, args.command);
+      }
+      return `Error: unknown tool ${call.function.name}`;
+    } catch (error) {
+      if (error instanceof BenchError) {
+        throw error;
+      }
+      return `Error: ${error instanceof Error ? error.message : error}`;
+    }
+  }
+
+  /**
+   * Run a shell command in the workspace, in the sandbox, with the agent environment.
+   */
+  private async runCommand(conversation: Conversation, command: string): Promise<string> {
+    const remaining = conversation.deadline === null ? Infinity : (conversation.deadline - Date.now()) / 1000;
+    const timeoutSecs = Math.max(1, Math.min(COMMAND_TIMEOUT_SECS, remaining));
+    const wrapped = await sandboxCommand(command, conversation.workspace, { shell: true });
+
+    this.running = startProcess(wrapped, {
+      cwd: conversation.workspace,
+      env: agentEnvironment(),
+      inheritEnv: false,
+      shell: typeof wrapped === 'string',
+      timeoutSecs,
+    });
+    const result = await this.running.done;
+    this.running = null;
+    await disposeSpoolFiles(result.stdoutFile, result.stderrFile);
+
+    const output = [result.stdout, result.stderr].filter(text => text.trim()).join('\n');
+    const status = result.timedOut ? `timed out after ${Math.round(timeoutSecs)}s` : `exit code ${result.exitCode ?? result.signal}`;
+    return `${status}\n${output}`;
+  }
+
+  private buildResult(conversation: Conversation): AgentResult {
+    const { usage } = conversation;
+    return {
+      success: true, // Determined by verification
+      output: conversation.output,
+      iterations: conversation.iterations,
+      tokensUsed: usage.input + usage.output,
+      usage: { ...usage },
+      cost: null,
+      durationSecs: (Date.now() - conversation.startTime) / 1000,
+      agentVersion: null,
+      modelName: `${this.provider}/${this.model}`,
+    };
+  }
+}
@@ -0,0 +1,240 @@
+/**
+ * Base for adapters that run an agent's CLI once per task in non-interactive mode.
+ *
+ * The CLI runs in the workspace through `startProcess`, wrapped by the configured sandbox
+ * and with the scrubbed agent environment. It is killed when the task's agent timeout
+ * elapses (a `TimeoutError`) or the run cancels it. A non-zero exit is an `AgentError`.
+ * Subclasses build the command line from the task's capabilities and parse what the CLI
+ * printed into metrics; anything they need outside the workspace goes in `scratch`, a
+ * temporary directory removed after the execution. Output is bounded like any process's:
+ * parsers that need all of it read it with `fullStdout`.
+ */
+
+import { mkdir, mkdtemp, readFile, rm } from 'fs/promises';
+import { tmpdir } from 'os';
+import { join } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import { probeBinary } from '../core/prerequisites.js';
+import type { TokenUsage } from '../evaluator/pricing.js';
+import { AgentError, BenchError, TimeoutError } from '../utils/errors.js';
+import { formatCommand, startProcess, type ExecResult, type RunningProcess } from '../utils/exec.js';
+import { logger } from '../utils/logger.js';
+import { disposeSpoolFiles } from '../utils/spool.js';
+import { sandboxCommand } from '../sandbox/index.js';
+import { agentEnvironment, providerEnvVars } from './env.js';
+import { resolveCapabilities, type Capabilities } from './permissions.js';
+import type { Agent, AgentInvocation, AgentResult, IterationListener } from './types.js';
+import { DEFAULT_MAX_ITERATIONS } from './types.js';
+
+/**
+ * Metrics a CLI printed.
+ */
+export interface CliOutput {
+  /** The agent's response, for `agent_output` */
+  output: string;
+  iterations: number;
+  /** null if the CLI does not report tokens */
+  usage: TokenUsage | null;
+  /** USD, as reported by the CLI */
+  cost: number | null;
+}
+
+/**
+ * How the CLI is started for a task.
+ */
+export interface CliCommand {
+  program: string;
+  args: string[];
+  /** Extra variables for the process, on top of the scrubbed environment */
+  env?: Record<string, string>;
+  /** Written to the CLI's stdin (default: no stdin) */
+  input?: string;
+}
+
+/**
+ * Parse a token count as CLIs print it: `1234`, `1,234`, `12.5k`, `1.2M`.
+ */
+export function parseTokenCount(value: string): number {
+  const match = value.trim().replace(/,/g, '').match(/^(\d+(?:\.\d+)?)\s*([kKmM]?)$/);
+  if (!match) {
+    return 0;
+  }
+  const scale = { '': 1, k: 1_000, m: 1_000_000 }[match[2].toLowerCase() as '' | 'k' | 'm'];
+  return Math.round(Number(match[1]) * scale);
+}
+
+/**
+ * Agent adapter running a CLI per task.
+ */
+export abstract class CliAgent implements Agent {
+  /** Model as `provider/model` */
+  protected model: string;
+  protected maxIterations: number;
+  /** Further arguments for the CLI (a named agent's `flags`) */
+  protected extraArgs: string[] = [];
+  private iterationListener: IterationListener | null = null;
+  private running: RunningProcess | null = null;
+  private cancelled = false;
+
+  /**
+   * @param model Model as `provider/model`
+   * @param maxIterations Default iteration limit, for CLIs that have one; a task's `max_iterations` overrides it
+   */
+  constructor(model: string, maxIterations: number = DEFAULT_MAX_ITERATIONS) {
+    this.model = model;
+    this.maxIterations = maxIterations;
+  }
+
+  abstract name(): string;
+
+  abstract capabilities(): AgentCapability[];
+
+  /** The CLI's executable, for version detection and `agents` */
+  protected abstract readonly binary: string;
+
+  /** Variables the CLI reads itself, passed through the scrubbed environment (e.g. `AIDER_*`) */
+  protected abstract readonly envVars: string[];
+
+  /**
+   * Command line for a task, with `extraArgs` where the CLI takes options.
+   * @param scratch Temporary directory for files the CLI should not write into the workspace
+   */
+  protected abstract buildCommand(task: Task, workspace: string, capabilities: Capabilities, scratch: string): CliCommand;
+
+  /**
+   * Parse the metrics out of a finished execution (and the files it left in `scratch`).
+   */
+  protected abstract parseOutput(result: ExecResult, scratch: string): CliOutput | Promise<CliOutput>;
+
+  /**
+   * Everything the CLI printed to stdout, read back from the spool file if it outgrew memory.
+   */
+  protected async fullStdout(result: ExecResult): Promise<string> {
+    return result.stdoutFile ? await readFile(result.stdoutFile, 'utf-8') : result.stdout;
+  }
+
+  /**
+   * Directories the CLI writes outside the workspace (its config and caches), made writable in the sandbox.
+   */
+  protected stateDirs(): string[] {
+    return [];
+  }
+
+  /**
+   * Settings recorded with the invocation in the reproduction manifest.
+   */
+  protected parameters(task: Task): Record<string, unknown> {
+    return { agent_timeout: task.agent_timeout ?? null };
+  }
+
+  setIterationListener(listener: IterationListener | null): void {
+    this.iterationListener = listener;
+  }
+
+  /**
+   * Add arguments to the CLI's command line.
+   */
+  setExtraArgs(args: string[]): void {
+    this.extraArgs = [...args];
+  }
+
+  /**
+   * Kill the running CLI; the execution then returns what it printed so far.
+   */
+  async cancel(): Promise<void> {
+    if (!this.running) {
+      return;
+    }
+    this.cancelled = true;
+    logger.debug(`Cancelling ${this.name()}...`);
+    await this.running.kill();
+  }
+
+  async describeInvocation(task: Task, workspace: string): Promise<AgentInvocation> {
+    const command = this.buildCommand(task, workspace, resolveCapabilities(task.permissions), '<scratch>');
+    const wrapped = await sandboxCommand({ program: command.program, args: command.args }, workspace, {
+      writable: this.stateDirs(),
+    });
+    return {
+      argv: typeof wrapped === 'string' ? [wrapped] : [wrapped.program, ...wrapped.args],
+      model: this.model,
+      parameters: this.parameters(task),
+    };
+  }
+
+  async execute(task: Task, workspace: string): Promise<AgentResult> {
+    const scratch = await mkdtemp(join(tmpdir(), `agent-bench-${this.name()}-`));
+    this.cancelled = false;
+    let result: ExecResult | null = null;
+    try {
+      const stateDirs = this.stateDirs();
+      await Promise.all(stateDirs.map(dir => mkdir(dir, { recursive: true })));
+      const command = this.buildCommand(task, workspace, resolveCapabilities(task.permissions), scratch);
+      const wrapped = await sandboxCommand({ program: command.program, args: command.args }, workspace, {
+        writable: [...stateDirs, scratch],
+      });
+      logger.debug(`Starting ${this.name()}: ${formatCommand(wrapped)}`);
+
+      this.running = startProcess(wrapped, {
+        cwd: workspace,
+        env: { ...agentEnvironment([...this.envVars, ...providerEnvVars(this.model)]), ...command.env },
+        inheritEnv: false,
+        timeoutSecs: task.agent_timeout ?? null,
+        input: command.input,
+      });
+      try {
+        result = await this.running.done;
+      } catch (error) {
+        // The CLI is not installed (or not executable): the agent is unavailable, not the harness broken
+        throw new AgentError(`${error instanceof Error ? error.message : error}`, { taskId: task.id, phase: 'agent' });
+      }
+
+      if (result.timedOut) {
+        throw new TimeoutError(task.agent_timeout!, { taskId: task.id, phase: 'agent' });
+      }
+      if (result.exitCode !== 0 && !this.cancelled) {
+        const detail = (result.stderr.trim() || result.stdout.trim()).split('\n').slice(-20).join('\n');
+        throw new AgentError(
+          `${this.name()} exited with ${result.exitCode === null ? `signal ${result.signal}` : `code ${result.exitCode}`}` +
+            (detail ? `\n${detail}` : ''),
+          { taskId: task.id, phase: 'agent', command: formatCommand(wrapped) }
+        );
+      }
+
+      const parsed = await this.parseOutput(result, scratch);
+      this.iterationListener?.({
+        iteration: parsed.iterations,
+        tokensUsed: parsed.usage ? parsed.usage.input + parsed.usage.output : 0,
+        cost: parsed.cost,
+      });
+      return {
+        success: true, // Determined by verification
+        output: parsed.output,
+        iterations: parsed.iterations,
+        tokensUsed: parsed.usage ? parsed.usage.input + parsed.usage.output : null,
+        usage: parsed.usage,
+        cost: parsed.cost,
+        durationSecs: result.durationSecs,
+        agentVersion: await this.version(),
+        modelName: this.model,
+      };
+    } catch (error) {
+      if (error instanceof BenchError) {
+        throw error;
+      }
+      throw new AgentError(`${this.name()} execution failed: ${error}`, { taskId: task.id, phase: 'agent' });
+    } finally {
+      this.running = null;
+      await rm(scratch, { recursive: true, force: true });
+      await disposeSpoolFiles(result?.stdoutFile, result?.stderrFile);
+    }
+  }
+
+  /**
+   * `<binary>@<version>`, or null if the version cannot be detected.
+   */
+  protected async version(): Promise<string | null> {
+    const { version } = await probeBinary(this.binary);
+    return version ? `${this.binary}@${version}` : null;
+  }
+}
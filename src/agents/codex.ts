@@ -0,0 +1,130 @@
+/**
+ * OpenAI Codex CLI agent adapter.
+ *
+ * Runs `codex exec --json <prompt>` once per task and reads its JSONL event stream. Codex
+ * confines its own shell commands, so task permissions choose its sandbox mode:
+ *
+ *   write or execute     →  --sandbox workspace-write (read-only otherwise)
+ *   network              →  -c sandbox_workspace_write.network_access=true
+ *   approval bypass      →  --dangerously-bypass-approvals-and-sandbox
+ *
+ * `codex exec` never asks for approval, so `auto` and `ask` behave alike. Shell access cannot
+ * be turned off: without `write` or `execute` the read-only sandbox still lets it run
+ * commands that only read. Models are passed by their bare ID (`openai/gpt-5` → `gpt-5`);
+ * other providers need a matching `model_provider` in the Codex config.
+ */
+
+import { homedir } from 'os';
+import { join } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import type { ExecResult } from '../utils/exec.js';
+import { AgentError } from '../utils/errors.js';
+import { emptyUsage } from '../evaluator/pricing.js';
+import type { Capabilities } from './permissions.js';
+import { CliAgent, type CliCommand, type CliOutput } from './cli.js';
+
+/**
+ * Events of `codex exec --json` that carry metrics (other events are ignored).
+ */
+interface CodexEvent {
+  type?: string;
+  item?: { type?: string; text?: string };
+  usage?: { input_tokens?: number; cached_input_tokens?: number; output_tokens?: number };
+  error?: { message?: string };
+  message?: string;
+}
+
+/**
+ * Parse the JSONL event stream of `codex exec --json`: the agent's messages, one iteration
+ * per completed item (message, command, file change), and usage summed over turns.
+ * Cached input tokens are a subset of input tokens and are counted as cache reads only.
+ * @throws AgentError If a turn failed
+ */
+export function parseCodexEvents(stdout: string): CliOutput {
+  const usage = emptyUsage();
+  const messages: string[] = [];
+  let iterations = 0;
+  let turns = 0;
+
+  for (const line of stdout.split('\n')) {
+    let event: CodexEvent;
+    try {
+      event = JSON.parse(line);
+    } catch {
+      continue; // Not an event (warnings, blank lines)
+    }
+    switch (event?.type) {
+      case 'item.completed':
+        if (event.item?.type !== 'reasoning') {
+          iterations++;
+        }
+        if (event.item?.type === 'agent_message' && event.item.text) {
+          messages.push(event.item.text);
+        }
+        break;
+      case 'turn.completed': {
+        turns++;
+        const cached = event.usage?.cached_input_tokens ?? 0;
+        usage.input += (event.usage?.input_tokens ?? 0) - cached;
+        usage.cache_read += cached;
+        usage.output += event.usage?.output_tokens ?? 0;
+        break;
+      }
+      case 'turn.failed':
+      case 'error':
+        throw new AgentError(`Codex failed: ${event.error?.message ?? event.message ?? 'unknown error'}`, { phase: 'agent' });
+    }
+  }
+
+  return { output: messages.join('\n\n'), iterations, usage: turns > 0 ? usage : null, cost: null };
+}
+
+/**
+ * Codex CLI adapter.
+ */
+export class CodexAgent extends CliAgent {
+  protected readonly binary = 'codex';
+  protected readonly envVars = ['CODEX_*'];
+
+  name(): string {
+    return 'codex';
+  }
+
+  capabilities(): AgentCapability[] {
+    return ['read', 'write', 'bash', 'web', 'token_reporting'];
+  }
+
+  /**
+   * `$CODEX_HOME` (default `~/.codex`): config, credentials, and session logs.
+   */
+  protected stateDirs(): string[] {
+    return [process.env.CODEX_HOME || join(homedir(), '.codex')];
+  }
+
+  protected buildCommand(task: Task, workspace: string, capabilities: Capabilities): CliCommand {
+    const args = [
+      'exec',
+      '--json',
+      '--color', 'never',
+      '--skip-git-repo-check',
+      '--cd', workspace,
+      '--model', this.model.slice(this.model.indexOf('/') + 1),
+    ];
+    if (capabilities.approval === 'bypass') {
+      args.push('--dangerously-bypass-approvals-and-sandbox');
+    } else {
+      args.push('--sandbox', capabilities.write || capabilities.execute ? 'workspace-write' : 'read-only');
+      if (capabilities.network) {
+        args.push('-c', 'sandbox_workspace_write.network_access=true');
+      }
+    }
+    args.push(...this.extraArgs);
+    // After `--`, a prompt starting with `-` is not read as an option
+    args.push('--', task.prompt);
+    return { program: 'codex', args };
+  }
+
+  protected async parseOutput(result: ExecResult): Promise<CliOutput> {
+    return parseCodexEvents(await this.fullStdout(result));
+  }
+}
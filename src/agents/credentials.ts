@@ -0,0 +1,224 @@
+/**
+ * Credential pool (`[[agent.credentials]]`).
+ *
+ * Several API keys or endpoints can be configured for agents; each task execution leases
+ * one and its agent process gets that credential's variables. Credentials are handed out
+ * round-robin, skipping any cooling down after a rate limit. When all are cooling down,
+ * executions wait for the first cooldown to end. A process executes one task at a time
+ * (API server runs are queued), so successive executions rotate through the pool; there
+ * is no per-credential concurrency limit. Separate processes (suite shards) each rotate
+ * through the whole pool.
+ *
+ * Values starting with `$` name a variable of the harness environment, so keys stay out
+ * of the config file.
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+import { ConfigError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+
+/**
+ * How long a credential is passed over after an execution with it hit a rate limit.
+ */
+export const RATE_LIMIT_COOLDOWN_MS = 60_000;
+
+/**
+ * Agent failures that indicate the provider rate-limited the credential.
+ */
+const RATE_LIMIT_PATTERN = /rate.?limit|\b429\b|too many requests|overloaded/i;
+
+/**
+ * A configured credential.
+ */
+export interface CredentialConfig {
+  name: string;
+  /** Variables set for the agent (`$NAME` values are read from the harness environment) */
+  env: Record<string, string>;
+}
+
+/**
+ * Usage of a pooled credential.
+ */
+export interface CredentialStats {
+  name: string;
+  /** Executions using it now */
+  active: number;
+  /** Executions it was leased to */
+  leases: number;
+  /** Executions that hit a rate limit with it */
+  rateLimits: number;
+  /** When it is usable again after a rate limit (null: not cooling down) */
+  coolingUntil: string | null;
+}
+
+/**
+ * A credential leased to one task execution.
+ */
+export interface CredentialLease {
+  name: string;
+  /** Resolved variables for the agent */
+  env: Record<string, string>;
+  /** Return the credential to the pool; a rate-limited execution starts its cooldown */
+  release(rateLimited?: boolean): void;
+}
+
+interface PooledCredential {
+  config: CredentialConfig;
+  /** Resolved on first lease */
+  env: Record<string, string> | null;
+  active: number;
+  leases: number;
+  rateLimits: number;
+  coolingUntil: number;
+}
+
+/**
+ * Round-robin pool of credentials with rate-limit tracking.
+ */
+export class CredentialPool {
+  private credentials: PooledCredential[];
+  private next = 0;
+  private source: Record<string, string | undefined>;
+
+  /**
+   * @param source Environment `$NAME` values are read from (default: this process's)
+   */
+  constructor(configs: CredentialConfig[], source: Record<string, string | undefined> = process.env) {
+    this.source = source;
+    this.credentials = configs.map(config => ({
+      config,
+      env: null,
+      active: 0,
+      leases: 0,
+      rateLimits: 0,
+      coolingUntil: 0,
+    }));
+  }
+
+  get size(): number {
+    return this.credentials.length;
+  }
+
+  /**
+   * Lease the next credential that is not cooling down, waiting for a cooldown to end if all are.
+   * @throws ConfigError If the credential refers to an unset variable
+   */
+  async acquire(): Promise<CredentialLease> {
+    for (;;) {
+      const now = Date.now();
+      for (let offset = 0; offset < this.credentials.length; offset++) {
+        const index = (this.next + offset) % this.credentials.length;
+        const credential = this.credentials[index];
+        if (credential.coolingUntil <= now) {
+          this.next = index + 1;
+          return this.lease(credential);
+        }
+      }
+
+      // Wait for the first cooldown to end
+      const until = Math.min(...this.credentials.map(credential => credential.coolingUntil));
+      logger.debug('All credentials are rate-limited; waiting');
+      await new Promise(resolve => setTimeout(resolve, until - now));
+    }
+  }
+
+  stats(): CredentialStats[] {
+    return this.credentials.map(credential => ({
+      name: credential.config.name,
+      active: credential.active,
+      leases: credential.leases,
+      rateLimits: credential.rateLimits,
+      coolingUntil: credential.coolingUntil > Date.now() ? new Date(credential.coolingUntil).toISOString() : null,
+    }));
+  }
+
+  private lease(credential: PooledCredential): CredentialLease {
+    const env = credential.env ?? resolveCredentialEnv(credential.config, this.source);
+    credential.env = env;
+    credential.active++;
+    credential.leases++;
+    let released = false;
+    return {
+      name: credential.config.name,
+      env,
+      release: (rateLimited = false) => {
+        if (released) {
+          return;
+        }
+        released = true;
+        credential.active--;
+        if (rateLimited) {
+          credential.rateLimits++;
+          credential.coolingUntil = Date.now() + RATE_LIMIT_COOLDOWN_MS;
+          logger.warn(`Credential ${credential.config.name} was rate-limited; not using it for ${RATE_LIMIT_COOLDOWN_MS / 1000}s`);
+        }
+      },
+    };
+  }
+}
+
+/**
+ * Resolve a credential's variables.
+ * @throws ConfigError If a `$NAME` value refers to an unset variable
+ */
+function resolveCredentialEnv(config: CredentialConfig, env: Record<string, string | undefined>): Record<string, string> {
+  const resolved: Record<string, string> = {};
+  for (const [name, value] of Object.entries(config.env)) {
+    if (value.startsWith('$')) {
+      const source = env[value.slice(1)];
+      if (!source) {
+        throw new ConfigError(`Credential ${config.name}: ${value.slice(1)} is not set (for ${name})`);
+      }
+      resolved[name] = source;
+    } else {
+      resolved[name] = value;
+    }
+  }
+  return resolved;
+}
+
+/**
+ * Whether an agent failure looks like a provider rate limit.
+ */
+export function isRateLimitError(error: string | null): boolean {
+  return error !== null && RATE_LIMIT_PATTERN.test(error);
+}
+
+let pool: CredentialPool | null = null;
+let poolKey = '';
+
+/**
+ * Set the configured credentials (the CLI and TaskRunner set them from `[[agent.credentials]]`).
+ * Setting the same credentials again keeps the existing pool and its usage counts.
+ */
+export function setCredentials(credentials: CredentialConfig[]): void {
+  const key = JSON.stringify(credentials);
+  if (key === poolKey) {
+    return;
+  }
+  pool = credentials.length > 0 ? new CredentialPool(credentials) : null;
+  poolKey = key;
+}
+
+/**
+ * The credential pool, or null if no credentials are configured.
+ */
+export function getCredentialPool(): CredentialPool | null {
+  return pool;
+}
+
+const leases = new AsyncLocalStorage<CredentialLease>();
+
+/**
+ * Run `fn` with a leased credential; agent processes it starts get the credential's variables.
+ */
+export function withCredential<T>(lease: CredentialLease, fn: () => T): T {
+  return leases.run(lease, fn);
+}
+
+/**
+ * The credential leased to the current execution, if any.
+ */
+export function currentCredential(): CredentialLease | null {
+  return leases.getStore() ?? null;
+}
@@ -0,0 +1,181 @@
+/**
+ * Custom command agent (`[agent.custom]`).
+ *
+ * Benchmarks any local coding agent with a command line, without an adapter of its own.
+ * The configured command template is split into words like a shell would (without
+ * expansion) and its placeholders are filled in for each task:
+ *
+ *   {prompt} {workspace} {model} {scratch} {max_iterations} {approval}
+ *   {write?--allow-write}              the text if the task grants `write`
+ *   {network?--online:--offline}       otherwise the text after `:`
+ *
+ * Conditions are `read`, `write`, `execute`, and `network`. A word that is only a
+ * condition becomes zero or more arguments (its text split on whitespace; quote it in the
+ * template to use spaces). `{{` and `}}` are literal braces. Values are passed as single
+ * arguments, never through a shell. What the command prints on stdout is the agent's
+ * output; it reports no usage.
+ */
+
+import type { AgentCapability, Task } from '../core/task.js';
+import type { ExecResult } from '../utils/exec.js';
+import { splitCommand } from '../utils/exec.js';
+import { AgentError } from '../utils/errors.js';
+import type { Capabilities } from './permissions.js';
+import { DEFAULT_MAX_ITERATIONS } from './types.js';
+import { CliAgent, type CliCommand, type CliOutput } from './cli.js';
+
+/**
+ * The configured custom agent.
+ */
+export interface CustomAgentConfig {
+  /** Command template, e.g. `mytool --prompt {prompt} --cwd {workspace}` */
+  command: string;
+  /** Variables the tool reads, passed through the scrubbed environment (names or `*` patterns) */
+  env: string[];
+  /** What the tool can do, for task requirement checks */
+  capabilities: AgentCapability[];
+}
+
+export const DEFAULT_CUSTOM_CAPABILITIES: readonly AgentCapability[] = ['read', 'write', 'bash'];
+
+const VALUE_PLACEHOLDERS = ['prompt', 'workspace', 'model', 'scratch', 'max_iterations', 'approval'] as const;
+const CONDITION_PLACEHOLDERS = ['read', 'write', 'execute', 'network'] as const;
+
+/**
+ * `{{`, `}}`, `{name}`, `{condition?then}`, or `{condition?then:else}`.
+ */
+const PLACEHOLDER = /\{\{|\}\}|\{(\w+)(?:\?([^{}:]*)(?::([^{}]*))?)?\}/g;
+
+let configured: CustomAgentConfig | null = null;
+
+/**
+ * Set the custom agent (the CLI and TaskRunner set it from `[agent.custom]`).
+ */
+export function setCustomAgent(config: CustomAgentConfig | null): void {
+  configured = config;
+}
+
+export function getCustomAgent(): CustomAgentConfig | null {
+  return configured;
+}
+
+/**
+ * Check a command template's syntax and placeholders.
+ * @throws AgentError If it cannot be split into words or uses an unknown placeholder
+ */
+export function validateCommandTemplate(template: string): void {
+  splitCommand(template);
+  for (const match of template.matchAll(PLACEHOLDER)) {
+    const [, name, then] = match;
+    if (!name) continue;
+    const known: readonly string[] = then === undefined ? VALUE_PLACEHOLDERS : CONDITION_PLACEHOLDERS;
+    if (!known.includes(name)) {
+      throw new AgentError(
+        then === undefined
+          ? `Unknown placeholder {${name}} (available: ${VALUE_PLACEHOLDERS.join(', ')})`
+          : `Unknown condition {${name}?...} (available: ${CONDITION_PLACEHOLDERS.join(', ')})`
+      );
+    }
+  }
+}
+
+/**
+ * Expand a command template into its words.
+ * @param values Values of the `{name}` placeholders
+ * @param conditions Values of the `{condition?...}` placeholders
+ * @throws AgentError If the template is invalid
+ */
+export function expandCommandTemplate(
+  template: string,
+  values: Record<(typeof VALUE_PLACEHOLDERS)[number], string>,
+  conditions: Record<(typeof CONDITION_PLACEHOLDERS)[number], boolean>
+): string[] {
+  validateCommandTemplate(template);
+  const substitute = (match: string, name?: string, then?: string, otherwise?: string): string => {
+    if (!name) return match[0]; // Escaped brace
+    if (then === undefined) return values[name as keyof typeof values];
+    return conditions[name as keyof typeof conditions] ? then : otherwise ?? '';
+  };
+
+  const [program, args] = splitCommand(template);
+  return [program, ...args].flatMap(word => {
+    const whole = word.match(/^\{(\w+)\?([^{}:]*)(?::([^{}]*))?\}$/);
+    if (whole) {
+      return substitute(word, whole[1], whole[2], whole[3]).split(/\s+/).filter(Boolean);
+    }
+    return [word.replace(PLACEHOLDER, substitute)];
+  });
+}
+
+/**
+ * Agent running the configured command template.
+ */
+export class CustomAgent extends CliAgent {
+  private config: CustomAgentConfig | null;
+
+  /**
+   * @param config The custom agent (default: the configured `[agent.custom]`, read when used)
+   */
+  constructor(model: string, maxIterations: number = DEFAULT_MAX_ITERATIONS, config: CustomAgentConfig | null = null) {
+    super(model, maxIterations);
+    this.config = config;
+  }
+
+  name(): string {
+    return 'custom';
+  }
+
+  capabilities(): AgentCapability[] {
+    return [...(this.settings(false)?.capabilities ?? DEFAULT_CUSTOM_CAPABILITIES)];
+  }
+
+  protected get binary(): string {
+    const settings = this.settings(false);
+    return settings ? splitCommand(settings.command)[0] : 'custom';
+  }
+
+  protected get envVars(): string[] {
+    return this.settings(false)?.env ?? [];
+  }
+
+  protected parameters(task: Task): Record<string, unknown> {
+    return { ...super.parameters(task), command: this.settings(false)?.command ?? null };
+  }
+
+  protected buildCommand(task: Task, workspace: string, capabilities: Capabilities, scratch: string): CliCommand {
+    const [program, ...args] = expandCommandTemplate(
+      this.settings(true)!.command,
+      {
+        prompt: task.prompt,
+        workspace,
+        model: this.model,
+        scratch,
+        max_iterations: String(task.max_iterations ?? this.maxIterations),
+        approval: capabilities.approval,
+      },
+      {
+        read: capabilities.read,
+        write: capabilities.write,
+        execute: capabilities.execute,
+        network: capabilities.network,
+      }
+    );
+    return { program, args: [...args, ...this.extraArgs] };
+  }
+
+  protected parseOutput(result: ExecResult): CliOutput {
+    return { output: result.stdout.trim(), iterations: 1, usage: null, cost: null };
+  }
+
+  /**
+   * The custom agent in use.
+   * @throws AgentError If `required` and none is configured
+   */
+  private settings(required: boolean): CustomAgentConfig | null {
+    const settings = this.config ?? configured;
+    if (!settings && required) {
+      throw new AgentError('No custom agent is configured (set [agent.custom] command in agent-bench.toml)', { phase: 'agent' });
+    }
+    return settings;
+  }
+}
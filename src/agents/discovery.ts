@@ -0,0 +1,134 @@
+/**
+ * Agent discovery: which `--agent` values work on this machine.
+ */
+
+import { existsSync } from 'fs';
+import { readFile } from 'fs/promises';
+import { homedir } from 'os';
+import { join } from 'path';
+import { AGENT_TYPES, createAgentFromSpec } from './factory.js';
+import type { AgentType, BuiltinAgentType } from './factory.js';
+import type { AgentCapability } from '../core/task.js';
+import { parseModel } from './types.js';
+import { probeBinary } from '../core/prerequisites.js';
+import { PROVIDER_ENV_VARS, agentEnvironment, providerEnvVars } from './env.js';
+import { getCustomAgent } from './custom.js';
+import { splitCommand } from '../utils/exec.js';
+
+/**
+ * Static facts about a built-in agent adapter.
+ */
+interface AgentAdapterInfo {
+  description: string;
+  /** null for agents that run in the harness itself */
+  binary: string | null;
+}
+
+const ADAPTERS: Record<BuiltinAgentType, AgentAdapterInfo> = {
+  opencode: { description: 'OpenCode via its SDK (embedded server)', binary: 'opencode' },
+  aider: { description: 'Aider CLI (aider --message, no auto-commits)', binary: 'aider' },
+  codex: { description: 'OpenAI Codex CLI (codex exec --json)', binary: 'codex' },
+  custom: { description: 'Command template from [agent.custom]', binary: 'custom' },
+  openhands: { description: 'OpenHands headless mode (docker image)', binary: 'docker' },
+  goose: { description: 'Goose CLI (goose run, built-in extensions per permission)', binary: 'goose' },
+  api: { description: 'OpenAI-compatible chat completions with a built-in tool loop', binary: null },
+  qwen: { description: 'Qwen Code CLI (qwen --prompt, JSON report)', binary: 'qwen' },
+};
+
+/**
+ * Detected state of an agent adapter.
+ */
+export interface AgentStatus {
+  type: AgentType;
+  description: string;
+  binary: string | null;
+  binaryPath: string | null;
+  version: string | null;
+  capabilities: AgentCapability[];
+  model: string;
+  credentials: string | null;
+  ready: boolean;
+}
+
+/**
+ * Inspect every built-in agent: binary and version, capabilities, and whether the model's provider has credentials.
+ * @param defaultModel Model checked for credentials
+ */
+export async function discoverAgents(defaultModel: string): Promise<AgentStatus[]> {
+  const statuses: AgentStatus[] = [];
+
+  for (const type of AGENT_TYPES) {
+    const info = ADAPTERS[type];
+    const custom = getCustomAgent();
+    const binary = type === 'custom' && custom ? splitCommand(custom.command)[0] : info.binary;
+    const probe = binary ? await probeBinary(binary) : { path: null, version: null };
+    const agent = createAgentFromSpec({ type, model: null }, defaultModel);
+    const credentials = await findCredentials(defaultModel, type);
+
+    statuses.push({
+      type,
+      description: info.description,
+      binary,
+      binaryPath: probe.path,
+      version: probe.version,
+      capabilities: agent.capabilities(),
+      model: defaultModel,
+      credentials,
+      // A custom command brings its own credentials
+      ready: (probe.path !== null || binary === null) && (credentials !== null || type === 'custom'),
+    });
+  }
+
+  return statuses;
+}
+
+/**
+ * Find credentials for a model's provider: an API key variable or the adapter's stored auth
+ * (OpenCode's per-provider `auth.json`, or `codex login` for OpenAI models).
+ * Variables only count if `[agent] env` passes them to the agent.
+ * @returns Where the credentials come from, or null if none were found
+ */
+async function findCredentials(model: string, type: AgentType): Promise<string | null> {
+  let provider: string;
+  try {
+    provider = parseModel(model).providerID;
+  } catch {
+    return null;
+  }
+
+  const env = agentEnvironment(providerEnvVars(provider));
+  if (type === 'openhands') {
+    // OpenHands reads one key for every provider
+    return env.LLM_API_KEY ? 'LLM_API_KEY' : null;
+  }
+  if (type === 'api') {
+    // The API agent reads <PROVIDER>_API_KEY; a local server may need no key but a base URL
+    const prefix = provider.toUpperCase().replace(/[^A-Z0-9]/g, '_');
+    return [`${prefix}_API_KEY`, `${prefix}_BASE_URL`].find(name => env[name]) ?? null;
+  }
+  for (const name of PROVIDER_ENV_VARS[provider] || []) {
+    if (env[name]) {
+      return name;
+    }
+  }
+
+  if (type === 'codex') {
+    const codexAuth = join(process.env.CODEX_HOME || join(homedir(), '.codex'), 'auth.json');
+    return provider === 'openai' && existsSync(codexAuth) ? codexAuth : null;
+  }
+  if (type !== 'opencode') {
+    return null;
+  }
+  const dataHome = process.env.XDG_DATA_HOME || join(homedir(), '.local', 'share');
+  const authPath = join(dataHome, 'opencode', 'auth.json');
+  try {
+    const auth = JSON.parse(await readFile(authPath, 'utf-8'));
+    if (auth && typeof auth === 'object' && provider in auth) {
+      return authPath;
+    }
+  } catch {
+    // No stored OpenCode credentials
+  }
+
+  return null;
+}
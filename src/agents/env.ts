@@ -0,0 +1,123 @@
+/**
+ * Environment of agent processes (`[agent] env`).
+ *
+ * Agents run with a scrubbed environment so a task cannot read credentials that happen
+ * to be in the operator's shell: only the variables programs generally need (PATH, HOME,
+ * locale, temp and XDG directories, Windows system paths), the adapter's own variables,
+ * and the configured allow-list are passed through. Allow-list entries are variable names
+ * or `*` patterns (`AWS_*`); a lone `*` passes the whole environment. Adapters also pass
+ * the API key variables of the model's provider (`providerEnvVars`), so the model being run
+ * works without listing its key. The variables of the credential leased to the current execution (see credentials.ts) are added on top, and
+ * those of the named agent running it (see profiles.ts) on top of these.
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+import { currentCredential } from './credentials.js';
+
+/**
+ * Variables every agent process gets.
+ */
+export const BASE_AGENT_ENV = [
+  'PATH',
+  'HOME',
+  'USER',
+  'LOGNAME',
+  'SHELL',
+  'TERM',
+  'TZ',
+  'LANG',
+  'LANGUAGE',
+  'LC_*',
+  'TMPDIR',
+  'TMP',
+  'TEMP',
+  'XDG_*',
+  // Windows
+  'PATHEXT',
+  'SYSTEMROOT',
+  'SYSTEMDRIVE',
+  'WINDIR',
+  'COMSPEC',
+  'USERPROFILE',
+  'USERNAME',
+  'APPDATA',
+  'LOCALAPPDATA',
+  'PROGRAMDATA',
+  'PROGRAMFILES',
+  'PROGRAMFILES(X86)',
+] as const;
+
+/**
+ * API key environment variables by model provider.
+ */
+export const PROVIDER_ENV_VARS: Record<string, string[]> = {
+  anthropic: ['ANTHROPIC_API_KEY'],
+  openai: ['OPENAI_API_KEY'],
+  google: ['GOOGLE_GENERATIVE_AI_API_KEY', 'GEMINI_API_KEY'],
+  openrouter: ['OPENROUTER_API_KEY'],
+  groq: ['GROQ_API_KEY'],
+  mistral: ['MISTRAL_API_KEY'],
+  deepseek: ['DEEPSEEK_API_KEY'],
+  xai: ['XAI_API_KEY'],
+};
+
+/**
+ * API key variables of a provider: its known ones and `<PROVIDER>_API_KEY`.
+ * @param model Model as `provider/model`, or a provider
+ */
+export function providerEnvVars(model: string): string[] {
+  const provider = model.split('/')[0];
+  const prefix = provider.toUpperCase().replace(/[^A-Z0-9]/g, '_');
+  return [...new Set([...(PROVIDER_ENV_VARS[provider] ?? []), `${prefix}_API_KEY`])];
+}
+
+let allowList: string[] = [];
+
+/**
+ * Set the configured allow-list (the CLI and TaskRunner set it from `[agent] env`).
+ */
+export function setAgentEnv(names: string[]): void {
+  allowList = [...names];
+}
+
+export function getAgentEnv(): string[] {
+  return allowList;
+}
+
+const agentVariables = new AsyncLocalStorage<Record<string, string>>();
+
+/**
+ * Run `fn` with variables added to the environment of the agent processes it starts
+ * (over those of an enclosing call).
+ */
+export function withAgentVariables<T>(variables: Record<string, string>, fn: () => T): T {
+  return agentVariables.run({ ...agentVariables.getStore(), ...variables }, fn);
+}
+
+/**
+ * Whether a variable name matches an allow-list entry (case-insensitively on Windows).
+ */
+export function matchesEnvPattern(name: string, pattern: string): boolean {
+  const fold = (value: string) => (process.platform === 'win32' ? value.toUpperCase() : value);
+  const regex = new RegExp(`^${fold(pattern).split('*').map(part => part.replace(/[.+?^${}()|[\]\\]/g, '\\$&')).join('.*')}$`);
+  return regex.test(fold(name));
+}
+
+/**
+ * The environment to start an agent process with.
+ * @param adapterVars Variables the adapter itself relies on (e.g. `OPENCODE_*`)
+ * @param env Environment to filter (default: this process's)
+ */
+export function agentEnvironment(
+  adapterVars: string[] = [],
+  env: Record<string, string | undefined> = process.env
+): Record<string, string> {
+  const patterns = [...BASE_AGENT_ENV, ...adapterVars, ...allowList];
+  const scrubbed: Record<string, string> = {};
+  for (const [name, value] of Object.entries(env)) {
+    if (value !== undefined && patterns.some(pattern => matchesEnvPattern(name, pattern))) {
+      scrubbed[name] = value;
+    }
+  }
+  return { ...scrubbed, ...currentCredential()?.env, ...agentVariables.getStore() };
+}
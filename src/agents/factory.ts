@@ -3,15 +3,191 @@
  */
 
 import { OpencodeAgent } from './opencode.js';
+import { AiderAgent } from './aider.js';
+import { CodexAgent } from './codex.js';
+import { CustomAgent } from './custom.js';
+import { OpenHandsAgent } from './openhands.js';
+import { GooseAgent } from './goose.js';
+import { ApiAgent } from './api.js';
+import { QwenAgent } from './qwen.js';
+import { PluginAgent } from './plugin.js';
+import { ProfileAgent, getAgentProfile, getAgentProfiles, resolveProfileEnv } from './profiles.js';
+import { CliAgent } from './cli.js';
 import type { Agent, ModelConfig } from './types.js';
-import { parseModel, DEFAULT_MODEL } from './types.js';
+import { parseModel, DEFAULT_MAX_ITERATIONS, DEFAULT_MODEL } from './types.js';
+import { AgentError, ConfigError } from '../utils/errors.js';
+
+/**
+ * Built-in agent types that can be selected with `--agent`.
+ */
+export const AGENT_TYPES = ['opencode', 'aider', 'codex', 'custom', 'openhands', 'goose', 'api', 'qwen'] as const;
+export type BuiltinAgentType = (typeof AGENT_TYPES)[number];
+
+/**
+ * A built-in agent type, or `plugin` for an external agent (`plugin:<path>`, see plugin.ts).
+ */
+export type AgentType = BuiltinAgentType | 'plugin';
+
+/**
+ * An agent selection: `<type>[@<provider/model>]`, `plugin:<path>[@<provider/model>]`,
+ * `<named agent>[@<provider/model>]`, or a bare `provider/model` for the default type.
+ */
+export interface AgentSpec {
+  type: AgentType;
+  model: string | null;
+  /** Plugin executable (type `plugin`) */
+  plugin?: string;
+  /** Named agent (`[agents.<name>]`, see profiles.ts) running the type */
+  profile?: string;
+}
+
+/**
+ * Parse an agent spec such as `opencode`, `opencode@anthropic/claude-opus-4`, `anthropic/claude-opus-4`,
+ * `plugin:./my-agent@anthropic/claude-opus-4`, or a named agent (`claude-opus`).
+ * @throws AgentError If the agent type is unknown
+ */
+export function parseAgentSpec(spec: string): AgentSpec {
+  const trimmed = spec.trim();
+  const at = trimmed.indexOf('@');
+  const name = at === -1 ? trimmed : trimmed.substring(0, at);
+  const profile = getAgentProfile(name);
+  if (!profile) {
+    return parseAdapterSpec(trimmed);
+  }
+  const adapter = parseAdapterSpec(profile.adapter);
+  return { ...adapter, model: at === -1 ? profile.model ?? adapter.model : trimmed.substring(at + 1), profile: name };
+}
+
+/**
+ * Parse an agent spec received over HTTP (`serve-api`, workers). Plugins are executables
+ * on the server's disk, so a request may only select one through a named agent of the
+ * config, never by path.
+ * @throws AgentError If the agent type is unknown or the spec is a `plugin:<path>`
+ */
+export function parseRemoteAgentSpec(spec: string): AgentSpec {
+  const parsed = parseAgentSpec(spec);
+  if (parsed.type === 'plugin' && !parsed.profile) {
+    throw new AgentError('Plugin agents cannot be selected by path over HTTP; configure one as a named agent ([agents.<name>])');
+  }
+  return parsed;
+}
+
+/**
+ * Parse an agent spec naming a built-in type or plugin.
+ */
+function parseAdapterSpec(trimmed: string): AgentSpec {
+  if (trimmed.startsWith('plugin:')) {
+    // Paths may contain `@`; models do not
+    const at = trimmed.lastIndexOf('@');
+    const path = at === -1 ? trimmed.substring(7) : trimmed.substring(7, at);
+    if (!path) {
+      throw new AgentError('Missing plugin path (expected plugin:<path>)');
+    }
+    return { type: 'plugin', model: at === -1 ? null : trimmed.substring(at + 1), plugin: path };
+  }
+
+  const at = trimmed.indexOf('@');
+  if (at === -1 && trimmed.includes('/')) {
+    return { type: AGENT_TYPES[0], model: trimmed };
+  }
+
+  const type = at === -1 ? trimmed : trimmed.substring(0, at);
+  if (!(AGENT_TYPES as readonly string[]).includes(type)) {
+    const available = [...AGENT_TYPES, 'plugin:<path>', ...Object.keys(getAgentProfiles())];
+    throw new AgentError(`Unknown agent "${type}" (available: ${available.join(', ')})`);
+  }
+  return { type: type as BuiltinAgentType, model: at === -1 ? null : trimmed.substring(at + 1) };
+}
+
+/**
+ * Parse repeated and comma-separated `--agent` values.
+ */
+export function parseAgentList(values: string[]): AgentSpec[] {
+  return values
+    .flatMap(value => value.split(','))
+    .filter(value => value.trim().length > 0)
+    .map(parseAgentSpec);
+}
+
+/**
+ * Display label for an agent spec.
+ */
+export function formatAgentSpec(spec: AgentSpec, defaultModel: string): string {
+  const type = spec.profile ?? (spec.type === 'plugin' ? `plugin:${spec.plugin}` : spec.type);
+  return `${type}@${spec.model || defaultModel}`;
+}
+
+/**
+ * Create an agent from a spec.
+ * @param defaultModel Model used when the spec does not name one
+ * @param maxIterations Iteration limit for tasks that do not set `max_iterations` (a named agent's own limit overrides it)
+ * @throws ConfigError If a named agent has flags for an adapter without a CLI, or a variable of it is not set
+ */
+export function createAgentFromSpec(
+  spec: AgentSpec,
+  defaultModel?: string,
+  maxIterations: number = DEFAULT_MAX_ITERATIONS
+): Agent {
+  if (!spec.profile) {
+    return createAdapter(spec, defaultModel, maxIterations);
+  }
+  const profile = getAgentProfile(spec.profile);
+  if (!profile) {
+    throw new AgentError(`Unknown agent "${spec.profile}"`);
+  }
+  const agent = createAdapter(spec, defaultModel, profile.maxIterations ?? maxIterations);
+  if (profile.flags.length > 0) {
+    if (!(agent instanceof CliAgent)) {
+      throw new ConfigError(`Agent ${spec.profile}: ${spec.type} takes no flags (only agents run as a CLI do)`);
+    }
+    agent.setExtraArgs(profile.flags);
+  }
+  return new ProfileAgent(spec.profile, agent, resolveProfileEnv(spec.profile, profile));
+}
+
+/**
+ * Create the agent of a built-in type or plugin.
+ */
+function createAdapter(spec: AgentSpec, defaultModel: string | undefined, maxIterations: number): Agent {
+  switch (spec.type) {
+    case 'opencode':
+      return createAgent(spec.model || defaultModel, maxIterations);
+    case 'aider':
+      return new AiderAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'codex':
+      return new CodexAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'custom':
+      return new CustomAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'openhands':
+      return new OpenHandsAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'goose':
+      return new GooseAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'api':
+      return new ApiAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'qwen':
+      return new QwenAgent(spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+    case 'plugin':
+      if (!spec.plugin) {
+        throw new AgentError('Missing plugin path (expected plugin:<path>)');
+      }
+      return new PluginAgent(spec.plugin, spec.model || defaultModel || formatModel(DEFAULT_MODEL), maxIterations);
+  }
+}
+
+/**
+ * A model configuration as `provider/model`.
+ */
+function formatModel(config: ModelConfig): string {
+  return `${config.providerID}/${config.modelID}`;
+}
 
 /**
  * Create an OpenCode agent with optional model configuration.
  * @param modelString Optional model string in format "provider/model" (e.g., "anthropic/claude-opus-4")
+ * @param maxIterations Iteration limit for tasks that do not set `max_iterations`
  * @returns Agent instance
  */
-export function createAgent(modelString?: string): Agent {
+export function createAgent(modelString?: string, maxIterations: number = DEFAULT_MAX_ITERATIONS): Agent {
   let modelConfig: ModelConfig;
 
   if (modelString) {
@@ -25,7 +201,7 @@ export function createAgent(modelString?: string): Agent {
     modelConfig = DEFAULT_MODEL;
   }
 
-  return new OpencodeAgent(modelConfig);
+  return new OpencodeAgent(modelConfig, undefined, maxIterations);
 }
 
 /**
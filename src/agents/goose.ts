@@ -0,0 +1,79 @@
+/**
+ * Goose CLI agent adapter.
+ *
+ * Runs `goose run --no-session --quiet -t <prompt>` once per task. Goose gets its tools
+ * from extensions, and the operator's own goose config (with whatever extensions it
+ * enables) is kept out of the run: `XDG_CONFIG_HOME` points into the scratch directory,
+ * provider and model come from `GOOSE_PROVIDER`/`GOOSE_MODEL`, and API keys from the
+ * provider's variables rather than the keyring. Task permissions toggle the built-in
+ * extensions:
+ *
+ *   read, write, or execute  →  --with-builtin developer (file viewing/editing and shell)
+ *   network                  →  --with-builtin computercontroller (web fetching/scraping)
+ *   approval auto / bypass   →  GOOSE_MODE=auto; ask → GOOSE_MODE=approve
+ *
+ * The developer extension bundles editing and the shell, so a task granting only one
+ * still gets both; use the sandbox to confine writes. With approval `ask` goose's
+ * confirmations go unanswered (stdin is closed) and the tool calls are declined. Goose
+ * reports no usage in this mode: the output is what it printed.
+ */
+
+import { join } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import type { ExecResult } from '../utils/exec.js';
+import { parseModel } from './types.js';
+import type { Capabilities } from './permissions.js';
+import { CliAgent, type CliCommand, type CliOutput } from './cli.js';
+
+/**
+ * Goose CLI adapter.
+ */
+export class GooseAgent extends CliAgent {
+  protected readonly binary = 'goose';
+  protected readonly envVars = ['GOOSE_*'];
+
+  name(): string {
+    return 'goose';
+  }
+
+  capabilities(): AgentCapability[] {
+    return ['read', 'write', 'bash', 'web'];
+  }
+
+  protected parameters(task: Task): Record<string, unknown> {
+    return { ...super.parameters(task), max_turns: task.max_iterations ?? this.maxIterations };
+  }
+
+  protected buildCommand(task: Task, _workspace: string, capabilities: Capabilities, scratch: string): CliCommand {
+    const { providerID, modelID } = parseModel(this.model);
+    const args = [
+      'run',
+      '--no-session',
+      '--quiet',
+      '--max-turns', String(task.max_iterations ?? this.maxIterations),
+    ];
+    if (capabilities.read || capabilities.write || capabilities.execute) {
+      args.push('--with-builtin', 'developer');
+    }
+    if (capabilities.network) {
+      args.push('--with-builtin', 'computercontroller');
+    }
+    args.push(...this.extraArgs, '-t', task.prompt);
+
+    return {
+      program: 'goose',
+      args,
+      env: {
+        XDG_CONFIG_HOME: join(scratch, 'config'),
+        GOOSE_PROVIDER: providerID,
+        GOOSE_MODEL: modelID,
+        GOOSE_MODE: capabilities.approval === 'ask' ? 'approve' : 'auto',
+        GOOSE_DISABLE_KEYRING: '1',
+      },
+    };
+  }
+
+  protected parseOutput(result: ExecResult): CliOutput {
+    return { output: result.stdout.trim(), iterations: 1, usage: null, cost: null };
+  }
+}
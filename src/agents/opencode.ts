@@ -2,15 +2,30 @@
  * OpenCode SDK agent adapter.
  */
 
-import { createOpencode } from "@opencode-ai/sdk";
+import { createOpencodeClient } from "@opencode-ai/sdk";
 import type { OpencodeClient } from "@opencode-ai/sdk";
 import { readFileSync } from "fs";
+import { mkdir } from "fs/promises";
+import { homedir } from "os";
 import { join, dirname } from "path";
 import { fileURLToPath } from "url";
-import { Task } from "../core/task.js";
-import { AgentError } from "../utils/errors.js";
-import type { Agent, AgentResult, ModelConfig } from "./types.js";
-import { DEFAULT_MODEL } from "./types.js";
+import type { AgentCapability, Task } from "../core/task.js";
+import { AgentError, BenchError, TimeoutError } from "../utils/errors.js";
+import { logger } from "../utils/logger.js";
+import { startProcess } from "../utils/exec.js";
+import { getSandbox, sandboxCommand } from "../sandbox/index.js";
+import { agentEnvironment, providerEnvVars } from "./env.js";
+import type {
+  Agent,
+  AgentInvocation,
+  AgentResult,
+  IterationListener,
+  ModelConfig,
+  StepCallback,
+} from "./types.js";
+import { DEFAULT_MAX_ITERATIONS, DEFAULT_MODEL } from "./types.js";
+import { isReadOnly, resolveCapabilities } from "./permissions.js";
+import type { Capabilities } from "./permissions.js";
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = dirname(__filename);
@@ -28,11 +43,34 @@ function getOpencodeVersion(): string {
       return `@opencode-ai/sdk@${version.replace(/^[\^~]/, "")}`;
     }
   } catch (error) {
-    console.warn(`Failed to read OpenCode SDK version: ${error}`);
+    logger.warn(`Failed to read OpenCode SDK version: ${error}`);
   }
   return "@opencode-ai/sdk@unknown";
 }
 
+/**
+ * Command starting the OpenCode server for a task.
+ */
+const SERVE_COMMAND = { program: "opencode", args: ["serve", "--hostname=127.0.0.1", "--port=0"] };
+
+/**
+ * How long an OpenCode server may take to start listening.
+ */
+const SERVER_START_TIMEOUT_MS = 15_000;
+
+/**
+ * OpenCode's data, cache, config, and state directories, which its server writes while running.
+ */
+function getOpencodeStateDirs(): string[] {
+  const home = homedir();
+  return [
+    join(process.env.XDG_DATA_HOME || join(home, ".local", "share"), "opencode"),
+    join(process.env.XDG_CACHE_HOME || join(home, ".cache"), "opencode"),
+    join(process.env.XDG_CONFIG_HOME || join(home, ".config"), "opencode"),
+    join(process.env.XDG_STATE_HOME || join(home, ".local", "state"), "opencode"),
+  ];
+}
+
 /**
  * Metrics collected during task execution.
  */
@@ -40,31 +78,128 @@ interface Metrics {
   iterations: number;
   inputTokens: number;
   outputTokens: number;
+  cacheReadTokens: number;
+  cacheWriteTokens: number;
   cost: number;
   output: string[];
+  limitReached: boolean;
+  timedOut: boolean;
+  cancelled: boolean;
 }
 
 /**
- * OpenCode SDK agent adapter.
+ * Add a message's or step's token counts to the metrics.
  */
+function addTokens(
+  metrics: Metrics,
+  tokens: { input?: number; output?: number; cache?: { read?: number; write?: number } },
+): void {
+  metrics.inputTokens += tokens.input || 0;
+  metrics.outputTokens += tokens.output || 0;
+  metrics.cacheReadTokens += tokens.cache?.read || 0;
+  metrics.cacheWriteTokens += tokens.cache?.write || 0;
+}
+
+/**
+ * OpenCode SDK agent adapter.
 export class OpencodeAgent implements Agent {
   private modelConfig: ModelConfig;
   private agentName: string;
+  private maxIterations: number;
+  private iterationListener: IterationListener | null = null;
+  private activeSession: { client: OpencodeClient; sessionId: string; metrics: Metrics } | null = null;
 
-  constructor(modelConfig?: ModelConfig, agentName: string = "opencode") {
+  /**
+   * @param modelConfig Model to use
+   * @param agentName Agent name reported in results
+   * @param maxIterations Default iteration limit; a task's `max_iterations` overrides it
+   */
+  constructor(
+    modelConfig?: ModelConfig,
+    agentName: string = "opencode",
+    maxIterations: number = DEFAULT_MAX_ITERATIONS,
+  ) {
     this.modelConfig = modelConfig || DEFAULT_MODEL;
     this.agentName = agentName;
+    this.maxIterations = maxIterations;
   }
 
   name(): string {
     return this.agentName;
   }
 
+  capabilities(): AgentCapability[] {
+    return ["read", "write", "bash", "web", "multi_turn", "mcp", "token_reporting"];
+  }
+
+  setIterationListener(listener: IterationListener | null): void {
+    this.iterationListener = listener;
+  }
+
+  /**
+   * Abort the running session; the current execution stops after it goes idle.
+   */
+  async cancel(): Promise<void> {
+    const session = this.activeSession;
+    if (!session) {
+      return;
+    }
+    session.metrics.cancelled = true;
+    logger.debug(`Cancelling OpenCode session ${session.sessionId}...`);
+    try {
+      await session.client.session.abort({ path: { id: session.sessionId } });
+    } catch (error) {
+      logger.debug(`Failed to abort session: ${error}`);
+    }
+  }
+
+  async describeInvocation(task: Task, workspace: string): Promise<AgentInvocation> {
+    const command = await sandboxCommand(SERVE_COMMAND, workspace, { writable: getOpencodeStateDirs() });
+    const argv = typeof command === "string" ? [command] : [command.program, ...command.args];
+    return {
+      argv,
+      model: `${this.modelConfig.providerID}/${this.modelConfig.modelID}`,
+      parameters: {
+        sdk: getOpencodeVersion(),
+        agent: this.selectAgentType(task),
+        max_iterations: task.max_iterations ?? this.maxIterations,
+        agent_timeout: task.agent_timeout ?? null,
+      },
+    };
+  }
+
   /**
    * Execute a task using OpenCode SDK.
    */
   async execute(task: Task, workspace: string): Promise<AgentResult> {
-    console.log(
+    return await this.withServer(task, workspace, (client) =>
+      this.runTask(task, client, workspace, [task.prompt]),
+    );
+  }
+
+  /**
+   * Execute a multi-step task, continuing the same OpenCode session between steps.
+   */
+  async executeSteps(
+    task: Task,
+    workspace: string,
+    prompts: string[],
+    onStep: StepCallback,
+  ): Promise<AgentResult> {
+    return await this.withServer(task, workspace, (client) =>
+      this.runTask(task, client, workspace, prompts, onStep),
+    );
+  }
+
+  /**
+   * Start an embedded OpenCode server in the workspace and run a callback against it.
+   */
+  private async withServer<T>(
+    task: Task,
+    workspace: string,
+    fn: (client: OpencodeClient) => Promise<T>,
+  ): Promise<T> {
+    logger.debug(
       `Starting OpenCode server for task ${task.id} in workspace: ${workspace}...`,
     );
 
@@ -75,43 +210,86 @@ export class OpencodeAgent implements Agent {
       // Change to workspace directory before starting server
       // This ensures the OpenCode agent starts with the correct working directory
       process.chdir(workspace);
-      console.log(`Changed working directory to: ${process.cwd()}`);
+      logger.debug(`Changed working directory to: ${process.cwd()}`);
 
-      // Start embedded OpenCode server for this task
-      const { server, client } = await createOpencode({
-        port: 0, // Auto-assign port
-      });
+      // Start an OpenCode server for this task (inside the sandbox when one is configured)
+      const { server, client } = await this.startServer(workspace);
 
       try {
-        return await this.runTask(task, client, workspace);
+        return await fn(client);
       } finally {
         // Always cleanup
-        console.log(`Closing OpenCode server...`);
+        logger.debug(`Closing OpenCode server...`);
         try {
           await server.close();
         } catch (error) {
-          console.warn("Warning: Failed to close OpenCode server:", error);
+          logger.warn("Failed to close OpenCode server:", error);
         }
       }
     } finally {
       // Restore original working directory
       process.chdir(originalCwd);
-      console.log(`Restored working directory to: ${process.cwd()}`);
+      logger.debug(`Restored working directory to: ${process.cwd()}`);
+    }
+  }
+
+  /**
+   * Start `opencode serve` with the scrubbed agent environment, through the configured
+   * sandbox if any (the SDK's `createOpencode` passes on the whole environment and cannot
+   * be wrapped), and connect a client once it is listening.
+   */
+  private async startServer(
+    workspace: string,
+  ): Promise<{ server: { close(): Promise<void> }; client: OpencodeClient }> {
+    const sandbox = getSandbox()?.name;
+    const stateDirs = getOpencodeStateDirs();
+    await Promise.all(stateDirs.map((dir) => mkdir(dir, { recursive: true })));
+
+    const command = await sandboxCommand(SERVE_COMMAND, workspace, { writable: stateDirs });
+    const server = startProcess(command, {
+      cwd: workspace,
+      env: agentEnvironment(["OPENCODE_*", ...providerEnvVars(this.modelConfig.providerID)]),
+      inheritEnv: false,
+    });
+    const failure: { error: string | null } = { error: null };
+    server.done.catch((error) => {
+      failure.error = error instanceof Error ? error.message : String(error);
+    });
+
+    const deadline = Date.now() + SERVER_START_TIMEOUT_MS;
+    for (;;) {
+      const url = server.stdout.match(/opencode server listening.*?\bon\s+(https?:\/\/\S+)/)?.[1];
+      if (url) {
+        logger.debug(`OpenCode server${sandbox ? ` (${sandbox} sandbox)` : ""} listening on ${url}`);
+        return { server: { close: () => server.kill() }, client: createOpencodeClient({ baseUrl: url }) };
+      }
+      if (failure.error || server.exitCode !== null || Date.now() > deadline) {
+        await server.kill();
+        const reason = failure.error
+          ?? (server.exitCode !== null ? `exited with code ${server.exitCode}` : `not listening after ${SERVER_START_TIMEOUT_MS / 1000}s`);
+        throw new AgentError(
+          `OpenCode server failed to start${sandbox ? ` in the ${sandbox} sandbox` : ""}: ${reason}` +
+            (server.stderr.trim() ? `\n${server.stderr.trim()}` : ""),
+        );
+      }
+      await Bun.sleep(100);
     }
   }
 
   /**
-   * Run the task with OpenCode client.
+   * Run the task prompts in a single OpenCode session.
    */
   private async runTask(
     task: Task,
     client: OpencodeClient,
     workspace: string,
+    prompts: string[],
+    onStep?: StepCallback,
   ): Promise<AgentResult> {
     const startTime = Date.now();
 
     // Create session in the workspace directory
-    console.log(`Creating OpenCode session in workspace: ${workspace}...`);
+    logger.debug(`Creating OpenCode session in workspace: ${workspace}...`);
     const sessionResponse = await client.session.create({
       query: {
         directory: workspace,
@@ -123,15 +301,21 @@ export class OpencodeAgent implements Agent {
     }
 
     const sessionId = sessionResponse.data.id;
-    console.log(`Session created: ${sessionId}`);
+    logger.debug(`Session created: ${sessionId}`);
 
     const metrics: Metrics = {
       iterations: 0,
       inputTokens: 0,
       outputTokens: 0,
+      cacheReadTokens: 0,
+      cacheWriteTokens: 0,
       cost: 0,
       output: [],
+      limitReached: false,
+      timedOut: false,
+      cancelled: false,
     };
+    this.activeSession = { client, sessionId, metrics };
 
     // Reset processed messages and parts for this task
     this.processedMessages.clear();
@@ -139,69 +323,136 @@ export class OpencodeAgent implements Agent {
 
     // Build agent configuration based on task permissions
     const agentType = this.selectAgentType(task);
-
-    // Start event stream subscription for metrics collection
-    const eventPromise = this.captureMetrics(client, workspace, metrics);
+    const maxIterations = task.max_iterations ?? this.maxIterations;
+
+    // Abort the session once the task's agent timeout elapses
+    const timeoutSecs = task.agent_timeout;
+    const timer = timeoutSecs
+      ? setTimeout(() => {
+          metrics.timedOut = true;
+          logger.warn(`Agent timeout of ${timeoutSecs}s reached, aborting session...`);
+          client.session.abort({ path: { id: sessionId } }).catch((error) => {
+            logger.debug(`Failed to abort session: ${error}`);
+          });
+        }, timeoutSecs * 1000)
+      : null;
 
     try {
-      // Send task prompt
-      console.log(`Sending prompt to OpenCode...`);
-      await client.session.prompt({
-        path: { id: sessionId },
-        body: {
-          parts: [
-            {
-              type: "text",
-              text: task.prompt,
-            },
-          ],
-          agent: agentType,
-          model: this.modelConfig,
-          // Note: Tool permissions are controlled at the agent level in OpenCode
-          // We would need to create custom agents for different permission sets
-        },
-      });
+      for (let step = 0; step < prompts.length; step++) {
+        // Start event stream subscription for metrics collection
+        const eventPromise = this.captureMetrics(
+          client,
+          sessionId,
+          metrics,
+          maxIterations,
+        );
+
+        // Send task prompt (later steps continue the same session)
+        logger.debug(
+          prompts.length > 1
+            ? `Sending step ${step + 1}/${prompts.length} to OpenCode...`
+            : `Sending prompt to OpenCode...`,
+        );
+        await client.session.prompt({
+          path: { id: sessionId },
+          body: {
+            parts: [
+              {
+                type: "text",
+                text: prompts[step],
+              },
+            ],
+            agent: agentType,
+            model: this.modelConfig,
+            // Note: Tool permissions are controlled at the agent level in OpenCode
+            // We would need to create custom agents for different permission sets
+          },
+        });
+
+        // Wait for session to complete (event stream will resolve)
+        await eventPromise;
+
+        if (metrics.timedOut) {
+          throw new TimeoutError(timeoutSecs!, { taskId: task.id, phase: "agent" });
+        }
+
+        if (metrics.cancelled) {
+          logger.warn(`Stopping: execution cancelled`);
+          break;
+        }
 
-      // Wait for session to complete (event stream will resolve)
-      await eventPromise;
+        if (metrics.limitReached) {
+          logger.warn(`Stopping: iteration limit of ${maxIterations} reached`);
+          break;
+        }
 
-      const durationSecs = (Date.now() - startTime) / 1000;
+        if (onStep && !(await onStep(step, this.buildResult(metrics, startTime, "")))) {
+          logger.debug(`Stopping after step ${step + 1}`);
+          break;
+        }
+      }
 
       // Get full conversation history after completion
-      console.log(`Retrieving full conversation history...`);
+      logger.debug(`Retrieving full conversation history...`);
       const conversationOutput = await this.getConversationHistory(
         client,
         sessionId,
       );
 
-      console.log(
+      logger.debug(
         `Task completed: ${metrics.iterations} iterations, ${metrics.inputTokens + metrics.outputTokens} tokens`,
       );
-      console.log(
+      logger.debug(
         `Agent output length: ${conversationOutput.length} characters`,
       );
 
-      return {
-        success: true, // Will be determined by verification
-        output: conversationOutput,
-        iterations: metrics.iterations,
-        tokensUsed: metrics.inputTokens + metrics.outputTokens,
-        cost: metrics.cost,
-        durationSecs,
-        agentVersion: getOpencodeVersion(),
-        modelName: `${this.modelConfig.providerID}/${this.modelConfig.modelID}`,
-      };
+      return this.buildResult(metrics, startTime, conversationOutput);
     } catch (error) {
-      throw new AgentError(`OpenCode execution failed: ${error}`);
+      // Keep the category of timeouts and errors raised above
+      if (error instanceof BenchError) {
+        throw error;
+      }
+      throw new AgentError(`OpenCode execution failed: ${error}`, { taskId: task.id, phase: "agent" });
+    } finally {
+      this.activeSession = null;
+      if (timer) {
+        clearTimeout(timer);
+      }
     }
   }
 
+  /**
+   * Build an agent result from the metrics collected so far.
+   */
+  private buildResult(
+    metrics: Metrics,
+    startTime: number,
+    output: string,
+  ): AgentResult {
+    return {
+      success: true, // Will be determined by verification
+      output,
+      iterations: metrics.iterations,
+      tokensUsed: metrics.inputTokens + metrics.outputTokens,
+      usage: {
+        input: metrics.inputTokens,
+        output: metrics.outputTokens,
+        cache_read: metrics.cacheReadTokens,
+        cache_write: metrics.cacheWriteTokens,
+      },
+      cost: metrics.cost,
+      durationSecs: (Date.now() - startTime) / 1000,
+      agentVersion: getOpencodeVersion(),
+      modelName: `${this.modelConfig.providerID}/${this.modelConfig.modelID}`,
+    };
+  }
+
   /**
    * Select appropriate OpenCode agent type based on task category.
    */
   private selectAgentType(task: Task): string {
     // Use 'plan' agent for read-only tasks, 'build' for others
-    if (!task.permissions.write && !task.permissions.bash) {
+    if (isReadOnly(resolveCapabilities(task.permissions))) {
       return "plan";
     }
     return "build";
@@ -209,13 +460,15 @@ export class OpencodeAgent implements Agent {
 
   /**
    * Subscribe to event stream and capture metrics.
+   * Aborts the session once the iteration limit is reached.
    */
   private async captureMetrics(
     client: OpencodeClient,
-    _workspace: string,
+    sessionId: string,
     metrics: Metrics,
+    maxIterations: number,
   ): Promise<void> {
-    console.log(`Subscribing to event stream...`);
+    logger.debug(`Subscribing to event stream...`);
 
     try {
       // Subscribe to SSE event stream
@@ -226,6 +479,13 @@ export class OpencodeAgent implements Agent {
         switch (event.type) {
           case "message.updated":
             await this.handleMessageUpdate(event, metrics);
+            if (metrics.iterations >= maxIterations && !metrics.limitReached) {
+              metrics.limitReached = true;
+              logger.warn(
+                `Iteration limit reached (${maxIterations}), aborting session...`,
+              );
+              await client.session.abort({ path: { id: sessionId } });
+            }
             break;
 
           case "message.part.updated":
@@ -233,7 +493,7 @@ export class OpencodeAgent implements Agent {
             break;
 
           case "session.idle":
-            console.log(`Session idle - task completed`);
+            logger.debug(`Session idle - task completed`);
             return; // Session completed
 
           case "session.error":
@@ -251,7 +511,7 @@ export class OpencodeAgent implements Agent {
         throw error;
       }
       // If stream ends normally, that's fine
-      console.log(`Event stream ended`);
+      logger.debug(`Event stream ended`);
     }
   }
 
@@ -290,8 +550,7 @@ export class OpencodeAgent implements Agent {
 
       // Accumulate tokens from message info
       if (msg.tokens) {
-        metrics.inputTokens += msg.tokens.input || 0;
-        metrics.outputTokens += msg.tokens.output || 0;
+        addTokens(metrics, msg.tokens);
       }
 
       // Also check for tokens in step-finish parts
@@ -300,8 +559,7 @@ export class OpencodeAgent implements Agent {
           const partID = part.id;
           if (!this.processedStepFinishParts.has(partID)) {
             this.processedStepFinishParts.add(partID);
-            metrics.inputTokens += part.tokens.input || 0;
-            metrics.outputTokens += part.tokens.output || 0;
+            addTokens(metrics, part.tokens);
             metrics.cost += part.cost || 0;
           }
         }
@@ -316,9 +574,14 @@ export class OpencodeAgent implements Agent {
         metrics.cost += msg.cost;
       }
 
-      console.log(
+      logger.debug(
         `  Iteration ${metrics.iterations}: ${metrics.inputTokens + metrics.outputTokens} tokens`,
       );
+      this.iterationListener?.({
+        iteration: metrics.iterations,
+        tokensUsed: metrics.inputTokens + metrics.outputTokens,
+        cost: metrics.cost,
+      });
     }
   }
 
@@ -345,8 +608,7 @@ export class OpencodeAgent implements Agent {
       this.processedStepFinishParts.add(partID);
 
       if (part.tokens) {
-        metrics.inputTokens += part.tokens.input || 0;
-        metrics.outputTokens += part.tokens.output || 0;
+        addTokens(metrics, part.tokens);
       }
 
       if (part.cost) {
@@ -368,7 +630,7 @@ export class OpencodeAgent implements Agent {
       });
 
       if (!messagesResponse.data) {
-        console.warn("No messages data returned from session");
+        logger.warn("No messages data returned from session");
         return "";
       }
 
@@ -400,52 +662,47 @@ export class OpencodeAgent implements Agent {
 
       return conversationParts.join("\n\n");
     } catch (error) {
-      console.warn(`Failed to retrieve conversation history: ${error}`);
+      logger.warn(`Failed to retrieve conversation history: ${error}`);
       return "";
     }
   }
 }
 
 /**
- * Build OpenCode agent configuration from task permissions.
+ * Build OpenCode agent configuration from task capabilities.
  * TODO: This would be used to create custom agent configs, but for now
  * we use the built-in 'build' and 'plan' agents.
  */
 export function buildAgentConfig(task: Task): any {
+  const capabilities: Capabilities = resolveCapabilities(task.permissions);
   const config: any = {
     tools: {},
     permission: {},
   };
 
   // Map tools
-  if (task.permissions.read) {
+  if (capabilities.read) {
     config.tools.Read = true;
     config.tools.Glob = true;
     config.tools.Grep = true;
   }
-  if (task.permissions.write) {
+  if (capabilities.write) {
     config.tools.Write = true;
     config.tools.Edit = true;
   }
-  if (task.permissions.bash) {
+  if (capabilities.execute) {
     config.tools.Bash = true;
   }
-  if (task.permissions.web_fetch) {
+  if (capabilities.network) {
     config.tools.WebFetch = true;
     config.tools.WebSearch = true;
   }
 
-  // Map permission mode
-  if (
-    task.permissions.mode === "dontAsk" ||
-    task.permissions.mode === "bypassPermissions"
-  ) {
-    config.permission.edit = "allow";
-    config.permission.bash = "allow";
-  } else {
-    config.permission.edit = "ask";
-    config.permission.bash = "ask";
-  }
+  // Map approval policy (OpenCode has no separate bypass mode)
+  const permission = capabilities.approval === "ask" ? "ask" : "allow";
+  config.permission.edit = permission;
+  config.permission.bash = permission;
+  config.permission.webfetch = permission;
 
   // Map max_iterations
   if (task.max_iterations) {
@@ -0,0 +1,173 @@
+/**
+ * OpenHands agent adapter.
+ *
+ * Runs OpenHands' headless mode (`python -m openhands.core.main -t <prompt>`) in its
+ * docker image. OpenHands starts a runtime container of its own through the docker
+ * socket, with the workspace mounted at `/workspace`, and saves the execution's
+ * trajectory (its event log) to the scratch directory, from which the output, iterations,
+ * and usage are read. Task permissions map onto OpenHands' agent settings:
+ *
+ *   write: false      →  the workspace is mounted read-only
+ *   execute: false    →  AGENT_ENABLE_CMD=false (no shell tool)
+ *   network: false    →  AGENT_ENABLE_BROWSING=false (no browser tool)
+ *
+ * Headless mode never asks for confirmation, so every approval policy behaves like
+ * `bypass`. The model is passed to LiteLLM as is (`anthropic/claude-sonnet-4-5`) and the
+ * key comes from `LLM_API_KEY`, which `[agent] env` has to pass on.
+ */
+
+import { readFile } from 'fs/promises';
+import { homedir } from 'os';
+import { join } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import type { ExecResult } from '../utils/exec.js';
+import { AgentError } from '../utils/errors.js';
+import { emptyUsage } from '../evaluator/pricing.js';
+import type { Capabilities } from './permissions.js';
+import { CliAgent, type CliCommand, type CliOutput } from './cli.js';
+
+/**
+ * OpenHands release the adapter runs (app and runtime images).
+ */
+export const OPENHANDS_VERSION = '0.59';
+const OPENHANDS_IMAGE = `docker.all-hands.dev/all-hands-ai/openhands:${OPENHANDS_VERSION}`;
+const RUNTIME_IMAGE = `docker.all-hands.dev/all-hands-ai/runtime:${OPENHANDS_VERSION}-nikolaik`;
+
+/**
+ * Where the scratch directory is mounted in the OpenHands container.
+ */
+const TRAJECTORY_DIR = '/trajectory';
+
+/**
+ * Trajectory events that carry metrics (other fields are ignored).
+ */
+interface OpenHandsEvent {
+  source?: string;
+  action?: string;
+  observation?: string;
+  message?: string;
+  args?: { final_thought?: string; content?: string };
+  extras?: { agent_state?: string; reason?: string };
+  llm_metrics?: {
+    accumulated_cost?: number;
+    accumulated_token_usage?: {
+      prompt_tokens?: number;
+      completion_tokens?: number;
+      cache_read_tokens?: number;
+      cache_write_tokens?: number;
+    };
+  };
+}
+
+/**
+ * Parse an OpenHands trajectory: the agent's final message, one iteration per agent
+ * action, and the accumulated usage and cost of its last metrics.
+ * @throws AgentError If the agent ended in the error state
+ */
+export function parseOpenHandsTrajectory(events: OpenHandsEvent[]): CliOutput {
+  let output = '';
+  let iterations = 0;
+  let metrics: OpenHandsEvent['llm_metrics'] | null = null;
+
+  for (const event of events) {
+    if (event.source === 'agent' && event.action && event.action !== 'change_agent_state' && event.action !== 'system') {
+      iterations++;
+      if (event.action === 'finish') {
+        output = event.args?.final_thought || event.message || output;
+      } else if (event.action === 'message') {
+        output = event.args?.content || event.message || output;
+      }
+    }
+    if (event.observation === 'agent_state_changed' && event.extras?.agent_state === 'error') {
+      throw new AgentError(`OpenHands failed: ${event.extras.reason || event.message || 'agent error'}`, { phase: 'agent' });
+    }
+    if (event.llm_metrics) {
+      metrics = event.llm_metrics;
+    }
+  }
+
+  const tokens = metrics?.accumulated_token_usage;
+  const usage = tokens ? emptyUsage() : null;
+  if (usage && tokens) {
+    // Prompt tokens include the cached ones
+    usage.cache_read = tokens.cache_read_tokens ?? 0;
+    usage.cache_write = tokens.cache_write_tokens ?? 0;
+    usage.input = Math.max(0, (tokens.prompt_tokens ?? 0) - usage.cache_read - usage.cache_write);
+    usage.output = tokens.completion_tokens ?? 0;
+  }
+  return { output, iterations, usage, cost: metrics?.accumulated_cost ?? null };
+}
+
+/**
+ * OpenHands adapter (headless mode in docker).
+ */
+export class OpenHandsAgent extends CliAgent {
+  protected readonly binary = 'docker';
+  protected readonly envVars = ['LLM_*', 'DOCKER_*'];
+
+  name(): string {
+    return 'openhands';
+  }
+
+  capabilities(): AgentCapability[] {
+    return ['read', 'write', 'bash', 'web', 'token_reporting'];
+  }
+
+  /**
+   * `~/.openhands` (settings and file store).
+   */
+  protected stateDirs(): string[] {
+    return [join(homedir(), '.openhands')];
+  }
+
+  protected parameters(task: Task): Record<string, unknown> {
+    return { ...super.parameters(task), image: OPENHANDS_IMAGE, max_iterations: task.max_iterations ?? this.maxIterations };
+  }
+
+  protected buildCommand(task: Task, workspace: string, capabilities: Capabilities, scratch: string): CliCommand {
+    const env: Record<string, string> = {
+      SANDBOX_RUNTIME_CONTAINER_IMAGE: RUNTIME_IMAGE,
+      SANDBOX_VOLUMES: `${workspace}:/workspace:${capabilities.write ? 'rw' : 'ro'}`,
+      SANDBOX_USER_ID: String(process.getuid?.() ?? 1000),
+      LLM_MODEL: this.model,
+      SAVE_TRAJECTORY_PATH: `${TRAJECTORY_DIR}/trajectory.json`,
+      AGENT_ENABLE_CMD: String(capabilities.execute),
+      AGENT_ENABLE_BROWSING: String(capabilities.network),
+      LOG_ALL_EVENTS: 'true',
+    };
+    const args = [
+      'run', '--rm', '--pull=missing',
+      ...Object.entries(env).flatMap(([name, value]) => ['-e', `${name}=${value}`]),
+      // Values from the agent environment
+      '-e', 'LLM_API_KEY',
+      '-e', 'LLM_BASE_URL',
+      '-v', '/var/run/docker.sock:/var/run/docker.sock',
+      '-v', `${join(homedir(), '.openhands')}:/.openhands`,
+      '-v', `${scratch}:${TRAJECTORY_DIR}:rw`,
+      '--add-host', 'host.docker.internal:host-gateway',
+      OPENHANDS_IMAGE,
+      'python', '-m', 'openhands.core.main',
+      '-t', task.prompt,
+      '-i', String(task.max_iterations ?? this.maxIterations),
+      ...this.extraArgs,
+    ];
+    return { program: 'docker', args };
+  }
+
+  protected async parseOutput(_result: ExecResult, scratch: string): Promise<CliOutput> {
+    let events: OpenHandsEvent[];
+    try {
+      events = JSON.parse(await readFile(join(scratch, 'trajectory.json'), 'utf-8'));
+    } catch (error) {
+      throw new AgentError(`OpenHands saved no trajectory: ${error instanceof Error ? error.message : error}`, { phase: 'agent' });
+    }
+    return parseOpenHandsTrajectory(Array.isArray(events) ? events : []);
+  }
+
+  /**
+   * The image's release rather than docker's version.
+   */
+  protected async version(): Promise<string | null> {
+    return `openhands@${OPENHANDS_VERSION}`;
+  }
+}
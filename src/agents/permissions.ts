@@ -0,0 +1,95 @@
+/**
+ * Agent-neutral capability model.
+ *
+ * Tasks declare what an agent may do (read, write, execute, network) and how
+ * actions are approved; each adapter translates these capabilities into its own
+ * flags, tool names, and permission settings.
+ */
+
+import type { AgentTool, PermissionsConfig } from '../core/task.js';
+
+/**
+ * How agent actions are approved.
+ * - auto: every permitted action is approved without prompting
+ * - bypass: permission checks are skipped entirely
+ * - ask: the agent asks before each sensitive action
+ */
+export type ApprovalPolicy = 'auto' | 'bypass' | 'ask';
+
+/**
+ * Capabilities granted to an agent for a task.
+ */
+export interface Capabilities {
+  read: boolean;
+  write: boolean;
+  execute: boolean;
+  network: boolean;
+  approval: ApprovalPolicy;
+}
+
+/**
+ * The capability each agent tool exercises. Tools keep their names (`bash`, `web`) in
+ * `requires_tools` and `Agent.capabilities()`; this is where they meet task permissions.
+ */
+export const TOOL_CAPABILITIES: Record<AgentTool, Exclude<keyof Capabilities, 'approval'>> = {
+  read: 'read',
+  write: 'write',
+  bash: 'execute',
+  web: 'network',
+};
+
+/**
+ * Legacy permission modes and the approval policy each maps to.
+ */
+const MODE_APPROVAL: Record<NonNullable<PermissionsConfig['mode']>, ApprovalPolicy> = {
+  dontAsk: 'auto',
+  bypassPermissions: 'bypass',
+  default: 'ask',
+};
+
+/**
+ * Resolve a task's permissions into neutral capabilities.
+ * Neutral fields (`execute`, `network`, `approval`) take precedence over the
+ * legacy `bash`, `web_fetch`, and `mode` fields.
+ */
+export function resolveCapabilities(permissions: PermissionsConfig): Capabilities {
+  return {
+    read: permissions.read,
+    write: permissions.write,
+    execute: permissions.execute ?? permissions.bash,
+    network: permissions.network ?? permissions.web_fetch,
+    approval: permissions.approval ?? (permissions.mode ? MODE_APPROVAL[permissions.mode] : 'ask'),
+  };
+}
+
+/**
+ * Tools a task requires that its capabilities do not let the agent use.
+ */
+export function ungrantedTools(tools: AgentTool[], capabilities: Capabilities): AgentTool[] {
+  return tools.filter(tool => !capabilities[TOOL_CAPABILITIES[tool]]);
+}
+
+/**
+ * Whether the capabilities only allow inspecting the workspace.
+ */
+export function isReadOnly(capabilities: Capabilities): boolean {
+  return !capabilities.write && !capabilities.execute;
+}
+
+/**
+ * Why a task's permissions give the agent the run of the host, if they do:
+ * approval checks are bypassed, or shell commands run without approval.
+ * Without a sandbox (`[sandbox] backend = "off"`, the default) these reach everything the
+ * operator's user can, so runs need acknowledgment (`--yes` or the prompt).
+ */
+export function describeDangerousPermissions(permissions: PermissionsConfig): string[] {
+  const capabilities = resolveCapabilities(permissions);
+  const reasons: string[] = [];
+  if (capabilities.approval === 'bypass') {
+    reasons.push('permission checks bypassed');
+  }
+  if (capabilities.execute && capabilities.approval !== 'ask') {
+    reasons.push('unrestricted shell access');
+  }
+  return reasons;
+}
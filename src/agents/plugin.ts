@@ -0,0 +1,203 @@
+/**
+ * External agent plugins (`--agent plugin:<path>`).
+ *
+ * A plugin is an executable speaking a JSON protocol over stdin and stdout, so agents can
+ * be added without changing the harness. It is started once per request, gets one JSON
+ * request on stdin (then end of input), and answers on stdout:
+ *
+ *   describe  →  {"name": "...", "version": "...", "capabilities": ["read", "write", "bash"]}
+ *   execute   →  {"type": "result", "output": "...", "iterations": 3,
+ *                 "usage": {"input": 1200, "output": 300}, "cost": 0.01}
+ *            or  {"type": "error", "message": "..."}
+ *
+ * An execute request carries the task (`id`, `title`, `prompt`, `category`, `difficulty`,
+ * `max_iterations`), the `workspace` (also the plugin's working directory), the `model`,
+ * the resolved `permissions`, and `timeout_secs`. The answer is the last JSON line of the
+ * plugin's stdout; other lines and stderr are left to the plugin (debug output). Plugins
+ * run like CLI agents: in the sandbox, with the scrubbed environment, killed on timeout,
+ * and a non-zero exit is an `AgentError`. `describe` is asked the same way, once, in
+ * `prepare()` before a run, and the described name is the agent's name. Requests carry
+ * `protocol` (`PLUGIN_PROTOCOL_VERSION`), which changes only incompatibly. See
+ * `examples/agent-plugin.ts` for a reference plugin.
+ */
+
+import { mkdtemp, rm } from 'fs/promises';
+import { tmpdir } from 'os';
+import { basename, join, resolve } from 'path';
+import { z } from 'zod';
+import { AgentCapabilitySchema, type AgentCapability, type Task } from '../core/task.js';
+import { startProcess, type ExecResult } from '../utils/exec.js';
+import { AgentError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+import { disposeSpoolFiles } from '../utils/spool.js';
+import { sandboxCommand } from '../sandbox/index.js';
+import { agentEnvironment, providerEnvVars } from './env.js';
+import type { Capabilities } from './permissions.js';
+import { DEFAULT_MAX_ITERATIONS } from './types.js';
+import { CliAgent, type CliCommand, type CliOutput } from './cli.js';
+
+export const PLUGIN_PROTOCOL_VERSION = 1;
+
+/**
+ * Seconds a plugin may take to answer `describe`.
+ */
+const DESCRIBE_TIMEOUT_SECS = 10;
+
+/**
+ * Answer to `describe`.
+ */
+export const PluginDescriptionSchema = z.object({
+  // Becomes the agent's name, which appears in result and temp directory names
+  name: z.string().regex(/^[\w.-]+$/, 'name must consist of letters, digits, ".", "_", and "-"'),
+  version: z.string().nullable().default(null),
+  capabilities: z.array(AgentCapabilitySchema).default(['read', 'write', 'bash']),
+});
+export type PluginDescription = z.infer<typeof PluginDescriptionSchema>;
+
+/**
+ * Answer to `execute`.
+ */
+export const PluginAnswerSchema = z.discriminatedUnion('type', [
+  z.object({
+    type: z.literal('result'),
+    output: z.string().default(''),
+    iterations: z.number().int().nonnegative().default(1),
+    usage: z
+      .object({
+        input: z.number().nonnegative(),
+        output: z.number().nonnegative(),
+        cache_read: z.number().nonnegative().default(0),
+        cache_write: z.number().nonnegative().default(0),
+      })
+      .nullable()
+      .default(null),
+    cost: z.number().nonnegative().nullable().default(null),
+  }),
+  z.object({ type: z.literal('error'), message: z.string() }),
+]);
+
+/**
+ * Parse a plugin's answer to `execute`: the last JSON line of its stdout.
+ * @throws AgentError If there is no valid answer, or the answer is an error
+ */
+export function parsePluginAnswer(stdout: string): CliOutput {
+  const line = stdout.split('\n').map(text => text.trim()).filter(text => text.startsWith('{')).at(-1);
+  let answer: z.infer<typeof PluginAnswerSchema>;
+  try {
+    answer = PluginAnswerSchema.parse(JSON.parse(line ?? ''));
+  } catch (error) {
+    throw new AgentError(
+      line ? `Invalid plugin answer: ${error instanceof z.ZodError ? error.issues.map(issue => issue.message).join('; ') : error}` : 'Plugin exited without an answer',
+      { phase: 'agent' }
+    );
+  }
+  if (answer.type === 'error') {
+    throw new AgentError(`Plugin failed: ${answer.message}`, { phase: 'agent' });
+  }
+  return { output: answer.output, iterations: answer.iterations, usage: answer.usage, cost: answer.cost };
+}
+
+/**
+ * Agent backed by a plugin executable.
+ */
+export class PluginAgent extends CliAgent {
+  protected readonly binary: string;
+  protected readonly envVars: string[] = [];
+  private description: PluginDescription | null = null;
+
+  /**
+   * @param path The plugin executable (relative to the working directory)
+   * @param model Model as `provider/model`, passed on to the plugin
+   */
+  constructor(path: string, model: string, maxIterations: number = DEFAULT_MAX_ITERATIONS) {
+    super(model, maxIterations);
+    this.binary = resolve(path);
+  }
+
+  name(): string {
+    return this.describe().name;
+  }
+
+  capabilities(): AgentCapability[] {
+    return [...this.describe().capabilities];
+  }
+
+  /**
+   * Ask the plugin to describe itself (once), in the sandbox and with the agent environment
+   * like an execution. A plugin that cannot answer is described by its file name with the
+   * default capabilities; executing it reports the actual error.
+   */
+  async prepare(): Promise<void> {
+    if (this.description) {
+      return;
+    }
+    const scratch = await mkdtemp(join(tmpdir(), 'agent-bench-plugin-'));
+    try {
+      const wrapped = await sandboxCommand({ program: this.binary, args: [] }, scratch);
+      const result = await startProcess(wrapped, {
+        cwd: scratch,
+        env: agentEnvironment(providerEnvVars(this.model)),
+        inheritEnv: false,
+        timeoutSecs: DESCRIBE_TIMEOUT_SECS,
+        input: JSON.stringify({ protocol: PLUGIN_PROTOCOL_VERSION, type: 'describe' }),
+      }).done;
+      await disposeSpoolFiles(result.stdoutFile, result.stderrFile);
+      if (result.timedOut) {
+        throw new Error(`no answer within ${DESCRIBE_TIMEOUT_SECS}s`);
+      }
+      this.description = PluginDescriptionSchema.parse(JSON.parse(result.stdout.trim().split('\n').at(-1) ?? ''));
+    } catch (error) {
+      logger.warn(`Plugin ${this.binary} did not describe itself: ${error instanceof Error ? error.message : error}`);
+      this.description = PluginDescriptionSchema.parse({ name: basename(this.binary).replace(/[^\w.-]/g, '_') });
+    } finally {
+      await rm(scratch, { recursive: true, force: true });
+    }
+  }
+
+  /**
+   * The plugin's `describe` answer.
+   * @throws AgentError If `prepare()` has not been awaited yet
+   */
+  describe(): PluginDescription {
+    if (!this.description) {
+      throw new AgentError(`Plugin ${this.binary} has not been described yet (await prepare() first)`);
+    }
+    return this.description;
+  }
+
+  protected parameters(task: Task): Record<string, unknown> {
+    return { ...super.parameters(task), plugin: this.binary, protocol: PLUGIN_PROTOCOL_VERSION };
+  }
+
+  protected buildCommand(task: Task, workspace: string, capabilities: Capabilities): CliCommand {
+    const request = {
+      protocol: PLUGIN_PROTOCOL_VERSION,
+      type: 'execute',
+      task: {
+        id: task.id,
+        title: task.title,
+        prompt: task.prompt,
+        category: task.category,
+        difficulty: task.difficulty,
+        max_iterations: task.max_iterations ?? this.maxIterations,
+      },
+      workspace,
+      model: this.model,
+      permissions: capabilities,
+      timeout_secs: task.agent_timeout ?? null,
+    };
+    return { program: this.binary, args: this.extraArgs, input: JSON.stringify(request) };
+  }
+
+  protected async parseOutput(result: ExecResult): Promise<CliOutput> {
+    return parsePluginAnswer(await this.fullStdout(result));
+  }
+
+  /**
+   * `<name>@<version>` from the plugin's description.
+   */
+  protected async version(): Promise<string | null> {
+    const { name, version } = this.describe();
+    return version ? `${name}@${version}` : null;
+  }
+}
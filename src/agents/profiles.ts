@@ -0,0 +1,134 @@
+/**
+ * Named agents (`[agents.<name>]`).
+ *
+ * A named agent bundles an adapter with a model, extra command-line flags, environment
+ * variables, and an iteration limit, and is selected like a built-in type:
+ * `--agent claude-opus`, or `--agent claude-opus@<provider/model>` to override its model.
+ * Results, suites, and recordings carry the name rather than the adapter's, so several
+ * configurations of one adapter can be compared side by side.
+ *
+ * Flags are appended to the adapter's command line (CLI adapters only). Variables are set
+ * on top of the agent environment, credentials' included, for the agent's executions;
+ * values starting with `$` name a variable of the harness environment, as for credentials.
+ */
+
+import type { Agent, AgentInvocation, AgentResult, IterationListener, StepCallback } from './types.js';
+import type { AgentCapability, Task } from '../core/task.js';
+import { ConfigError } from '../utils/errors.js';
+import { withAgentVariables } from './env.js';
+
+/**
+ * A configured named agent.
+ */
+export interface AgentProfile {
+  /** Agent type it runs: a built-in type or `plugin:<path>` */
+  adapter: string;
+  /** Model as `provider/model` (null: the default model) */
+  model: string | null;
+  /** Extra arguments for the adapter's CLI */
+  flags: string[];
+  /** Variables set for the agent (`$NAME` values are read from the harness environment) */
+  env: Record<string, string>;
+  /** Iteration limit for tasks that do not set `max_iterations` (null: `[agent] max_iterations`) */
+  maxIterations: number | null;
+}
+
+let profiles: Record<string, AgentProfile> = {};
+
+/**
+ * Set the configured named agents (the CLI and TaskRunner set them from `[agents.<name>]`).
+ */
+export function setAgentProfiles(config: Record<string, AgentProfile>): void {
+  profiles = { ...config };
+}
+
+export function getAgentProfiles(): Record<string, AgentProfile> {
+  return profiles;
+}
+
+/**
+ * A named agent, or null if none is configured under the name.
+ */
+export function getAgentProfile(name: string): AgentProfile | null {
+  return Object.hasOwn(profiles, name) ? profiles[name] : null;
+}
+
+/**
+ * Resolve a named agent's variables.
+ * @param env Environment `$NAME` values are read from (default: this process's)
+ * @throws ConfigError If a `$NAME` value refers to an unset variable
+ */
+export function resolveProfileEnv(
+  name: string,
+  profile: AgentProfile,
+  env: Record<string, string | undefined> = process.env
+): Record<string, string> {
+  const resolved: Record<string, string> = {};
+  for (const [variable, value] of Object.entries(profile.env)) {
+    if (value.startsWith('$')) {
+      const source = env[value.slice(1)];
+      if (!source) {
+        throw new ConfigError(`Agent ${name}: ${value.slice(1)} is not set (for ${variable})`);
+      }
+      resolved[variable] = source;
+    } else {
+      resolved[variable] = value;
+    }
+  }
+  return resolved;
+}
+
+/**
+ * Agent decorator that runs the wrapped adapter as a named agent: under the agent's name
+ * and with its variables.
+ */
+export class ProfileAgent implements Agent {
+  private profileName: string;
+  private inner: Agent;
+  private env: Record<string, string>;
+  executeSteps?: (task: Task, workspace: string, prompts: string[], onStep: StepCallback) => Promise<AgentResult>;
+
+  /**
+   * @param env The agent's resolved variables
+   */
+  constructor(name: string, inner: Agent, env: Record<string, string>) {
+    this.profileName = name;
+    this.inner = inner;
+    this.env = env;
+    if (inner.executeSteps) {
+      this.executeSteps = async (task, workspace, prompts, onStep) =>
+        await withAgentVariables(this.env, () => inner.executeSteps!(task, workspace, prompts, onStep));
+    }
+  }
+
+  name(): string {
+    return this.profileName;
+  }
+
+  async prepare(): Promise<void> {
+    // Plugins describe themselves with the agent's variables
+    await withAgentVariables(this.env, async () => await this.inner.prepare?.());
+  }
+
+  capabilities(): AgentCapability[] {
+    return this.inner.capabilities();
+  }
+
+  setIterationListener(listener: IterationListener | null): void {
+    this.inner.setIterationListener?.(listener);
+  }
+
+  async cancel(): Promise<void> {
+    await this.inner.cancel?.();
+  }
+
+  async describeInvocation(task: Task, workspace: string): Promise<AgentInvocation> {
+    const invocation = (await this.inner.describeInvocation?.(task, workspace)) ?? { argv: null, model: null, parameters: {} };
+    // Names only: values may be API keys
+    return { ...invocation, parameters: { ...invocation.parameters, agent: this.profileName, env: Object.keys(this.env) } };
+  }
+
+  async execute(task: Task, workspace: string): Promise<AgentResult> {
+    return await withAgentVariables(this.env, () => this.inner.execute(task, workspace));
+  }
+}
@@ -0,0 +1,125 @@
+/**
+ * Qwen Code CLI agent adapter.
+ *
+ * Runs `qwen --prompt <prompt> --output-format json` once per task and reads the response
+ * and per-model statistics from its JSON report. Task permissions choose the approval
+ * mode, as far as Qwen Code can express them:
+ *
+ *   no write                        →  --approval-mode plan (read-only)
+ *   write, no execute               →  --approval-mode auto-edit (edits approved, shell declined)
+ *   write and execute               →  --approval-mode yolo
+ *   approval ask                    →  --approval-mode default
+ *
+ * Prompts for approval go unanswered in non-interactive mode, so whatever a mode does not
+ * approve is declined. Network access is not controlled beyond the sandbox. Models are
+ * passed by their bare ID (`openai/qwen3-coder-plus` → `qwen3-coder-plus`) to Qwen Code's
+ * OpenAI-compatible client, configured through `OPENAI_API_KEY` and `OPENAI_BASE_URL` (or
+ * a stored Qwen login in `~/.qwen`).
+ */
+
+import { homedir } from 'os';
+import { join } from 'path';
+import type { AgentCapability, Task } from '../core/task.js';
+import type { ExecResult } from '../utils/exec.js';
+import { AgentError } from '../utils/errors.js';
+import { emptyUsage } from '../evaluator/pricing.js';
+import type { Capabilities } from './permissions.js';
+import { CliAgent, type CliCommand, type CliOutput } from './cli.js';
+
+/**
+ * The parts of the JSON report the adapter uses.
+ */
+interface QwenReport {
+  response?: string;
+  error?: { message?: string } | null;
+  stats?: {
+    models?: Record<string, {
+      api?: { totalRequests?: number };
+      tokens?: { prompt?: number; candidates?: number; cached?: number; thoughts?: number };
+    }>;
+  };
+}
+
+/**
+ * Parse Qwen Code's JSON report: the response, one iteration per API request, and usage
+ * summed over models (cached prompt tokens count as cache reads, thoughts as output).
+ * Output that is not a report is passed on as the response.
+ * @throws AgentError If the report carries an error
+ */
+export function parseQwenReport(stdout: string): CliOutput {
+  const start = stdout.indexOf('{');
+  let report: QwenReport;
+  try {
+    report = JSON.parse(stdout.slice(Math.max(0, start)));
+  } catch {
+    return { output: stdout.trim(), iterations: 1, usage: null, cost: null };
+  }
+  if (report.error) {
+    throw new AgentError(`Qwen Code failed: ${report.error.message ?? 'unknown error'}`, { phase: 'agent' });
+  }
+
+  const models = Object.values(report.stats?.models ?? {});
+  const usage = emptyUsage();
+  let iterations = 0;
+  for (const model of models) {
+    iterations += model.api?.totalRequests ?? 0;
+    const cached = model.tokens?.cached ?? 0;
+    usage.input += (model.tokens?.prompt ?? 0) - cached;
+    usage.cache_read += cached;
+    usage.output += (model.tokens?.candidates ?? 0) + (model.tokens?.thoughts ?? 0);
+  }
+  return { output: report.response?.trim() ?? '', iterations, usage: models.length > 0 ? usage : null, cost: null };
+}
+
+/**
+ * Qwen Code CLI adapter.
+ */
+export class QwenAgent extends CliAgent {
+  protected readonly binary = 'qwen';
+  protected readonly envVars = ['QWEN_*'];
+
+  name(): string {
+    return 'qwen';
+  }
+
+  capabilities(): AgentCapability[] {
+    return ['read', 'write', 'bash', 'web', 'token_reporting'];
+  }
+
+  /**
+   * `~/.qwen` (settings, login, session history).
+   */
+  protected stateDirs(): string[] {
+    return [join(homedir(), '.qwen')];
+  }
+
+  protected buildCommand(task: Task, _workspace: string, capabilities: Capabilities): CliCommand {
+    return {
+      program: 'qwen',
+      args: [
+        '--prompt', task.prompt,
+        '--model', this.model.slice(this.model.indexOf('/') + 1),
+        '--approval-mode', approvalMode(capabilities),
+        '--output-format', 'json',
+        ...this.extraArgs,
+      ],
+    };
+  }
+
+  protected async parseOutput(result: ExecResult): Promise<CliOutput> {
+    return parseQwenReport(await this.fullStdout(result));
+  }
+}
+
+/**
+ * Qwen Code approval mode for a task's capabilities.
+ */
+function approvalMode(capabilities: Capabilities): string {
+  if (!capabilities.write) {
+    return 'plan';
+  }
+  if (capabilities.approval === 'ask') {
+    return 'default';
+  }
+  return capabilities.execute ? 'yolo' : 'auto-edit';
+}
@@ -0,0 +1,229 @@
+/**
+ * Record/replay of agent executions.
+ *
+ * A recording holds an agent's result and the files it changed in the workspace, keyed by
+ * the task definition, agent, and model. Replaying one restores those files and returns the
+ * recorded result without starting the agent, so verification, scoring, and reporting can be
+ * developed (or run in CI) against canned agent outputs without spending tokens.
+ */
+
+import { chmod, mkdir, readFile, rm, stat, writeFile } from 'fs/promises';
+import { dirname, join, resolve } from 'path';
+import { createHash } from 'crypto';
+import type { Agent, AgentInvocation, AgentResult, IterationListener, StepCallback } from './types.js';
+import type { AgentCapability, Task } from '../core/task.js';
+import type { RecordMode } from '../core/config.js';
+import { diffManifests, readFileManifest } from '../core/workspace.js';
+import { AgentError } from '../utils/errors.js';
+import { isWithin, resolveWithin } from '../utils/files.js';
+import { logger } from '../utils/logger.js';
+
+/**
+ * Recording file format version.
+ */
+const RECORDING_VERSION = 1;
+
+/**
+ * A changed workspace file: its content (base64) and mode, or null if the agent deleted it.
+ */
+type RecordedFile = { content: string; mode: number } | null;
+
+/**
+ * A recorded agent execution.
+ */
+export interface AgentRecording {
+  version: number;
+  key: string;
+  task_id: string;
+  agent: string;
+  model: string;
+  recorded_at: string;
+  result: AgentResult;
+  files: Record<string, RecordedFile>;
+}
+
+export interface RecordingOptions {
+  mode: RecordMode;
+  /** Directory holding `<key>.json` recordings */
+  dir: string;
+  /** Model the agent runs, part of the recording key */
+  model: string;
+}
+
+/**
+ * Recording key: the task definition (with the workspace path normalized), agent, and model.
+ */
+export function getRecordingKey(task: Task, workspace: string, agent: string, model: string): string {
+  // Where the task file lives does not change what the agent is asked to do
+  const normalized = JSON.stringify({ ...task, file_path: undefined }).split(workspace).join('{{workspace}}');
+  return createHash('sha256').update(`${agent}\n${model}\n${normalized}`).digest('hex').substring(0, 32);
+}
+
+/**
+ * Wrap an agent with record/replay (returned unchanged when the mode is `off`).
+ */
+export function withRecording(agent: Agent, options: RecordingOptions): Agent {
+  return options.mode === 'off' ? agent : new RecordingAgent(agent, options);
+}
+
+/**
+ * Agent decorator that records executions of the wrapped agent or replays earlier ones.
+ * Multi-step tasks are passed through unrecorded (and cannot be replayed).
+ */
+export class RecordingAgent implements Agent {
+  private inner: Agent;
+  private options: RecordingOptions;
+  executeSteps?: (task: Task, workspace: string, prompts: string[], onStep: StepCallback) => Promise<AgentResult>;
+
+  constructor(inner: Agent, options: RecordingOptions) {
+    this.inner = inner;
+    this.options = options;
+    if (inner.executeSteps) {
+      this.executeSteps = async (task, workspace, prompts, onStep) => {
+        if (this.options.mode === 'replay') {
+          throw new AgentError(`Multi-step task ${task.id} cannot be replayed`, { taskId: task.id, phase: 'agent' });
+        }
+        logger.warn(`Multi-step task ${task.id} is not recorded`);
+        return await inner.executeSteps!(task, workspace, prompts, onStep);
+      };
+    }
+  }
+
+  name(): string {
+    return this.inner.name();
+  }
+
+  capabilities(): AgentCapability[] {
+    return this.inner.capabilities();
+  }
+
+  async prepare(): Promise<void> {
+    await this.inner.prepare?.();
+  }
+
+  setIterationListener(listener: IterationListener | null): void {
+    this.inner.setIterationListener?.(listener);
+  }
+
+  async cancel(): Promise<void> {
+    await this.inner.cancel?.();
+  }
+
+  async describeInvocation(task: Task, workspace: string): Promise<AgentInvocation> {
+    const invocation = (await this.inner.describeInvocation?.(task, workspace)) ?? { argv: null, model: this.options.model, parameters: {} };
+    return { ...invocation, parameters: { ...invocation.parameters, record_mode: this.options.mode } };
+  }
+
+  async execute(task: Task, workspace: string): Promise<AgentResult> {
+    const key = getRecordingKey(task, workspace, this.name(), this.options.model);
+    const path = join(this.options.dir, `${key}.json`);
+
+    if (this.options.mode !== 'record') {
+      const recording = await readRecording(path);
+      if (recording) {
+        await restoreFiles(workspace, recording.files);
+        logger.info(`Replayed agent execution recorded ${recording.recorded_at} (${Object.keys(recording.files).length} files)`);
+        // Recordings made before token usage was reported lack it
+        return { ...recording.result, usage: recording.result.usage ?? null };
+      }
+      if (this.options.mode === 'replay') {
+        throw new AgentError(
+          `No recording of ${task.id} for ${this.name()} with ${this.options.model} in ${this.options.dir} (record one with --record-mode record)`,
+          { taskId: task.id, phase: 'agent' }
+        );
+      }
+    }
+
+    const before = await readFileManifest(workspace);
+    const result = await this.inner.execute(task, workspace);
+    const changes = diffManifests(before, await readFileManifest(workspace));
+
+    const files: Record<string, RecordedFile> = {};
+    for (const file of [...changes.added, ...changes.modified]) {
+      const fullPath = join(workspace, file);
+      files[file] = {
+        content: (await readFile(fullPath)).toString('base64'),
+        mode: (await stat(fullPath)).mode & 0o777,
+      };
+    }
+    for (const file of changes.deleted) {
+      files[file] = null;
+    }
+
+    const recording: AgentRecording = {
+      version: RECORDING_VERSION,
+      key,
+      task_id: task.id,
+      agent: this.name(),
+      model: this.options.model,
+      recorded_at: new Date().toISOString(),
+      result,
+      files,
+    };
+    await mkdir(this.options.dir, { recursive: true });
+    await writeFile(path, JSON.stringify(recording), 'utf-8');
+    logger.debug(`Recorded agent execution to ${path}`);
+
+    return result;
+  }
+}
+
+/**
+ * Read a recording, or null if there is none.
+ * @throws AgentError If the recording exists but cannot be used
+ */
+async function readRecording(path: string): Promise<AgentRecording | null> {
+  let content: string;
+  try {
+    content = await readFile(path, 'utf-8');
+  } catch {
+    return null;
+  }
+
+  let recording: AgentRecording;
+  try {
+    recording = JSON.parse(content) as AgentRecording;
+  } catch (error) {
+    throw new AgentError(`Recording ${path} is not valid JSON: ${error instanceof Error ? error.message : error}`);
+  }
+  if (recording.version !== RECORDING_VERSION) {
+    throw new AgentError(`Recording ${path} has unsupported version ${recording.version}`);
+  }
+  return recording;
+}
+
+/**
+ * Apply recorded file changes to a workspace.
+ * @throws AgentError If a recorded path is outside the workspace (checked before any file is written)
+ */
+async function restoreFiles(workspace: string, files: Record<string, RecordedFile>): Promise<void> {
+  const root = resolve(workspace);
+  const outside = (file: string) => new AgentError(`Recording writes outside the workspace: ${file}`, { phase: 'agent' });
+  for (const file of Object.keys(files)) {
+    const fullPath = resolve(root, file);
+    if (fullPath === root || !isWithin(root, fullPath)) {
+      throw outside(file);
+    }
+  }
+
+  // Symlinks in the workspace must not lead a change elsewhere: the nearest existing
+  // ancestor is checked before any directory is created
+  for (const [file, recorded] of Object.entries(files)) {
+    if (recorded === null) {
+      // Deleting removes a symlink itself, so only its directory has to be inside
+      const parent = await resolveWithin(root, dirname(file));
+      if (!parent) {
+        throw outside(file);
+      }
+      await rm(resolve(root, file), { force: true });
+      continue;
+    }
+    const fullPath = await resolveWithin(root, file);
+    if (!fullPath) {
+      throw outside(file);
+    }
+    await mkdir(dirname(fullPath), { recursive: true });
+    await writeFile(fullPath, Buffer.from(recorded.content, 'base64'));
+    await chmod(fullPath, recorded.mode);
+  }
+}
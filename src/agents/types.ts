@@ -2,7 +2,8 @@
  * Base agent interface and types.
  */
 
-import { Task } from '../core/task.js';
+import { AgentCapability, Task } from '../core/task.js';
+import type { TokenUsage } from '../evaluator/pricing.js';
 
 /**
  * Result from an agent execution.
@@ -12,12 +13,46 @@ export interface AgentResult {
   output: string;
   iterations: number;
   tokensUsed: number | null;
+  /** Tokens by kind, for pricing (null if the agent does not report them) */
+  usage: TokenUsage | null;
+  /** USD cost as reported by the agent */
   cost: number | null;
   durationSecs: number;
   agentVersion: string | null;
   modelName: string | null;
 }
 
+/**
+ * How an agent is invoked for a task: enough to start it the same way again.
+ */
+export interface AgentInvocation {
+  /** Command line of the agent process (after sandbox wrapping), if the agent runs one */
+  argv: string[] | null;
+  /** Model as `provider/model` */
+  model: string | null;
+  /** Further settings that affect the agent's behavior (agent mode, iteration limit, ...) */
+  parameters: Record<string, unknown>;
+}
+
+/**
+ * Callback invoked after each step of a multi-step task.
+ * @param stepIndex Zero-based index of the completed step
+ * @param progress Agent metrics accumulated so far
+ * @returns Whether the agent should continue with the next step
+ */
+export type StepCallback = (stepIndex: number, progress: AgentResult) => Promise<boolean>;
+
+/**
+ * Progress reported after each agent iteration (one model turn).
+ */
+export interface AgentIteration {
+  iteration: number;
+  tokensUsed: number;
+  cost: number | null;
+}
+
+export type IterationListener = (progress: AgentIteration) => void;
+
 /**
  * Base interface for AI agent adapters.
  */
@@ -27,6 +62,20 @@ export interface Agent {
    */
   name(): string;
 
+  /**
+   * Declare what this agent can do; the runner skips tasks requiring capabilities it lacks
+   * (and warns for soft ones such as `token_reporting`).
+   */
+  capabilities(): AgentCapability[];
+
+  /**
+   * Finish setup that needs I/O (a plugin describing itself) before `name()` and
+   * `capabilities()` are used. The runner awaits it at the start of each run, with the
+   * run's audit log open, so it must be cheap to call again.
+   * Optional: agents without it are ready once constructed.
+   */
+  prepare?(): Promise<void>;
+
   /**
    * Execute a task in the given workspace.
    * @param task The task to execute
@@ -34,6 +83,42 @@ export interface Agent {
    * @returns Agent execution result
    */
   execute(task: Task, workspace: string): Promise<AgentResult>;
+
+  /**
+   * Execute a multi-step task, continuing the same agent session between steps.
+   * Optional: agents without session continuation cannot run multi-step tasks.
+   * @param task The task to execute
+   * @param workspace The workspace path
+   * @param prompts Rendered prompt for each step, in order
+   * @param onStep Called after each step; returning false stops the run
+   * @returns Agent execution result covering all completed steps
+   */
+  executeSteps?(
+    task: Task,
+    workspace: string,
+    prompts: string[],
+    onStep: StepCallback
+  ): Promise<AgentResult>;
+
+  /**
+   * Report progress after each iteration of subsequent executions.
+   * Optional: agents without it report no per-iteration progress.
+   * @param listener Listener to call, or null to stop reporting
+   */
+  setIterationListener?(listener: IterationListener | null): void;
+
+  /**
+   * Stop the running execution early (e.g. when the workspace exceeds its disk quota);
+   * `execute` then returns or throws as soon as the agent has stopped.
+   * Optional: agents without it run to completion.
+   */
+  cancel?(): Promise<void>;
+
+  /**
+   * Describe how a task will be run (recorded in the run's reproduction manifest).
+   * Optional: agents without it are recorded by name only.
+   */
+  describeInvocation?(task: Task, workspace: string): Promise<AgentInvocation>;
 }
 
 /**
@@ -66,3 +151,8 @@ export const DEFAULT_MODEL: ModelConfig = {
   providerID: 'anthropic',
   modelID: 'claude-sonnet-4-5',
 };
+
+/**
+ * Default maximum number of agent iterations when a task does not set `max_iterations`.
+ */
+export const DEFAULT_MAX_ITERATIONS = 20;
@@ -0,0 +1,54 @@
+/**
+ * Agents command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { discoverAgents } from '../../agents/discovery.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createAgentsCommand(config: RunnerConfig): Command {
+  const command = new Command('agents')
+    .description('List agent adapters with detected versions, capabilities, and configuration status')
+    .option('-m, --model <model>', 'Model to check credentials for (default from config)')
+    .action(async (options) => {
+      try {
+        const agents = await discoverAgents(options.model || config.defaultModel);
+
+        emit(
+          agents,
+          () => {
+            for (const agent of agents) {
+              const status = agent.ready ? chalk.green.bold('ready') : chalk.yellow.bold('not ready');
+              console.log(`${chalk.bold.cyan(agent.type)}  ${status}  ${chalk.gray(agent.description)}`);
+              if (agent.binary) {
+                console.log(`  Binary: ${agent.binaryPath
+                  ? `${agent.binaryPath}${agent.version ? ` (${agent.version})` : ''}`
+                  : chalk.red(`${agent.binary} not found on PATH`)}`);
+              }
+              console.log(`  Capabilities: ${agent.capabilities.join(', ')}`);
+              console.log(`  Model: ${agent.model} · credentials: ${agent.credentials ?? chalk.red('none found')}`);
+              console.log(chalk.gray(`  Use: --agent ${agent.type} or --agent ${agent.type}@${agent.model}\n`));
+            }
+          },
+          () => ({
+            columns: ['Agent', 'Ready', 'Version', 'Capabilities', 'Credentials'],
+            rows: agents.map(agent => [
+              agent.type,
+              agent.ready ? 'yes' : 'no',
+              agent.binary ? agent.version ?? (agent.binaryPath ? 'unknown' : 'not found') : null,
+              agent.capabilities.join(','),
+              agent.credentials,
+            ]),
+          })
+        );
+      } catch (error) {
+        logger.error(`Failed to list agents: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -0,0 +1,110 @@
+/**
+ * Cache command implementation (repository mirror management).
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { TaskLoader } from '../../core/loader.js';
+import { filterTasks, parseFilterExpression } from '../../core/filter.js';
+import { clearRepoCache, getRepoCacheDir, listCachedRepos } from '../../core/repo-cache.js';
+import { prefetchRepositories } from '../../core/prefetch.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+import { formatBytes } from '../../utils/disk.js';
+
+export function createCacheCommand(config: RunnerConfig): Command {
+  const command = new Command('cache')
+    .description('Manage cached mirrors of task repositories');
+
+  command
+    .command('list')
+    .description('List cached repository mirrors')
+    .action(async () => {
+      try {
+        const repos = await listCachedRepos();
+        if (repos.length === 0) {
+          logger.info(`No cached repositories in ${getRepoCacheDir()}`);
+          return;
+        }
+
+        emit(
+          repos,
+          () => {
+            for (const repo of repos) {
+              console.log(`${chalk.bold(repo.url || chalk.yellow('(unknown origin)'))}`);
+              console.log(chalk.gray(`  ${repo.path} · ${formatBytes(repo.sizeBytes)} · updated ${repo.updatedAt}`));
+            }
+            const total = repos.reduce((sum, repo) => sum + repo.sizeBytes, 0);
+            console.log(`\n${repos.length} repositories, ${formatBytes(total)}`);
+          },
+          () => ({
+            columns: ['URL', 'Size', 'Updated', 'Path'],
+            rows: repos.map(repo => [repo.url, formatBytes(repo.sizeBytes), repo.updatedAt, repo.path]),
+          })
+        );
+      } catch (error) {
+        logger.error(`Failed to list cache: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  command
+    .command('clear')
+    .description('Remove cached repository mirrors')
+    .argument('[match]', 'Only remove mirrors whose URL contains this text')
+    .action(async (match?: string) => {
+      try {
+        const removed = await clearRepoCache(match);
+        const freed = removed.reduce((sum, repo) => sum + repo.sizeBytes, 0);
+        logger.success(`Removed ${removed.length} cached repositories (${formatBytes(freed)})`);
+      } catch (error) {
+        logger.error(`Failed to clear cache: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  command
+    .command('prewarm')
+    .description('Fetch every repository referenced by a suite into the cache')
+    .option('-s, --suite <suite>', 'Task suite (all, category name)', 'all')
+    .option('--filter <expr>', 'Filter expression (e.g. "tag=suite:smoke")')
+    .option('--concurrency <n>', 'Repositories fetched at once (default: 4)')
+    .action(async (options) => {
+      try {
+        const loader = new TaskLoader(config.tasksDir);
+        const suiteTasks = options.suite === 'all'
+          ? await loader.loadAll()
+          : await loader.filterByCategory(options.suite);
+        const tasks = filterTasks(suiteTasks, {
+          expression: options.filter ? parseFilterExpression(options.filter) : undefined,
+        });
+
+        // Every pinned commit must be present afterwards
+        const concurrency = options.concurrency ? parseInt(options.concurrency, 10) : undefined;
+        const report = await prefetchRepositories(tasks, concurrency);
+        const total = report.fetched.length + report.failed.length;
+        if (total === 0) {
+          logger.warn(`No repositories referenced by ${tasks.length} tasks`);
+          return;
+        }
+
+        for (const url of report.fetched) {
+          logger.success(url);
+        }
+        for (const failure of report.failed) {
+          logger.error(failure.error);
+        }
+        if (report.failed.length > 0) {
+          logger.error(`${report.failed.length} of ${total} repositories could not be cached`);
+          process.exit(1);
+        }
+        logger.info(`Prewarmed ${total} repositories in ${report.durationSecs.toFixed(1)}s`);
+      } catch (error) {
+        logger.error(`Prewarm failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
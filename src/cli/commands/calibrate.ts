@@ -0,0 +1,70 @@
+/**
+ * Calibrate command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { TaskLoader } from '../../core/loader.js';
+import { calibrateDifficulty } from '../../evaluator/calibration.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { loadStoredResults } from '../../storage/store.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+import { colorDifficulty } from '../../utils/colors.js';
+
+export function createCalibrateCommand(config: RunnerConfig): Command {
+  const command = new Command('calibrate')
+    .description('Compare declared task difficulty with historical pass rates')
+    .option('--min-runs <n>', 'Runs required before flagging a one-level disagreement', '5')
+    .option('--flagged', 'Only show tasks whose declared difficulty disagrees with observed data')
+    .action(async (options) => {
+      try {
+        const tasks = await new TaskLoader(config.tasksDir).loadAll();
+        const results = await loadStoredResults(config);
+        let calibrations = calibrateDifficulty(tasks, results, parseInt(options.minRuns, 10));
+
+        if (options.flagged) {
+          calibrations = calibrations.filter(c => c.flagged);
+        }
+
+        if (calibrations.length === 0) {
+          logger.warn('No tasks with historical results to calibrate');
+          return;
+        }
+
+        emit(
+          calibrations,
+          () => {
+            console.log(
+              chalk.bold('ID'.padEnd(20)) + chalk.bold('Declared'.padEnd(10)) + chalk.bold('Observed'.padEnd(10)) +
+              chalk.bold('Pass Rate'.padEnd(11)) + chalk.bold('Runs'.padEnd(6)) + chalk.bold('Agents')
+            );
+            console.log('─'.repeat(65));
+
+            for (const c of calibrations) {
+              const line = `${c.taskId.padEnd(20)}${colorDifficulty(c.declared, c.declared.padEnd(10))}` +
+                `${colorDifficulty(c.observed, c.observed.padEnd(10))}` +
+                `${(c.passRate * 100).toFixed(0).padStart(5)}%     ${String(c.runs).padEnd(6)}${c.agents}`;
+              console.log(c.flagged ? `${line}  ${chalk.yellow('⚠ miscalibrated')}` : line);
+            }
+          },
+          () => ({
+            columns: ['ID', 'Declared', 'Observed', 'Pass Rate', 'Runs', 'Agents', 'Flagged'],
+            rows: calibrations.map(c => [
+              c.taskId, c.declared, c.observed, `${(c.passRate * 100).toFixed(0)}%`, c.runs, c.agents, c.flagged ? 'yes' : '',
+            ]),
+          })
+        );
+
+        const flagged = calibrations.filter(c => c.flagged).length;
+        if (flagged > 0) {
+          logger.warn(`\n${flagged} task(s) disagree with their declared difficulty`);
+        }
+      } catch (error) {
+        logger.error(`Calibration failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
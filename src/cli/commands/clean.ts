@@ -0,0 +1,130 @@
+/**
+ * Clean command implementation.
+ */
+
+import { Command } from 'commander';
+import { readdir, rm, stat } from 'fs/promises';
+import { join, parse, resolve } from 'path';
+import { homedir } from 'os';
+import { getCacheDir } from '../../core/config.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { diskUsage, formatBytes } from '../../utils/disk.js';
+
+/**
+ * Seconds per duration unit accepted by --older-than.
+ */
+const DURATION_UNITS: Record<string, number> = {
+  m: 60,
+  h: 60 * 60,
+  d: 24 * 60 * 60,
+  w: 7 * 24 * 60 * 60,
+};
+
+/**
+ * Parse a duration such as `30d`, `12h`, or `2w` into milliseconds.
+ */
+function parseDuration(value: string): number {
+  const match = value.trim().match(/^(\d+)([mhdw])$/);
+  if (!match) {
+    throw new Error(`Invalid duration "${value}" (expected e.g. 30d, 12h, 2w)`);
+  }
+  return parseInt(match[1], 10) * DURATION_UNITS[match[2]] * 1000;
+}
+
+/**
+ * Refuse to delete directories that are obviously not generated state.
+ */
+function assertSafeToRemove(path: string): void {
+  const target = resolve(path);
+  const protectedPaths = [parse(target).root, homedir(), process.cwd()].map(p => resolve(p));
+  if (protectedPaths.includes(target)) {
+    throw new Error(`Refusing to remove ${target}`);
+  }
+}
+
+/**
+ * Remove a generated directory.
+ * @returns Bytes freed
+ */
+async function removeDirectory(path: string, dryRun: boolean): Promise<number> {
+  assertSafeToRemove(path);
+  const size = await diskUsage(path);
+  if (!dryRun) {
+    await rm(path, { recursive: true, force: true });
+  }
+  return size;
+}
+
+/**
+ * Remove result JSON files last modified before the cutoff.
+ * The summary CSV is kept.
+ */
+async function removeOldResults(resultsDir: string, cutoff: number, dryRun: boolean): Promise<{ files: number; bytes: number }> {
+  let files = 0;
+  let bytes = 0;
+
+  let entries: string[];
+  try {
+    entries = await readdir(resultsDir);
+  } catch {
+    return { files, bytes };
+  }
+
+  for (const entry of entries.filter(name => name.endsWith('.json'))) {
+    const path = join(resultsDir, entry);
+    const info = await stat(path);
+    if (info.isFile() && info.mtimeMs < cutoff) {
+      files++;
+      bytes += info.size;
+      if (!dryRun) {
+        await rm(path, { force: true });
+      }
+    }
+  }
+
+  return { files, bytes };
+}
+
+export function createCleanCommand(config: RunnerConfig): Command {
+  const command = new Command('clean')
+    .description('Remove generated workspaces, caches, and old results')
+    .option('--workspaces', 'Remove task workspaces (default when nothing is selected)')
+    .option('--cache', 'Remove cached task repositories, clones, and indexes')
+    .option('--results', 'Remove result files (requires --older-than)')
+    .option('--older-than <duration>', 'Only remove results older than this (e.g. 30d, 12h, 2w)')
+    .option('--dry-run', 'Show what would be removed without deleting anything')
+    .action(async (options) => {
+      try {
+        const dryRun = Boolean(options.dryRun);
+        const cleanWorkspaces = options.workspaces || (!options.cache && !options.results);
+        const verb = dryRun ? 'Would remove' : 'Removed';
+
+        if (options.results && !options.olderThan) {
+          throw new Error('--results requires --older-than to avoid deleting all results');
+        }
+
+        if (cleanWorkspaces) {
+          const freed = await removeDirectory(config.workspaceDir, dryRun);
+          logger.success(`${verb} workspaces in ${config.workspaceDir} (${formatBytes(freed)})`);
+        }
+
+        if (options.cache) {
+          const cacheDir = getCacheDir();
+          const freed = await removeDirectory(cacheDir, dryRun);
+          logger.success(`${verb} cache in ${cacheDir} (${formatBytes(freed)})`);
+        }
+
+        if (options.results) {
+          const cutoff = Date.now() - parseDuration(options.olderThan);
+          const { files, bytes } = await removeOldResults(config.resultsDir, cutoff, dryRun);
+          logger.success(`${verb} ${files} result file(s) older than ${options.olderThan} (${formatBytes(bytes)})`);
+        }
+      } catch (error) {
+        logger.error(`Clean failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
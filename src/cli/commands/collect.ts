@@ -4,17 +4,23 @@
 
 import { Command } from 'commander';
 import { join } from 'path';
-import { collectAndWrite } from '../../collectors/csv.js';
+import { writeCSV } from '../../collectors/csv.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { loadStoredResults } from '../../storage/store.js';
 import { logger } from '../../utils/logger.js';
 
-export function createCollectCommand(resultsDir: string): Command {
+export function createCollectCommand(config: RunnerConfig): Command {
   const command = new Command('collect')
     .description('Collect benchmark results into CSV format')
-    .option('-o, --output <path>', 'Output CSV path', join(resultsDir, 'summary.csv'))
+    .option('-o, --output <path>', 'Output CSV path (default: <results-dir>/summary.csv)')
     .action(async (options) => {
       try {
-        await collectAndWrite(resultsDir, options.output);
-        logger.success(`\nResults summary available at: ${options.output}`);
+        const output = options.output || join(config.resultsDir, 'summary.csv');
+        logger.info(`Collecting ${config.resultsBackend} results...`);
+        const results = await loadStoredResults(config);
+        logger.info(`Found ${results.length} results`);
+        await writeCSV(results, output);
+        logger.success(`\nResults summary available at: ${output}`);
       } catch (error) {
         logger.error(`Collection failed: ${error}`);
         process.exit(1);
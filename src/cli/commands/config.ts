@@ -0,0 +1,83 @@
+/**
+ * Config command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import type { ResolvedConfig, RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+/**
+ * Runtime-only fields that no config layer sets.
+ */
+const HIDDEN_FIELDS: (keyof RunnerConfig)[] = ['permissionAck'];
+
+export function createConfigCommand(resolved: ResolvedConfig): Command {
+  const command = new Command('config')
+    .description('Inspect the effective configuration');
+
+  command
+    .command('show')
+    .description('Print the effective configuration and where each value came from (default, file, env, or flag)')
+    .action(() => {
+      try {
+        const entries = (Object.keys(resolved.config) as (keyof RunnerConfig)[])
+          .filter(key => !HIDDEN_FIELDS.includes(key))
+          .map(key => ({
+            key,
+            value: displayValue(resolved.config, key),
+            source: resolved.sources[key],
+          }));
+
+        const width = Math.max(...entries.map(entry => entry.key.length));
+        emit(
+          entries,
+          () => {
+            for (const entry of entries) {
+              const value = entry.value === null ? chalk.gray('(unset)') : String(entry.value);
+              console.log(`${chalk.bold(entry.key.padEnd(width))}  ${value}  ${chalk.gray(`# ${entry.source}`)}`);
+            }
+          },
+          () => ({
+            columns: ['Key', 'Value', 'Source'],
+            rows: entries.map(entry => [entry.key, entry.value === null ? null : String(entry.value), entry.source]),
+          })
+        );
+      } catch (error) {
+        logger.error(`Failed to show config: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
+
+/**
+ * A config value for display, without secrets.
+ */
+function displayValue(config: RunnerConfig, key: keyof RunnerConfig): unknown {
+  switch (key) {
+    case 'resultsUrl':
+      return redactUrl(config.resultsUrl);
+    case 'agentCredentials':
+      // Names only: literal env values may be API keys
+      return config.agentCredentials.map(credential => credential.name);
+    case 'customAgent':
+      return config.customAgent?.command ?? null;
+    case 'agentProfiles':
+      // Names only: literal env values may be API keys
+      return Object.keys(config.agentProfiles);
+    case 'pricing':
+      return Object.keys(config.pricing);
+    default:
+      return config[key];
+  }
+}
+
+/**
+ * Hide the password in a connection URL.
+ */
+function redactUrl(url: string | null): string | null {
+  return url ? url.replace(/(\/\/[^:/@]+:)[^@]+@/, '$1***@') : url;
+}
@@ -0,0 +1,103 @@
+/**
+ * Coordinate command implementation.
+ */
+
+import { Command } from 'commander';
+import { TaskRunner } from '../../core/runner.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { parseFilterExpression } from '../../core/filter.js';
+import type { TaskFilterOptions } from '../../core/filter.js';
+import type { Task } from '../../core/task.js';
+import { formatAgentSpec, parseAgentSpec } from '../../agents/factory.js';
+import { EXIT_CODES, getErrorExitCode, getExitCode } from '../../evaluator/results.js';
+import type { SuiteResults } from '../../evaluator/results.js';
+import { logger } from '../../utils/logger.js';
+import { consoleReporter } from '../../utils/reporter.js';
+import { emit } from '../../utils/output.js';
+import { acknowledgePermissions } from './run.js';
+
+export function createCoordinateCommand(config: RunnerConfig): Command {
+  const command = new Command('coordinate')
+    .description('Run a suite across agent-bench worker nodes and collect their results here')
+    .option('-t, --task <task-id>', 'Run a specific task by ID')
+    .option('-s, --suite <suite>', 'Run a task suite (all, category name)')
+    .option('--filter <expr>', 'Filter suite tasks (same syntax as run --filter); implies -s all')
+    .option('-a, --agent <agent>', 'Agent as <type>[@provider/model] (default: AGENT_BENCH_AGENT)')
+    .option('-m, --model <model>', 'Model to use (format: provider/model; default from config)')
+    .option('-w, --workers <urls>', 'Comma-separated worker URLs (default: AGENT_BENCH_WORKERS)')
+    .option('--token <token>', 'Bearer token of the workers (default: AGENT_BENCH_WORKER_TOKEN)')
+    .option('--poll <secs>', 'Seconds between task status polls', '5')
+    .option('--no-verify', 'Skip verification step')
+    .option('--fail-fast', 'Stop dispatching tasks after the first failure')
+    .option('-y, --yes', 'Run tasks that give the agent unrestricted shell access without asking')
+    .action(async (options) => {
+      try {
+        if (options.failFast) config.failFast = true;
+        const workers = String(options.workers || process.env.AGENT_BENCH_WORKERS || '')
+          .split(',')
+          .map(url => url.trim())
+          .filter(Boolean);
+        if (workers.length === 0) {
+          throw new Error('No workers given; pass --workers or set AGENT_BENCH_WORKERS');
+        }
+        const pollSecs = Number(options.poll);
+        if (!Number.isFinite(pollSecs) || pollSecs <= 0) {
+          throw new Error(`Invalid --poll: ${options.poll}`);
+        }
+
+        const suiteName: string | undefined = options.suite || (options.filter ? 'all' : undefined);
+        if (!options.task && !suiteName) {
+          logger.error('Please specify either --task or --suite');
+          process.exit(EXIT_CODES.harness);
+        }
+        const selection = { taskId: options.task, suite: suiteName };
+        const filter: TaskFilterOptions = {
+          expression: options.filter ? parseFilterExpression(options.filter) : undefined,
+        };
+        const spec = parseAgentSpec(options.agent || config.defaultAgent || 'opencode');
+        const agent = formatAgentSpec({ ...spec, model: spec.model || options.model || null }, config.defaultModel);
+
+        const runner = new TaskRunner(config);
+        let tasks: Task[];
+        try {
+          tasks = await runner.selectTasks(selection, filter);
+        } finally {
+          await runner.close();
+        }
+        if (tasks.length === 0) {
+          logger.warn('No tasks selected');
+          process.exit(EXIT_CODES.passed);
+        }
+        const acknowledgment = await acknowledgePermissions(config, selection, filter, Boolean(options.yes));
+
+        // The coordinator (the `server` feature) is only loaded when coordinating
+        const { Coordinator } = await import('../../server/coordinator.js');
+        const coordinator = new Coordinator(config, {
+          workers,
+          token: options.token || process.env.AGENT_BENCH_WORKER_TOKEN || null,
+          pollSecs,
+          listeners: [consoleReporter],
+        });
+        logger.info(`Run ID: ${coordinator.runId}`);
+        logger.info(`Using agent: ${agent}`);
+        let suite: SuiteResults;
+        try {
+          suite = await coordinator.run({
+            tasks,
+            agent,
+            skipVerify: !options.verify,
+            yes: acknowledgment !== null,
+          });
+        } finally {
+          await coordinator.close();
+        }
+        emit(suite, () => {});
+        process.exit(getExitCode(suite.results));
+      } catch (error) {
+        logger.error(`Coordinated run failed: ${error}`);
+        process.exit(getErrorExitCode(error));
+      }
+    });
+
+  return command;
+}
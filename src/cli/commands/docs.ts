@@ -0,0 +1,62 @@
+/**
+ * Docs command implementation.
+ */
+
+import { Command } from 'commander';
+import { mkdir, writeFile } from 'fs/promises';
+import { join } from 'path';
+import { collectCommands, manPageName, renderManPage, renderReference } from '../docs.js';
+import { logger } from '../../utils/logger.js';
+
+export function createDocsCommand(): Command {
+  const command = new Command('docs')
+    .description('Generate documentation from the CLI definitions');
+
+  command
+    .command('gen')
+    .description('Write man pages (man/*.1) and a Markdown CLI reference (cli-reference.md)')
+    .option('-o, --out-dir <dir>', 'Output directory', 'docs')
+    .option('--no-man', 'Skip man pages')
+    .option('--no-reference', 'Skip the Markdown reference')
+    .action(async (options, gen: Command) => {
+      try {
+        let program = gen;
+        while (program.parent) {
+          program = program.parent;
+        }
+
+        // SOURCE_DATE_EPOCH keeps packaged man pages reproducible
+        const epoch = Number(process.env.SOURCE_DATE_EPOCH);
+        const date = (Number.isFinite(epoch) && epoch > 0 ? new Date(epoch * 1000) : new Date())
+          .toISOString()
+          .substring(0, 10);
+
+        const written: string[] = [];
+        if (options.man) {
+          const manDir = join(options.outDir, 'man');
+          await mkdir(manDir, { recursive: true });
+          for (const entry of collectCommands(program)) {
+            const path = join(manDir, manPageName(entry.path));
+            await writeFile(path, renderManPage(entry, program, date), 'utf-8');
+            written.push(path);
+          }
+        }
+        if (options.reference) {
+          await mkdir(options.outDir, { recursive: true });
+          const path = join(options.outDir, 'cli-reference.md');
+          await writeFile(path, renderReference(program), 'utf-8');
+          written.push(path);
+        }
+
+        for (const path of written) {
+          logger.debug(`Wrote ${path}`);
+        }
+        logger.success(`Generated ${written.length} files in ${options.outDir}`);
+      } catch (error) {
+        logger.error(`Failed to generate docs: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -0,0 +1,71 @@
+/**
+ * Export command implementation.
+ */
+
+import { Command } from 'commander';
+import { writeFile } from 'fs/promises';
+import { EXPORTERS, getExporter } from '../../collectors/export.js';
+import type { BenchmarkResult } from '../../evaluator/results.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { loadStoredResults } from '../../storage/store.js';
+import { logger } from '../../utils/logger.js';
+import { getCommandFormat } from '../../utils/output.js';
+
+/**
+ * Format used when neither the command nor the global flag selects one.
+ */
+const DEFAULT_EXPORT_FORMAT = 'jsonl';
+
+export function createExportCommand(config: RunnerConfig): Command {
+  const command = new Command('export')
+    .description('Export stored results in a given format')
+    .option('-r, --run <id>', 'Only export results from this run ID (prefix match; "latest" for the most recent run)')
+    .option('-f, --format <format>', `Export format: ${Object.keys(EXPORTERS).join(', ')} (default: ${DEFAULT_EXPORT_FORMAT})`)
+    .option('-o, --output <path>', 'Write to a file instead of stdout')
+    .action(async (options, cmd: Command) => {
+      try {
+        const exporter = getExporter(getCommandFormat(cmd, options.format) || DEFAULT_EXPORT_FORMAT);
+
+        let results = await loadStoredResults(config);
+        if (options.run) {
+          results = selectRun(results, options.run);
+        }
+        if (results.length === 0) {
+          logger.warn(options.run ? `No results found for run: ${options.run}` : 'No results found');
+          return;
+        }
+
+        const document = exporter.render(results);
+        if (options.output) {
+          await writeFile(options.output, document, 'utf-8');
+          logger.success(`Exported ${results.length} results to ${options.output}`);
+        } else {
+          process.stdout.write(document);
+        }
+      } catch (error) {
+        logger.error(`Export failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
+
+/**
+ * Results belonging to a run, by ID prefix or `latest`.
+ * @throws Error If a prefix matches several runs
+ */
+function selectRun(results: BenchmarkResult[], run: string): BenchmarkResult[] {
+  const runIds = [...new Set(results.map(r => r.run_id).filter((id): id is string => !!id))].sort();
+  let runId: string | undefined;
+  if (run === 'latest') {
+    runId = runIds[runIds.length - 1];
+  } else {
+    const matches = runIds.filter(id => id.startsWith(run));
+    if (matches.length > 1 && !matches.includes(run)) {
+      throw new Error(`Run "${run}" is ambiguous: ${matches.join(', ')}`);
+    }
+    runId = matches.includes(run) ? run : matches[0];
+  }
+  return runId ? results.filter(r => r.run_id === runId) : [];
+}
@@ -0,0 +1,51 @@
+/**
+ * Flake-check command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { TaskLoader } from '../../core/loader.js';
+import { checkFlakiness } from '../../core/flake.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createFlakeCheckCommand(config: RunnerConfig): Command {
+  const command = new Command('flake-check')
+    .description('Repeat workspace preparation and verification (no agent) to detect non-deterministic tasks')
+    .requiredOption('-t, --task <task-id>', 'Task ID to check')
+    .option('-n, --trials <n>', 'Number of trials', '10')
+    .action(async (options) => {
+      try {
+        const trials = parseInt(options.trials, 10);
+        if (!Number.isInteger(trials) || trials < 2) {
+          throw new Error(`Invalid --trials: ${options.trials} (need at least 2)`);
+        }
+
+        const task = await new TaskLoader(config.tasksDir).loadById(options.task);
+        logger.info(`Checking ${task.id} over ${trials} trials\n`);
+
+        const report = await checkFlakiness(task, config.workspaceDir, trials);
+
+        emit(report, () => {
+          const durations = report.trials.map(t => t.durationSecs);
+          console.log(chalk.bold(`\n${report.taskId}`));
+          console.log(`  Passed: ${report.passed}/${report.trials.length}  Failed: ${report.failed}  Errors: ${report.errors}`);
+          console.log(`  Verification time: ${Math.min(...durations).toFixed(2)}s – ${Math.max(...durations).toFixed(2)}s`);
+          console.log(report.stable
+            ? chalk.green('  Stable: every trial produced the same outcome')
+            : chalk.red.bold('  Flaky: trials produced different outcomes'));
+          if (report.passesWithoutChanges) {
+            console.log(chalk.yellow('  Warning: verification passes without any agent changes'));
+          }
+        });
+
+        process.exit(report.stable ? 0 : 1);
+      } catch (error) {
+        logger.error(`Flake check failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
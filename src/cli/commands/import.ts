@@ -0,0 +1,115 @@
+/**
+ * Import command implementation.
+ */
+
+import { Command } from 'commander';
+import { mkdir, writeFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import yaml from 'js-yaml';
+import { readFailureLog, taskFromCiFailure } from '../../importers/ci.js';
+import { readProblems, taskFromProblem, type CodegenFormat, type ImportedTask } from '../../importers/humaneval.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { getCommandFormat } from '../../utils/output.js';
+
+/**
+ * Write a generated task definition as YAML.
+ */
+async function writeTaskYaml(task: Record<string, unknown>, path: string): Promise<void> {
+  await mkdir(dirname(path), { recursive: true });
+  await writeFile(path, yaml.dump(task, { lineWidth: 120, noRefs: true }), 'utf-8');
+}
+
+/**
+ * Write a generated task and its fixture and hidden test files under `<dir>/<category>/`.
+ * @returns The path to the task YAML
+ */
+async function writeImportedTask(imported: ImportedTask, dir: string): Promise<string> {
+  const categoryDir = join(dir, String(imported.task.category));
+  const id = String(imported.task.id);
+  const taskPath = join(categoryDir, `${id}.yaml`);
+  await writeTaskYaml(imported.task, taskPath);
+
+  for (const [kind, files] of [['fixtures', imported.fixtures], ['hidden_tests', imported.hiddenTests]] as const) {
+    for (const [name, content] of Object.entries(files)) {
+      const path = join(categoryDir, id, kind, name);
+      await mkdir(dirname(path), { recursive: true });
+      await writeFile(path, content, 'utf-8');
+    }
+  }
+
+  return taskPath;
+}
+
+export function createImportCommand(config: RunnerConfig): Command {
+  const command = new Command('import')
+    .description('Generate tasks from external sources');
+
+  command
+    .command('ci')
+    .description('Create a bug-fix task from a failing CI run')
+    .requiredOption('--repo <url>', 'Repository URL')
+    .requiredOption('--commit <sha>', 'Failing commit')
+    .requiredOption('--command <command>', 'Failing test command (becomes the verification command)')
+    .option('--log <path>', 'Failure log file to include in the prompt')
+    .option('--id <id>', 'Task ID (default: CI-<short sha>)')
+    .option('--title <title>', 'Task title')
+    .option('--timeout <secs>', 'Verification timeout in seconds', '300')
+    .option('-o, --output <path>', 'Output YAML path (default: <tasks-dir>/bug-fix/<id>.yaml)')
+    .action(async (options) => {
+      try {
+        const task = taskFromCiFailure({
+          repository: options.repo,
+          commit: options.commit,
+          command: options.command,
+          log: options.log ? await readFailureLog(options.log) : null,
+          id: options.id,
+          title: options.title,
+          timeout: parseInt(options.timeout, 10),
+        });
+
+        const output = options.output || join(config.tasksDir, 'bug-fix', `${task.id}.yaml`);
+        await writeTaskYaml(task, output);
+        logger.success(`Created task ${task.id} at ${output}`);
+      } catch (error) {
+        logger.error(`Import failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  command
+    .command('humaneval')
+    .description('Convert HumanEval or MBPP problems into tasks')
+    .argument('<file>', 'Problem set file (JSONL or JSON array)')
+    .option('-f, --format <format>', 'Problem format: auto, humaneval, or mbpp', 'auto')
+    .option('-l, --limit <n>', 'Import at most n problems')
+    .option('-o, --output <dir>', 'Output tasks directory (default: --tasks-dir)')
+    .action(async (file: string, options, cmd: Command) => {
+      try {
+        const format = getCommandFormat(cmd, options.format) ?? 'auto';
+        if (!['auto', 'humaneval', 'mbpp'].includes(format)) {
+          throw new Error(`Unknown format: ${format}`);
+        }
+
+        let problems = await readProblems(file);
+        if (options.limit) {
+          problems = problems.slice(0, parseInt(options.limit, 10));
+        }
+
+        const outputDir = options.output || config.tasksDir;
+        for (const problem of problems) {
+          const imported = format === 'auto'
+            ? taskFromProblem(problem)
+            : taskFromProblem(problem, format as CodegenFormat);
+          await writeImportedTask(imported, outputDir);
+        }
+
+        logger.success(`Imported ${problems.length} task(s) into ${outputDir}`);
+      } catch (error) {
+        logger.error(`Import failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -3,19 +3,46 @@
  */
 
 import { Command } from 'commander';
+import { resolve } from 'path';
 import { saveUserConfig, getConfigPath } from '../../core/config.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { scaffoldProject } from '../../core/scaffold.js';
 import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
 
-export function createInitCommand(): Command {
+export function createInitCommand(runnerConfig: RunnerConfig): Command {
   const command = new Command('init')
-    .description('Initialize agent-bench configuration')
+    .description('Scaffold a benchmark project (tasks/, suites/, results/, agent-bench.toml, example task)')
+    .argument('[directory]', 'Project directory', '.')
+    .option('--force', 'Overwrite existing scaffold files')
+    .option('--global', 'Save settings to the user config file instead of scaffolding a project')
     .option('--opencode-url <url>', 'OpenCode server URL')
     .option('--default-model <model>', 'Default model (provider/model)')
     .option('--tasks-dir <path>', 'Tasks directory path')
     .option('--results-dir <path>', 'Results directory path')
     .option('--workspace-dir <path>', 'Workspace directory path')
-    .action(async (options) => {
+    .action(async (directory: string, options) => {
       try {
+        if (!options.global) {
+          const projectDir = resolve(directory);
+          const result = await scaffoldProject(projectDir, {
+            model: options.defaultModel || runnerConfig.defaultModel,
+            force: options.force,
+          });
+
+          emit({ directory: projectDir, ...result }, () => {
+            for (const path of result.created) {
+              logger.success(`Created ${path}`);
+            }
+            for (const path of result.skipped) {
+              logger.warn(`Kept existing ${path} (use --force to overwrite)`);
+            }
+            logger.info(`Project ready in ${projectDir}`);
+            logger.info('Next: agent-bench list, then agent-bench run -t EXAMPLE-001');
+          });
+          return;
+        }
+
         const config: any = {};
 
         if (options.opencodeUrl) config.opencodeUrl = options.opencodeUrl;
@@ -29,10 +56,12 @@ export function createInitCommand(): Command {
         const configPath = getConfigPath();
         logger.success(`Configuration saved to: ${configPath}`);
 
-        console.log('\nConfiguration:');
-        for (const [key, value] of Object.entries(config)) {
-          console.log(`  ${key}: ${value}`);
-        }
+        emit(config, () => {
+          console.log('\nConfiguration:');
+          for (const [key, value] of Object.entries(config)) {
+            console.log(`  ${key}: ${value}`);
+          }
+        });
       } catch (error) {
         logger.error(`Init failed: ${error}`);
         process.exit(1);
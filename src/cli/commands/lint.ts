@@ -0,0 +1,84 @@
+/**
+ * Lint command implementation.
+ */
+
+import { Command } from 'commander';
+import { relative } from 'path';
+import chalk from 'chalk';
+import { TaskLoader } from '../../core/loader.js';
+import { INVALID_TASK_RULE, LINT_RULES, LintIssue, LintSeverity, lintTasks } from '../../core/lint.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { getOutputFormat } from '../../utils/output.js';
+
+/**
+ * Collect repeated `--rule name=severity` options.
+ */
+function collectRule(value: string, previous: Record<string, LintSeverity>): Record<string, LintSeverity> {
+  const [name, severity] = value.split('=');
+  const known = [INVALID_TASK_RULE, ...LINT_RULES.map(rule => rule.name)];
+  if (!known.includes(name) || !['off', 'warn', 'error'].includes(severity)) {
+    throw new Error(`Invalid rule option "${value}" (expected <rule>=off|warn|error)`);
+  }
+  return { ...previous, [name]: severity as LintSeverity };
+}
+
+export function createLintCommand(config: RunnerConfig): Command {
+  const command = new Command('lint')
+    .description('Check tasks for quality problems')
+    .option('--rule <rule=severity>', 'Override a rule severity (off, warn, error); repeatable', collectRule, {})
+    .option('--output <format>', 'Output format: text, json, github (default: json with --format json, else text)')
+    .option('--list-rules', 'List available rules and exit')
+    .action(async (options) => {
+      if (options.listRules) {
+        for (const rule of LINT_RULES) {
+          console.log(`${rule.name.padEnd(34)}${rule.defaultSeverity.padEnd(8)}${rule.description}`);
+        }
+        return;
+      }
+
+      try {
+        const issues = await lintTasks(new TaskLoader(config.tasksDir), options.rule);
+        printIssues(issues, options.output || (getOutputFormat() === 'json' ? 'json' : 'text'));
+
+        const errors = issues.filter(issue => issue.severity === 'error').length;
+        process.exit(errors > 0 ? 1 : 0);
+      } catch (error) {
+        logger.error(`Lint failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
+
+/**
+ * Print lint issues in the requested format.
+ */
+function printIssues(issues: LintIssue[], format: string): void {
+  if (format === 'json') {
+    console.log(JSON.stringify(issues, null, 2));
+    return;
+  }
+
+  for (const issue of issues) {
+    const file = relative(process.cwd(), issue.file);
+    if (format === 'github') {
+      const level = issue.severity === 'error' ? 'error' : 'warning';
+      console.log(`::${level} file=${file},title=${issue.rule}::${issue.message}`);
+    } else {
+      const severity = issue.severity === 'error' ? chalk.red('error') : chalk.yellow('warn');
+      console.log(`${file}: ${severity} [${issue.rule}] ${issue.message}`);
+    }
+  }
+
+  if (format === 'text') {
+    const errors = issues.filter(issue => issue.severity === 'error').length;
+    const warnings = issues.length - errors;
+    if (issues.length === 0) {
+      logger.success('No problems found');
+    } else {
+      console.log(`\n${errors} error(s), ${warnings} warning(s)`);
+    }
+  }
+}
@@ -4,36 +4,34 @@
 
 import { Command } from 'commander';
 import { TaskLoader } from '../../core/loader.js';
+import { getSources, toCatalogEntry } from '../../core/task.js';
+import type { Task } from '../../core/task.js';
+import { collectValues, parseFilterExpression, parseTagList } from '../../core/filter.js';
+import type { RunnerConfig } from '../../core/config.js';
 import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+import { colorDifficulty } from '../../utils/colors.js';
 import chalk from 'chalk';
 
-export function createListCommand(tasksDir: string): Command {
+export function createListCommand(config: RunnerConfig): Command {
   const command = new Command('list')
-    .description('List all available benchmark tasks')
+    .description('List all available benchmark tasks (full task definitions with --format json)')
     .option('-c, --category <category>', 'Filter by category')
     .option('-d, --difficulty <difficulty>', 'Filter by difficulty')
-    .option('-t, --tags <tags>', 'Filter by tags (comma-separated)')
+    .option('-t, --tags <tags>', 'Filter by tags (comma-separated, e.g. lang:python,area:cli)')
+    .option('--tag <tag>', 'Filter by tag; namespace-aware (lang:python, lang:*); repeatable', collectValues, [])
+    .option('--filter <expr>', 'Filter expression (e.g. "category=bug-fix && difficulty!=hard && tag~python")')
     .option('-v, --verbose', 'Show detailed task information')
     .action(async (options) => {
-      const loader = new TaskLoader(tasksDir);
+      const loader = new TaskLoader(config.tasksDir);
 
       try {
-        let tasks = await loader.loadAll();
-
-        // Apply filters
-        if (options.category) {
-          tasks = tasks.filter(t => t.category === options.category);
-        }
-        if (options.difficulty) {
-          tasks = tasks.filter(t => t.difficulty === options.difficulty);
-        }
-        if (options.tags) {
-          const filterTags = options.tags.split(',').map((t: string) => t.trim());
-          tasks = tasks.filter(task => {
-            const taskTags = task.metadata?.tags || [];
-            return filterTags.some(tag => taskTags.includes(tag));
-          });
-        }
+        const tasks = await loader.loadAll({
+          category: options.category,
+          difficulty: options.difficulty,
+          tags: parseTagList(options.tags, options.tag),
+          expression: options.filter ? parseFilterExpression(options.filter) : undefined,
+        });
 
         if (tasks.length === 0) {
           logger.warn('No tasks found matching the criteria');
@@ -42,37 +40,15 @@ export function createListCommand(tasksDir: string): Command {
 
         logger.info(`Found ${tasks.length} tasks\n`);
 
-        if (options.verbose) {
-          // Verbose mode: show full details
-          for (const task of tasks) {
-            console.log(chalk.bold.cyan(`━━━ ${task.id} ━━━`));
-            console.log(chalk.bold(`  Title: ${task.title}`));
-            console.log(`  Category: ${task.category}`);
-            console.log(`  Difficulty: ${task.difficulty}`);
-            console.log(`  Repository: ${task.source.repository}`);
-            console.log(`  Commit: ${task.source.commit}`);
-            if (task.metadata?.tags && task.metadata.tags.length > 0) {
-              console.log(`  Tags: ${task.metadata.tags.join(', ')}`);
-            }
-            if (task.max_iterations) {
-              console.log(`  Max Iterations: ${task.max_iterations}`);
-            }
-            console.log();
-          }
-        } else {
-          // Compact mode: table format
-          console.log(chalk.bold('ID'.padEnd(20)) + chalk.bold('Title'.padEnd(40)) + chalk.bold('Category'.padEnd(15)) + chalk.bold('Difficulty'));
-          console.log('─'.repeat(85));
-
-          for (const task of tasks) {
-            const id = task.id.padEnd(20);
-            const title = task.title.substring(0, 37).padEnd(40);
-            const category = task.category.padEnd(15);
-            const difficulty = task.difficulty;
-
-            console.log(`${id}${title}${category}${difficulty}`);
-          }
-        }
+        // JSON output carries the full task definitions so tooling never re-parses the YAML
+        emit(
+          tasks.map(toCatalogEntry),
+          options.verbose ? () => printVerbose(tasks) : () => printCompact(tasks),
+          () => ({
+            columns: ['ID', 'Title', 'Category', 'Difficulty', 'Tags'],
+            rows: tasks.map(task => [task.id, task.title, task.category, task.difficulty, (task.metadata?.tags || []).join(',')]),
+          })
+        );
       } catch (error) {
         logger.error(`Failed to list tasks: ${error}`);
         process.exit(1);
@@ -81,3 +57,62 @@ export function createListCommand(tasksDir: string): Command {
 
   return command;
 }
+
+/**
+ * Print full details for each task.
+ */
+function printVerbose(tasks: Task[]): void {
+  for (const task of tasks) {
+    console.log(chalk.bold.cyan(`━━━ ${task.id} ━━━`));
+    console.log(chalk.bold(`  Title: ${task.title}`));
+    console.log(`  Category: ${task.category}`);
+    console.log(`  Difficulty: ${colorDifficulty(task.difficulty)}`);
+    for (const source of getSources(task)) {
+      const location = source.path ? ` (${source.path}/)` : '';
+      console.log(`  Repository: ${source.repository}${location}`);
+      console.log(`  Commit: ${source.commit}`);
+    }
+    if (task.metadata?.tags && task.metadata.tags.length > 0) {
+      console.log(`  Tags: ${task.metadata.tags.join(', ')}`);
+    }
+    const provenance: [string, string | undefined][] = [
+      ['Author', task.metadata?.author],
+      ['Created', task.metadata?.created],
+      ['License', task.metadata?.license],
+      ['Upstream', task.metadata?.upstream],
+      ['Solution', task.metadata?.solution],
+    ];
+    for (const [label, value] of provenance) {
+      if (value) {
+        console.log(`  ${label}: ${value}`);
+      }
+    }
+    if (task.max_iterations) {
+      console.log(`  Max Iterations: ${task.max_iterations}`);
+    }
+    if (task.disabled) {
+      console.log(chalk.yellow(`  Skipped: ${task.disabled_reason || 'disabled'}`));
+    }
+    console.log();
+  }
+}
+
+/**
+ * Print tasks as a compact table.
+ */
+function printCompact(tasks: Task[]): void {
+  console.log(chalk.bold('ID'.padEnd(20)) + chalk.bold('Title'.padEnd(40)) + chalk.bold('Category'.padEnd(15)) + chalk.bold('Difficulty'));
+  console.log('─'.repeat(85));
+
+  for (const task of tasks) {
+    const id = task.id.padEnd(20);
+    const title = task.title.substring(0, 37).padEnd(40);
+    const category = task.category.padEnd(15);
+    const difficulty = colorDifficulty(task.difficulty);
+    const status = task.disabled
+      ? chalk.yellow(`  [skipped${task.disabled_reason ? `: ${task.disabled_reason}` : ''}]`)
+      : '';
+
+    console.log(`${id}${title}${category}${difficulty}${status}`);
+  }
+}
@@ -0,0 +1,40 @@
+/**
+ * Lock command implementation.
+ */
+
+import { Command } from 'commander';
+import { TaskLoader } from '../../core/loader.js';
+import { createLockfile, writeLockfile } from '../../core/lock.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createLockCommand(config: RunnerConfig): Command {
+  const command = new Command('lock')
+    .description('Pin task source refs to commit SHAs and record task file checksums')
+    .action(async () => {
+      try {
+        const loader = new TaskLoader(config.tasksDir);
+        logger.info('Resolving task sources...');
+
+        const lockfile = await createLockfile(loader);
+        const path = await writeLockfile(await loader.getTasksDir(), lockfile);
+
+        emit(lockfile, () => {
+          for (const [id, entry] of Object.entries(lockfile.tasks)) {
+            for (const source of entry.sources) {
+              const location = source.path ? ` (${source.path}/)` : '';
+              console.log(`  ${id}${location}: ${source.ref} → ${source.commit}`);
+            }
+          }
+        });
+
+        logger.success(`Locked ${Object.keys(lockfile.tasks).length} tasks in ${path}`);
+      } catch (error) {
+        logger.error(`Lock failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
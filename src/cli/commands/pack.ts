@@ -0,0 +1,35 @@
+/**
+ * Pack command implementation.
+ */
+
+import { Command } from 'commander';
+import { basename, resolve } from 'path';
+import { createPack } from '../../core/pack.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createPackCommand(config: RunnerConfig): Command {
+  const command = new Command('pack')
+    .description('Package tasks, fixtures, and hidden tests into a single archive')
+    .option('-o, --output <path>', 'Output archive path', 'tasks.pack.tar.gz')
+    .option('--name <name>', 'Pack name (default: tasks directory name)')
+    .option('--pack-version <version>', 'Pack version', '0.0.0')
+    .action(async (options) => {
+      try {
+        const name = options.name || basename(resolve(config.tasksDir));
+        const manifest = await createPack(config.tasksDir, options.output, name, options.packVersion);
+
+        logger.success(`Packed ${manifest.tasks.length} tasks (${manifest.files.length} files) into ${options.output}`);
+        emit(manifest, () => {
+          console.log(`  Name: ${manifest.name}`);
+          console.log(`  Version: ${manifest.version}`);
+        });
+      } catch (error) {
+        logger.error(`Pack failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
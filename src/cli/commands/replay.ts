@@ -0,0 +1,54 @@
+/**
+ * Replay command implementation.
+ */
+
+import { Command } from 'commander';
+import { readFile } from 'fs/promises';
+import { TaskRunner } from '../../core/runner.js';
+import type { BenchmarkResult } from '../../evaluator/results.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createReplayCommand(config: RunnerConfig): Command {
+  const command = new Command('replay')
+    .description('Re-run verification and scoring on a preserved workspace without invoking an agent')
+    .requiredOption('-t, --task <task-id>', 'Task ID')
+    .requiredOption('-w, --workspace <path>', 'Workspace from a previous run')
+    .option('-r, --result <path>', 'Original result file to carry agent metrics and output from')
+    .option('--save', 'Save the replayed result to the results directory')
+    .action(async (options) => {
+      try {
+        const original: BenchmarkResult | null = options.result
+          ? JSON.parse(await readFile(options.result, 'utf-8'))
+          : null;
+        if (original && original.task_id !== options.task) {
+          throw new Error(`Result ${options.result} is for task ${original.task_id}, not ${options.task}`);
+        }
+
+        logger.info(`Replaying verification for ${options.task} in ${options.workspace}\n`);
+
+        const runner = new TaskRunner(config);
+        const result = await runner.replayTask(options.task, options.workspace, original, Boolean(options.save));
+
+        logger.taskResult(
+          result.success,
+          result.score,
+          result.iterations,
+          result.duration_secs,
+          result.tokens_used || undefined
+        );
+        if (original && original.success !== result.success) {
+          logger.warn(`Outcome changed: ${original.outcome ?? (original.success ? 'passed' : 'failed')} → ${result.outcome}`);
+        }
+        emit(result, () => {});
+
+        process.exit(result.success ? 0 : 1);
+      } catch (error) {
+        logger.error(`Replay failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -3,67 +3,409 @@
  */
 
 import { Command } from 'commander';
+import chalk from 'chalk';
 import { TaskRunner } from '../../core/runner.js';
-import { createAgent } from '../../agents/factory.js';
-import type { RunnerConfig } from '../../core/config.js';
+import { createAgentFromSpec, formatAgentSpec, parseAgentList } from '../../agents/factory.js';
+import { withRecording } from '../../agents/recording.js';
+import type { AgentSpec } from '../../agents/factory.js';
+import { parseQuota, parseRecordMode, parseTimeBudget, type RunnerConfig } from '../../core/config.js';
+import { collectValues, parseFilterExpression } from '../../core/filter.js';
+import type { TaskFilterOptions } from '../../core/filter.js';
+import type { AgentPlan, RunPlan } from '../../core/plan.js';
+import { findDangerousTasks } from '../../core/manifest.js';
+import type { DangerousTask, PermissionAcknowledgment } from '../../core/manifest.js';
+import { EXIT_CODES, getErrorExitCode, getExitCode, getOutcome } from '../../evaluator/results.js';
+import type { BenchmarkResult, SuiteResults } from '../../evaluator/results.js';
 import { logger } from '../../utils/logger.js';
+import { consoleReporter } from '../../utils/reporter.js';
+import { createGithubReporter } from '../../utils/github.js';
+import { createTapReporter } from '../../utils/tap.js';
+import type { RunEventListener } from '../../core/events.js';
+import { PermissionAcknowledgmentError } from '../../utils/errors.js';
+import { confirm, isInteractive } from '../../utils/prompt.js';
+import { emit } from '../../utils/output.js';
 
 export function createRunCommand(config: RunnerConfig): Command {
   const command = new Command('run')
     .description('Run benchmark tasks')
     .option('-t, --task <task-id>', 'Run a specific task by ID')
     .option('-s, --suite <suite>', 'Run a task suite (all, category name)')
-    .option('-m, --model <model>', 'Model to use (format: provider/model)', 'anthropic/claude-sonnet-4-5')
+    .option('-m, --model <model>', 'Model to use (format: provider/model; default from config)')
+    .option('-a, --agent <agent>', 'Agent as <type>[@provider/model]; repeatable or comma-separated to compare agents (default: AGENT_BENCH_AGENT)', collectValues, [])
+    .option('--max-iterations <n>', 'Maximum agent iterations for tasks without max_iterations (default from config, 20)')
+    .option('--agent-timeout <secs>', 'Override every task\'s agent timeout (seconds)')
+    .option('--verify-timeout <secs>', 'Override every task\'s verification timeout (seconds)')
     .option('--no-verify', 'Skip verification step')
-    .option('--filter <filter>', 'Filter tasks (e.g., difficulty=easy)')
+    .option('--filter <expr>', 'Filter suite tasks (e.g. "category=bug-fix && difficulty!=hard && tag~python"); implies -s all')
+    .option('--locked', 'Require tasks to match agent-bench.lock and use its pinned commits')
+    .option('--fail-fast', 'Stop the suite at the first failed task')
+    .option('--notify', 'Desktop notification when a suite finishes or a task fails (default from config)')
+    .option('--keep-workspace', 'Leave task workspaces in place after the run, e.g. for replay (default from config)')
+    .option('--workspace-quota <size>', 'Fail tasks whose workspace grows past this size, e.g. 10GB (default from config)')
+    .option('--no-prefetch', 'Do not fetch the suite\'s repositories before its tasks start')
+    .option('--time-budget <duration>', 'Skip suite tasks that would not finish within this, e.g. 2h or 90m (default from config)')
+    .option('--record-mode <mode>', 'Record agent executions or replay recorded ones: off, record, replay, auto (default from config)')
+    .option('--recordings-dir <path>', 'Directory of agent recordings (default from config)')
+    .option('--resume <run-id>', 'Continue an interrupted run: execute the tasks it did not score (same agent)')
+    .option('--dry-run', 'Print the execution plan (tasks, agents, estimated duration) without running anything')
+    .option('--plan', 'Print the execution plan, then run')
+    .option('--output <reporter>', 'Progress reporting: console, github (also annotate failures and regressions and write the Actions job summary), or tap (TAP stream on stdout)', 'console')
+    .option('-y, --yes', 'Run tasks that give the agent unrestricted shell access without asking')
     .action(async (options) => {
       try {
-        const agent = createAgent(options.model);
-        const runner = new TaskRunner(config);
+        if (options.locked) config.locked = true;
+        if (options.failFast) config.failFast = true;
+        if (options.notify) config.notify = true;
+        if (options.keepWorkspace) config.keepWorkspace = true;
+        if (options.workspaceQuota) config.workspaceQuota = parseQuota(options.workspaceQuota);
+        if (options.prefetch === false) config.prefetch = false;
+        if (options.timeBudget) config.timeBudget = parseTimeBudget(options.timeBudget);
+        if (options.recordMode) config.recordMode = parseRecordMode(options.recordMode);
+        if (options.recordingsDir) config.recordingsDir = options.recordingsDir;
+        if (options.maxIterations) {
+          const maxIterations = parseInt(options.maxIterations, 10);
+          if (!Number.isInteger(maxIterations) || maxIterations < 1) {
+            throw new Error(`Invalid --max-iterations: ${options.maxIterations}`);
+          }
+          config.maxIterations = maxIterations;
+        }
+        if (options.agentTimeout) config.agentTimeout = parseSeconds('--agent-timeout', options.agentTimeout);
+        if (options.verifyTimeout) config.verifyTimeout = parseSeconds('--verify-timeout', options.verifyTimeout);
+        const model: string = options.model || config.defaultModel;
+        const reporters = createReporters(options.output);
+        const { listeners } = reporters;
+
+        const specs: AgentSpec[] = parseAgentList(
+          options.agent.length > 0 || !config.defaultAgent ? options.agent : [config.defaultAgent]
+        );
+        if (specs.length === 0) {
+          specs.push({ type: 'opencode', model });
+        }
+
         const skipVerify = !options.verify;
+        const filter: TaskFilterOptions = {
+          expression: options.filter ? parseFilterExpression(options.filter) : undefined,
+        };
+        const suiteName: string | undefined = options.suite || (options.filter ? 'all' : undefined);
+
+        if (options.resume) {
+          if (specs.length > 1) {
+            throw new Error('--resume continues a single-agent run; pass one --agent');
+          }
+          const [spec] = specs;
+          const agent = withRecording(createAgentFromSpec(spec, model, config.maxIterations), {
+            mode: config.recordMode,
+            dir: config.recordingsDir,
+            model: spec.model || model,
+          });
+          const runner = new TaskRunner(config, { listeners, runId: options.resume });
+          logger.info(`Run ID: ${runner.runId}`);
+          const suite = await runner.resumeRun(agent, skipVerify);
+          reporters.end();
+          if (!reporters.stdout) {
+            emit(suite, () => {});
+          }
+          process.exit(getExitCode(suite.results));
+        }
+
+        if ((options.dryRun || options.plan) && (options.task || suiteName)) {
+          const plan = await buildRunPlan(config, specs, model, { taskId: options.task, suite: suiteName }, filter);
+          plan.filter = options.filter ?? null;
+          emitPlan(plan);
+          if (options.dryRun) {
+            process.exit(EXIT_CODES.passed);
+          }
+        }
+
+        if (options.task || suiteName) {
+          config.permissionAck = await acknowledgePermissions(
+            config,
+            { taskId: options.task, suite: suiteName },
+            filter,
+            Boolean(options.yes)
+          );
+        }
 
         if (options.task) {
-          // Run single task
+          // Run single task with each agent
           logger.info(`Running task: ${options.task}`);
-          logger.info(`Using model: ${options.model}`);
           logger.info(`Skip verification: ${skipVerify}\n`);
 
-          const result = await runner.runTask(options.task, agent, skipVerify);
+          const results: BenchmarkResult[] = [];
+          for (const spec of specs) {
+            logger.info(`Using agent: ${formatAgentSpec(spec, model)}`);
+            const agent = withRecording(createAgentFromSpec(spec, model, config.maxIterations), {
+              mode: config.recordMode,
+              dir: config.recordingsDir,
+              model: spec.model || model,
+            });
+            const runner = new TaskRunner(config, { listeners });
+            logger.info(`Run ID: ${runner.runId}`);
+            results.push(await runner.runTask(options.task, agent, skipVerify));
+          }
 
-          logger.taskResult(
-            result.success,
-            result.score,
-            result.iterations,
-            result.duration_secs,
-            result.tokens_used || undefined
-          );
+          reporters.end();
+          if (!reporters.stdout) {
+            if (results.length === 1) {
+              emit(results[0], () => {});
+            } else {
+              emitTaskComparison(results, specs, model);
+            }
+          }
 
-          process.exit(result.success ? 0 : 1);
-        } else if (options.suite) {
-          // Run suite
-          if (options.suite === 'all') {
-            logger.info('Running all tasks');
-            logger.info(`Using model: ${options.model}`);
+          process.exit(getExitCode(results));
+        } else if (suiteName) {
+          // Run suite with each agent
+          const suites: SuiteResults[] = [];
+          for (const spec of specs) {
+            const agent = withRecording(createAgentFromSpec(spec, model, config.maxIterations), {
+              mode: config.recordMode,
+              dir: config.recordingsDir,
+              model: spec.model || model,
+            });
+            const runner = new TaskRunner(config, { listeners });
+            logger.info(`Run ID: ${runner.runId}`);
+            logger.info(suiteName === 'all' ? 'Running all tasks' : `Running category: ${suiteName}`);
+            logger.info(`Using agent: ${formatAgentSpec(spec, model)}`);
             logger.info(`Skip verification: ${skipVerify}\n`);
 
-            await runner.runAll(agent, skipVerify);
-          } else {
-            // Run category suite
-            logger.info(`Running category: ${options.suite}`);
-            logger.info(`Using model: ${options.model}`);
-            logger.info(`Skip verification: ${skipVerify}\n`);
+            const suite = suiteName === 'all'
+              ? await runner.runAll(agent, skipVerify, filter)
+              : await runner.runCategory(suiteName, agent, skipVerify, filter);
+            if (suite) {
+              suites.push(suite);
+              if (config.failFast && suite.failed > 0) {
+                break;
+              }
+            }
+          }
 
-            await runner.runCategory(options.suite, agent, skipVerify);
+          reporters.end();
+          if (!reporters.stdout) {
+            if (suites.length === 1) {
+              emit(suites[0], () => {});
+            } else if (suites.length > 1) {
+              emitSuiteComparison(suites, specs, model);
+            }
           }
+
+          process.exit(getExitCode(suites.flatMap(suite => suite.results)));
         } else {
           logger.error('Please specify either --task or --suite');
-          process.exit(1);
+          process.exit(EXIT_CODES.harness);
         }
       } catch (error) {
         logger.error(`Run failed: ${error}`);
-        process.exit(1);
+        process.exit(getErrorExitCode(error));
       }
     });
 
   return command;
 }
+
+/**
+ * Reporters selected with `--output`.
+ */
+interface Reporters {
+  listeners: RunEventListener[];
+  /** Finish the output once every run is done */
+  end(): void;
+  /** The reporter's stream is the stdout output, so command data is not printed there */
+  stdout: boolean;
+}
+
+/**
+ * Run event listeners for an `--output` value.
+ */
+function createReporters(output: string): Reporters {
+  switch (output) {
+    case 'console':
+      return { listeners: [consoleReporter], end: () => {}, stdout: false };
+    case 'github':
+      return { listeners: [consoleReporter, createGithubReporter()], end: () => {}, stdout: false };
+    case 'tap': {
+      const tap = createTapReporter();
+      return { listeners: [consoleReporter, tap.listener], end: () => tap.end(), stdout: true };
+    }
+    default:
+      throw new Error(`Unknown --output "${output}" (expected console, github, tap)`);
+  }
+}
+
+/**
+ * Parse a positive whole number of seconds from a flag value.
+ */
+function parseSeconds(flag: string, value: string): number {
+  const secs = Number(value);
+  if (!Number.isInteger(secs) || secs < 1) {
+    throw new Error(`Invalid ${flag}: ${value}`);
+  }
+  return secs;
+}
+
+/**
+ * Resolve the plan for a run: the same task selection, lock pinning, and skips as the run itself.
+ */
+async function buildRunPlan(
+  config: RunnerConfig,
+  specs: AgentSpec[],
+  model: string,
+  selection: { taskId?: string; suite?: string },
+  filter: TaskFilterOptions
+): Promise<RunPlan> {
+  const runner = new TaskRunner(config);
+  try {
+    const tasks = await runner.selectTasks(selection, filter);
+    const agents: AgentPlan[] = [];
+    for (const spec of specs) {
+      const agent = createAgentFromSpec(spec, model, config.maxIterations);
+      agents.push({ ...(await runner.planTasks(tasks, agent, spec.model || model)), agent: formatAgentSpec(spec, model) });
+    }
+    return {
+      selection: selection.taskId ? `task ${selection.taskId}` : selection.suite === 'all' ? 'all tasks' : `category ${selection.suite}`,
+      filter: null,
+      concurrency: 1,
+      fail_fast: config.failFast,
+      locked: config.locked,
+      agents,
+      estimated_duration_secs: agents.reduce((sum, agent) => sum + agent.estimated_duration_secs, 0),
+    };
+  } finally {
+    await runner.close();
+  }
+}
+
+/**
+ * Ask before running tasks that give the agent unsandboxed access to this machine.
+ * With a sandbox configured, the agent cannot modify the machine outside the workspace, so nothing is asked.
+ * @returns The acknowledgment to record in the run manifest, or null if no task needs one
+ * @throws PermissionAcknowledgmentError If confirmation is declined or cannot be asked for
+ */
+export async function acknowledgePermissions(
+  config: RunnerConfig,
+  selection: { taskId?: string; suite?: string },
+  filter: TaskFilterOptions,
+  yes: boolean
+): Promise<PermissionAcknowledgment | null> {
+  if (config.sandbox !== 'off') {
+    return null;
+  }
+  const runner = new TaskRunner(config);
+  let dangerous: DangerousTask[];
+  try {
+    dangerous = findDangerousTasks(await runner.selectTasks(selection, filter));
+  } finally {
+    await runner.close();
+  }
+  if (dangerous.length === 0) {
+    return null;
+  }
+
+  if (!yes) {
+    logger.warn(`${dangerous.length} task(s) give the agent unsandboxed access to this machine:`);
+    for (const entry of dangerous) {
+      logger.warn(`  ${entry.task_id}: ${entry.reasons.join(', ')}`);
+    }
+    if (!isInteractive()) {
+      throw new PermissionAcknowledgmentError('Not asking for confirmation in a non-interactive session; pass --yes to run anyway');
+    }
+    if (!(await confirm('Run these tasks anyway?'))) {
+      throw new PermissionAcknowledgmentError('Run cancelled');
+    }
+  }
+
+  return { method: yes ? 'flag' : 'prompt', acknowledged_at: new Date().toISOString(), tasks: dangerous };
+}
+
+/**
+ * Format an estimated duration (e.g. `1h 05m`, `4m 30s`).
+ */
+function formatEstimate(secs: number | null): string {
+  if (secs === null) {
+    return '?';
+  }
+  const rounded = Math.round(secs);
+  const hours = Math.floor(rounded / 3600);
+  const minutes = Math.floor((rounded % 3600) / 60);
+  if (hours > 0) {
+    return `${hours}h ${String(minutes).padStart(2, '0')}m`;
+  }
+  return minutes > 0 ? `${minutes}m ${String(rounded % 60).padStart(2, '0')}s` : `${rounded}s`;
+}
+
+/**
+ * Print a run plan.
+ */
+function emitPlan(plan: RunPlan): void {
+  const text = () => {
+    console.log(chalk.bold(`Plan: ${plan.selection}${plan.filter ? ` matching "${plan.filter}"` : ''}`));
+    console.log(chalk.gray(
+      `  Concurrency: ${plan.concurrency} (agents run one after another)` +
+        `${plan.fail_fast ? ' · fail-fast' : ''}${plan.locked ? ' · locked' : ''}`
+    ));
+    for (const agent of plan.agents) {
+      const runnable = agent.tasks.filter(task => !task.skip_reason).length;
+      console.log(`\n${chalk.bold.cyan(agent.agent)}: ${runnable} to run, ${agent.tasks.length - runnable} to skip, ` +
+        `est. ${formatEstimate(agent.estimated_duration_secs)}` +
+        (agent.unestimated > 0 ? chalk.yellow(` + ${agent.unestimated} without history`) : ''));
+      for (const task of agent.tasks) {
+        const estimate = task.skip_reason
+          ? chalk.yellow(`skip: ${task.skip_reason}`)
+          : chalk.gray(`${formatEstimate(task.estimated_duration_secs)}${task.estimate_source ? ` (${task.estimate_source})` : ''}`);
+        console.log(`  ${String(task.order).padStart(3)}. ${task.task_id}  ${task.title}  ${estimate}`);
+      }
+    }
+    console.log(chalk.bold(`\nEstimated total: ${formatEstimate(plan.estimated_duration_secs)}`));
+  };
+  const table = () => ({
+    columns: ['Agent', '#', 'Task', 'Category', 'Difficulty', 'Estimate', 'Source', 'Skip Reason'],
+    rows: plan.agents.flatMap(agent => agent.tasks.map(task => [
+      agent.agent,
+      task.order,
+      task.task_id,
+      task.category,
+      task.difficulty,
+      task.estimated_duration_secs === null ? null : Math.round(task.estimated_duration_secs),
+      task.estimate_source,
+      task.skip_reason,
+    ])),
+  });
+  emit(plan, text, table);
+}
+
+/**
+ * Print a side-by-side summary of one task run by several agents.
+ */
+function emitTaskComparison(results: BenchmarkResult[], specs: AgentSpec[], defaultModel: string): void {
+  const table = () => ({
+    columns: ['Agent', 'Outcome', 'Score', 'Iterations', 'Duration', 'Tokens'],
+    rows: results.map((result, index) => [
+      formatAgentSpec(specs[index], defaultModel),
+      getOutcome(result),
+      result.score,
+      result.iterations,
+      `${result.duration_secs.toFixed(1)}s`,
+      result.tokens_used,
+    ]),
+  });
+  emit(results, undefined, table);
+}
+
+/**
+ * Print a side-by-side summary of suites run by several agents.
+ */
+function emitSuiteComparison(suites: SuiteResults[], specs: AgentSpec[], defaultModel: string): void {
+  const table = () => ({
+    columns: ['Agent', 'Passed', 'Failed', 'Skipped', 'Pass Rate', 'Regressions', 'Duration'],
+    rows: suites.map((suite, index) => [
+      formatAgentSpec(specs[index], defaultModel),
+      suite.passed,
+      suite.failed,
+      suite.skipped,
+      `${(suite.pass_rate * 100).toFixed(1)}%`,
+      suite.regressions.length,
+      `${suite.total_duration_secs.toFixed(1)}s`,
+    ]),
+  });
+  emit(suites, undefined, table);
+}
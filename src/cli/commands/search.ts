@@ -0,0 +1,72 @@
+/**
+ * Search command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { TaskLoader } from '../../core/loader.js';
+import { collectValues, parseFilterExpression, parseTagList } from '../../core/filter.js';
+import { searchTasks } from '../../core/search.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+import { colorDifficulty } from '../../utils/colors.js';
+
+export function createSearchCommand(config: RunnerConfig): Command {
+  const command = new Command('search')
+    .description('Search tasks by ID, title, prompt, and tags')
+    .argument('<query...>', 'Search terms (all must match)')
+    .option('-c, --category <category>', 'Filter by category')
+    .option('-d, --difficulty <difficulty>', 'Filter by difficulty')
+    .option('-t, --tags <tags>', 'Filter by tags (comma-separated, e.g. lang:python,area:cli)')
+    .option('--tag <tag>', 'Filter by tag; namespace-aware (lang:python, lang:*); repeatable', collectValues, [])
+    .option('--filter <expr>', 'Filter expression (e.g. "category=bug-fix && difficulty!=hard && tag~python")')
+    .action(async (query: string[], options) => {
+      try {
+        const tasks = await new TaskLoader(config.tasksDir).loadAll({
+          category: options.category,
+          difficulty: options.difficulty,
+          tags: parseTagList(options.tags, options.tag),
+          expression: options.filter ? parseFilterExpression(options.filter) : undefined,
+        });
+
+        const matches = searchTasks(tasks, query.join(' '));
+        if (matches.length === 0) {
+          logger.warn('No tasks match the query');
+          return;
+        }
+
+        logger.info(`Found ${matches.length} matching tasks\n`);
+
+        emit(
+          matches.map(match => ({
+            id: match.task.id,
+            title: match.task.title,
+            category: match.task.category,
+            difficulty: match.task.difficulty,
+            score: match.score,
+            fields: match.fields,
+            snippet: match.snippet,
+          })),
+          () => {
+            for (const match of matches) {
+              console.log(`${chalk.bold.cyan(match.task.id.padEnd(20))}${match.task.title}`);
+              console.log(`  ${chalk.gray(`${match.task.category} ·`)} ${colorDifficulty(match.task.difficulty)} ${chalk.gray(`· matched: ${match.fields.join(', ')}`)}`);
+              if (match.snippet) {
+                console.log(`  ${match.snippet}`);
+              }
+            }
+          },
+          () => ({
+            columns: ['ID', 'Title', 'Score', 'Matched'],
+            rows: matches.map(match => [match.task.id, match.task.title, match.score, match.fields.join(',')]),
+          })
+        );
+      } catch (error) {
+        logger.error(`Search failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
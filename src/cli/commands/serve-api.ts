@@ -0,0 +1,41 @@
+/**
+ * Serve-api command implementation.
+ */
+
+import { Command } from 'commander';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+
+export function createServeApiCommand(config: RunnerConfig): Command {
+  const command = new Command('serve-api')
+    .description('Serve a REST API to list tasks, queue runs, poll their status, and fetch results')
+    .option('--host <host>', 'Address to bind', '127.0.0.1')
+    .option('-p, --port <port>', 'Port to listen on', '8787')
+    .option('--token <token>', 'Require "Authorization: Bearer <token>" (default: AGENT_BENCH_API_TOKEN, else a generated token)')
+    .action(async (options) => {
+      try {
+        const port = Number(options.port);
+        if (!Number.isInteger(port) || port < 0 || port > 65535) {
+          throw new Error(`Invalid --port: ${options.port}`);
+        }
+        const configuredToken: string | undefined = options.token || process.env.AGENT_BENCH_API_TOKEN;
+
+        // The server (the `server` feature) is only loaded when serving
+        const { startApiServer } = await import('../../server/api.js');
+        const { generateToken } = await import('../../server/http.js');
+        const token = configuredToken || generateToken();
+        const server = startApiServer(config, { host: options.host, port, token });
+        logger.success(`API listening on http://${server.hostname}:${server.port}/api`);
+        if (!configuredToken) {
+          // Printed once to stderr rather than logged, so it stays out of log files
+          process.stderr.write(`Bearer token: ${token} (set --token or AGENT_BENCH_API_TOKEN to choose one)\n`);
+        }
+        logger.info('Runs execute one at a time; poll GET /api/runs/:id for status');
+      } catch (error) {
+        logger.error(`Failed to start API server: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -0,0 +1,200 @@
+/**
+ * Show command implementation.
+ */
+
+import { Command } from 'commander';
+import { readdir, readFile, stat } from 'fs/promises';
+import { join } from 'path';
+import chalk from 'chalk';
+import { getOutcome } from '../../evaluator/results.js';
+import type { BenchmarkResult, SuiteResults } from '../../evaluator/results.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { getOverheadSecs, type PhaseTimings } from '../../core/phases.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+import { outcomeColor, outcomeMarker } from '../../utils/colors.js';
+
+/**
+ * Number of trailing lines shown for outputs without --full.
+ */
+const TRUNCATED_LINES = 20;
+
+/**
+ * Resolve a result reference: a file path, a file name in the results directory
+ * (with or without `.json`), or a prefix such as a task ID or `suite_<agent>`
+ * (the most recent match wins).
+ */
+async function resolveResultPath(reference: string, resultsDir: string): Promise<string> {
+  for (const candidate of [reference, join(resultsDir, reference), join(resultsDir, `${reference}.json`)]) {
+    try {
+      if ((await stat(candidate)).isFile()) {
+        return candidate;
+      }
+    } catch {
+      // Try the next candidate
+    }
+  }
+
+  const matches = (await readdir(resultsDir))
+    .filter(file => file.endsWith('.json') && file.startsWith(reference))
+    .sort();
+  if (matches.length === 0) {
+    throw new Error(`No result matching "${reference}" in ${resultsDir}`);
+  }
+  return join(resultsDir, matches[matches.length - 1]);
+}
+
+/**
+ * Keep the last lines of an output unless the full output was requested.
+ */
+function truncate(output: string, full: boolean): string {
+  const lines = output.trimEnd().split('\n');
+  if (full || lines.length <= TRUNCATED_LINES) {
+    return lines.join('\n');
+  }
+  const hidden = lines.length - TRUNCATED_LINES;
+  return [chalk.gray(`… ${hidden} earlier lines (use --full)`), ...lines.slice(-TRUNCATED_LINES)].join('\n');
+}
+
+/**
+ * One-line phase breakdown, e.g. `workspace 1.20s · agent 41.03s (3 iterations, longest 20.50s) · verification 3.10s`.
+ */
+function formatPhases(phases: PhaseTimings): string {
+  const parts: string[] = [];
+  for (const [name, secs] of [
+    ['toolchain', phases.toolchain_secs],
+    ['workspace', phases.workspace_secs],
+    ['agent', phases.agent_secs],
+    ['verification', phases.verification_secs],
+  ] as const) {
+    if (secs === null) continue;
+    let part = `${name} ${secs.toFixed(2)}s`;
+    if (name === 'agent' && phases.iteration_secs.length > 0) {
+      part += ` (${phases.iteration_secs.length} iterations, longest ${Math.max(...phases.iteration_secs).toFixed(2)}s)`;
+    }
+    parts.push(part);
+  }
+  return `${parts.join(' · ')}  (harness overhead ${getOverheadSecs(phases).toFixed(2)}s)`;
+}
+
+/**
+ * Pretty-print a single task result.
+ */
+function printResult(result: BenchmarkResult, path: string, full: boolean): void {
+  console.log(chalk.bold.cyan(`━━━ ${result.task_id} ━━━`));
+  console.log(`  Outcome: ${outcomeMarker(getOutcome(result))}  (score ${result.score})`);
+  console.log(`  Agent: ${result.agent}${result.model_name ? ` (${result.model_name})` : ''}`);
+  if (result.agent_version) {
+    console.log(`  Agent Version: ${result.agent_version}`);
+  }
+  if (result.credential) {
+    console.log(`  Credential: ${result.credential}`);
+  }
+  if (result.worker) {
+    console.log(`  Worker: ${result.worker}`);
+  }
+  console.log(`  Timestamp: ${result.timestamp}`);
+  console.log(`  Duration: ${result.duration_secs.toFixed(2)}s`);
+  if (result.setup_secs !== null && result.setup_secs !== undefined) {
+    console.log(`  Workspace Setup: ${result.setup_secs.toFixed(2)}s`);
+  }
+  if (result.phases) {
+    console.log(`  Phases: ${formatPhases(result.phases)}`);
+  }
+  if (result.expected_duration_secs) {
+    console.log(`  Expected Duration: ${result.expected_duration_secs}s`);
+  }
+  console.log(`  Iterations: ${result.iterations}`);
+  if (result.tokens_used !== null) {
+    console.log(`  Tokens: ${result.tokens_used}`);
+  }
+  if (result.total_checkpoints) {
+    console.log(`  Checkpoints: ${result.checkpoints_reached ?? 0}/${result.total_checkpoints}`);
+  }
+  if (result.tags && result.tags.length > 0) {
+    console.log(`  Tags: ${result.tags.join(', ')}`);
+  }
+  if (result.leaked_processes && result.leaked_processes.length > 0) {
+    console.log(chalk.yellow(`  Leaked Processes (killed): ${result.leaked_processes.map(entry => entry.command).join('; ')}`));
+  }
+  if (result.skip_reason) {
+    console.log(chalk.yellow(`  Skip Reason: ${result.skip_reason}`));
+  }
+  if (result.error) {
+    console.log(chalk.red(`  Error: ${result.error}`));
+  }
+  console.log(`  File: ${path}`);
+  if (result.log_path) {
+    console.log(`  Log: ${result.log_path}`);
+  }
+
+  if (result.verification_output) {
+    console.log(chalk.bold('\n  Verification Output:'));
+    console.log(truncate(result.verification_output, full));
+  }
+  if (result.agent_output) {
+    console.log(chalk.bold('\n  Agent Output:'));
+    console.log(truncate(result.agent_output, full));
+  }
+  console.log();
+}
+
+/**
+ * Pretty-print a suite overview.
+ */
+function printSuite(suite: SuiteResults, path: string): void {
+  console.log(chalk.bold.cyan(`━━━ Suite: ${suite.agent} (${suite.timestamp}) ━━━`));
+  console.log(`  Passed: ${suite.passed}/${suite.total_tasks - suite.skipped}  Skipped: ${suite.skipped}  Pass Rate: ${(suite.pass_rate * 100).toFixed(1)}%`);
+  console.log(`  Total Duration: ${suite.total_duration_secs.toFixed(2)}s`);
+  if (suite.prefetch_secs !== null && suite.prefetch_secs !== undefined) {
+    console.log(`  Prefetch: ${suite.prefetch_secs.toFixed(2)}s`);
+  }
+  if (suite.regressions && suite.regressions.length > 0) {
+    console.log(chalk.red.bold(`  Regressions: ${suite.regressions.join(', ')}`));
+  }
+  console.log(`  File: ${path}\n`);
+
+  for (const result of suite.results) {
+    const outcome = getOutcome(result);
+    const regressed = suite.regressions?.includes(result.task_id) ? chalk.red.bold(' (regression)') : '';
+    const detail = result.error || result.skip_reason || '';
+    console.log(`  ${result.task_id.padEnd(20)}${outcomeColor(outcome)(outcome.padEnd(9))}${result.duration_secs.toFixed(1).padStart(8)}s${regressed}  ${chalk.gray(detail)}`);
+  }
+  console.log(chalk.gray('\n  Use --task <id> for details'));
+}
+
+export function createShowCommand(config: RunnerConfig): Command {
+  const command = new Command('show')
+    .description('Show details of a stored result or suite run')
+    .argument('<result>', 'Result file, suite file, or prefix (task ID, suite_<agent>)')
+    .option('-t, --task <task-id>', 'Show one task of a suite run')
+    .option('--full', 'Show complete agent and verification output')
+    .action(async (reference: string, options) => {
+      try {
+        const path = await resolveResultPath(reference, config.resultsDir);
+        const data = JSON.parse(await readFile(path, 'utf-8'));
+
+        if (!Array.isArray(data.results)) {
+          emit(data, () => printResult(data as BenchmarkResult, path, Boolean(options.full)));
+          return;
+        }
+
+        const suite = data as SuiteResults;
+        if (!options.task) {
+          emit(suite, () => printSuite(suite, path));
+          return;
+        }
+
+        const result = suite.results.find(r => r.task_id === options.task);
+        if (!result) {
+          throw new Error(`Task ${options.task} not found in ${path}`);
+        }
+        emit(result, () => printResult(result, path, Boolean(options.full)));
+      } catch (error) {
+        logger.error(`Failed to show result: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -0,0 +1,135 @@
+/**
+ * Stats command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import { TaskLoader } from '../../core/loader.js';
+import { computeStats } from '../../evaluator/stats.js';
+import type { GroupStats } from '../../evaluator/stats.js';
+import { formatCost } from '../../evaluator/pricing.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { loadStoredResults } from '../../storage/store.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createStatsCommand(config: RunnerConfig): Command {
+  const command = new Command('stats')
+    .description('Summarize stored results: pass rates per agent and category, durations, tokens, costs, most-failed tasks')
+    .option('-a, --agent <agent>', 'Only include results from this agent')
+    .option('--top <n>', 'Number of most-failed tasks to list', '5')
+    .action(async (options) => {
+      try {
+        let results = await loadStoredResults(config);
+        if (options.agent) {
+          results = results.filter(r => r.agent === options.agent);
+        }
+
+        if (results.length === 0) {
+          logger.warn('No results found');
+          return;
+        }
+
+        const categories = new Map<string, string>();
+        try {
+          for (const task of await new TaskLoader(config.tasksDir).loadAll()) {
+            categories.set(task.id, task.category);
+          }
+        } catch (error) {
+          logger.warn(`Could not load tasks for category breakdown: ${error}`);
+        }
+
+        const stats = computeStats(results, taskId => categories.get(taskId) || 'unknown', parseInt(options.top, 10), config.pricing);
+
+        emit(
+          stats,
+          () => {
+            const { overall } = stats;
+            console.log(chalk.bold('Overall'));
+            console.log(`  Results: ${overall.runs} (${overall.skipped} skipped)`);
+            console.log(`  Pass Rate: ${formatRate(overall)} (${overall.passed}/${overall.passed + overall.failed})`);
+            console.log(`  Median Duration: ${formatDuration(overall.medianDurationSecs)}`);
+            if (overall.medianAgentSecs !== null) {
+              console.log(
+                `  Median Agent Time: ${formatDuration(overall.medianAgentSecs)} (harness overhead ${formatDuration(overall.medianOverheadSecs)})`
+              );
+            }
+            console.log(`  Total Tokens: ${overall.totalTokens}`);
+
+            printGroups('By Agent', stats.byAgent);
+            printGroups('By Category', stats.byCategory);
+
+            const costed = stats.costs.filter(entry => entry.unpriced < entry.runs);
+            if (costed.length > 0) {
+              console.log(chalk.bold('\nCosts'));
+              for (const entry of costed) {
+                const name = entry.model ? `${entry.agent} (${entry.model})` : entry.agent;
+                const unpriced = entry.unpriced > 0 ? chalk.gray(` (${entry.unpriced} runs without a price)`) : '';
+                console.log(`  ${name.substring(0, 38).padEnd(40)}${formatCost(entry.costUsd).padStart(12)}${unpriced}`);
+              }
+            }
+
+            if (stats.mostFailed.length > 0) {
+              console.log(chalk.bold('\nMost Failed'));
+              for (const task of stats.mostFailed) {
+                console.log(`  ${task.taskId.padEnd(24)}${chalk.red(`${task.failures}/${task.runs} failed`)}`);
+              }
+            }
+          },
+          () => ({
+            columns: ['Group', 'Name', 'Runs', 'Passed', 'Failed', 'Skipped', 'Pass Rate', 'Median', 'Agent', 'Overhead', 'Tokens'],
+            rows: [
+              ['overall', stats.overall] as const,
+              ...stats.byAgent.map(group => ['agent', group] as const),
+              ...stats.byCategory.map(group => ['category', group] as const),
+            ].map(([kind, group]) => [
+              kind,
+              group.name,
+              group.runs,
+              group.passed,
+              group.failed,
+              group.skipped,
+              formatRate(group),
+              formatDuration(group.medianDurationSecs),
+              formatDuration(group.medianAgentSecs),
+              formatDuration(group.medianOverheadSecs),
+              group.totalTokens,
+            ]),
+          })
+        );
+      } catch (error) {
+        logger.error(`Failed to compute stats: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
+
+/**
+ * Print one breakdown section.
+ */
+function printGroups(title: string, groups: GroupStats[]): void {
+  console.log(chalk.bold(`\n${title}`));
+  console.log(chalk.gray(`  ${'Name'.padEnd(40)}${'Runs'.padStart(6)}${'Pass Rate'.padStart(11)}${'Median'.padStart(10)}${'Tokens'.padStart(12)}`));
+  for (const group of groups) {
+    console.log(
+      `  ${group.name.substring(0, 38).padEnd(40)}${String(group.runs).padStart(6)}${formatRate(group).padStart(11)}` +
+      `${formatDuration(group.medianDurationSecs).padStart(10)}${String(group.totalTokens).padStart(12)}`
+    );
+  }
+}
+
+/**
+ * Format a group's pass rate as a percentage.
+ */
+function formatRate(group: GroupStats): string {
+  return `${(group.passRate * 100).toFixed(1)}%`;
+}
+
+/**
+ * Format an optional duration in seconds.
+ */
+function formatDuration(secs: number | null): string {
+  return secs === null ? '-' : `${secs.toFixed(1)}s`;
+}
@@ -0,0 +1,105 @@
+/**
+ * Status command implementation.
+ */
+
+import { Command } from 'commander';
+import chalk from 'chalk';
+import type { RunnerConfig } from '../../core/config.js';
+import { findLatestRun, getRunStatus, readRunState, type RunState } from '../../core/state.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+import { outcomeMarker } from '../../utils/colors.js';
+
+export function createStatusCommand(config: RunnerConfig): Command {
+  const command = new Command('status')
+    .description('Show where each task of a run stands (follow a long suite, or see where a crashed run stopped)')
+    .argument('[run-id]', 'Run to show (default: the most recently updated)')
+    .option('-w, --watch [secs]', 'Refresh until the run ends (every 2 seconds by default)')
+    .action(async (runId: string | undefined, options) => {
+      try {
+        const id = runId ?? (await findLatestRun(config.resultsDir));
+        if (!id) {
+          logger.warn(`No runs with recorded state in ${config.resultsDir}`);
+          return;
+        }
+
+        if (!options.watch) {
+          printStatus(await readRunState(config.resultsDir, id));
+          return;
+        }
+
+        const interval = options.watch === true ? 2 : Number(options.watch);
+        if (!(interval > 0)) {
+          throw new Error(`Invalid --watch interval: ${options.watch}`);
+        }
+        for (;;) {
+          const state = await readRunState(config.resultsDir, id);
+          if (process.stdout.isTTY) {
+            process.stdout.write('\x1b[2J\x1b[H');
+          }
+          printStatus(state);
+          if (getRunStatus(state) !== 'running') {
+            break;
+          }
+          await Bun.sleep(interval * 1000);
+        }
+      } catch (error) {
+        logger.error(`Failed to read run status: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
+
+/**
+ * Print a run's task states.
+ */
+function printStatus(state: RunState): void {
+  const status = getRunStatus(state);
+  const now = Date.now();
+
+  emit(
+    { ...state, status },
+    () => {
+      const label = status === 'finished' ? chalk.green(status) : status === 'interrupted' ? chalk.red(status) : chalk.cyan(status);
+      console.log(chalk.bold(`Run ${state.run_id}`) + ` (${state.agent}): ${label}`);
+      console.log(chalk.gray(`  Started ${state.started_at} · updated ${state.updated_at} · pid ${state.pid} on ${state.host}`));
+
+      for (const taskId of state.task_ids) {
+        const entry = state.tasks[taskId];
+        const marker = entry.outcome ? outcomeMarker(entry.outcome) : chalk.gray('....');
+        const detail = entry.state === 'scored'
+          ? (entry.error ? chalk.gray(entry.error.split('\n')[0].substring(0, 80)) : '')
+          : entry.state === 'pending'
+            ? ''
+            : chalk.cyan(`${entry.state} for ${formatElapsed(now - Date.parse(entry.since))}`);
+        console.log(`  ${marker} ${taskId.padEnd(24)}${detail}`);
+      }
+
+      if (status === 'interrupted') {
+        const stopped = state.task_ids.filter(taskId => !['pending', 'scored'].includes(state.tasks[taskId].state));
+        if (stopped.length > 0) {
+          console.log(chalk.red(`\nProcess ${state.pid} exited while ${stopped.map(taskId => `${taskId} was ${state.tasks[taskId].state}`).join(', ')}`));
+        }
+        console.log(chalk.gray(`Continue with: agent-bench run --resume ${state.run_id} --agent <agent>`));
+      }
+    },
+    () => ({
+      columns: ['Task', 'State', 'Since', 'Outcome', 'Error'],
+      rows: state.task_ids.map(taskId => {
+        const entry = state.tasks[taskId];
+        return [taskId, entry.state, entry.since, entry.outcome, entry.error?.split('\n')[0] ?? null];
+      }),
+    })
+  );
+}
+
+/**
+ * Format milliseconds as e.g. `4m 05s`.
+ */
+function formatElapsed(ms: number): string {
+  const secs = Math.max(0, Math.round(ms / 1000));
+  const minutes = Math.floor(secs / 60);
+  return minutes > 0 ? `${minutes}m ${String(secs % 60).padStart(2, '0')}s` : `${secs}s`;
+}
@@ -0,0 +1,101 @@
+/**
+ * Tasks command implementation (corpus maintenance tools).
+ */
+
+import { Command } from 'commander';
+import { writeFile } from 'fs/promises';
+import { TaskLoader } from '../../core/loader.js';
+import { buildTaskGraph, renderDot } from '../../core/graph.js';
+import { migrateTaskFile, type TaskMigration } from '../../core/migrate.js';
+import { TASK_SCHEMA_VERSION } from '../../core/task.js';
+import { collectValues, parseTagList } from '../../core/filter.js';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+import { emit, getCommandFormat } from '../../utils/output.js';
+
+export function createTasksCommand(config: RunnerConfig): Command {
+  const command = new Command('tasks')
+    .description('Inspect the task corpus');
+
+  command
+    .command('graph')
+    .description('Export suites, categories, and tags as a graph')
+    .option('-f, --format <format>', 'Graph format: dot, json (default: dot)')
+    .option('-c, --category <category>', 'Filter by category')
+    .option('--tag <tag>', 'Filter by tag; repeatable', collectValues, [])
+    .option('--no-tags', 'Omit tag nodes (suites are always shown)')
+    .option('-o, --output <path>', 'Write to a file instead of stdout')
+    .action(async (options, cmd: Command) => {
+      try {
+        const format = getCommandFormat(cmd, options.format) ?? 'dot';
+        if (format !== 'dot' && format !== 'json') {
+          throw new Error(`Unsupported graph format: ${format}`);
+        }
+
+        const loader = new TaskLoader(config.tasksDir);
+        const tasks = await loader.loadAll({
+          category: options.category,
+          tags: parseTagList(undefined, options.tag),
+        });
+        const graph = buildTaskGraph(tasks, { tags: options.tags });
+        const rendered = format === 'json'
+          ? JSON.stringify(graph, null, 2) + '\n'
+          : renderDot(graph);
+
+        if (options.output) {
+          await writeFile(options.output, rendered, 'utf-8');
+          logger.success(`Graph of ${tasks.length} tasks written to ${options.output}`);
+        } else {
+          process.stdout.write(rendered);
+        }
+      } catch (error) {
+        logger.error(`Failed to build task graph: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  command
+    .command('migrate')
+    .description(`Upgrade task files to task schema ${TASK_SCHEMA_VERSION} (adds the schema field where missing)`)
+    .option('--dry-run', 'List the files that would change without writing them')
+    .action(async (options) => {
+      try {
+        const loader = new TaskLoader(config.tasksDir);
+        const migrations: TaskMigration[] = [];
+        let failed = 0;
+        for (const file of await loader.listTaskFiles()) {
+          try {
+            migrations.push(await migrateTaskFile(file, !options.dryRun));
+          } catch (error) {
+            failed++;
+            logger.error(error instanceof Error ? error.message : String(error));
+          }
+        }
+
+        const changed = migrations.filter(migration => migration.changed);
+        emit(
+          changed,
+          () => {
+            for (const migration of changed) {
+              console.log(`${migration.path}: schema ${migration.from} → ${migration.to}`);
+            }
+            const verb = options.dryRun ? 'would be migrated' : 'migrated';
+            logger.info(`${changed.length} of ${migrations.length} task files ${verb}`);
+          },
+          () => ({
+            columns: ['File', 'From', 'To'],
+            rows: changed.map(migration => [migration.path, String(migration.from), String(migration.to)]),
+          })
+        );
+
+        if (failed > 0) {
+          process.exit(1);
+        }
+      } catch (error) {
+        logger.error(`Failed to migrate tasks: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
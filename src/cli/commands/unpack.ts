@@ -0,0 +1,31 @@
+/**
+ * Unpack command implementation.
+ */
+
+import { Command } from 'commander';
+import { extractPack } from '../../core/pack.js';
+import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
+
+export function createUnpackCommand(): Command {
+  const command = new Command('unpack')
+    .description('Extract a task pack archive and verify its checksums')
+    .argument('<archive>', 'Pack archive path')
+    .option('-o, --output <path>', 'Destination directory', 'tasks')
+    .action(async (archive: string, options) => {
+      try {
+        const manifest = await extractPack(archive, options.output);
+
+        logger.success(`Unpacked ${manifest.name}@${manifest.version} into ${options.output}`);
+        emit(manifest, () => {
+          console.log(`  Tasks: ${manifest.tasks.length}`);
+          console.log(`  Files verified: ${manifest.files.length}`);
+        });
+      } catch (error) {
+        logger.error(`Unpack failed: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
@@ -4,22 +4,26 @@
 
 import { Command } from 'commander';
 import { TaskLoader } from '../../core/loader.js';
+import { WorkspaceManager } from '../../core/workspace.js';
 import { Verifier } from '../../evaluator/verifier.js';
+import type { RunnerConfig } from '../../core/config.js';
 import { logger } from '../../utils/logger.js';
+import { emit } from '../../utils/output.js';
 
-export function createVerifyCommand(tasksDir: string): Command {
+export function createVerifyCommand(config: RunnerConfig): Command {
   const command = new Command('verify')
     .description('Manually verify a task in a workspace')
     .requiredOption('-t, --task <task-id>', 'Task ID to verify')
     .requiredOption('-w, --workspace <path>', 'Workspace path')
     .action(async (options) => {
       try {
-        const loader = new TaskLoader(tasksDir);
+        const loader = new TaskLoader(config.tasksDir);
         const task = await loader.loadById(options.task);
 
         logger.info(`Verifying task: ${task.id}`);
         logger.info(`Workspace: ${options.workspace}\n`);
 
+        await new WorkspaceManager(config.workspaceDir).applyHiddenTests(task, options.workspace);
         const result = await Verifier.verify(task, options.workspace);
 
         logger.taskResult(
@@ -30,9 +34,11 @@ export function createVerifyCommand(tasksDir: string): Command {
           undefined
         );
 
-        console.log(`\nExit code: ${result.exitCode}`);
-        console.log(`\nSTDOUT:\n${result.stdout}`);
-        console.log(`\nSTDERR:\n${result.stderr}`);
+        emit({ task_id: task.id, ...result }, () => {
+          console.log(`\nExit code: ${result.exitCode}`);
+          console.log(`\nSTDOUT:\n${result.stdout}`);
+          console.log(`\nSTDERR:\n${result.stderr}`);
+        });
 
         process.exit(result.passed ? 0 : 1);
       } catch (error) {
@@ -0,0 +1,41 @@
+/**
+ * Worker command implementation.
+ */
+
+import { Command } from 'commander';
+import type { RunnerConfig } from '../../core/config.js';
+import { logger } from '../../utils/logger.js';
+
+export function createWorkerCommand(config: RunnerConfig): Command {
+  const command = new Command('worker')
+    .description('Execute tasks dispatched by an agent-bench coordinate process, one at a time')
+    .option('--host <host>', 'Address to bind', '127.0.0.1')
+    .option('-p, --port <port>', 'Port to listen on', '8788')
+    .option('--token <token>', 'Require "Authorization: Bearer <token>" (default: AGENT_BENCH_WORKER_TOKEN, else a generated token)')
+    .action(async (options) => {
+      try {
+        const port = Number(options.port);
+        if (!Number.isInteger(port) || port < 0 || port > 65535) {
+          throw new Error(`Invalid --port: ${options.port}`);
+        }
+        const configuredToken: string | undefined = options.token || process.env.AGENT_BENCH_WORKER_TOKEN;
+
+        // The worker (the `server` feature) is only loaded when serving
+        const { startWorkerServer } = await import('../../server/worker.js');
+        const { generateToken } = await import('../../server/http.js');
+        const token = configuredToken || generateToken();
+        const server = startWorkerServer(config, { host: options.host, port, token });
+        logger.success(`Worker listening on http://${server.hostname}:${server.port}`);
+        if (!configuredToken) {
+          // Printed once to stderr rather than logged, so it stays out of log files
+          process.stderr.write(`Bearer token: ${token} (pass it to coordinate with --token, or set AGENT_BENCH_WORKER_TOKEN)\n`);
+        }
+        logger.info(`Tasks are loaded from ${config.tasksDir}; results are also stored in ${config.resultsDir}`);
+      } catch (error) {
+        logger.error(`Failed to start worker: ${error}`);
+        process.exit(1);
+      }
+    });
+
+  return command;
+}
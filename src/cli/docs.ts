@@ -0,0 +1,181 @@
+/**
+ * Man page and CLI reference generation from the Commander definitions.
+ *
+ * Everything is derived from the registered commands, so the generated docs
+ * cannot drift from `--help`.
+ */
+
+import type { Command, Option } from 'commander';
+import { isAbsolute } from 'path';
+
+/**
+ * A command and its full name (e.g. `agent-bench cache list`).
+ */
+export interface DocumentedCommand {
+  command: Command;
+  path: string[];
+}
+
+/**
+ * Collect the program and every visible subcommand, depth first.
+ */
+export function collectCommands(program: Command): DocumentedCommand[] {
+  const collected: DocumentedCommand[] = [];
+  const visit = (command: Command, path: string[]) => {
+    collected.push({ command, path });
+    for (const child of command.createHelp().visibleCommands(command)) {
+      if (child.name() !== 'help') {
+        visit(child, [...path, child.name()]);
+      }
+    }
+  };
+  visit(program, [program.name()]);
+  return collected;
+}
+
+/**
+ * Default value worth documenting. Values resolved on this machine (absolute
+ * paths from the config) would make the generated docs non-reproducible.
+ */
+function documentedDefault(option: Option): string | null {
+  const value = option.defaultValue;
+  if (value === undefined || value === false || (Array.isArray(value) && value.length === 0)) {
+    return null;
+  }
+  if (option.negate && value === true) {
+    return null;
+  }
+  if (typeof value === 'string' && isAbsolute(value)) {
+    return null;
+  }
+  return JSON.stringify(value);
+}
+
+/**
+ * Option description with its default appended.
+ */
+function optionDescription(option: Option): string {
+  const value = documentedDefault(option);
+  return value === null ? option.description : `${option.description} (default: ${value})`;
+}
+
+/**
+ * Escape text for roff.
+ */
+function roff(text: string): string {
+  return text
+    .replace(/\\/g, '\\e')
+    .replace(/-/g, '\\-')
+    .replace(/^([.'])/gm, '\\&$1');
+}
+
+/**
+ * Man page file name for a command (`agent-bench-cache-list.1`).
+ */
+export function manPageName(path: string[]): string {
+  return `${path.join('-')}.1`;
+}
+
+/**
+ * Render a section 1 man page.
+ * @param date Date shown in the page footer (YYYY-MM-DD)
+ */
+export function renderManPage(entry: DocumentedCommand, root: Command, date: string): string {
+  const { command, path } = entry;
+  const help = command.createHelp();
+  const name = path.join('-');
+  const lines = [
+    `.TH ${roff(name.toUpperCase())} 1 "${date}" "${roff(`${root.name()} ${root.version() ?? ''}`.trim())}" "Agent Bench Manual"`,
+    '.SH NAME',
+    `${roff(name)} \\- ${roff(command.description())}`,
+    '.SH SYNOPSIS',
+    `.B ${roff(help.commandUsage(command))}`,
+  ];
+
+  const description = command.description();
+  if (description) {
+    lines.push('.SH DESCRIPTION', roff(description));
+  }
+
+  const args = help.visibleArguments(command);
+  if (args.length > 0) {
+    lines.push('.SH ARGUMENTS');
+    for (const argument of args) {
+      lines.push('.TP', `.B ${roff(help.argumentTerm(argument))}`, roff(argument.description || ''));
+    }
+  }
+
+  const options = help.visibleOptions(command);
+  if (options.length > 0) {
+    lines.push('.SH OPTIONS');
+    for (const option of options) {
+      lines.push('.TP', `.B ${roff(help.optionTerm(option))}`, roff(optionDescription(option)));
+    }
+  }
+
+  const subcommands = help.visibleCommands(command).filter(child => child.name() !== 'help');
+  if (subcommands.length > 0) {
+    lines.push('.SH COMMANDS');
+    for (const child of subcommands) {
+      lines.push('.TP', `.BR ${roff([...path, child.name()].join('-'))} (1)`, roff(child.description()));
+    }
+  }
+
+  const related = [path.slice(0, -1), ...(path.length > 2 ? [[path[0]]] : [])]
+    .filter(parent => parent.length > 0)
+    .map(parent => parent.join('-'));
+  if (related.length > 0) {
+    lines.push('.SH SEE ALSO', related.map(page => `.BR ${roff(page)} (1)`).join(',\n'));
+  }
+
+  return lines.join('\n') + '\n';
+}
+
+/**
+ * Render a Markdown CLI reference covering every command.
+ */
+export function renderReference(program: Command): string {
+  const lines = [
+    `# ${program.name()} CLI reference`,
+    '',
+    '<!-- Generated by `agent-bench docs gen`; do not edit by hand. -->',
+    '',
+  ];
+
+  for (const { command, path } of collectCommands(program)) {
+    const help = command.createHelp();
+    const heading = '#'.repeat(Math.min(path.length + 1, 6));
+    lines.push(`${heading} \`${path.join(' ')}\``, '');
+    if (command.description()) {
+      lines.push(command.description(), '');
+    }
+    lines.push('```', help.commandUsage(command), '```', '');
+
+    const args = help.visibleArguments(command);
+    if (args.length > 0) {
+      lines.push('| Argument | Description |', '| --- | --- |');
+      for (const argument of args) {
+        lines.push(`| \`${help.argumentTerm(argument)}\` | ${cell(argument.description || '')} |`);
+      }
+      lines.push('');
+    }
+
+    const options = help.visibleOptions(command);
+    if (options.length > 0) {
+      lines.push('| Option | Description |', '| --- | --- |');
+      for (const option of options) {
+        lines.push(`| \`${help.optionTerm(option)}\` | ${cell(optionDescription(option))} |`);
+      }
+      lines.push('');
+    }
+  }
+
+  return lines.join('\n');
+}
+
+/**
+ * Escape a Markdown table cell.
+ */
+function cell(text: string): string {
+  return text.replace(/\|/g, '\\|').replace(/\n/g, ' ');
+}
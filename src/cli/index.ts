@@ -3,22 +3,73 @@
  */
 
 import { Command } from 'commander';
-import { createDefaultConfig, loadUserConfig, mergeConfig } from '../core/config.js';
-import { logger } from '../utils/logger.js';
+import {
+  GIT_BACKENDS,
+  parseGitBackend,
+  parseResultsBackend,
+  parseSandbox,
+  resolveConfig,
+  RESULTS_BACKENDS,
+  SANDBOXES,
+} from '../core/config.js';
+import type { RunnerConfig } from '../core/config.js';
+import { LOG_FORMATS, logger, parseLogFormat } from '../utils/logger.js';
+import { OUTPUT_FORMATS, setOutputFormat } from '../utils/output.js';
+import { configureColors } from '../utils/colors.js';
+import { setGitBackend } from '../core/git.js';
+import { setSandbox } from '../sandbox/index.js';
+import { setAgentEnv } from '../agents/env.js';
+import { setCredentials } from '../agents/credentials.js';
+import { setCustomAgent } from '../agents/custom.js';
+import { setAgentProfiles } from '../agents/profiles.js';
+import { hasFeature } from '../features.js';
 import { createListCommand } from './commands/list.js';
 import { createRunCommand } from './commands/run.js';
 import { createCollectCommand } from './commands/collect.js';
 import { createVerifyCommand } from './commands/verify.js';
 import { createInitCommand } from './commands/init.js';
+import { createPackCommand } from './commands/pack.js';
+import { createUnpackCommand } from './commands/unpack.js';
+import { createLockCommand } from './commands/lock.js';
+import { createLintCommand } from './commands/lint.js';
+import { createSearchCommand } from './commands/search.js';
+import { createCalibrateCommand } from './commands/calibrate.js';
+import { createImportCommand } from './commands/import.js';
+import { createTasksCommand } from './commands/tasks.js';
+import { createShowCommand } from './commands/show.js';
+import { createCleanCommand } from './commands/clean.js';
+import { createReplayCommand } from './commands/replay.js';
+import { createStatsCommand } from './commands/stats.js';
+import { createExportCommand } from './commands/export.js';
+import { createCacheCommand } from './commands/cache.js';
+import { createFlakeCheckCommand } from './commands/flake-check.js';
+import { createAgentsCommand } from './commands/agents.js';
+import { createServeApiCommand } from './commands/serve-api.js';
+import { createWorkerCommand } from './commands/worker.js';
+import { createCoordinateCommand } from './commands/coordinate.js';
+import { createDocsCommand } from './commands/docs.js';
+import { createConfigCommand } from './commands/config.js';
+import { createStatusCommand } from './commands/status.js';
+
+/**
+ * Global flags overriding config fields (option attribute names match the fields).
+ */
+const CONFIG_FLAGS: Partial<Record<keyof RunnerConfig, string>> = {
+  tasksDir: '--tasks-dir',
+  resultsDir: '--results-dir',
+  workspaceDir: '--workspace-dir',
+  resultsBackend: '--results-backend',
+  gitBackend: '--git-backend',
+  sandbox: '--sandbox',
+};
 
 /**
  * Create and configure the CLI program.
  */
 export async function createCLI(): Promise<Command> {
-  // Load configuration
-  const defaultConfig = createDefaultConfig();
-  const userConfig = await loadUserConfig();
-  const config = mergeConfig(userConfig, defaultConfig);
+  // Load configuration (defaults < user config < agent-bench.toml files < AGENT_BENCH_* env; CLI flags applied below)
+  const resolved = await resolveConfig();
+  const config = resolved.config;
 
   // Create program
   const program = new Command();
@@ -28,28 +79,98 @@ export async function createCLI(): Promise<Command> {
     .description('An open-source benchmark for evaluating AI coding agents')
     .version('0.2.0')
     .option('--debug', 'Enable debug logging')
-    .option('--tasks-dir <path>', 'Tasks directory', config.tasksDir)
+    .option('--tasks-dir <path>', 'Tasks directory or git+<url>#<ref>', config.tasksDir)
     .option('--results-dir <path>', 'Results directory', config.resultsDir)
     .option('--workspace-dir <path>', 'Workspace directory', config.workspaceDir)
-    .hook('preAction', (thisCommand) => {
+    .option('--results-backend <backend>', `Result storage: ${RESULTS_BACKENDS.join(', ')}`, config.resultsBackend)
+    .option('--git-backend <backend>', `Git implementation: ${GIT_BACKENDS.join(', ')}`, config.gitBackend)
+    .option('--sandbox <backend>', `Sandbox for agents and verification commands: ${SANDBOXES.join(', ')}`, config.sandbox)
+    .option('--format <format>', `Output format: ${OUTPUT_FORMATS.join(', ')}`, 'text')
+    .option('--no-color', 'Disable colored output (also honors NO_COLOR)')
+    .option(
+      '--log-format <format>',
+      `Log format on stderr and in run logs: ${LOG_FORMATS.join(', ')}`,
+      process.env.AGENT_BENCH_LOG_FORMAT || 'text'
+    )
+    .hook('preAction', (thisCommand, actionCommand) => {
       // Enable debug logging if requested
       const opts = thisCommand.opts();
       if (opts.debug) {
         logger.setDebug(true);
       }
+      configureColors(opts.color);
+      try {
+        logger.setFormat(parseLogFormat(opts.logFormat));
+      } catch (error) {
+        logger.error(`${error instanceof Error ? error.message : error}`);
+        process.exit(1);
+      }
+
+      // Commands declaring their own --format (export, import humaneval, tasks graph) interpret the value themselves
+      if (!actionCommand.options.some(option => option.long === '--format')) {
+        try {
+          setOutputFormat(opts.format);
+        } catch (error) {
+          logger.error(`${error instanceof Error ? error.message : error}`);
+          process.exit(1);
+        }
+      }
 
       // Update config with CLI options
       if (opts.tasksDir) config.tasksDir = opts.tasksDir;
       if (opts.resultsDir) config.resultsDir = opts.resultsDir;
       if (opts.workspaceDir) config.workspaceDir = opts.workspaceDir;
+      try {
+        config.resultsBackend = parseResultsBackend(opts.resultsBackend);
+        config.gitBackend = parseGitBackend(opts.gitBackend);
+        config.sandbox = parseSandbox(opts.sandbox);
+      } catch (error) {
+        logger.error(`${error instanceof Error ? error.message : error}`);
+        process.exit(1);
+      }
+      setGitBackend(config.gitBackend);
+      setSandbox(config.sandbox, config.sandboxWritable);
+      setAgentEnv(config.agentEnv);
+      setCredentials(config.agentCredentials);
+      setCustomAgent(config.customAgent);
+      setAgentProfiles(config.agentProfiles);
+      for (const [key, flag] of Object.entries(CONFIG_FLAGS) as [keyof RunnerConfig, string][]) {
+        if (thisCommand.getOptionValueSource(key) === 'cli') {
+          resolved.sources[key] = `flag ${flag}`;
+        }
+      }
     });
 
   // Register commands
-  program.addCommand(createListCommand(config.tasksDir));
+  program.addCommand(createListCommand(config));
   program.addCommand(createRunCommand(config));
-  program.addCommand(createCollectCommand(config.resultsDir));
-  program.addCommand(createVerifyCommand(config.tasksDir));
-  program.addCommand(createInitCommand());
+  program.addCommand(createCollectCommand(config));
+  program.addCommand(createVerifyCommand(config));
+  program.addCommand(createInitCommand(config));
+  program.addCommand(createPackCommand(config));
+  program.addCommand(createUnpackCommand());
+  program.addCommand(createLockCommand(config));
+  program.addCommand(createLintCommand(config));
+  program.addCommand(createSearchCommand(config));
+  program.addCommand(createCalibrateCommand(config));
+  program.addCommand(createImportCommand(config));
+  program.addCommand(createTasksCommand(config));
+  program.addCommand(createShowCommand(config));
+  program.addCommand(createStatusCommand(config));
+  program.addCommand(createCleanCommand(config));
+  program.addCommand(createReplayCommand(config));
+  program.addCommand(createStatsCommand(config));
+  program.addCommand(createExportCommand(config));
+  program.addCommand(createCacheCommand(config));
+  program.addCommand(createFlakeCheckCommand(config));
+  program.addCommand(createAgentsCommand(config));
+  if (hasFeature('server')) {
+    program.addCommand(createServeApiCommand(config));
+    program.addCommand(createWorkerCommand(config));
+    program.addCommand(createCoordinateCommand(config));
+  }
+  program.addCommand(createDocsCommand());
+  program.addCommand(createConfigCommand(resolved));
 
   return program;
 }
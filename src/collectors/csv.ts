@@ -2,11 +2,12 @@
  * Collect and consolidate benchmark results into CSV format.
  */
 
-import { readdir, readFile, writeFile } from 'fs/promises';
+import { readdir, readFile } from 'fs/promises';
 import { join } from 'path';
 import { stringify } from 'csv-stringify/sync';
 import type { BenchmarkResult } from '../evaluator/results.js';
 import { logger } from '../utils/logger.js';
+import { withFileLock, writeFileAtomic } from '../utils/files.js';
 
 /**
  * Load a single result JSON file.
@@ -38,14 +39,9 @@ export async function collectResults(resultsDir: string): Promise<BenchmarkResul
 }
 
 /**
- * Write results to a CSV file.
+ * Render results as CSV with a header row.
  */
-export async function writeCSV(results: BenchmarkResult[], outputPath: string): Promise<void> {
-  if (results.length === 0) {
-    logger.warn('No results to write');
-    return;
-  }
-
+export function formatCSV(results: BenchmarkResult[]): string {
   // Define CSV columns
   const columns = [
     'task_id',
@@ -77,13 +73,23 @@ export async function writeCSV(results: BenchmarkResult[], outputPath: string):
   }));
 
   // Generate CSV
-  const csv = stringify(rows, {
+  return stringify(rows, {
     header: true,
     columns,
   });
+}
+
+/**
+ * Write results to a CSV file.
+ */
+export async function writeCSV(results: BenchmarkResult[], outputPath: string): Promise<void> {
+  if (results.length === 0) {
+    logger.warn('No results to write');
+    return;
+  }
 
   // Write to file
-  await writeFile(outputPath, csv, 'utf-8');
+  await writeFileAtomic(outputPath, formatCSV(results));
 
   logger.success(`Wrote ${results.length} results to ${outputPath}`);
 }
@@ -101,87 +107,95 @@ export async function collectAndWrite(resultsDir: string, outputPath: string): P
 
 /**
  * Append a single result to the CSV file.
- * Creates the file with header if it doesn't exist.
+ * Creates the file with header if it doesn't exist. Appends are serialized with a lock
+ * file, so concurrent runs sharing a results directory don't interleave rows.
  */
 export async function appendResultToCSV(result: BenchmarkResult, outputPath: string): Promise<void> {
   try {
-    // Check if file exists
-    let existingContent = '';
-    try {
-      existingContent = await readFile(outputPath, 'utf-8');
-    } catch {
-      // File doesn't exist, will create with header
-    }
+    await withFileLock(outputPath, () => appendRow(result, outputPath));
+  } catch (error) {
+    logger.warn(`Failed to append to CSV: ${error}`);
+    // Don't throw - CSV append is best-effort
+  }
+}
+
+/**
+ * Append a result row (the caller holds the CSV lock).
+ */
+async function appendRow(result: BenchmarkResult, outputPath: string): Promise<void> {
+  // Check if file exists
+  let existingContent = '';
+  try {
+    existingContent = await readFile(outputPath, 'utf-8');
+  } catch {
+    // File doesn't exist, will create with header
+  }
+
+  // Define CSV columns
+  const columns = [
+    'task_id',
+    'agent',
+    'agent_version',
+    'model_name',
+    'timestamp',
+    'success',
+    'score',
+    'iterations',
+    'duration_secs',
+    'tokens_used',
+    'error',
+  ];
 
-    // Define CSV columns
-    const columns = [
-      'task_id',
-      'agent',
-      'agent_version',
-      'model_name',
-      'timestamp',
-      'success',
-      'score',
-      'iterations',
-      'duration_secs',
-      'tokens_used',
-      'error',
-    ];
-
-    // Convert result to CSV row
-    const row = {
-      task_id: result.task_id,
-      agent: result.agent,
-      agent_version: result.agent_version || '',
-      model_name: result.model_name || '',
-      timestamp: result.timestamp,
-      success: result.success,
-      score: result.score,
-      iterations: result.iterations,
-      duration_secs: result.duration_secs.toFixed(2),
-      tokens_used: result.tokens_used || '',
-      error: result.error ? result.error.substring(0, 100) : '',
-    };
-
-    // Check for duplicates
-    if (existingContent) {
-      const lines = existingContent.trim().split('\n');
-      // Skip header line, check data rows
-      for (let i = 1; i < lines.length; i++) {
-        const line = lines[i];
-        const [existingTaskId, existingAgent, , , existingTimestamp] = line.split(',');
-        if (existingTaskId === result.task_id && 
-            existingAgent === result.agent && 
-            existingTimestamp === result.timestamp) {
-          // Duplicate found, skip
-          logger.debug(`Result already exists in CSV, skipping: ${result.task_id} (${result.agent})`);
-          return;
-        }
+  // Convert result to CSV row
+  const row = {
+    task_id: result.task_id,
+    agent: result.agent,
+    agent_version: result.agent_version || '',
+    model_name: result.model_name || '',
+    timestamp: result.timestamp,
+    success: result.success,
+    score: result.score,
+    iterations: result.iterations,
+    duration_secs: result.duration_secs.toFixed(2),
+    tokens_used: result.tokens_used || '',
+    error: result.error ? result.error.substring(0, 100) : '',
+  };
+
+  // Check for duplicates
+  if (existingContent) {
+    const lines = existingContent.trim().split('\n');
+    // Skip header line, check data rows
+    for (let i = 1; i < lines.length; i++) {
+      const line = lines[i];
+      const [existingTaskId, existingAgent, , , existingTimestamp] = line.split(',');
+      if (existingTaskId === result.task_id && 
+          existingAgent === result.agent && 
+          existingTimestamp === result.timestamp) {
+        // Duplicate found, skip
+        logger.debug(`Result already exists in CSV, skipping: ${result.task_id} (${result.agent})`);
+        return;
       }
     }
+  }
 
-    // Generate CSV for the new row
-    const { stringify } = await import('csv-stringify/sync');
-    const needsHeader = !existingContent;
-    const csv = stringify([row], {
-      header: needsHeader,
-      columns,
-    });
-
-    // Append to file
-    const { appendFile, writeFile } = await import('fs/promises');
-    if (needsHeader) {
-      await writeFile(outputPath, csv, 'utf-8');
-    } else {
-      // csv already has a trailing newline, just append it directly
-      await appendFile(outputPath, csv, 'utf-8');
-    }
+  // Generate CSV for the new row
+  const { stringify } = await import('csv-stringify/sync');
+  const needsHeader = !existingContent;
+  const csv = stringify([row], {
+    header: needsHeader,
+    columns,
+  });
 
-    logger.debug(`Appended result to CSV: ${result.task_id} (${result.agent})`);
-  } catch (error) {
-    logger.warn(`Failed to append to CSV: ${error}`);
-    // Don't throw - CSV append is best-effort
+  // Append to file
+  const { appendFile, writeFile } = await import('fs/promises');
+  if (needsHeader) {
+    await writeFile(outputPath, csv, 'utf-8');
+  } else {
+    // csv already has a trailing newline, just append it directly
+    await appendFile(outputPath, csv, 'utf-8');
   }
+
+  logger.debug(`Appended result to CSV: ${result.task_id} (${result.agent})`);
 }
 
 
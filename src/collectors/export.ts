@@ -0,0 +1,132 @@
+/**
+ * Result exporters behind `agent-bench export`.
+ *
+ * Each format is one entry in EXPORTERS; adding a format means adding an
+ * entry here rather than a new command or flag.
+ */
+
+import type { BenchmarkResult } from '../evaluator/results.js';
+import { getOutcome } from '../evaluator/results.js';
+import { formatCSV } from './csv.js';
+
+/**
+ * Renders a set of results into a single document.
+ */
+export interface ResultExporter {
+  description: string;
+  extension: string;
+  render(results: BenchmarkResult[]): string;
+}
+
+/**
+ * Escape text for XML attributes and content.
+ */
+function escapeXml(value: string): string {
+  return value
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;')
+    .replace(/'/g, '&apos;')
+    // XML 1.0 disallows most control characters
+    .replace(/[\x00-\x08\x0B\x0C\x0E-\x1F]/g, '');
+}
+
+/**
+ * Render results as JUnit XML, one test suite per agent.
+ */
+function renderJUnit(results: BenchmarkResult[]): string {
+  const byAgent = new Map<string, BenchmarkResult[]>();
+  for (const result of results) {
+    if (!byAgent.has(result.agent)) byAgent.set(result.agent, []);
+    byAgent.get(result.agent)!.push(result);
+  }
+
+  const count = (group: BenchmarkResult[], outcome: string) => group.filter(r => getOutcome(r) === outcome).length;
+  const time = (group: BenchmarkResult[]) => group.reduce((sum, r) => sum + r.duration_secs, 0).toFixed(2);
+
+  const lines = [
+    '<?xml version="1.0" encoding="UTF-8"?>',
+    `<testsuites name="agent-bench" tests="${results.length}" failures="${count(results, 'failed')}" ` +
+      `skipped="${count(results, 'skipped')}" time="${time(results)}">`,
+  ];
+  for (const [agent, group] of byAgent) {
+    lines.push(
+      `  <testsuite name="${escapeXml(agent)}" tests="${group.length}" failures="${count(group, 'failed')}" ` +
+        `skipped="${count(group, 'skipped')}" time="${time(group)}">`
+    );
+    for (const result of group) {
+      const outcome = getOutcome(result);
+      const attributes = `name="${escapeXml(result.task_id)}" classname="${escapeXml(agent)}" time="${result.duration_secs.toFixed(2)}"`;
+      if (outcome === 'passed') {
+        lines.push(`    <testcase ${attributes}/>`);
+        continue;
+      }
+      lines.push(`    <testcase ${attributes}>`);
+      if (outcome === 'skipped') {
+        lines.push(`      <skipped message="${escapeXml(result.skip_reason || '')}"/>`);
+      } else {
+        lines.push(
+          `      <failure message="${escapeXml(result.error || 'failed')}" type="${result.failure_kind || 'verification'}">` +
+            `${escapeXml(result.verification_output || '')}</failure>`
+        );
+      }
+      lines.push('    </testcase>');
+    }
+    lines.push('  </testsuite>');
+  }
+  lines.push('</testsuites>');
+  return lines.join('\n') + '\n';
+}
+
+/**
+ * Render results as a Markdown table with a pass-rate summary.
+ */
+function renderMarkdown(results: BenchmarkResult[]): string {
+  const cell = (value: string) => value.replace(/\|/g, '\\|').replace(/\n/g, ' ');
+  const executed = results.filter(r => getOutcome(r) !== 'skipped');
+  const passed = executed.filter(r => getOutcome(r) === 'passed').length;
+  const rate = executed.length > 0 ? ((passed / executed.length) * 100).toFixed(1) : '0.0';
+
+  const lines = [
+    '| Task | Agent | Model | Outcome | Score | Duration | Tokens |',
+    '| --- | --- | --- | --- | ---: | ---: | ---: |',
+    ...results.map(r =>
+      `| ${cell(r.task_id)} | ${cell(r.agent)} | ${cell(r.model_name || '')} | ${getOutcome(r)} | ${r.score} | ` +
+        `${r.duration_secs.toFixed(1)}s | ${r.tokens_used ?? ''} |`
+    ),
+    '',
+    `**Passed:** ${passed}/${executed.length} (${rate}%)` +
+      (executed.length < results.length ? `, ${results.length - executed.length} skipped` : ''),
+  ];
+  return lines.join('\n') + '\n';
+}
+
+/**
+ * Render results as JSON Lines, one full result per line.
+ */
+function renderJsonl(results: BenchmarkResult[]): string {
+  return results.map(result => JSON.stringify(result)).join('\n') + (results.length > 0 ? '\n' : '');
+}
+
+/**
+ * Available export formats.
+ */
+export const EXPORTERS: Record<string, ResultExporter> = {
+  csv: { description: 'CSV summary (same columns as collect)', extension: 'csv', render: formatCSV },
+  junit: { description: 'JUnit XML for CI test reports', extension: 'xml', render: renderJUnit },
+  markdown: { description: 'Markdown table with pass rate', extension: 'md', render: renderMarkdown },
+  jsonl: { description: 'One JSON result per line', extension: 'jsonl', render: renderJsonl },
+};
+
+/**
+ * Look up an exporter by format name.
+ * @throws Error If the format is unknown
+ */
+export function getExporter(format: string): ResultExporter {
+  const exporter = EXPORTERS[format];
+  if (!exporter) {
+    throw new Error(`Unknown export format "${format}" (expected ${Object.keys(EXPORTERS).join(', ')})`);
+  }
+  return exporter;
+}
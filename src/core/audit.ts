@@ -0,0 +1,111 @@
+/**
+ * Per-run audit log: every subprocess the harness launched during a run (verification
+ * commands, git, servers under test, agent servers, toolchain probes, notifiers), one JSON
+ * record per line in `<results-dir>/<run-id>/audit.jsonl`.
+ */
+
+import { createWriteStream } from 'fs';
+import { mkdir, readFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import { addCommandListener, type CommandRecord } from '../utils/exec.js';
+import { logger } from '../utils/logger.js';
+
+/**
+ * One audited command.
+ */
+export interface AuditEntry {
+  /** When the command finished */
+  timestamp: string;
+  run_id: string | null;
+  task_id: string | null;
+  program: string;
+  args: string[];
+  cwd: string;
+  /** Variables added or changed relative to the harness environment (secret-looking values redacted) */
+  env_set: Record<string, string>;
+  /** Harness variables the command did not get */
+  env_removed: string[];
+  exit_code: number | null;
+  signal: string | null;
+  duration_secs: number;
+  timed_out: boolean;
+  error: string | null;
+}
+
+/**
+ * Variable names whose values are not written to the audit log.
+ */
+const SECRET_NAME = /KEY|TOKEN|SECRET|PASS|CREDENTIAL|AUTH/i;
+
+export const REDACTED = '<redacted>';
+
+/**
+ * Audit log path for a run.
+ */
+export function getAuditLogPath(resultsDir: string, runId: string): string {
+  return join(resultsDir, runId, 'audit.jsonl');
+}
+
+/**
+ * Build the audit entry for a command, tagged with the current run and task.
+ */
+export function toAuditEntry(record: CommandRecord): AuditEntry {
+  const context = logger.getContext();
+  const envSet: Record<string, string> = {};
+  for (const [name, value] of Object.entries(record.envSet)) {
+    envSet[name] = SECRET_NAME.test(name) ? REDACTED : value;
+  }
+  return {
+    timestamp: new Date().toISOString(),
+    run_id: context.run_id ?? null,
+    task_id: context.task_id ?? null,
+    program: record.program,
+    args: record.args,
+    cwd: record.cwd,
+    env_set: envSet,
+    env_removed: [...record.envRemoved].sort(),
+    exit_code: record.exitCode,
+    signal: record.signal,
+    duration_secs: record.durationSecs,
+    timed_out: record.timedOut,
+    error: record.error,
+  };
+}
+
+/**
+ * Append every command a run starts from now on to its audit log (commands are matched to
+ * runs by the log context, so concurrent runs in one process keep separate logs).
+ * @returns A function that detaches and closes the log
+ */
+export async function openAuditLog(resultsDir: string, runId: string): Promise<() => Promise<void>> {
+  const path = getAuditLogPath(resultsDir, runId);
+  await mkdir(dirname(path), { recursive: true });
+  const stream = createWriteStream(path, { flags: 'a' });
+  const detach = addCommandListener(record => {
+    const entry = toAuditEntry(record);
+    if (entry.run_id === runId) {
+      stream.write(`${JSON.stringify(entry)}\n`);
+    }
+  });
+
+  return async () => {
+    detach();
+    await new Promise<void>(resolve => stream.end(resolve));
+  };
+}
+
+/**
+ * Read a run's audit log (empty if it has none).
+ */
+export async function readAuditLog(resultsDir: string, runId: string): Promise<AuditEntry[]> {
+  let content: string;
+  try {
+    content = await readFile(getAuditLogPath(resultsDir, runId), 'utf-8');
+  } catch {
+    return [];
+  }
+  return content
+    .split('\n')
+    .filter(line => line.trim().length > 0)
+    .map(line => JSON.parse(line) as AuditEntry);
+}
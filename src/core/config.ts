@@ -2,17 +2,101 @@
  * Configuration for Agent Bench.
  */
 
-import { join } from 'path';
+import { delimiter, dirname, join, resolve } from 'path';
 import { homedir, tmpdir } from 'os';
+import { isDeepStrictEqual } from 'util';
+import { ConfigError } from '../utils/errors.js';
+import { parseSize } from '../utils/disk.js';
+import { DEFAULT_MAX_ITERATIONS } from '../agents/types.js';
+import type { CredentialConfig } from '../agents/credentials.js';
+import { DEFAULT_CUSTOM_CAPABILITIES, validateCommandTemplate, type CustomAgentConfig } from '../agents/custom.js';
+import { AGENT_TYPES } from '../agents/factory.js';
+import type { AgentProfile } from '../agents/profiles.js';
+import { AgentCapabilitySchema } from './task.js';
+import type { PricingTable } from '../evaluator/pricing.js';
+import { DEFAULT_PLUGIN_TIMEOUT_SECS } from '../plugins/wasm.js';
+import type { PermissionAcknowledgment } from './manifest.js';
 
 /**
- * Runner configuration.
+ * Configuration file name, looked up in the project directory and ~/.config/agent-bench.
+ */
+export const CONFIG_FILE_NAME = 'agent-bench.toml';
+
+/**
+ * Default model used when neither the config nor the CLI selects one.
+ */
+const DEFAULT_MODEL_STRING = 'anthropic/claude-sonnet-4-5';
+
+/**
+ * Supported result storage backends (see src/storage).
+ */
+export const RESULTS_BACKENDS = ['json', 'sqlite', 'postgres'] as const;
+export type ResultsBackend = (typeof RESULTS_BACKENDS)[number];
+
+/**
+ * How agent executions use recordings (see agents/recording.ts): `off`, `record` (run the agent
+ * and save its recording), `replay` (replay only; a missing recording fails the task), or
+ * `auto` (replay if recorded, else record).
  */
+export const RECORD_MODES = ['off', 'record', 'replay', 'auto'] as const;
+export type RecordMode = (typeof RECORD_MODES)[number];
+
+/**
+ * Git backends (see core/git.ts): the simple-git package, or the git CLI run directly.
+ */
+export const GIT_BACKENDS = ['simple-git', 'shell'] as const;
+export type GitBackendName = (typeof GIT_BACKENDS)[number];
+
+/**
+ * Sandboxes for agents and verification commands (see src/sandbox), all Linux only: `off`,
+ * `landlock` (Landlock filesystem rules plus a seccomp filter), `bubblewrap`, or `firejail`.
+ */
+export const SANDBOXES = ['off', 'landlock', 'bubblewrap', 'firejail'] as const;
+export type SandboxName = (typeof SANDBOXES)[number];
+
+/**
+ * Runner configuration.
 export interface RunnerConfig {
   tasksDir: string;
   resultsDir: string;
   workspaceDir: string;
   maxIterations: number;
+  locked: boolean;
+  failFast: boolean;
+  notify: boolean;
+  keepWorkspace: boolean;
+  /** Maximum workspace size in bytes while and after the agent runs (null: unlimited) */
+  workspaceQuota: number | null;
+  /** Fetch a suite's repositories into the cache before its timed portion starts */
+  prefetch: boolean;
+  /** Seconds a suite may run; tasks that would not finish in time are skipped (null: unlimited) */
+  timeBudget: number | null;
+  wasmPlugins: boolean;
+  /** Seconds a WebAssembly plugin call may run before it is stopped */
+  pluginTimeout: number;
+  permissionAck: PermissionAcknowledgment | null;
+  agentTimeout: number | null;
+  verifyTimeout: number | null;
+  resultsBackend: ResultsBackend;
+  resultsUrl: string | null;
+  recordMode: RecordMode;
+  recordingsDir: string;
+  gitBackend: GitBackendName;
+  sandbox: SandboxName;
+  /** Paths sandboxed processes may write besides the workspace */
+  sandboxWritable: string[];
+  /** Environment variables (names or `*` patterns) passed to agents besides the base set */
+  agentEnv: string[];
+  /** API credentials task executions are balanced across (empty: agents use the environment's) */
+  agentCredentials: CredentialConfig[];
+  /** Command template of the `custom` agent (null: not configured) */
+  customAgent: CustomAgentConfig | null;
+  /** Named agents, selectable with `--agent <name>` */
+  agentProfiles: Record<string, AgentProfile>;
+  /** Model prices in USD per million tokens, for cost accounting */
+  pricing: PricingTable;
+  defaultModel: string;
+  defaultAgent: string | null;
 }
 
 /**
@@ -23,7 +107,33 @@ export function createDefaultConfig(): RunnerConfig {
     tasksDir: join(process.cwd(), 'tasks'),
     resultsDir: join(process.cwd(), 'results'),
     workspaceDir: join(tmpdir(), 'agent-bench'),
-    maxIterations: 1,
+    maxIterations: DEFAULT_MAX_ITERATIONS,
+    locked: false,
+    failFast: false,
+    notify: false,
+    keepWorkspace: false,
+    workspaceQuota: null,
+    prefetch: true,
+    timeBudget: null,
+    wasmPlugins: false,
+    pluginTimeout: DEFAULT_PLUGIN_TIMEOUT_SECS,
+    permissionAck: null,
+    agentTimeout: null,
+    verifyTimeout: null,
+    resultsBackend: 'json',
+    resultsUrl: null,
+    recordMode: 'off',
+    recordingsDir: join(getCacheDir(), 'recordings'),
+    gitBackend: 'simple-git',
+    sandbox: 'off',
+    sandboxWritable: [],
+    agentEnv: [],
+    agentCredentials: [],
+    customAgent: null,
+    agentProfiles: {},
+    pricing: {},
+    defaultModel: DEFAULT_MODEL_STRING,
+    defaultAgent: null,
   };
 }
 
@@ -45,6 +155,14 @@ export function getConfigPath(): string {
   return join(homedir(), '.config', 'agent-bench', 'config.json');
 }
 
+/**
+ * Get the cache directory for task indexes, clones, and other generated state.
+ */
+export function getCacheDir(): string {
+  const base = process.env.XDG_CACHE_HOME || join(homedir(), '.cache');
+  return join(base, 'agent-bench');
+}
+
 /**
  * Load user configuration from file.
  */
@@ -81,5 +199,730 @@ export function mergeConfig(userConfig: UserConfig, defaultConfig: RunnerConfig)
     resultsDir: userConfig.resultsDir || defaultConfig.resultsDir,
     workspaceDir: userConfig.workspaceDir || defaultConfig.workspaceDir,
     maxIterations: defaultConfig.maxIterations,
+    locked: defaultConfig.locked,
+    failFast: defaultConfig.failFast,
+    notify: defaultConfig.notify,
+    keepWorkspace: defaultConfig.keepWorkspace,
+    workspaceQuota: defaultConfig.workspaceQuota,
+    prefetch: defaultConfig.prefetch,
+    timeBudget: defaultConfig.timeBudget,
+    wasmPlugins: defaultConfig.wasmPlugins,
+    pluginTimeout: defaultConfig.pluginTimeout,
+    permissionAck: defaultConfig.permissionAck,
+    agentTimeout: defaultConfig.agentTimeout,
+    verifyTimeout: defaultConfig.verifyTimeout,
+    resultsBackend: defaultConfig.resultsBackend,
+    resultsUrl: defaultConfig.resultsUrl,
+    recordMode: defaultConfig.recordMode,
+    recordingsDir: defaultConfig.recordingsDir,
+    gitBackend: defaultConfig.gitBackend,
+    sandbox: defaultConfig.sandbox,
+    sandboxWritable: defaultConfig.sandboxWritable,
+    agentEnv: defaultConfig.agentEnv,
+    agentCredentials: defaultConfig.agentCredentials,
+    customAgent: defaultConfig.customAgent,
+    agentProfiles: defaultConfig.agentProfiles,
+    pricing: defaultConfig.pricing,
+    defaultModel: userConfig.defaultModel || defaultConfig.defaultModel,
+    defaultAgent: defaultConfig.defaultAgent,
   };
 }
+
+/**
+ * Contents of an `agent-bench.toml` file.
+ *
+ * ```toml
+ * tasks_dir = "tasks"
+ * results_dir = "results"
+ * workspace_dir = "/tmp/agent-bench"  # default: <OS temp dir>/agent-bench
+ * notify = true        # Desktop notification when a suite finishes or a task fails
+ * keep_workspace = true  # Leave task workspaces in place after runs (for inspection or replay)
+ * workspace_quota = "10GB"  # Fail tasks whose workspace grows past this (KB/MB/GB/TB or bytes)
+ * prefetch = false     # Skip fetching a suite's repositories before its tasks run (default: true)
+ * time_budget = "2h"   # Skip suite tasks that would not finish within this (s/m/h, e.g. 1h30m)
+ *
+ * [agent]
+ * model = "anthropic/claude-sonnet-4-5"
+ * max_iterations = 20
+ * env = ["ANTHROPIC_API_KEY", "AWS_*"]  # Variables agents may see besides PATH, HOME, locale, ("*": all)
+ *
+ * [[agent.credentials]]  # Balance task executions across several keys (round-robin, rate-limit aware)
+ * name = "team-a"
+ * env = { ANTHROPIC_API_KEY = "$TEAM_A_KEY" }  # "$NAME": read from the harness environment
+ *
+ * [[agent.credentials]]
+ * name = "team-b"
+ * env = { ANTHROPIC_API_KEY = "$TEAM_B_KEY", ANTHROPIC_BASE_URL = "https://proxy.example.com" }
+ *
+ * [agent.custom]       # --agent custom: any agent with a command line
+ * command = "mytool --prompt {prompt} --cwd {workspace} {write?--allow-write:--read-only}"
+ * env = ["MYTOOL_*"]   # Variables the tool reads
+ * capabilities = ["read", "write", "bash"]  # Default
+ *
+ * [agents.claude-opus]  # Named agent: --agent claude-opus (or claude-opus@<provider/model>)
+ * adapter = "aider"     # Any --agent type, or plugin:<path>
+ * model = "anthropic/claude-opus-4-1"
+ * flags = ["--edit-format", "diff"]  # Appended to the adapter's command line (CLI adapters)
+ * env = { ANTHROPIC_API_KEY = "$OPUS_KEY" }  # "$NAME": read from the harness environment
+ * max_iterations = 40  # Default: [agent] max_iterations
+ *
+ * [results]
+ * backend = "sqlite"   # json (default), sqlite, postgres
+ * url = "results/results.db"
+ *
+ * [plugins]
+ * enabled = true       # Allow tasks to load WebAssembly verifier/scorer plugins
+ * timeout = 30         # Seconds a plugin call may run (default: 10)
+ *
+ * [recordings]
+ * mode = "auto"        # off (default), record, replay, auto
+ * dir = "recordings"
+ *
+ * [git]
+ * backend = "shell"    # simple-git (default), shell
+ *
+ * [sandbox]
+ * backend = "bubblewrap" # off (default), landlock, bubblewrap, firejail: confine agents' and verification's writes to the workspace
+ * writable = ["~/.cargo", "~/.cache/pip"]  # further writable paths (package caches)
+ *
+ * [pricing."anthropic/claude-sonnet-4-5"]  # USD per million tokens; a bare model ID matches any provider
+ * input = 3.0
+ * output = 15.0
+ * cache_read = 0.30    # Default: the input price
+ * cache_write = 3.75   # Default: the input price
+ * ```
+ */
+export interface FileConfig {
+  tasks_dir?: string;
+  results_dir?: string;
+  workspace_dir?: string;
+  notify?: boolean;
+  keep_workspace?: boolean;
+  workspace_quota?: string | number;
+  prefetch?: boolean;
+  time_budget?: string | number;
+  agent?: {
+    model?: string;
+    max_iterations?: number;
+    env?: string[];
+    credentials?: {
+      name: string;
+      env: Record<string, string>;
+    }[];
+    custom?: {
+      command: string;
+      env?: string[];
+      capabilities?: string[];
+    };
+  };
+  agents?: Record<string, {
+    adapter: string;
+    model?: string;
+    flags?: string[];
+    env?: Record<string, string>;
+    max_iterations?: number;
+  }>;
+  results?: {
+    backend?: ResultsBackend;
+    url?: string;
+  };
+  plugins?: {
+    enabled?: boolean;
+    timeout?: number;
+  };
+  recordings?: {
+    mode?: RecordMode;
+    dir?: string;
+  };
+  git?: {
+    backend?: GitBackendName;
+  };
+  sandbox?: {
+    backend?: SandboxName;
+    writable?: string[];
+  };
+  pricing?: Record<string, {
+    input: number;
+    output: number;
+    cache_read?: number;
+    cache_write?: number;
+  }>;
+}
+
+/**
+ * Validate a result backend name.
+ * @throws ConfigError If the backend is unknown
+ */
+export function parseResultsBackend(value: string): ResultsBackend {
+  if (!(RESULTS_BACKENDS as readonly string[]).includes(value)) {
+    throw new ConfigError(`Unknown results backend "${value}" (expected ${RESULTS_BACKENDS.join(', ')})`);
+  }
+  return value as ResultsBackend;
+}
+
+/**
+ * Validate a record mode name.
+ * @throws ConfigError If the mode is unknown
+ */
+export function parseRecordMode(value: string): RecordMode {
+  if (!(RECORD_MODES as readonly string[]).includes(value)) {
+    throw new ConfigError(`Unknown record mode "${value}" (expected ${RECORD_MODES.join(', ')})`);
+  }
+  return value as RecordMode;
+}
+
+/**
+ * Validate a git backend name.
+ * @throws ConfigError If the backend is unknown
+ */
+export function parseGitBackend(value: string): GitBackendName {
+  if (!(GIT_BACKENDS as readonly string[]).includes(value)) {
+    throw new ConfigError(`Unknown git backend "${value}" (expected ${GIT_BACKENDS.join(', ')})`);
+  }
+  return value as GitBackendName;
+}
+
+/**
+ * Validate a sandbox name.
+ * @throws ConfigError If the sandbox is unknown
+ */
+export function parseSandbox(value: string): SandboxName {
+  if (!(SANDBOXES as readonly string[]).includes(value)) {
+    throw new ConfigError(`Unknown sandbox "${value}" (expected ${SANDBOXES.join(', ')})`);
+  }
+  return value as SandboxName;
+}
+
+/**
+ * Validate a disk size (`10GB`, `500MB`, or bytes).
+ * @throws ConfigError If the size is malformed
+ */
+export function parseQuota(value: string | number): number {
+  const bytes = parseSize(value);
+  if (bytes === null) {
+    throw new ConfigError(`Invalid size "${value}" (expected e.g. 500MB, 10GB, or a byte count)`);
+  }
+  return bytes;
+}
+
+/**
+ * Seconds per time budget unit.
+ */
+const DURATION_UNITS: Record<string, number> = { s: 1, m: 60, h: 60 * 60 };
+
+/**
+ * Validate a time budget (`2h`, `90m`, `1h30m`, or seconds).
+ * @returns Seconds
+ * @throws ConfigError If the duration is malformed
+ */
+export function parseTimeBudget(value: string | number): number {
+  const text = String(value).trim();
+  let secs: number | null = null;
+  if (/^\d+$/.test(text)) {
+    secs = Number(text);
+  } else if (/^(\d+[smh])+$/i.test(text)) {
+    secs = [...text.matchAll(/(\d+)([smh])/gi)].reduce((sum, [, amount, unit]) => sum + Number(amount) * DURATION_UNITS[unit.toLowerCase()], 0);
+  }
+  if (!secs) {
+    throw new ConfigError(`Invalid duration "${value}" (expected e.g. 2h, 90m, 1h30m, or seconds)`);
+  }
+  return secs;
+}
+
+/**
+ * Candidate config files, lowest precedence first.
+ */
+export function getConfigFilePaths(projectDir: string = process.cwd()): string[] {
+  return [
+    join(homedir(), '.config', 'agent-bench', CONFIG_FILE_NAME),
+    join(projectDir, CONFIG_FILE_NAME),
+  ];
+}
+
+/**
+ * Load an `agent-bench.toml` file. Relative directories are resolved against the file's directory.
+ * @returns The parsed config, or null if the file does not exist
+ * @throws ConfigError If the file cannot be parsed
+ */
+export async function loadFileConfig(path: string): Promise<FileConfig | null> {
+  const { readFile } = await import('fs/promises');
+
+  let content: string;
+  try {
+    content = await readFile(path, 'utf-8');
+  } catch {
+    return null;
+  }
+
+  let config: FileConfig;
+  try {
+    config = Bun.TOML.parse(content) as FileConfig;
+  } catch (error) {
+    throw new ConfigError(`Invalid config file ${path}: ${error}`);
+  }
+
+  const base = dirname(path);
+  for (const key of ['tasks_dir', 'results_dir', 'workspace_dir'] as const) {
+    const value = config[key];
+    if (value !== undefined && typeof value !== 'string') {
+      throw new ConfigError(`Invalid config file ${path}: ${key} must be a string`);
+    }
+    // git+<url> task specs are not paths
+    if (value && !value.startsWith('git+')) {
+      config[key] = resolve(base, value.replace(/^~(?=[\\/]|$)/, homedir()));
+    }
+  }
+
+  if (config.notify !== undefined && typeof config.notify !== 'boolean') {
+    throw new ConfigError(`Invalid config file ${path}: notify must be true or false`);
+  }
+  if (config.keep_workspace !== undefined && typeof config.keep_workspace !== 'boolean') {
+    throw new ConfigError(`Invalid config file ${path}: keep_workspace must be true or false`);
+  }
+  if (config.prefetch !== undefined && typeof config.prefetch !== 'boolean') {
+    throw new ConfigError(`Invalid config file ${path}: prefetch must be true or false`);
+  }
+  if (config.time_budget !== undefined) {
+    try {
+      parseTimeBudget(config.time_budget);
+    } catch (error) {
+      throw new ConfigError(`Invalid config file ${path}: time_budget: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  if (config.workspace_quota !== undefined) {
+    try {
+      parseQuota(config.workspace_quota);
+    } catch (error) {
+      throw new ConfigError(`Invalid config file ${path}: workspace_quota: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  if (config.plugins?.enabled !== undefined && typeof config.plugins.enabled !== 'boolean') {
+    throw new ConfigError(`Invalid config file ${path}: plugins.enabled must be true or false`);
+  }
+  if (config.plugins?.timeout !== undefined && !(typeof config.plugins.timeout === 'number' && config.plugins.timeout > 0)) {
+    throw new ConfigError(`Invalid config file ${path}: plugins.timeout must be a positive number of seconds`);
+  }
+
+  if (config.results?.backend !== undefined) {
+    try {
+      parseResultsBackend(String(config.results.backend));
+    } catch (error) {
+      throw new ConfigError(`Invalid config file ${path}: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  if (config.recordings?.mode !== undefined) {
+    try {
+      parseRecordMode(String(config.recordings.mode));
+    } catch (error) {
+      throw new ConfigError(`Invalid config file ${path}: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  if (config.git?.backend !== undefined) {
+    try {
+      parseGitBackend(String(config.git.backend));
+    } catch (error) {
+      throw new ConfigError(`Invalid config file ${path}: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  if (config.sandbox?.backend !== undefined) {
+    try {
+      parseSandbox(String(config.sandbox.backend));
+    } catch (error) {
+      throw new ConfigError(`Invalid config file ${path}: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  if (config.agent?.env !== undefined) {
+    if (!Array.isArray(config.agent.env) || !config.agent.env.every(entry => typeof entry === 'string')) {
+      throw new ConfigError(`Invalid config file ${path}: agent.env must be a list of variable names`);
+    }
+  }
+  if (config.agent?.credentials !== undefined) {
+    validateCredentials(config.agent.credentials, path);
+  }
+  if (config.agent?.custom !== undefined) {
+    validateCustomAgent(config.agent.custom, path);
+  }
+  if (config.agents !== undefined) {
+    validateAgentProfiles(config.agents, path);
+    // A plugin path is relative to the config file
+    for (const profile of Object.values(config.agents)) {
+      if (profile.adapter.startsWith('plugin:')) {
+        profile.adapter = `plugin:${resolve(base, profile.adapter.substring(7).replace(/^~(?=[\\/]|$)/, homedir()))}`;
+      }
+    }
+  }
+  if (config.pricing !== undefined) {
+    validatePricing(config.pricing, path);
+  }
+  if (config.sandbox?.writable !== undefined) {
+    if (!Array.isArray(config.sandbox.writable) || !config.sandbox.writable.every(entry => typeof entry === 'string')) {
+      throw new ConfigError(`Invalid config file ${path}: sandbox.writable must be a list of paths`);
+    }
+    config.sandbox.writable = config.sandbox.writable.map(entry => resolve(base, entry.replace(/^~(?=[\\/]|$)/, homedir())));
+  }
+  if (config.recordings?.dir) {
+    config.recordings.dir = resolve(base, config.recordings.dir.replace(/^~(?=[\\/]|$)/, homedir()));
+  }
+
+  // A relative SQLite path is a file next to the config; connection URLs are left alone
+  const url = config.results?.url;
+  if (url && config.results?.backend === 'sqlite' && !url.includes('://')) {
+    config.results!.url = resolve(base, url.replace(/^~(?=[\\/]|$)/, homedir()));
+  }
+
+  return config;
+}
+
+/**
+ * Validate `[[agent.credentials]]` entries.
+ * @throws ConfigError If an entry is malformed or a name is repeated
+ */
+function validateCredentials(credentials: unknown, path: string): void {
+  if (!Array.isArray(credentials)) {
+    throw new ConfigError(`Invalid config file ${path}: agent.credentials must be a list of tables ([[agent.credentials]])`);
+  }
+  const names = new Set<string>();
+  for (const credential of credentials) {
+    if (typeof credential?.name !== 'string' || !credential.name) {
+      throw new ConfigError(`Invalid config file ${path}: every agent.credentials entry needs a name`);
+    }
+    if (names.has(credential.name)) {
+      throw new ConfigError(`Invalid config file ${path}: credential ${credential.name} is defined twice`);
+    }
+    names.add(credential.name);
+    const env = credential.env;
+    if (typeof env !== 'object' || env === null || Array.isArray(env) || !Object.values(env).every(value => typeof value === 'string')) {
+      throw new ConfigError(`Invalid config file ${path}: credential ${credential.name}: env must be a table of strings`);
+    }
+  }
+}
+
+/**
+ * Validate `[agent.custom]`.
+ * @throws ConfigError If the command template is missing or invalid, or a list is malformed
+ */
+function validateCustomAgent(custom: unknown, path: string): void {
+  if (typeof custom !== 'object' || custom === null || Array.isArray(custom)) {
+    throw new ConfigError(`Invalid config file ${path}: agent.custom must be a table`);
+  }
+  const { command, env, capabilities } = custom as Record<string, unknown>;
+  if (typeof command !== 'string' || !command.trim()) {
+    throw new ConfigError(`Invalid config file ${path}: agent.custom needs a command`);
+  }
+  try {
+    validateCommandTemplate(command);
+  } catch (error) {
+    throw new ConfigError(`Invalid config file ${path}: agent.custom.command: ${error instanceof Error ? error.message : error}`);
+  }
+  if (env !== undefined && (!Array.isArray(env) || !env.every(entry => typeof entry === 'string'))) {
+    throw new ConfigError(`Invalid config file ${path}: agent.custom.env must be a list of variable names`);
+  }
+  if (capabilities !== undefined) {
+    const invalid = Array.isArray(capabilities)
+      ? capabilities.filter(entry => !AgentCapabilitySchema.safeParse(entry).success)
+      : [capabilities];
+    if (invalid.length > 0) {
+      throw new ConfigError(
+        `Invalid config file ${path}: agent.custom.capabilities: unknown ${invalid.join(', ')} (available: ${AgentCapabilitySchema.options.join(', ')})`
+      );
+    }
+  }
+}
+
+/**
+ * Validate `[agents.<name>]` tables.
+ * @throws ConfigError If a name is taken by a built-in type or unusable in `--agent`, or a table is malformed
+ */
+function validateAgentProfiles(agents: unknown, path: string): void {
+  if (typeof agents !== 'object' || agents === null || Array.isArray(agents)) {
+    throw new ConfigError(`Invalid config file ${path}: agents must be a table of named agents ([agents.<name>])`);
+  }
+  for (const [name, profile] of Object.entries(agents)) {
+    if (!/^[A-Za-z0-9][\w.-]*$/.test(name) || (AGENT_TYPES as readonly string[]).includes(name) || name === 'plugin') {
+      throw new ConfigError(
+        `Invalid config file ${path}: agents.${name}: names are letters, digits, ".", "-", and "_", other than the built-in types`
+      );
+    }
+    if (typeof profile !== 'object' || profile === null || Array.isArray(profile)) {
+      throw new ConfigError(`Invalid config file ${path}: agents.${name} must be a table`);
+    }
+    const { adapter, model, flags, env, max_iterations } = profile as Record<string, unknown>;
+    if (typeof adapter !== 'string' || !((AGENT_TYPES as readonly string[]).includes(adapter) || /^plugin:[^@]+$/.test(adapter))) {
+      throw new ConfigError(
+        `Invalid config file ${path}: agents.${name}.adapter must be an agent type (${AGENT_TYPES.join(', ')}, plugin:<path>)`
+      );
+    }
+    if (model !== undefined && (typeof model !== 'string' || !model.includes('/'))) {
+      throw new ConfigError(`Invalid config file ${path}: agents.${name}.model must be a provider/model string`);
+    }
+    if (flags !== undefined && (!Array.isArray(flags) || !flags.every(entry => typeof entry === 'string'))) {
+      throw new ConfigError(`Invalid config file ${path}: agents.${name}.flags must be a list of arguments`);
+    }
+    if (env !== undefined && (typeof env !== 'object' || env === null || Array.isArray(env) || !Object.values(env).every(value => typeof value === 'string'))) {
+      throw new ConfigError(`Invalid config file ${path}: agents.${name}.env must be a table of strings`);
+    }
+    if (max_iterations !== undefined && (!Number.isInteger(max_iterations) || (max_iterations as number) < 1)) {
+      throw new ConfigError(`Invalid config file ${path}: agents.${name}.max_iterations must be a positive integer`);
+    }
+  }
+}
+
+/**
+ * Validate `[pricing."<model>"]` tables.
+ * @throws ConfigError If a price is missing or not a non-negative number
+ */
+function validatePricing(pricing: unknown, path: string): void {
+  if (typeof pricing !== 'object' || pricing === null || Array.isArray(pricing)) {
+    throw new ConfigError(`Invalid config file ${path}: pricing must be a table of models ([pricing."provider/model"])`);
+  }
+  for (const [model, price] of Object.entries(pricing)) {
+    if (typeof price !== 'object' || price === null) {
+      throw new ConfigError(`Invalid config file ${path}: pricing.${model} must be a table`);
+    }
+    for (const key of ['input', 'output', 'cache_read', 'cache_write']) {
+      const value = (price as Record<string, unknown>)[key];
+      const required = key === 'input' || key === 'output';
+      if ((value !== undefined || required) && (typeof value !== 'number' || !(value >= 0))) {
+        throw new ConfigError(`Invalid config file ${path}: pricing.${model}.${key} must be a price in USD per million tokens`);
+      }
+    }
+  }
+}
+
+/**
+ * Apply a config file on top of a runner config.
+ */
+export function applyFileConfig(config: RunnerConfig, fileConfig: FileConfig): RunnerConfig {
+  return {
+    ...config,
+    tasksDir: fileConfig.tasks_dir || config.tasksDir,
+    resultsDir: fileConfig.results_dir || config.resultsDir,
+    workspaceDir: fileConfig.workspace_dir || config.workspaceDir,
+    notify: fileConfig.notify ?? config.notify,
+    keepWorkspace: fileConfig.keep_workspace ?? config.keepWorkspace,
+    workspaceQuota: fileConfig.workspace_quota !== undefined ? parseQuota(fileConfig.workspace_quota) : config.workspaceQuota,
+    prefetch: fileConfig.prefetch ?? config.prefetch,
+    timeBudget: fileConfig.time_budget !== undefined ? parseTimeBudget(fileConfig.time_budget) : config.timeBudget,
+    wasmPlugins: fileConfig.plugins?.enabled ?? config.wasmPlugins,
+    pluginTimeout: fileConfig.plugins?.timeout ?? config.pluginTimeout,
+    defaultModel: fileConfig.agent?.model || config.defaultModel,
+    maxIterations: fileConfig.agent?.max_iterations || config.maxIterations,
+    resultsBackend: fileConfig.results?.backend || config.resultsBackend,
+    resultsUrl: fileConfig.results?.url || config.resultsUrl,
+    recordMode: fileConfig.recordings?.mode || config.recordMode,
+    recordingsDir: fileConfig.recordings?.dir || config.recordingsDir,
+    gitBackend: fileConfig.git?.backend || config.gitBackend,
+    sandbox: fileConfig.sandbox?.backend || config.sandbox,
+    sandboxWritable: fileConfig.sandbox?.writable ?? config.sandboxWritable,
+    agentEnv: fileConfig.agent?.env ?? config.agentEnv,
+    agentCredentials: fileConfig.agent?.credentials?.map(credential => ({
+      name: credential.name,
+      env: credential.env,
+    })) ?? config.agentCredentials,
+    customAgent: fileConfig.agent?.custom ? {
+      command: fileConfig.agent.custom.command,
+      env: fileConfig.agent.custom.env ?? [],
+      capabilities: (fileConfig.agent.custom.capabilities ?? [...DEFAULT_CUSTOM_CAPABILITIES]) as CustomAgentConfig['capabilities'],
+    } : config.customAgent,
+    // Per name, so a project file can override or add to the user file's agents
+    agentProfiles: {
+      ...config.agentProfiles,
+      ...Object.fromEntries(Object.entries(fileConfig.agents ?? {}).map(([name, profile]) => [name, {
+        adapter: profile.adapter,
+        model: profile.model ?? null,
+        flags: profile.flags ?? [],
+        env: profile.env ?? {},
+        maxIterations: profile.max_iterations ?? null,
+      }])),
+    },
+    // Per model, so a project file can override or add to the user file's prices
+    pricing: {
+      ...config.pricing,
+      ...Object.fromEntries(Object.entries(fileConfig.pricing ?? {}).map(([model, price]) => [model, {
+        input: price.input,
+        output: price.output,
+        cacheRead: price.cache_read ?? null,
+        cacheWrite: price.cache_write ?? null,
+      }])),
+    },
+  };
+}
+
+/**
+ * Environment variables read by applyEnvConfig.
+ */
+export const ENV_VARS = {
+  tasksDir: 'AGENT_BENCH_TASKS_DIR',
+  resultsDir: 'AGENT_BENCH_RESULTS_DIR',
+  workspaceDir: 'AGENT_BENCH_WORKSPACE_DIR',
+  agent: 'AGENT_BENCH_AGENT',
+  model: 'AGENT_BENCH_MODEL',
+  maxIterations: 'AGENT_BENCH_MAX_ITERATIONS',
+  resultsBackend: 'AGENT_BENCH_RESULTS_BACKEND',
+  resultsUrl: 'AGENT_BENCH_RESULTS_URL',
+  notify: 'AGENT_BENCH_NOTIFY',
+  keepWorkspace: 'AGENT_BENCH_KEEP_WORKSPACE',
+  workspaceQuota: 'AGENT_BENCH_WORKSPACE_QUOTA',
+  prefetch: 'AGENT_BENCH_PREFETCH',
+  timeBudget: 'AGENT_BENCH_TIME_BUDGET',
+  wasmPlugins: 'AGENT_BENCH_WASM_PLUGINS',
+  recordMode: 'AGENT_BENCH_RECORD_MODE',
+  recordingsDir: 'AGENT_BENCH_RECORDINGS_DIR',
+  gitBackend: 'AGENT_BENCH_GIT_BACKEND',
+  sandbox: 'AGENT_BENCH_SANDBOX',
+  sandboxWritable: 'AGENT_BENCH_SANDBOX_WRITABLE',
+  agentEnv: 'AGENT_BENCH_AGENT_ENV',
+} as const;
+
+/**
+ * Apply `AGENT_BENCH_*` environment variables on top of a runner config. Empty values are ignored.
+ * @throws ConfigError If a value is invalid
+ */
+export function applyEnvConfig(config: RunnerConfig, env: Record<string, string | undefined> = process.env): RunnerConfig {
+  const read = (name: string): string | undefined => env[name]?.trim() || undefined;
+
+  let maxIterations = config.maxIterations;
+  const maxIterationsValue = read(ENV_VARS.maxIterations);
+  if (maxIterationsValue) {
+    maxIterations = Number(maxIterationsValue);
+    if (!Number.isInteger(maxIterations) || maxIterations < 1) {
+      throw new ConfigError(`Invalid ${ENV_VARS.maxIterations}: ${maxIterationsValue}`);
+    }
+  }
+
+  const backend = read(ENV_VARS.resultsBackend);
+  const recordMode = read(ENV_VARS.recordMode);
+  const gitBackend = read(ENV_VARS.gitBackend);
+  const sandbox = read(ENV_VARS.sandbox);
+  const sandboxWritable = read(ENV_VARS.sandboxWritable);
+  const agentEnv = read(ENV_VARS.agentEnv);
+  const workspaceQuota = read(ENV_VARS.workspaceQuota);
+  const timeBudget = read(ENV_VARS.timeBudget);
+
+  const readBoolean = (name: string, fallback: boolean): boolean => {
+    const value = read(name)?.toLowerCase();
+    if (!value) {
+      return fallback;
+    }
+    if (!['1', 'true', 'yes', '0', 'false', 'no'].includes(value)) {
+      throw new ConfigError(`Invalid ${name}: ${value} (expected 1/0, true/false, yes/no)`);
+    }
+    return ['1', 'true', 'yes'].includes(value);
+  };
+
+  return {
+    ...config,
+    tasksDir: read(ENV_VARS.tasksDir) || config.tasksDir,
+    resultsDir: read(ENV_VARS.resultsDir) || config.resultsDir,
+    workspaceDir: read(ENV_VARS.workspaceDir) || config.workspaceDir,
+    defaultAgent: read(ENV_VARS.agent) || config.defaultAgent,
+    defaultModel: read(ENV_VARS.model) || config.defaultModel,
+    maxIterations,
+    notify: readBoolean(ENV_VARS.notify, config.notify),
+    keepWorkspace: readBoolean(ENV_VARS.keepWorkspace, config.keepWorkspace),
+    workspaceQuota: workspaceQuota ? parseQuota(workspaceQuota) : config.workspaceQuota,
+    prefetch: readBoolean(ENV_VARS.prefetch, config.prefetch),
+    timeBudget: timeBudget ? parseTimeBudget(timeBudget) : config.timeBudget,
+    wasmPlugins: readBoolean(ENV_VARS.wasmPlugins, config.wasmPlugins),
+    resultsBackend: backend ? parseResultsBackend(backend) : config.resultsBackend,
+    resultsUrl: read(ENV_VARS.resultsUrl) || config.resultsUrl,
+    recordMode: recordMode ? parseRecordMode(recordMode) : config.recordMode,
+    recordingsDir: read(ENV_VARS.recordingsDir) || config.recordingsDir,
+    gitBackend: gitBackend ? parseGitBackend(gitBackend) : config.gitBackend,
+    sandbox: sandbox ? parseSandbox(sandbox) : config.sandbox,
+    // Separated like PATH
+    sandboxWritable: sandboxWritable ? sandboxWritable.split(delimiter).filter(Boolean) : config.sandboxWritable,
+    agentEnv: agentEnv ? agentEnv.split(',').map(name => name.trim()).filter(Boolean) : config.agentEnv,
+  };
+}
+
+/**
+ * Environment variable setting each config field.
+ */
+const ENV_FIELDS: Partial<Record<keyof RunnerConfig, string>> = {
+  tasksDir: ENV_VARS.tasksDir,
+  resultsDir: ENV_VARS.resultsDir,
+  workspaceDir: ENV_VARS.workspaceDir,
+  defaultAgent: ENV_VARS.agent,
+  defaultModel: ENV_VARS.model,
+  maxIterations: ENV_VARS.maxIterations,
+  resultsBackend: ENV_VARS.resultsBackend,
+  resultsUrl: ENV_VARS.resultsUrl,
+  notify: ENV_VARS.notify,
+  keepWorkspace: ENV_VARS.keepWorkspace,
+  workspaceQuota: ENV_VARS.workspaceQuota,
+  prefetch: ENV_VARS.prefetch,
+  timeBudget: ENV_VARS.timeBudget,
+  wasmPlugins: ENV_VARS.wasmPlugins,
+  recordMode: ENV_VARS.recordMode,
+  recordingsDir: ENV_VARS.recordingsDir,
+  gitBackend: ENV_VARS.gitBackend,
+  sandbox: ENV_VARS.sandbox,
+  sandboxWritable: ENV_VARS.sandboxWritable,
+  agentEnv: ENV_VARS.agentEnv,
+};
+
+/**
+ * Where each effective config value came from: `default`, a config file path,
+ * `env AGENT_BENCH_*`, or `flag --*`.
+ */
+export type ConfigSources = Record<keyof RunnerConfig, string>;
+
+/**
+ * An effective configuration and the source of each value.
+ */
+export interface ResolvedConfig {
+  config: RunnerConfig;
+  sources: ConfigSources;
+}
+
+/**
+ * Apply a configuration layer, attributing every value it changed to the layer.
+ * @param source Source label for a changed field
+ */
+export function applyConfigLayer(
+  resolved: ResolvedConfig,
+  next: RunnerConfig,
+  source: (key: keyof RunnerConfig) => string
+): ResolvedConfig {
+  const sources = { ...resolved.sources };
+  for (const key of Object.keys(next) as (keyof RunnerConfig)[]) {
+    if (!isDeepStrictEqual(next[key], resolved.config[key])) {
+      sources[key] = source(key);
+    }
+  }
+  return { config: next, sources };
+}
+
+/**
+ * Resolve the effective configuration and where each value came from. Layers, lowest
+ * precedence first: built-in defaults, the legacy JSON user config,
+ * `~/.config/agent-bench/agent-bench.toml`, the project `agent-bench.toml`, and
+ * `AGENT_BENCH_*` environment variables. CLI flags are applied on top by the CLI.
+ */
+export async function resolveConfig(
+  projectDir: string = process.cwd(),
+  env: Record<string, string | undefined> = process.env
+): Promise<ResolvedConfig> {
+  const defaults = createDefaultConfig();
+  let resolved: ResolvedConfig = {
+    config: defaults,
+    sources: Object.fromEntries(Object.keys(defaults).map(key => [key, 'default'])) as ConfigSources,
+  };
+
+  resolved = applyConfigLayer(resolved, mergeConfig(await loadUserConfig(), defaults), () => getConfigPath());
+  for (const path of getConfigFilePaths(projectDir)) {
+    const fileConfig = await loadFileConfig(path);
+    if (fileConfig) {
+      resolved = applyConfigLayer(resolved, applyFileConfig(resolved.config, fileConfig), () => path);
+    }
+  }
+  return applyConfigLayer(resolved, applyEnvConfig(resolved.config, env), key => `env ${ENV_FIELDS[key] ?? ''}`.trim());
+}
+
+/**
+ * Resolve the effective configuration (see resolveConfig).
+ */
+export async function loadConfig(projectDir: string = process.cwd()): Promise<RunnerConfig> {
+  return (await resolveConfig(projectDir)).config;
+}
@@ -0,0 +1,112 @@
+/**
+ * Structured run events.
+ *
+ * The runner emits events as a run progresses; reporters (the console output,
+ * progress bars, webhooks, a TUI) and library users subscribe to an EventBus
+ * instead of the runner printing directly.
+ */
+
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import { logger } from '../utils/logger.js';
+
+interface EventBase {
+  run_id: string;
+  timestamp: string;
+}
+
+export interface SuiteStartedEvent extends EventBase {
+  type: 'suite_started';
+  agent: string;
+  task_ids: string[];
+}
+
+export interface TaskStartedEvent extends EventBase {
+  type: 'task_started';
+  task_id: string;
+  title: string;
+  agent: string;
+  /** The task's YAML file */
+  file_path: string | null;
+}
+
+export interface AgentIterationFinishedEvent extends EventBase {
+  type: 'agent_iteration_finished';
+  task_id: string;
+  iteration: number;
+  tokens_used: number;
+  cost: number | null;
+}
+
+export interface VerificationFinishedEvent extends EventBase {
+  type: 'verification_finished';
+  task_id: string;
+  /** One-based checkpoint of a multi-step task; null for final verification */
+  checkpoint: number | null;
+  passed: boolean;
+  exit_code: number | null;
+  duration_secs: number;
+}
+
+export interface TaskFinishedEvent extends EventBase {
+  type: 'task_finished';
+  task_id: string;
+  result: BenchmarkResult;
+}
+
+export interface SuiteFinishedEvent extends EventBase {
+  type: 'suite_finished';
+  suite: SuiteResults;
+}
+
+export type RunEvent =
+  | SuiteStartedEvent
+  | TaskStartedEvent
+  | AgentIterationFinishedEvent
+  | VerificationFinishedEvent
+  | TaskFinishedEvent
+  | SuiteFinishedEvent;
+
+export type RunEventType = RunEvent['type'];
+
+/**
+ * Event without the fields the bus fills in.
+ */
+type WithoutBase<E> = E extends RunEvent ? Omit<E, keyof EventBase> : never;
+export type RunEventInput = WithoutBase<RunEvent>;
+
+export type RunEventListener = (event: RunEvent) => void | Promise<void>;
+
+/**
+ * Delivers run events to subscribers in registration order.
+ * A failing listener is logged and does not affect the run or other listeners.
+ */
+export class EventBus {
+  private listeners: RunEventListener[] = [];
+
+  /**
+   * @param runId Run ID stamped on every event
+   */
+  constructor(private runId: string) {}
+
+  /**
+   * Subscribe to all events.
+   * @returns A function removing the listener
+   */
+  on(listener: RunEventListener): () => void {
+    this.listeners.push(listener);
+    return () => {
+      this.listeners = this.listeners.filter(entry => entry !== listener);
+    };
+  }
+
+  async emit(event: RunEventInput): Promise<void> {
+    const stamped = { ...event, run_id: this.runId, timestamp: new Date().toISOString() } as RunEvent;
+    for (const listener of [...this.listeners]) {
+      try {
+        await listener(stamped);
+      } catch (error) {
+        logger.warn(`Event listener failed on ${stamped.type}: ${error instanceof Error ? error.message : error}`);
+      }
+    }
+  }
+}
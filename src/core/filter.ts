@@ -0,0 +1,226 @@
+/**
+ * Task filtering shared by CLI commands.
+ */
+
+import { Task } from './task.js';
+import { matchesTag } from './tags.js';
+import { FilterSyntaxError } from '../utils/errors.js';
+
+/**
+ * Field filters for selecting tasks.
+ */
+export interface TaskFilterOptions {
+  category?: string;
+  difficulty?: string;
+  tags?: string[];
+  expression?: FilterExpression;
+}
+
+/**
+ * Parse a comma-separated tag list, merged with any repeated `--tag` values.
+ */
+export function parseTagList(tags: string | undefined, extra: string[] = []): string[] | undefined {
+  const parsed = [...(tags ? tags.split(',') : []), ...extra].map(tag => tag.trim()).filter(Boolean);
+  return parsed.length > 0 ? parsed : undefined;
+}
+
+/**
+ * Collect repeated option values (for Commander).
+ */
+export function collectValues(value: string, previous: string[] = []): string[] {
+  return [...previous, value];
+}
+
+/**
+ * Apply field filters to a task list.
+ * Tags match if the task has any of the given tags, using namespace-aware matching.
+ */
+export function filterTasks(tasks: Task[], options: TaskFilterOptions): Task[] {
+  return tasks.filter(task => matchesFilter(task, options));
+}
+
+/**
+ * Whether a single task passes the field filters.
+ */
+export function matchesFilter(task: Task, options: TaskFilterOptions): boolean {
+  if (options.category && task.category !== options.category) {
+    return false;
+  }
+  if (options.difficulty && task.difficulty !== options.difficulty) {
+    return false;
+  }
+  if (options.tags && options.tags.length > 0) {
+    const taskTags = task.metadata?.tags || [];
+    if (!options.tags.some(filter => taskTags.some(tag => matchesTag(filter, tag)))) {
+      return false;
+    }
+  }
+  if (options.expression && !evaluateFilter(options.expression, task)) {
+    return false;
+  }
+  return true;
+}
+
+/**
+ * Comparison operators in filter expressions.
+ * - `=` / `!=`: equality (namespace-aware for tags)
+ * - `~` / `!~`: case-insensitive substring match
+ */
+export type FilterOperator = '=' | '!=' | '~' | '!~';
+
+/**
+ * Parsed filter expression.
+ */
+export type FilterExpression =
+  | { type: 'and' | 'or'; left: FilterExpression; right: FilterExpression }
+  | { type: 'not'; operand: FilterExpression }
+  | { type: 'compare'; field: string; operator: FilterOperator; value: string };
+
+/**
+ * Parse a filter expression such as `category=bug-fix && difficulty!=hard && tag~python`.
+ *
+ * Clauses are `<field><op><value>` with `=`, `!=`, `~`, or `!~`; combine them with
+ * `&&`, `||`, `!`, and parentheses. Fields are `id`, `title`, `category`,
+ * `difficulty`, `expect`, `disabled`, `tag`, or any scalar metadata key.
+ * Values may be quoted.
+ * @throws FilterSyntaxError If the expression is malformed
+ */
+export function parseFilterExpression(input: string): FilterExpression {
+  const tokens = tokenizeFilter(input);
+  let position = 0;
+
+  const peek = (): string | undefined => tokens[position];
+  const next = (): string => {
+    const token = tokens[position++];
+    if (token === undefined) {
+      throw new FilterSyntaxError(`Unexpected end of filter: ${input}`);
+    }
+    return token;
+  };
+
+  const parseOr = (): FilterExpression => {
+    let left = parseAnd();
+    while (peek() === '||') {
+      next();
+      left = { type: 'or', left, right: parseAnd() };
+    }
+    return left;
+  };
+
+  const parseAnd = (): FilterExpression => {
+    let left = parseUnary();
+    while (peek() === '&&') {
+      next();
+      left = { type: 'and', left, right: parseUnary() };
+    }
+    return left;
+  };
+
+  const parseUnary = (): FilterExpression => {
+    const token = next();
+    if (token === '!') {
+      return { type: 'not', operand: parseUnary() };
+    }
+    if (token === '(') {
+      const inner = parseOr();
+      if (next() !== ')') {
+        throw new FilterSyntaxError(`Expected ")" in filter: ${input}`);
+      }
+      return inner;
+    }
+    if (!/^[A-Za-z_][\w.-]*$/.test(token)) {
+      throw new FilterSyntaxError(`Expected a field name, got "${token}" in filter: ${input}`);
+    }
+    const operator = next();
+    if (!['=', '!=', '~', '!~'].includes(operator)) {
+      throw new FilterSyntaxError(`Expected =, !=, ~, or !~ after "${token}" in filter: ${input}`);
+    }
+    return { type: 'compare', field: token, operator: operator as FilterOperator, value: unquote(next()) };
+  };
+
+  const expression = parseOr();
+  if (position < tokens.length) {
+    throw new FilterSyntaxError(`Unexpected "${tokens[position]}" in filter: ${input}`);
+  }
+  return expression;
+}
+
+/**
+ * Evaluate a parsed filter expression against a task.
+ */
+export function evaluateFilter(expression: FilterExpression, task: Task): boolean {
+  switch (expression.type) {
+    case 'and':
+      return evaluateFilter(expression.left, task) && evaluateFilter(expression.right, task);
+    case 'or':
+      return evaluateFilter(expression.left, task) || evaluateFilter(expression.right, task);
+    case 'not':
+      return !evaluateFilter(expression.operand, task);
+    case 'compare':
+      return compareField(task, expression.field, expression.operator, expression.value);
+  }
+}
+
+/**
+ * Apply one comparison. Multi-valued fields (tags) match if any value matches.
+ */
+function compareField(task: Task, field: string, operator: FilterOperator, value: string): boolean {
+  const isTag = field === 'tag' || field === 'tags';
+  const values = isTag ? task.metadata?.tags || [] : fieldValues(task, field);
+  const wanted = value.toLowerCase();
+
+  const matches = values.some(actual =>
+    operator === '=' || operator === '!='
+      ? isTag ? matchesTag(value, actual) : actual === value
+      : actual.toLowerCase().includes(wanted)
+  );
+
+  return operator.startsWith('!') ? !matches : matches;
+}
+
+/**
+ * Values of a scalar task field, falling back to metadata keys.
+ */
+function fieldValues(task: Task, field: string): string[] {
+  const known: Record<string, unknown> = {
+    id: task.id,
+    title: task.title,
+    category: task.category,
+    difficulty: task.difficulty,
+    expect: task.expect,
+    disabled: task.disabled,
+  };
+  const value = field in known ? known[field] : (task.metadata as Record<string, unknown> | undefined)?.[field];
+  return value === undefined || value === null || typeof value === 'object' ? [] : [String(value)];
+}
+
+/**
+ * Split a filter expression into tokens.
+ */
+function tokenizeFilter(input: string): string[] {
+  const pattern = /\s*(&&|\|\||!=|!~|==|[()!=~]|"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'|[^\s()!=~&|]+)/y;
+  const tokens: string[] = [];
+
+  let position = 0;
+  while (input.substring(position).trim().length > 0) {
+    pattern.lastIndex = position;
+    const match = pattern.exec(input);
+    if (!match) {
+      throw new FilterSyntaxError(`Invalid filter syntax at "${input.substring(position).trim()}"`);
+    }
+    tokens.push(match[1] === '==' ? '=' : match[1]);
+    position = pattern.lastIndex;
+  }
+
+  return tokens;
+}
+
+/**
+ * Strip surrounding quotes from a value.
+ */
+function unquote(token: string): string {
+  if (/^(["']).*\1$/.test(token)) {
+    return token.slice(1, -1).replace(/\\(.)/g, '$1');
+  }
+  return token;
+}
@@ -0,0 +1,100 @@
+/**
+ * Flakiness check: repeat workspace preparation and verification without an agent.
+ */
+
+import type { Task } from './task.js';
+import { WorkspaceManager } from './workspace.js';
+import { prepareToolchain, toolchainVariables } from './toolchain.js';
+import { Verifier } from '../evaluator/verifier.js';
+import { logger } from '../utils/logger.js';
+import { disposeSpoolFiles } from '../utils/spool.js';
+
+/**
+ * Outcome of one trial.
+ */
+export interface FlakeTrial {
+  trial: number;
+  passed: boolean;
+  exitCode: number | null;
+  durationSecs: number;
+  error: string | null;
+}
+
+/**
+ * Result of repeating a task's verification on untouched workspaces.
+ */
+export interface FlakeReport {
+  taskId: string;
+  trials: FlakeTrial[];
+  passed: number;
+  failed: number;
+  errors: number;
+  /**
+   * Every trial produced the same pass/fail outcome and exit code.
+   */
+  stable: boolean;
+  /**
+   * Verification passed on the untouched workspace: the task may be solved without any change.
+   */
+  passesWithoutChanges: boolean;
+}
+
+/**
+ * Prepare a fresh workspace and run verification `trials` times, reporting non-determinism.
+ */
+export async function checkFlakiness(task: Task, workspaceDir: string, trials: number): Promise<FlakeReport> {
+  const workspace = new WorkspaceManager(workspaceDir, false, trials > 1);
+  const env = toolchainVariables(await prepareToolchain(task));
+  const results: FlakeTrial[] = [];
+
+  for (let trial = 1; trial <= trials; trial++) {
+    const startTime = Date.now();
+    try {
+      const verification = await workspace.withWorkspace(task, async (trialWorkspace) => {
+        await trialWorkspace.applyHiddenTests();
+        return await Verifier.verify(task, trialWorkspace.path, env);
+      });
+      await disposeSpoolFiles(verification.stdoutFile, verification.stderrFile);
+      results.push({
+        trial,
+        passed: verification.passed,
+        exitCode: verification.exitCode,
+        durationSecs: verification.durationSecs,
+        error: null,
+      });
+      logger.debug(`Trial ${trial}: exit ${verification.exitCode}\n${verification.stdout}\n${verification.stderr}`);
+    } catch (error) {
+      results.push({
+        trial,
+        passed: false,
+        exitCode: null,
+        durationSecs: (Date.now() - startTime) / 1000,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+    logger.info(`Trial ${trial}/${trials}: ${describeTrial(results[results.length - 1])}`);
+  }
+
+  const signatures = new Set(results.map(describeTrial));
+  const passed = results.filter(r => r.passed).length;
+
+  return {
+    taskId: task.id,
+    trials: results,
+    passed,
+    failed: results.length - passed,
+    errors: results.filter(r => r.error !== null).length,
+    stable: signatures.size <= 1,
+    passesWithoutChanges: passed > 0 && task.expect !== 'fail',
+  };
+}
+
+/**
+ * Short description of a trial, also used to compare trials.
+ */
+function describeTrial(trial: FlakeTrial): string {
+  if (trial.error) {
+    return `error (${trial.error})`;
+  }
+  return `${trial.passed ? 'pass' : 'fail'} (exit ${trial.exitCode})`;
+}
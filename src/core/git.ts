@@ -0,0 +1,183 @@
+/**
+ * Git access through a selectable backend: simple-git, or the git CLI run directly
+ * through the shared exec module (`[git] backend = "shell"`).
+ *
+ * Everything that touches repositories (workspaces, the mirror cache, remote task
+ * repositories, the lockfile, the LLM judge's diff) goes through `git()` so the backend
+ * is one config choice.
+ */
+
+import simpleGit from 'simple-git';
+import type { GitBackendName } from './config.js';
+import { exec, formatCommand, reportCommand } from '../utils/exec.js';
+import { GitError } from '../utils/errors.js';
+
+/**
+ * Runs git subcommands.
+ */
+export interface GitBackend {
+  readonly name: GitBackendName;
+
+  /**
+   * Run `git <args>` in `cwd` (default: the current directory).
+   * @returns Standard output
+   * @throws GitError If git fails
+   */
+  run(args: string[], cwd?: string): Promise<string>;
+}
+
+/**
+ * Backend using the simple-git package.
+ */
+class SimpleGitBackend implements GitBackend {
+  readonly name = 'simple-git' as const;
+
+  async run(args: string[], cwd?: string): Promise<string> {
+    // simple-git spawns git itself, so report the command to the audit log here
+    const startTime = Date.now();
+    const record = { program: 'git', args, cwd: cwd ?? process.cwd(), envSet: {}, envRemoved: [], signal: null, timedOut: false };
+    try {
+      const output = await simpleGit(cwd).raw(args);
+      reportCommand({ ...record, exitCode: 0, durationSecs: (Date.now() - startTime) / 1000, error: null });
+      return output;
+    } catch (error) {
+      reportCommand({
+        ...record,
+        exitCode: null,
+        durationSecs: (Date.now() - startTime) / 1000,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      throw new GitError(`git ${args.join(' ')} failed: ${error instanceof Error ? error.message : error}`, {
+        command: `git ${args.join(' ')}`,
+      });
+    }
+  }
+}
+
+/**
+ * Backend running the git CLI directly; credentials prompts are disabled so a missing
+ * credential fails instead of hanging.
+ */
+class ShellGitBackend implements GitBackend {
+  readonly name = 'shell' as const;
+
+  async run(args: string[], cwd?: string): Promise<string> {
+    const command = { program: 'git', args };
+    // Callers parse the output, so it is never cut to an excerpt
+    const result = await exec(command, { cwd, env: { GIT_TERMINAL_PROMPT: '0' }, maxOutputBytes: Infinity });
+    if (result.exitCode !== 0) {
+      throw new GitError(`${formatCommand(command)} failed: ${result.stderr.trim() || `exit ${result.exitCode}`}`, {
+        command: formatCommand(command),
+      });
+    }
+    return result.stdout;
+  }
+}
+
+/**
+ * Create a backend by name.
+ */
+export function createGitBackend(name: GitBackendName): GitBackend {
+  switch (name) {
+    case 'simple-git':
+      return new SimpleGitBackend();
+    case 'shell':
+      return new ShellGitBackend();
+  }
+}
+
+let activeBackend: GitBackend = createGitBackend('simple-git');
+
+/**
+ * Select the backend used by `git()` (the CLI and TaskRunner set it from config).
+ */
+export function setGitBackend(name: GitBackendName): void {
+  if (activeBackend.name !== name) {
+    activeBackend = createGitBackend(name);
+  }
+}
+
+export function getGitBackend(): GitBackend {
+  return activeBackend;
+}
+
+/**
+ * Clone options.
+ */
+export interface CloneOptions {
+  /** Bare mirror of every ref */
+  mirror?: boolean;
+  /** Shallow clone with this many commits */
+  depth?: number;
+  /** Partial clone filter, e.g. `blob:none` (blobs are fetched when checked out) */
+  filter?: string;
+}
+
+/**
+ * Git operations in a repository (or, for clone and ls-remote, anywhere).
+ */
+export class Git {
+  private cwd: string | undefined;
+  private backend: GitBackend;
+
+  constructor(cwd?: string, backend: GitBackend = getGitBackend()) {
+    this.cwd = cwd;
+    this.backend = backend;
+  }
+
+  /**
+   * Run any git subcommand, returning its stdout.
+   */
+  async raw(args: string[]): Promise<string> {
+    return await this.backend.run(args, this.cwd);
+  }
+
+  async clone(url: string, destination: string, options: CloneOptions = {}): Promise<void> {
+    const args = ['clone'];
+    if (options.mirror) args.push('--mirror');
+    if (options.depth) args.push(`--depth=${options.depth}`);
+    if (options.filter) args.push(`--filter=${options.filter}`);
+    await this.raw([...args, '--', url, destination]);
+  }
+
+  async fetch(args: string[] = []): Promise<void> {
+    await this.raw(['fetch', ...args]);
+  }
+
+  async checkout(args: string | string[]): Promise<void> {
+    await this.raw(['checkout', ...(Array.isArray(args) ? args : [args])]);
+  }
+
+  /**
+   * Resolve a revision to an object name.
+   */
+  async revParse(revision: string): Promise<string> {
+    return (await this.raw(['rev-parse', '--verify', revision])).trim();
+  }
+
+  async setRemoteUrl(remote: string, url: string): Promise<void> {
+    await this.raw(['remote', 'set-url', remote, url]);
+  }
+
+  async listRemote(args: string[]): Promise<string> {
+    return await this.raw(['ls-remote', ...args]);
+  }
+
+  async diff(args: string[] = []): Promise<string> {
+    return await this.raw(['diff', ...args]);
+  }
+
+  /**
+   * Untracked files that are not ignored.
+   */
+  async untrackedFiles(): Promise<string[]> {
+    return (await this.raw(['ls-files', '--others', '--exclude-standard'])).split('\n').filter(Boolean);
+  }
+}
+
+/**
+ * Git operations in `cwd` through the selected backend.
+ */
+export function git(cwd?: string): Git {
+  return new Git(cwd);
+}
@@ -0,0 +1,133 @@
+/**
+ * Graph export of the task corpus.
+ */
+
+import { Task } from './task.js';
+import { parseTag } from './tags.js';
+
+/**
+ * Tag namespace whose values define suites.
+ */
+const SUITE_NAMESPACE = 'suite';
+
+/**
+ * Options for graph export.
+ */
+export interface TaskGraphOptions {
+  /** Include non-suite tags as nodes. */
+  tags: boolean;
+}
+
+/**
+ * A node of the task graph.
+ */
+export interface TaskGraphNode {
+  id: string;
+  kind: 'task' | 'suite' | 'tag';
+  label: string;
+  category?: string;
+  difficulty?: string;
+  disabled?: boolean;
+}
+
+/**
+ * An edge of the task graph: suite → task or task → tag.
+ */
+export interface TaskGraphEdge {
+  from: string;
+  to: string;
+  kind: 'member' | 'tagged';
+}
+
+/**
+ * Task graph with suites (`suite:*` tags), tasks, and tags as nodes.
+ */
+export interface TaskGraph {
+  nodes: TaskGraphNode[];
+  edges: TaskGraphEdge[];
+}
+
+/**
+ * Build the graph of tasks, the suites containing them, and their tags.
+ */
+export function buildTaskGraph(tasks: Task[], options: TaskGraphOptions = { tags: true }): TaskGraph {
+  const nodes: TaskGraphNode[] = tasks.map(task => ({
+    id: task.id,
+    kind: 'task',
+    label: task.title,
+    category: task.category,
+    difficulty: task.difficulty,
+    disabled: task.disabled,
+  }));
+  const edges: TaskGraphEdge[] = [];
+  const seen = new Set<string>();
+
+  for (const task of tasks) {
+    for (const tag of task.metadata?.tags || []) {
+      const { namespace, value } = parseTag(tag);
+      const isSuite = namespace === SUITE_NAMESPACE;
+      if (!isSuite && !options.tags) {
+        continue;
+      }
+
+      const id = isSuite ? `suite:${value}` : `tag:${tag}`;
+      if (!seen.has(id)) {
+        seen.add(id);
+        nodes.push({ id, kind: isSuite ? 'suite' : 'tag', label: isSuite ? value : tag });
+      }
+      edges.push(isSuite ? { from: id, to: task.id, kind: 'member' } : { from: task.id, to: id, kind: 'tagged' });
+    }
+  }
+
+  return { nodes, edges };
+}
+
+/**
+ * Render a task graph as Graphviz DOT, clustering tasks by category.
+ */
+export function renderDot(graph: TaskGraph): string {
+  const lines: string[] = [
+    'digraph tasks {',
+    '  rankdir=LR;',
+    '  node [fontname="Helvetica", fontsize=10];',
+  ];
+
+  const byCategory = new Map<string, TaskGraphNode[]>();
+  for (const node of graph.nodes.filter(n => n.kind === 'task')) {
+    const category = node.category || 'uncategorized';
+    byCategory.set(category, [...(byCategory.get(category) || []), node]);
+  }
+
+  for (const [category, categoryNodes] of byCategory) {
+    lines.push(`  subgraph ${quote(`cluster_${category}`)} {`);
+    lines.push(`    label=${quote(category)};`);
+    for (const node of categoryNodes) {
+      const style = node.disabled ? ', style=dashed' : '';
+      lines.push(`    ${quote(node.id)} [label=${quote(`${node.id}\\n${node.difficulty}`)}, shape=box${style}];`);
+    }
+    lines.push('  }');
+  }
+
+  for (const node of graph.nodes) {
+    if (node.kind === 'suite') {
+      lines.push(`  ${quote(node.id)} [label=${quote(node.label)}, shape=folder, style=filled, fillcolor=lightblue];`);
+    } else if (node.kind === 'tag') {
+      lines.push(`  ${quote(node.id)} [label=${quote(node.label)}, shape=ellipse, color=gray];`);
+    }
+  }
+
+  for (const edge of graph.edges) {
+    const style = edge.kind === 'tagged' ? ' [style=dotted, arrowhead=none]' : '';
+    lines.push(`  ${quote(edge.from)} -> ${quote(edge.to)}${style};`);
+  }
+
+  lines.push('}');
+  return lines.join('\n') + '\n';
+}
+
+/**
+ * Quote a DOT identifier.
+ */
+function quote(value: string): string {
+  return `"${value.replace(/"/g, '\\"')}"`;
+}
@@ -0,0 +1,217 @@
+/**
+ * Task quality linter.
+ */
+
+import { readdir, readFile } from 'fs/promises';
+import { dirname, isAbsolute, join } from 'path';
+import yaml from 'js-yaml';
+import { TASK_SCHEMA_VERSION, Task, VerificationConfig } from './task.js';
+import { TaskLoader } from './loader.js';
+import { resolveCapabilities, TOOL_CAPABILITIES, ungrantedTools } from '../agents/permissions.js';
+
+/**
+ * Rule severity; `off` disables the rule.
+ */
+export type LintSeverity = 'off' | 'warn' | 'error';
+
+/**
+ * A problem reported by a lint rule.
+ */
+export interface LintIssue {
+  rule: string;
+  severity: Exclude<LintSeverity, 'off'>;
+  file: string;
+  taskId: string | null;
+  message: string;
+}
+
+/**
+ * Everything a rule can inspect about a task.
+ */
+interface LintContext {
+  task: Task;
+  raw: Record<string, any>;
+  file: string;
+  hiddenTestFiles: string[];
+}
+
+/**
+ * A lint rule.
+ */
+export interface LintRule {
+  name: string;
+  description: string;
+  defaultSeverity: LintSeverity;
+  check(context: LintContext): string[];
+}
+
+/**
+ * Rule reported for task files that fail to load.
+ */
+export const INVALID_TASK_RULE = 'invalid-task';
+
+/**
+ * Built-in lint rules.
+ */
+export const LINT_RULES: LintRule[] = [
+  {
+    name: 'solution-leak',
+    description: 'Prompt reveals the verification command or hidden test files',
+    defaultSeverity: 'warn',
+    check: ({ task, hiddenTestFiles }) => {
+      const messages: string[] = [];
+      for (const verification of verificationsOf(task)) {
+        if (verification.command && task.prompt.includes(verification.command)) {
+          messages.push(`Prompt contains the verification command "${verification.command}"`);
+        }
+      }
+      for (const file of hiddenTestFiles) {
+        if (task.prompt.includes(file)) {
+          messages.push(`Prompt mentions hidden test file "${file}"`);
+        }
+      }
+      return messages;
+    },
+  },
+  {
+    name: 'verification-outside-workspace',
+    description: 'Verification command references files outside the workspace',
+    defaultSeverity: 'error',
+    check: ({ task }) => {
+      const messages: string[] = [];
+      for (const verification of verificationsOf(task)) {
+        for (const token of (verification.command || '').split(/\s+/).slice(1)) {
+          const path = token.replace(/^["']|["']$/g, '');
+          if (isAbsolute(path) || path.startsWith('~') || path.split(/[\\/]/).includes('..')) {
+            messages.push(`Verification command references "${path}" outside the workspace`);
+          }
+        }
+      }
+      return messages;
+    },
+  },
+  {
+    name: 'missing-timeout',
+    description: 'Verification has no explicit timeout',
+    defaultSeverity: 'warn',
+    check: ({ raw }) => {
+      const messages: string[] = [];
+      if (raw.verification && raw.verification.timeout === undefined) {
+        messages.push('Verification has no explicit timeout (defaults to 60s)');
+      }
+      (Array.isArray(raw.steps) ? raw.steps : []).forEach((step: any, index: number) => {
+        if (step?.verification && step.verification.timeout === undefined) {
+          messages.push(`Step ${index + 1} verification has no explicit timeout (defaults to 60s)`);
+        }
+      });
+      return messages;
+    },
+  },
+  {
+    name: 'outdated-schema',
+    description: 'Task does not declare the current schema version',
+    defaultSeverity: 'warn',
+    check: ({ raw }) => raw.schema === TASK_SCHEMA_VERSION
+      ? []
+      : [`Task ${raw.schema === undefined ? 'has no schema field' : `uses schema ${raw.schema}`} (current: ${TASK_SCHEMA_VERSION}); run \`tasks migrate\``],
+  },
+  {
+    name: 'broad-permissions',
+    description: 'Task grants broader permissions than it likely needs',
+    defaultSeverity: 'warn',
+    check: ({ task }) => {
+      const messages: string[] = [];
+      const capabilities = resolveCapabilities(task.permissions);
+      if (capabilities.approval === 'bypass') {
+        messages.push('Approval policy "bypass" skips all permission checks');
+      }
+      if (capabilities.execute && capabilities.network) {
+        messages.push('Task allows both command execution and network access');
+      }
+      return messages;
+    },
+  },
+  {
+    name: 'ungranted-tool',
+    description: 'Task requires a tool its permissions do not let the agent use',
+    defaultSeverity: 'error',
+    check: ({ task }) => ungrantedTools(task.requires_tools || [], resolveCapabilities(task.permissions)).map(
+      tool => `Task requires the ${tool} tool but its permissions do not grant ${TOOL_CAPABILITIES[tool]}`
+    ),
+  },
+];
+
+/**
+ * Lint every task file in the tasks directory.
+ * @param loader Task loader for the tasks directory
+ * @param overrides Severity overrides by rule name
+ */
+export async function lintTasks(
+  loader: TaskLoader,
+  overrides: Record<string, LintSeverity> = {}
+): Promise<LintIssue[]> {
+  const issues: LintIssue[] = [];
+  const severityOf = (rule: string, fallback: LintSeverity): LintSeverity => overrides[rule] ?? fallback;
+
+  for (const file of await loader.listTaskFiles()) {
+    let task: Task;
+    let raw: Record<string, any>;
+    try {
+      raw = (yaml.load(await readFile(file, 'utf-8')) || {}) as Record<string, any>;
+      task = await loader.loadFromFile(file);
+    } catch (error) {
+      const severity = severityOf(INVALID_TASK_RULE, 'error');
+      if (severity !== 'off') {
+        issues.push({ rule: INVALID_TASK_RULE, severity, file, taskId: null, message: String(error) });
+      }
+      continue;
+    }
+
+    const context: LintContext = {
+      task,
+      raw,
+      file,
+      hiddenTestFiles: await listFiles(join(dirname(file), task.id, 'hidden_tests')),
+    };
+
+    for (const rule of LINT_RULES) {
+      const severity = severityOf(rule.name, rule.defaultSeverity);
+      if (severity === 'off') continue;
+      for (const message of rule.check(context)) {
+        issues.push({ rule: rule.name, severity, file, taskId: task.id, message });
+      }
+    }
+  }
+
+  return issues;
+}
+
+/**
+ * All verification configs of a task, including multi-step checkpoints.
+ */
+function verificationsOf(task: Task): VerificationConfig[] {
+  return [task.verification, ...(task.steps || []).map(step => step.verification)];
+}
+
+/**
+ * Recursively list file paths relative to a directory (empty if it does not exist).
+ */
+async function listFiles(directory: string, prefix: string = ''): Promise<string[]> {
+  let entries;
+  try {
+    entries = await readdir(directory, { withFileTypes: true });
+  } catch {
+    return [];
+  }
+
+  const files: string[] = [];
+  for (const entry of entries) {
+    const relativePath = prefix ? `${prefix}/${entry.name}` : entry.name;
+    if (entry.isDirectory()) {
+      files.push(...(await listFiles(join(directory, entry.name), relativePath)));
+    } else {
+      files.push(relativePath);
+    }
+  }
+  return files;
+}
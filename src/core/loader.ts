@@ -2,75 +2,120 @@
  * Task loader for discovering and loading benchmark tasks.
  */
 
-import { readdir, readFile } from 'fs/promises';
-import { join } from 'path';
+import { mkdir, readdir, readFile, stat } from 'fs/promises';
+import { basename, dirname, join, resolve } from 'path';
+import { createHash } from 'crypto';
 import yaml from 'js-yaml';
 import { Task, TaskSchema } from './task.js';
-import { TaskLoadError, TaskNotFoundError } from '../utils/errors.js';
+import { getCacheDir } from './config.js';
+import { fetchTaskRepository, isGitTasksSpec } from './remote.js';
+import { matchesFilter, type TaskFilterOptions } from './filter.js';
+import { upgradeTask } from './migrate.js';
+import { TaskLoadError, TaskNotFoundError, TaskSchemaError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+import { mapBounded, mapConcurrent } from '../utils/concurrency.js';
+import { writeFileAtomic } from '../utils/files.js';
+
+/**
+ * Task files read and parsed at once.
+ */
+const LOAD_CONCURRENCY = 16;
+
+/**
+ * Index entry mapping a task file to its ID.
+ */
+interface TaskIndexEntry {
+  id: string | null;
+  mtimeMs: number;
+}
+
+/**
+ * Task index keyed by task file path.
+ */
+type TaskIndex = Record<string, TaskIndexEntry>;
 
 /**
  * Task loader for discovering and loading benchmark tasks.
  */
 export class TaskLoader {
   private tasksDir: string;
+  private resolvedDir: Promise<string> | null = null;
+  private index: TaskIndex | null = null;
 
+  /**
+   * @param tasksDir Local directory, or a git repository as `git+<url>#<ref>`
+   */
   constructor(tasksDir: string) {
     this.tasksDir = tasksDir;
   }
 
   /**
-   * Load all tasks from the tasks directory.
+   * Resolve the tasks directory, cloning remote task repositories on first use.
    */
-  async loadAll(): Promise<Task[]> {
-    const tasks: Task[] = [];
-
-    try {
-      await this.loadRecursive(this.tasksDir, tasks);
-    } catch (error) {
-      // If directory doesn't exist, return empty array
-      if ((error as any)?.code === 'ENOENT') {
-        return tasks;
-      }
-      throw error;
+  getTasksDir(): Promise<string> {
+    if (!this.resolvedDir) {
+      this.resolvedDir = isGitTasksSpec(this.tasksDir)
+        ? fetchTaskRepository(this.tasksDir)
+        : Promise.resolve(this.tasksDir);
     }
+    return this.resolvedDir;
+  }
 
+  /**
+   * Load all tasks from the tasks directory (ordered by file path), optionally narrowed by a filter.
+   */
+  async loadAll(filter: TaskFilterOptions = {}): Promise<Task[]> {
+    const tasks: Task[] = [];
+    for await (const task of this.stream(filter)) {
+      tasks.push(task);
+    }
     return tasks;
   }
 
   /**
-   * Recursively load tasks from a directory.
+   * Stream tasks as they load, reading and parsing up to LOAD_CONCURRENCY files at once.
+   * Tasks arrive in file path order; files that fail to load are reported and skipped.
    */
-  private async loadRecursive(directory: string, tasks: Task[]): Promise<void> {
-    const entries = await readdir(directory, { withFileTypes: true });
-
-    for (const entry of entries) {
-      const fullPath = join(directory, entry.name);
-
-      if (entry.isDirectory()) {
-        await this.loadRecursive(fullPath, tasks);
-      } else if (entry.name.endsWith('.yaml') || entry.name.endsWith('.yml')) {
-        try {
-          const task = await this.loadFromFile(fullPath);
-          tasks.push(task);
-        } catch (error) {
-          console.warn(`Warning: Failed to load ${fullPath}:`, error);
-        }
+  async *stream(filter: TaskFilterOptions = {}): AsyncGenerator<Task> {
+    const files = await this.listTaskFiles();
+    for await (const task of mapBounded(files, LOAD_CONCURRENCY, file => this.tryLoadFromFile(file))) {
+      if (task && matchesFilter(task, filter)) {
+        yield task;
       }
     }
   }
 
+  /**
+   * Load a task file, warning and returning null if it is invalid.
+   */
+  private async tryLoadFromFile(filePath: string): Promise<Task | null> {
+    try {
+      return await this.loadFromFile(filePath);
+    } catch (error) {
+      logger.warn(`Failed to load ${filePath}: ${error instanceof Error ? error.message : error}`);
+      return null;
+    }
+  }
+
   /**
    * Load a task from a YAML file.
    */
-  private async loadFromFile(filePath: string): Promise<Task> {
+  async loadFromFile(filePath: string): Promise<Task> {
     try {
       const content = await readFile(filePath, 'utf-8');
       const data = yaml.load(content);
 
+      // Refuse tasks written for a newer harness before their fields fail validation
+      const upgraded = data && typeof data === 'object' ? upgradeTask(data as Record<string, unknown>, filePath) : data;
+      await this.resolvePromptFile(upgraded, filePath);
+
       // Validate with Zod schema
-      const task = TaskSchema.parse(data);
-      return task;
+      const task = TaskSchema.parse(upgraded);
+      return { ...task, file_path: filePath };
     } catch (error) {
+      if (error instanceof TaskSchemaError) {
+        throw error;
+      }
       if ((error as any)?.code === 'ENOENT') {
         throw new TaskLoadError(`Failed to read ${filePath}: file not found`);
       }
@@ -78,42 +123,213 @@ export class TaskLoader {
     }
   }
 
+  /**
+   * Inline `prompt_file` references (resolved relative to the task YAML) for the task and its steps.
+   * Multi-step tasks default their prompt to the first step and verification to the last step.
+   */
+  private async resolvePromptFile(data: unknown, filePath: string): Promise<void> {
+    if (!data || typeof data !== 'object') {
+      return;
+    }
+
+    const raw = data as Record<string, unknown>;
+    await this.inlinePromptFile(raw, filePath);
+
+    if (Array.isArray(raw.steps) && raw.steps.length > 0) {
+      for (const step of raw.steps) {
+        if (step && typeof step === 'object') {
+          await this.inlinePromptFile(step as Record<string, unknown>, filePath);
+        }
+      }
+
+      const first = raw.steps[0] as Record<string, unknown>;
+      const last = raw.steps[raw.steps.length - 1] as Record<string, unknown>;
+      if (raw.prompt === undefined) raw.prompt = first?.prompt;
+      if (raw.verification === undefined) raw.verification = last?.verification;
+    }
+  }
+
+  /**
+   * Replace an object's `prompt_file` with the file contents as `prompt`.
+   */
+  private async inlinePromptFile(raw: Record<string, unknown>, filePath: string): Promise<void> {
+    if (typeof raw.prompt_file !== 'string') {
+      return;
+    }
+
+    if (raw.prompt !== undefined) {
+      throw new TaskLoadError(`Task in ${filePath} specifies both prompt and prompt_file`);
+    }
+
+    const promptPath = resolve(dirname(filePath), raw.prompt_file);
+    try {
+      raw.prompt = await readFile(promptPath, 'utf-8');
+    } catch (error) {
+      throw new TaskLoadError(`Failed to read prompt file ${promptPath}: ${error}`);
+    }
+  }
+
   /**
    * Load a specific task by ID.
+   * Uses the task index so only the matching file is parsed and validated.
    */
   async loadById(taskId: string): Promise<Task> {
-    const tasks = await this.loadAll();
-    const task = tasks.find(t => t.id === taskId);
+    const index = await this.buildIndex();
+    const filePath = Object.keys(index).find(path => index[path].id === taskId);
 
-    if (!task) {
-      throw new TaskNotFoundError(`Task ${taskId} not found`);
+    if (!filePath) {
+      throw new TaskNotFoundError(`Task ${taskId} not found`, { taskId });
     }
 
-    return task;
+    return await this.loadFromFile(filePath);
   }
 
   /**
    * List all available task IDs.
    */
   async listIds(): Promise<string[]> {
-    const tasks = await this.loadAll();
-    return tasks.map(t => t.id);
+    const index = await this.buildIndex();
+    return Object.values(index)
+      .map(entry => entry.id)
+      .filter((id): id is string => id !== null);
+  }
+
+  /**
+   * List all task YAML files in the tasks directory.
+   */
+  async listTaskFiles(): Promise<string[]> {
+    const files: string[] = [];
+    try {
+      await this.collectTaskFiles(await this.getTasksDir(), files);
+    } catch (error) {
+      if ((error as any)?.code === 'ENOENT') {
+        return files;
+      }
+      throw error;
+    }
+    return files.sort();
+  }
+
+  /**
+   * Build the path → ID index, re-reading only files whose mtime changed.
+   * The index is persisted in the cache directory between invocations.
+   */
+  private async buildIndex(): Promise<TaskIndex> {
+    const tasksDir = await this.getTasksDir();
+    const files = await this.listTaskFiles();
+
+    const cached = this.index ?? (await this.readIndexFile(tasksDir));
+    const index: TaskIndex = {};
+    let changed = Object.keys(cached).length !== files.length;
+
+    const entries = await mapConcurrent(files, LOAD_CONCURRENCY, async (file): Promise<[TaskIndexEntry, boolean]> => {
+      const { mtimeMs } = await stat(file);
+      const entry = cached[file];
+      if (entry && entry.mtimeMs === mtimeMs) {
+        return [entry, false];
+      }
+      return [{ id: await this.readTaskId(file), mtimeMs }, true];
+    });
+    files.forEach((file, i) => {
+      index[file] = entries[i][0];
+      changed ||= entries[i][1];
+    });
+
+    if (changed) {
+      await this.writeIndexFile(tasksDir, index);
+    }
+
+    this.index = index;
+    return index;
+  }
+
+  /**
+   * Recursively collect task YAML files under a directory. Asset directories next to a
+   * task file (named after its file or ID, or `fixtures`/`hidden_tests`) are not searched:
+   * their YAML files are task data, not tasks.
+   */
+  private async collectTaskFiles(directory: string, files: string[]): Promise<void> {
+    const entries = await readdir(directory, { withFileTypes: true });
+    const taskFiles = entries
+      .filter(entry => !entry.isDirectory() && (entry.name.endsWith('.yaml') || entry.name.endsWith('.yml')))
+      .map(entry => join(directory, entry.name));
+    files.push(...taskFiles);
+
+    const assetDirs = new Set<string>();
+    if (taskFiles.length > 0) {
+      assetDirs.add('fixtures').add('hidden_tests');
+      for (const file of taskFiles) {
+        assetDirs.add(basename(file).replace(/\.ya?ml$/, ''));
+        const id = await this.readTaskId(file);
+        if (id) {
+          assetDirs.add(id);
+        }
+      }
+    }
+    for (const entry of entries) {
+      if (entry.isDirectory() && !assetDirs.has(entry.name)) {
+        await this.collectTaskFiles(join(directory, entry.name), files);
+      }
+    }
+  }
+
+  /**
+   * Read just the ID from a task file without validating it.
+   */
+  private async readTaskId(filePath: string): Promise<string | null> {
+    try {
+      const data = yaml.load(await readFile(filePath, 'utf-8')) as { id?: unknown } | null;
+      return typeof data?.id === 'string' ? data.id : null;
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Path of the persisted index for this tasks directory.
+   */
+  private getIndexPath(tasksDir: string): string {
+    const key = createHash('sha256').update(resolve(tasksDir)).digest('hex').substring(0, 16);
+    return join(getCacheDir(), 'index', `${key}.json`);
+  }
+
+  /**
+   * Read the persisted index, returning an empty index if missing or corrupt.
+   */
+  private async readIndexFile(tasksDir: string): Promise<TaskIndex> {
+    try {
+      return JSON.parse(await readFile(this.getIndexPath(tasksDir), 'utf-8'));
+    } catch {
+      return {};
+    }
+  }
+
+  /**
+   * Persist the index (best-effort).
+   */
+  private async writeIndexFile(tasksDir: string, index: TaskIndex): Promise<void> {
+    try {
+      const indexPath = this.getIndexPath(tasksDir);
+      await mkdir(dirname(indexPath), { recursive: true });
+      // Concurrent loaders may each write it; rename keeps the file whole
+      await writeFileAtomic(indexPath, JSON.stringify(index));
+    } catch {
+      // The index is only a cache; ignore write failures
+    }
   }
 
   /**
    * Filter tasks by category.
    */
   async filterByCategory(category: string): Promise<Task[]> {
-    const tasks = await this.loadAll();
-    return tasks.filter(t => t.category === category);
+    return await this.loadAll({ category });
   }
 
   /**
    * Filter tasks by difficulty.
    */
   async filterByDifficulty(difficulty: string): Promise<Task[]> {
-    const tasks = await this.loadAll();
-    return tasks.filter(t => t.difficulty === difficulty);
+    return await this.loadAll({ difficulty });
   }
 
   /**
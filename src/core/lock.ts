@@ -0,0 +1,244 @@
+/**
+ * Task lockfile pinning source commits and task checksums.
+ */
+
+import { readdir, readFile } from 'fs/promises';
+import { dirname, join, relative, resolve, sep } from 'path';
+import { createHash } from 'crypto';
+import { Task, getSources } from './task.js';
+import { TaskLoader } from './loader.js';
+import { getSourceKind } from './workspace.js';
+import { git } from './git.js';
+import { LockError } from '../utils/errors.js';
+import { writeFileAtomic } from '../utils/files.js';
+
+/**
+ * Lockfile name, stored at the root of the tasks directory.
+ */
+export const LOCKFILE_NAME = 'agent-bench.lock';
+
+/**
+ * Current lockfile format version.
+ */
+export const LOCKFILE_VERSION = 1;
+
+/**
+ * Pinned source of a locked task.
+ */
+export interface LockedSource {
+  repository: string;
+  ref: string;
+  commit: string;
+  path: string | null;
+}
+
+/**
+ * Lock entry for a single task.
+ */
+export interface LockedTask {
+  file: string;
+  /** Checksum of the task's inputs (see hashTask) */
+  sha256: string;
+  sources: LockedSource[];
+}
+
+/**
+ * Lockfile contents.
+ */
+export interface Lockfile {
+  version: number;
+  generated: string;
+  tasks: Record<string, LockedTask>;
+}
+
+/**
+ * Resolve every task's source refs to commit SHAs and hash its inputs.
+ */
+export async function createLockfile(loader: TaskLoader): Promise<Lockfile> {
+  const tasksDir = await loader.getTasksDir();
+  const tasks = await loader.loadAll();
+  const lockfile: Lockfile = { version: LOCKFILE_VERSION, generated: new Date().toISOString(), tasks: {} };
+
+  for (const task of tasks) {
+    if (!task.file_path) continue;
+
+    const sources: LockedSource[] = [];
+    for (const source of getSources(task)) {
+      // Only git sources have refs to pin
+      const commit = (await getSourceKind(task, source)) !== 'git'
+        ? source.commit
+        : await resolveRef(source.repository, source.commit);
+      sources.push({ repository: source.repository, ref: source.commit, commit, path: source.path });
+    }
+
+    lockfile.tasks[task.id] = {
+      file: relative(tasksDir, task.file_path).split(sep).join('/'),
+      sha256: await hashTask(task),
+      sources,
+    };
+  }
+
+  return lockfile;
+}
+
+/**
+ * Write a lockfile to the tasks directory.
+ * @returns The lockfile path
+ */
+export async function writeLockfile(tasksDir: string, lockfile: Lockfile): Promise<string> {
+  const path = join(tasksDir, LOCKFILE_NAME);
+  await writeFileAtomic(path, JSON.stringify(lockfile, null, 2) + '\n');
+  return path;
+}
+
+/**
+ * Read the lockfile from the tasks directory.
+ * @throws LockError if the lockfile is missing or invalid
+ */
+export async function readLockfile(tasksDir: string): Promise<Lockfile> {
+  const path = join(tasksDir, LOCKFILE_NAME);
+  try {
+    const lockfile = JSON.parse(await readFile(path, 'utf-8')) as Lockfile;
+    if (lockfile.version > LOCKFILE_VERSION) {
+      throw new LockError(`Lockfile version ${lockfile.version} is newer than supported version ${LOCKFILE_VERSION}`);
+    }
+    return lockfile;
+  } catch (error) {
+    if (error instanceof LockError) throw error;
+    throw new LockError(`Failed to read ${path}: ${error}`);
+  }
+}
+
+/**
+ * Enforce the lockfile on a set of tasks: task files must match their recorded checksums,
+ * and sources are pinned to the locked commits.
+ * @throws LockError listing every task that is missing from or out of date with the lockfile
+ */
+export async function applyLockfile(lockfile: Lockfile, tasks: Task[]): Promise<Task[]> {
+  const problems: string[] = [];
+  const locked: Task[] = [];
+
+  for (const task of tasks) {
+    const entry = lockfile.tasks[task.id];
+    if (!entry) {
+      problems.push(`${task.id}: not in lockfile`);
+      continue;
+    }
+    if (task.file_path && (await hashTask(task)) !== entry.sha256) {
+      problems.push(`${task.id}: task file, prompt file, fixtures, or hidden tests changed since lock`);
+      continue;
+    }
+    locked.push(pinSources(task, entry));
+  }
+
+  if (problems.length > 0) {
+    throw new LockError(`Tasks do not match ${LOCKFILE_NAME} (run 'agent-bench lock'):\n  ${problems.join('\n  ')}`);
+  }
+
+  return locked;
+}
+
+/**
+ * Replace a task's source commits with the locked commits.
+ */
+function pinSources(task: Task, entry: LockedTask): Task {
+  const commitFor = (repository: string, path: string | null): string | undefined =>
+    entry.sources.find(s => s.repository === repository && s.path === path)?.commit;
+
+  if (Array.isArray(task.source)) {
+    return {
+      ...task,
+      source: task.source.map(source => ({
+        ...source,
+        commit: commitFor(source.repository, source.path) ?? source.commit,
+      })),
+    };
+  }
+
+  return {
+    ...task,
+    source: {
+      ...task.source,
+      commit: commitFor(task.source.repository, null) ?? task.source.commit,
+    },
+  };
+}
+
+/**
+ * Resolve a branch, tag, or HEAD to a commit SHA with `git ls-remote`.
+ * Full SHAs are returned as-is; refs that cannot be resolved remotely are an error.
+ */
+async function resolveRef(repository: string, ref: string): Promise<string> {
+  if (/^[0-9a-f]{40}$/i.test(ref)) {
+    return ref;
+  }
+
+  let output: string;
+  try {
+    output = await git().listRemote([repository, ref]);
+  } catch (error) {
+    throw new LockError(`Failed to resolve '${ref}' in ${repository}: ${error}`);
+  }
+
+  const refs = new Map<string, string>();
+  for (const line of output.trim().split('\n')) {
+    const [sha, name] = line.split('\t');
+    if (sha && name) refs.set(name, sha);
+  }
+
+  // Prefer peeled tags (the commit) over annotated tag objects
+  const candidates = [`refs/tags/${ref}^{}`, `refs/heads/${ref}`, `refs/tags/${ref}`, ref];
+  for (const candidate of candidates) {
+    const sha = refs.get(candidate);
+    if (sha) return sha;
+  }
+
+  throw new LockError(`Ref '${ref}' not found in ${repository}`);
+}
+
+/**
+ * SHA-256 of everything that defines a task: its YAML file, the prompt files it and its
+ * steps reference, and its `fixtures/` and `hidden_tests/` asset directories. Each input
+ * is hashed with its path, so renaming or moving files changes the hash too.
+ */
+export async function hashTask(task: Task): Promise<string> {
+  const hash = createHash('sha256');
+  if (!task.file_path) {
+    return hash.update(JSON.stringify(task)).digest('hex');
+  }
+  const taskDir = dirname(task.file_path);
+  const add = async (path: string) => {
+    hash.update(`\0${relative(taskDir, path).split(sep).join('/')}\0`).update(await readFile(path));
+  };
+
+  await add(task.file_path);
+  for (const promptFile of [task.prompt_file, ...(task.steps || []).map(step => step.prompt_file)]) {
+    if (promptFile) {
+      await add(resolve(taskDir, promptFile));
+    }
+  }
+  for (const kind of ['fixtures', 'hidden_tests']) {
+    for (const file of await listFiles(join(taskDir, task.id, kind))) {
+      await add(file);
+    }
+  }
+  return hash.digest('hex');
+}
+
+/**
+ * Every file under a directory, sorted (empty if it does not exist).
+ */
+async function listFiles(directory: string): Promise<string[]> {
+  let entries;
+  try {
+    entries = await readdir(directory, { withFileTypes: true });
+  } catch {
+    return [];
+  }
+  const files: string[] = [];
+  for (const entry of entries.sort((a, b) => (a.name < b.name ? -1 : a.name > b.name ? 1 : 0))) {
+    const path = join(directory, entry.name);
+    files.push(...(entry.isDirectory() ? await listFiles(path) : [path]));
+  }
+  return files;
+}
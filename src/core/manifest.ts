@@ -0,0 +1,82 @@
+/**
+ * Per-run manifest: what a run was about to execute and what the operator agreed to.
+ * Written to `<results-dir>/<run-id>/manifest.json` before the first task starts.
+ */
+
+import { mkdir, readFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import type { Task } from './task.js';
+import { describeDangerousPermissions } from '../agents/permissions.js';
+import { writeFileAtomic } from '../utils/files.js';
+
+/**
+ * A task whose permissions give the agent the run of the host.
+ */
+export interface DangerousTask {
+  task_id: string;
+  reasons: string[];
+}
+
+/**
+ * How the operator accepted dangerous permissions.
+ * - prompt: answered yes at the interactive confirmation
+ * - flag: passed `--yes`
+ * - api: set `"yes": true` on a serve-api run request
+ * - coordinator: acknowledged on the `coordinate` process that dispatched the task to this worker
+ */
+export interface PermissionAcknowledgment {
+  method: 'prompt' | 'flag' | 'api' | 'coordinator';
+  acknowledged_at: string;
+  tasks: DangerousTask[];
+}
+
+/**
+ * Contents of a run manifest.
+ */
+export interface RunManifest {
+  run_id: string;
+  created_at: string;
+  agent: string;
+  tasks: string[];
+  dangerous_permissions: DangerousTask[];
+  acknowledgment: PermissionAcknowledgment | null;
+}
+
+/**
+ * Manifest path for a run.
+ */
+export function getRunManifestPath(resultsDir: string, runId: string): string {
+  return join(resultsDir, runId, 'manifest.json');
+}
+
+/**
+ * Read a run's manifest, or null if it has none.
+ */
+export async function readRunManifest(resultsDir: string, runId: string): Promise<RunManifest | null> {
+  try {
+    return JSON.parse(await readFile(getRunManifestPath(resultsDir, runId), 'utf-8')) as RunManifest;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Tasks whose permissions need explicit acknowledgment before running.
+ */
+export function findDangerousTasks(tasks: Task[]): DangerousTask[] {
+  return tasks
+    .filter(task => !task.disabled)
+    .map(task => ({ task_id: task.id, reasons: describeDangerousPermissions(task.permissions) }))
+    .filter(entry => entry.reasons.length > 0);
+}
+
+/**
+ * Write a run manifest.
+ * @returns Path of the manifest
+ */
+export async function writeRunManifest(resultsDir: string, manifest: RunManifest): Promise<string> {
+  const path = getRunManifestPath(resultsDir, manifest.run_id);
+  await mkdir(dirname(path), { recursive: true });
+  await writeFileAtomic(path, JSON.stringify(manifest, null, 2));
+  return path;
+}
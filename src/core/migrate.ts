@@ -0,0 +1,101 @@
+/**
+ * Task schema versions: compatibility checks at load time and migration of task files.
+ */
+
+import { readFile, writeFile } from 'fs/promises';
+import yaml from 'js-yaml';
+import { TASK_SCHEMA_VERSION } from './task.js';
+import { TaskSchemaError } from '../utils/errors.js';
+
+/**
+ * Raw task data as parsed from YAML.
+ */
+type RawTask = Record<string, unknown>;
+
+/**
+ * `MIGRATIONS[n]` upgrades a schema `n` task to schema `n + 1`.
+ * Bumping TASK_SCHEMA_VERSION requires adding the migration from the previous version.
+ */
+const MIGRATIONS: Record<number, (task: RawTask) => RawTask> = {};
+
+/**
+ * Outcome of migrating one task file.
+ */
+export interface TaskMigration {
+  path: string;
+  from: number;
+  to: number;
+  changed: boolean;
+}
+
+/**
+ * Read a task's schema version (tasks without `schema` are version 1).
+ * @throws TaskSchemaError If the version is invalid or newer than this harness supports
+ */
+export function getSchemaVersion(data: RawTask, filePath: string): number {
+  if (data.schema === undefined) {
+    return 1;
+  }
+
+  const version = data.schema;
+  if (typeof version !== 'number' || !Number.isInteger(version) || version < 1) {
+    throw new TaskSchemaError(`${filePath}: schema must be a positive integer, got ${JSON.stringify(version)}`);
+  }
+  if (version > TASK_SCHEMA_VERSION) {
+    throw new TaskSchemaError(
+      `${filePath} uses task schema ${version}, but this agent-bench supports schema ${TASK_SCHEMA_VERSION} at most; upgrade agent-bench to run it`
+    );
+  }
+  return version;
+}
+
+/**
+ * Bring raw task data up to the current schema in memory.
+ * @throws TaskSchemaError If the task's schema is newer than supported or cannot be migrated
+ */
+export function upgradeTask(data: RawTask, filePath: string): RawTask {
+  let version = getSchemaVersion(data, filePath);
+  let task = data;
+  while (version < TASK_SCHEMA_VERSION) {
+    const migrate = MIGRATIONS[version];
+    if (!migrate) {
+      throw new TaskSchemaError(`${filePath}: no migration from task schema ${version} to ${version + 1}`);
+    }
+    task = migrate(task);
+    version++;
+  }
+  return { ...task, schema: TASK_SCHEMA_VERSION };
+}
+
+/**
+ * Rewrite a task file at the current schema version.
+ * Files that only lack the `schema` field get it added in place, keeping comments and layout;
+ * files migrated across versions are re-serialized.
+ * @param write Write the migrated file (false reports what would change)
+ */
+export async function migrateTaskFile(path: string, write: boolean = true): Promise<TaskMigration> {
+  const content = await readFile(path, 'utf-8');
+  const data = yaml.load(content);
+  if (!data || typeof data !== 'object') {
+    throw new TaskSchemaError(`${path}: not a task definition`);
+  }
+
+  const raw = data as RawTask;
+  const from = getSchemaVersion(raw, path);
+  if (from === TASK_SCHEMA_VERSION && raw.schema !== undefined) {
+    return { path, from, to: from, changed: false };
+  }
+
+  let output: string;
+  if (from === TASK_SCHEMA_VERSION) {
+    output = `schema: ${TASK_SCHEMA_VERSION}\n${content}`;
+  } else {
+    // `schema` first, like hand-written tasks
+    output = yaml.dump({ schema: TASK_SCHEMA_VERSION, ...upgradeTask(raw, path) }, { lineWidth: 120, noRefs: true });
+  }
+
+  if (write) {
+    await writeFile(path, output, 'utf-8');
+  }
+  return { path, from, to: TASK_SCHEMA_VERSION, changed: true };
+}
@@ -0,0 +1,219 @@
+/**
+ * Task pack archives for distributing and pinning benchmark versions.
+ */
+
+import { mkdir, mkdtemp, readdir, readFile, rename, rm, writeFile } from 'fs/promises';
+import { dirname, join, relative, resolve, sep } from 'path';
+import { tmpdir } from 'os';
+import { createHash } from 'crypto';
+import { TaskLoader } from './loader.js';
+import { PackError } from '../utils/errors.js';
+import { exec } from '../utils/exec.js';
+
+/**
+ * Name of the manifest file stored at the root of a pack.
+ */
+export const PACK_MANIFEST = 'agent-bench-pack.json';
+
+/**
+ * Current pack format version.
+ */
+export const PACK_FORMAT_VERSION = 1;
+
+/**
+ * File entry in a pack manifest.
+ */
+export interface PackFileEntry {
+  path: string;
+  sha256: string;
+  size: number;
+}
+
+/**
+ * Pack manifest describing the archive contents.
+ */
+export interface PackManifest {
+  format_version: number;
+  name: string;
+  version: string;
+  created: string;
+  tasks: string[];
+  files: PackFileEntry[];
+}
+
+/**
+ * Create a pack archive (.tar.gz) from a tasks directory.
+ * All files under the directory (task YAMLs, prompts, fixtures, hidden tests) are included,
+ * except those in dot directories (`.git`, editor state).
+ * @returns The manifest written into the archive
+ */
+export async function createPack(
+  tasksDir: string,
+  outputPath: string,
+  name: string,
+  version: string
+): Promise<PackManifest> {
+  const files = await collectFiles(tasksDir);
+  const entries: PackFileEntry[] = [];
+  for (const file of files) {
+    const content = await readFile(join(tasksDir, file));
+    entries.push({ path: file, sha256: sha256(content), size: content.length });
+  }
+
+  const manifest: PackManifest = {
+    format_version: PACK_FORMAT_VERSION,
+    name,
+    version,
+    created: new Date().toISOString(),
+    tasks: await new TaskLoader(tasksDir).listIds(),
+    files: entries,
+  };
+
+  const stagingDir = await mkdtemp(join(tmpdir(), 'agent-bench-pack-'));
+  try {
+    await writeFile(join(stagingDir, PACK_MANIFEST), JSON.stringify(manifest, null, 2), 'utf-8');
+    // `--` keeps file names starting with `-` from being read as options
+    await runTar(['-czf', outputPath, '-C', stagingDir, PACK_MANIFEST, '-C', resolve(tasksDir), '--', ...files]);
+  } finally {
+    await rm(stagingDir, { recursive: true, force: true });
+  }
+
+  return manifest;
+}
+
+/**
+ * Extract a pack archive, verify it against the manifest, and move its files into `destDir`.
+ * The archive is unpacked into a staging directory first, so nothing reaches `destDir`
+ * unless every file is listed in the manifest with a matching checksum.
+ * @returns The verified manifest
+ */
+export async function extractPack(archivePath: string, destDir: string): Promise<PackManifest> {
+  const target = resolve(destDir);
+  // Next to the destination, so files can be renamed into place
+  await mkdir(dirname(target), { recursive: true });
+  const stagingDir = await mkdtemp(join(dirname(target), '.agent-bench-unpack-'));
+  try {
+    await runTar(['-xzf', archivePath, '-C', stagingDir]);
+    const manifest = await verifyPack(stagingDir);
+    for (const path of [PACK_MANIFEST, ...manifest.files.map(entry => entry.path)]) {
+      await mkdir(dirname(join(target, path)), { recursive: true });
+      await rename(join(stagingDir, path), join(target, path));
+    }
+    return manifest;
+  } finally {
+    await rm(stagingDir, { recursive: true, force: true });
+  }
+}
+
+/**
+ * Check an extracted pack: every file must be listed in the manifest with its checksum.
+ * @throws PackError If the manifest is missing or invalid, or a file is missing, altered, or unlisted
+ */
+async function verifyPack(directory: string): Promise<PackManifest> {
+  let manifest: PackManifest;
+  try {
+    manifest = JSON.parse(await readFile(join(directory, PACK_MANIFEST), 'utf-8'));
+  } catch (error) {
+    throw new PackError(`Archive has no valid ${PACK_MANIFEST}: ${error}`);
+  }
+
+  if (manifest.format_version > PACK_FORMAT_VERSION) {
+    throw new PackError(
+      `Pack format version ${manifest.format_version} is newer than supported version ${PACK_FORMAT_VERSION}`
+    );
+  }
+
+  const mismatches: string[] = [];
+  const listed = new Set(manifest.files.map(entry => entry.path));
+  const extracted = await listExtracted(directory);
+  for (const [path, regular] of extracted) {
+    if (path !== PACK_MANIFEST && !listed.has(path)) {
+      mismatches.push(`${path}: not in the manifest`);
+    } else if (!regular) {
+      mismatches.push(`${path}: not a regular file`);
+    }
+  }
+  for (const entry of manifest.files) {
+    if (!extracted.has(entry.path)) {
+      mismatches.push(`${entry.path}: missing`);
+      continue;
+    }
+    try {
+      const content = await readFile(join(directory, entry.path));
+      if (sha256(content) !== entry.sha256) {
+        mismatches.push(`${entry.path}: checksum mismatch`);
+      }
+    } catch {
+      mismatches.push(`${entry.path}: missing`);
+    }
+  }
+
+  if (mismatches.length > 0) {
+    throw new PackError(`Pack verification failed:\n  ${mismatches.join('\n  ')}`);
+  }
+
+  return manifest;
+}
+
+/**
+ * Recursively list files relative to a root directory, using forward slashes.
+ * Dot directories (`.git`, `.vscode`, ...) are skipped.
+ */
+async function collectFiles(root: string, directory: string = root): Promise<string[]> {
+  const files: string[] = [];
+  const entries = await readdir(directory, { withFileTypes: true });
+
+  for (const entry of entries) {
+    const fullPath = join(directory, entry.name);
+    if (entry.isDirectory()) {
+      if (!entry.name.startsWith('.')) {
+        files.push(...(await collectFiles(root, fullPath)));
+      }
+    } else if (entry.isFile() && entry.name !== PACK_MANIFEST) {
+      files.push(relative(root, fullPath).split(sep).join('/'));
+    }
+  }
+
+  return files.sort();
+}
+
+/**
+ * Every entry of an extracted archive that is not a directory, relative to its root with
+ * forward slashes, and whether it is a regular file (symlinks and special files are not).
+ */
+async function listExtracted(root: string, directory: string = root): Promise<Map<string, boolean>> {
+  const paths = new Map<string, boolean>();
+  for (const entry of await readdir(directory, { withFileTypes: true })) {
+    const fullPath = join(directory, entry.name);
+    if (entry.isDirectory()) {
+      for (const [path, regular] of await listExtracted(root, fullPath)) {
+        paths.set(path, regular);
+      }
+    } else {
+      paths.set(relative(root, fullPath).split(sep).join('/'), entry.isFile());
+    }
+  }
+  return paths;
+}
+
+/**
+ * Compute the SHA-256 hex digest of a buffer.
+ */
+function sha256(content: Buffer): string {
+  return createHash('sha256').update(content).digest('hex');
+}
+
+/**
+ * Run the system tar binary.
+ */
+async function runTar(args: string[]): Promise<void> {
+  let result;
+  try {
+    result = await exec({ program: 'tar', args });
+  } catch (error) {
+    throw new PackError(`Failed to run tar: ${error instanceof Error ? error.message : error}`);
+  }
+  if (result.exitCode !== 0) {
+    throw new PackError(`tar exited with code ${result.exitCode}: ${result.stderr.trim()}`);
+  }
+}
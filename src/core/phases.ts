@@ -0,0 +1,98 @@
+/**
+ * Phase timing: where a task execution's time goes.
+ *
+ * The runner marks each phase as it enters it, at the same points where it records run
+ * state, and a phase lasts until the next mark. A phase entered more than once has its
+ * times summed; multi-step tasks alternate between the agent and checkpoint verification.
+ * An agent iteration is timed from the agent's (re)start or the end of the previous
+ * iteration. Comparing the phases separates harness overhead (toolchain, workspace,
+ * verification) from the agent's own time.
+ */
+
+/**
+ * Phases of a task execution.
+ */
+export type Phase = 'toolchain' | 'workspace' | 'agent' | 'verification';
+
+/**
+ * Seconds spent in each phase (null: the phase did not run).
+ */
+export interface PhaseTimings {
+  /** Installing the task's toolchain (`mise install`) */
+  toolchain_secs: number | null;
+  /** Cloning sources and copying fixtures */
+  workspace_secs: number | null;
+  /** Running the agent */
+  agent_secs: number | null;
+  /** Each agent iteration (model turn), in order */
+  iteration_secs: number[];
+  /** Applying hidden tests, running verification and plugins, and cleaning up */
+  verification_secs: number | null;
+}
+
+/**
+ * Times the phases of one task execution.
+ */
+export class PhaseClock {
+  private totals = new Map<Phase, number>();
+  private current: { phase: Phase; since: number } | null = null;
+  private iterations: number[] = [];
+  private lastIteration: number | null = null;
+
+  /**
+   * End the current phase and start `phase`.
+   */
+  enter(phase: Phase): void {
+    this.stop();
+    const now = Date.now();
+    this.current = { phase, since: now };
+    this.lastIteration = phase === 'agent' ? now : null;
+  }
+
+  /**
+   * Record the end of an agent iteration.
+   */
+  iteration(): void {
+    if (this.lastIteration === null) {
+      return;
+    }
+    const now = Date.now();
+    this.iterations.push((now - this.lastIteration) / 1000);
+    this.lastIteration = now;
+  }
+
+  /**
+   * End the current phase.
+   */
+  stop(): void {
+    if (!this.current) {
+      return;
+    }
+    const { phase, since } = this.current;
+    this.totals.set(phase, (this.totals.get(phase) ?? 0) + (Date.now() - since) / 1000);
+    this.current = null;
+    this.lastIteration = null;
+  }
+
+  /**
+   * Stop the clock and report the phases so far.
+   */
+  timings(): PhaseTimings {
+    this.stop();
+    const secs = (phase: Phase) => this.totals.get(phase) ?? null;
+    return {
+      toolchain_secs: secs('toolchain'),
+      workspace_secs: secs('workspace'),
+      agent_secs: secs('agent'),
+      iteration_secs: [...this.iterations],
+      verification_secs: secs('verification'),
+    };
+  }
+}
+
+/**
+ * Harness time of a task: everything but the agent.
+ */
+export function getOverheadSecs(timings: PhaseTimings): number {
+  return (timings.toolchain_secs ?? 0) + (timings.workspace_secs ?? 0) + (timings.verification_secs ?? 0);
+}
@@ -0,0 +1,134 @@
+/**
+ * Execution plans for `run --dry-run`: what a run would execute, in order, and how long it should take.
+ */
+
+import type { Task } from './task.js';
+import type { BenchmarkResult } from '../evaluator/results.js';
+import { getOutcome } from '../evaluator/results.js';
+
+/**
+ * Past runs considered when estimating a task's duration.
+ */
+const HISTORY_WINDOW = 5;
+
+/**
+ * Where a duration estimate comes from.
+ */
+export type EstimateSource = 'history' | 'history-any-model' | 'expected' | null;
+
+/**
+ * A task as it would be executed.
+ */
+export interface PlannedTask {
+  order: number;
+  task_id: string;
+  title: string;
+  category: string;
+  difficulty: string;
+  skip_reason: string | null;
+  estimated_duration_secs: number | null;
+  estimate_source: EstimateSource;
+}
+
+/**
+ * The tasks one agent would run.
+ */
+export interface AgentPlan {
+  agent: string;
+  model: string;
+  tasks: PlannedTask[];
+  estimated_duration_secs: number;
+  unestimated: number;
+}
+
+/**
+ * A complete run plan. Agents run one after another, each executing its tasks sequentially.
+ */
+export interface RunPlan {
+  selection: string;
+  filter: string | null;
+  concurrency: number;
+  fail_fast: boolean;
+  locked: boolean;
+  agents: AgentPlan[];
+  estimated_duration_secs: number;
+}
+
+/**
+ * Median duration of the most recent executed runs.
+ */
+function recentMedian(results: BenchmarkResult[]): number | null {
+  const durations = results
+    .filter(result => getOutcome(result) !== 'skipped')
+    .sort((a, b) => a.timestamp.localeCompare(b.timestamp))
+    .slice(-HISTORY_WINDOW)
+    .map(result => result.duration_secs)
+    .sort((a, b) => a - b);
+  if (durations.length === 0) {
+    return null;
+  }
+  const middle = Math.floor(durations.length / 2);
+  return durations.length % 2 === 1 ? durations[middle] : (durations[middle - 1] + durations[middle]) / 2;
+}
+
+/**
+ * Estimate a task's duration: recent runs with the same agent and model, then the same agent
+ * with any model, then the task's `expected_duration_secs`.
+ * @param model Model the agent uses (null if unknown: any model's runs count)
+ */
+export function estimateDuration(
+  task: Task,
+  agent: string,
+  model: string | null,
+  history: BenchmarkResult[]
+): { secs: number | null; source: EstimateSource } {
+  const sameAgent = history.filter(result => result.task_id === task.id && result.agent === agent);
+
+  const sameModel = model === null ? null : recentMedian(sameAgent.filter(result => result.model_name === model));
+  if (sameModel !== null) {
+    return { secs: sameModel, source: 'history' };
+  }
+  const anyModel = recentMedian(sameAgent);
+  if (anyModel !== null) {
+    return { secs: anyModel, source: 'history-any-model' };
+  }
+  if (task.expected_duration_secs) {
+    return { secs: task.expected_duration_secs, source: 'expected' };
+  }
+  return { secs: null, source: null };
+}
+
+/**
+ * Plan one agent's tasks.
+ * @param skipReasons Why each task would be skipped (by task ID), if it would
+ */
+export function planAgent(
+  tasks: Task[],
+  agent: string,
+  model: string,
+  history: BenchmarkResult[],
+  skipReasons: Map<string, string>
+): AgentPlan {
+  const planned = tasks.map((task, index): PlannedTask => {
+    const skipReason = skipReasons.get(task.id) ?? null;
+    const estimate = skipReason ? { secs: 0, source: null } : estimateDuration(task, agent, model, history);
+    return {
+      order: index + 1,
+      task_id: task.id,
+      title: task.title,
+      category: task.category,
+      difficulty: task.difficulty,
+      skip_reason: skipReason,
+      estimated_duration_secs: estimate.secs,
+      estimate_source: estimate.source,
+    };
+  });
+
+  return {
+    agent,
+    model,
+    tasks: planned,
+    estimated_duration_secs: planned.reduce((sum, task) => sum + (task.estimated_duration_secs ?? 0), 0),
+    unestimated: planned.filter(task => task.estimated_duration_secs === null).length,
+  };
+}
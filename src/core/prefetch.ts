@@ -0,0 +1,76 @@
+/**
+ * Prefetch phase: bring every repository a set of tasks needs into the mirror cache, in
+ * parallel, before a suite's timed portion starts. Workspaces then clone from local
+ * mirrors, so task timings do not depend on network speed.
+ */
+
+import type { Task } from './task.js';
+import { getSources } from './task.js';
+import { getSourceKind } from './workspace.js';
+import { ensureMirror } from './repo-cache.js';
+import { mapConcurrent } from '../utils/concurrency.js';
+import { logger } from '../utils/logger.js';
+
+/**
+ * Repositories fetched at once.
+ */
+export const PREFETCH_CONCURRENCY = 4;
+
+/**
+ * Outcome of a prefetch.
+ */
+export interface PrefetchReport {
+  /** Repository URLs fetched (or already current) */
+  fetched: string[];
+  /** Repositories that could not be fetched, with the error */
+  failed: { url: string; error: string }[];
+  durationSecs: number;
+}
+
+/**
+ * The git repositories tasks check out, with every commit or ref each one needs.
+ * Disabled tasks are left out.
+ */
+export async function collectRepositories(tasks: Task[]): Promise<Map<string, Set<string>>> {
+  const commits = new Map<string, Set<string>>();
+  for (const task of tasks.filter(task => !task.disabled)) {
+    for (const source of getSources(task)) {
+      if ((await getSourceKind(task, source)) !== 'git') continue;
+      if (!commits.has(source.repository)) commits.set(source.repository, new Set());
+      commits.get(source.repository)!.add(source.commit);
+    }
+  }
+  return commits;
+}
+
+/**
+ * Mirror every repository the tasks need, `concurrency` repositories at a time.
+ * Failures are reported, not thrown: the affected tasks retry when their workspace is prepared.
+ */
+export async function prefetchRepositories(
+  tasks: Task[],
+  concurrency: number = PREFETCH_CONCURRENCY
+): Promise<PrefetchReport> {
+  const startTime = Date.now();
+  const commits = await collectRepositories(tasks);
+  const report: PrefetchReport = { fetched: [], failed: [], durationSecs: 0 };
+  if (commits.size === 0) {
+    return report;
+  }
+
+  logger.info(`Prefetching ${commits.size} repositories for ${tasks.length} tasks...`);
+  await mapConcurrent([...commits], concurrency, async ([url, refs]) => {
+    try {
+      for (const ref of refs) {
+        await ensureMirror(url, ref);
+      }
+      report.fetched.push(url);
+      logger.debug(`Prefetched ${url}`);
+    } catch (error) {
+      report.failed.push({ url, error: error instanceof Error ? error.message : String(error) });
+    }
+  });
+
+  report.durationSecs = (Date.now() - startTime) / 1000;
+  return report;
+}
@@ -0,0 +1,93 @@
+/**
+ * Runtime prerequisite probing for tasks.
+ */
+
+import { Task } from './task.js';
+import { exec, findExecutable } from '../utils/exec.js';
+
+/**
+ * Result of probing a binary on the current machine.
+ */
+export interface ProbeResult {
+  path: string | null;
+  version: string | null;
+}
+
+/**
+ * Probe results cached per process, keyed by version command.
+ */
+const probeCache = new Map<string, Promise<ProbeResult>>();
+
+/**
+ * Check a task's `requires` entries against the environment.
+ * @returns A description of unmet prerequisites, or null if all are satisfied
+ */
+export async function checkRequirements(task: Task): Promise<string | null> {
+  const unmet: string[] = [];
+
+  for (const requirement of task.requires || []) {
+    const { binary, min_version, version_command } =
+      typeof requirement === 'string' ? { binary: requirement, min_version: undefined, version_command: undefined } : requirement;
+
+    const probe = await probeBinary(binary, version_command);
+    if (!probe.path) {
+      unmet.push(`${binary} not found`);
+    } else if (min_version) {
+      if (!probe.version) {
+        unmet.push(`${binary} version unknown (need >= ${min_version})`);
+      } else if (compareVersions(probe.version, min_version) < 0) {
+        unmet.push(`${binary} ${probe.version} < ${min_version}`);
+      }
+    }
+  }
+
+  return unmet.length > 0 ? `Unmet prerequisites: ${unmet.join(', ')}` : null;
+}
+
+/**
+ * Locate a binary on PATH and detect its version.
+ * @param binary Binary name
+ * @param versionCommand Command printing the version (default: `<binary> --version`)
+ */
+export function probeBinary(binary: string, versionCommand?: string): Promise<ProbeResult> {
+  const command = versionCommand || `${binary} --version`;
+  let probe = probeCache.get(command);
+  if (!probe) {
+    probe = (async () => {
+      const path = findExecutable(binary);
+      const version = path ? await detectVersion(command) : null;
+      return { path, version };
+    })();
+    probeCache.set(command, probe);
+  }
+  return probe;
+}
+
+/**
+ * Compare two dotted version strings numerically.
+ * @returns Negative if a < b, zero if equal, positive if a > b
+ */
+export function compareVersions(a: string, b: string): number {
+  const left = a.split('.').map(part => parseInt(part, 10) || 0);
+  const right = b.split('.').map(part => parseInt(part, 10) || 0);
+  for (let i = 0; i < Math.max(left.length, right.length); i++) {
+    const diff = (left[i] || 0) - (right[i] || 0);
+    if (diff !== 0) {
+      return diff;
+    }
+  }
+  return 0;
+}
+
+/**
+ * Run a version command and extract the first dotted version number from its output.
+ */
+async function detectVersion(command: string): Promise<string | null> {
+  try {
+    const result = await exec(command, { timeoutSecs: 10 });
+    const match = (result.stdout + result.stderr).match(/(\d+(?:\.\d+)+|\d+)/);
+    return match ? match[1] : null;
+  } catch {
+    return null;
+  }
+}
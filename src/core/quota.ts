@@ -0,0 +1,87 @@
+/**
+ * Per-workspace disk quota (`workspace_quota`): the workspace size is sampled while the
+ * agent runs and measured once more after it stops. An agent that fills the disk is
+ * cancelled as soon as a sample exceeds the quota, and its task fails with `disk_quota`.
+ */
+
+import { diskUsage, formatBytes } from '../utils/disk.js';
+import { DiskQuotaExceededError, type ErrorContext } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+
+/**
+ * Milliseconds between workspace size samples.
+ */
+export const QUOTA_POLL_INTERVAL_MS = 5000;
+
+/**
+ * Watches a workspace's size against a quota.
+ */
+export class DiskQuotaMonitor {
+  private workspace: string;
+  private limitBytes: number;
+  private context: ErrorContext;
+  private timer: ReturnType<typeof setTimeout> | null = null;
+  private stopped = false;
+  /** Set once a measurement exceeds the quota */
+  exceeded: DiskQuotaExceededError | null = null;
+
+  /**
+   * @param context Error context for the quota failure (e.g. `taskId`)
+   */
+  constructor(workspace: string, limitBytes: number, context: ErrorContext = {}) {
+    this.workspace = workspace;
+    this.limitBytes = limitBytes;
+    this.context = context;
+  }
+
+  /**
+   * Sample the workspace size until stopped, calling `onExceeded` once when it goes over the quota.
+   */
+  start(onExceeded: (error: DiskQuotaExceededError) => void | Promise<void>): void {
+    logger.debug(`Workspace quota: ${formatBytes(this.limitBytes)}`);
+    const poll = async () => {
+      const error = await this.check();
+      if (this.stopped) {
+        return;
+      }
+      if (error) {
+        await onExceeded(error);
+        return;
+      }
+      this.schedule(poll);
+    };
+    this.schedule(poll);
+  }
+
+  private schedule(poll: () => Promise<void>): void {
+    this.timer = setTimeout(() => {
+      poll().catch(error => logger.warn(`Workspace quota check failed: ${error}`));
+    }, QUOTA_POLL_INTERVAL_MS);
+  }
+
+  /**
+   * Stop sampling.
+   */
+  stop(): void {
+    this.stopped = true;
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+
+  /**
+   * Measure the workspace now.
+   * @returns The quota error if the workspace is (or was) over the quota, else null
+   */
+  async check(): Promise<DiskQuotaExceededError | null> {
+    if (this.exceeded) {
+      return this.exceeded;
+    }
+    const used = await diskUsage(this.workspace);
+    if (used > this.limitBytes) {
+      this.exceeded = new DiskQuotaExceededError(used, this.limitBytes, { ...this.context, phase: 'agent' });
+    }
+    return this.exceeded;
+  }
+}
@@ -0,0 +1,107 @@
+/**
+ * Remote task repositories referenced as `git+<url>#<ref>`.
+ */
+
+import { mkdir, stat } from 'fs/promises';
+import { dirname, join } from 'path';
+import { createHash } from 'crypto';
+import { getCacheDir } from './config.js';
+import { git } from './git.js';
+import { GitError } from '../utils/errors.js';
+
+/**
+ * Prefix marking a tasks directory as a git repository.
+ */
+const GIT_SPEC_PREFIX = 'git+';
+
+/**
+ * Parsed remote task repository specification.
+ */
+export interface GitTasksSpec {
+  url: string;
+  ref: string | null;
+}
+
+/**
+ * Check whether a tasks directory refers to a git repository.
+ */
+export function isGitTasksSpec(spec: string): boolean {
+  return spec.startsWith(GIT_SPEC_PREFIX);
+}
+
+/**
+ * Parse `git+https://host/org/repo.git#ref` into URL and optional ref.
+ */
+export function parseGitTasksSpec(spec: string): GitTasksSpec {
+  const withoutPrefix = spec.substring(GIT_SPEC_PREFIX.length);
+  const hashIndex = withoutPrefix.indexOf('#');
+  if (hashIndex === -1) {
+    return { url: withoutPrefix, ref: null };
+  }
+  return {
+    url: withoutPrefix.substring(0, hashIndex),
+    ref: withoutPrefix.substring(hashIndex + 1) || null,
+  };
+}
+
+/**
+ * Clone (or update) a remote task repository into the cache and check out the requested ref.
+ * @param spec Tasks directory specification (`git+<url>#<ref>`)
+ * @returns Local path of the checked-out repository
+ */
+export async function fetchTaskRepository(spec: string): Promise<string> {
+  const { url, ref } = parseGitTasksSpec(spec);
+  const key = createHash('sha256').update(url).digest('hex').substring(0, 16);
+  const localPath = join(getCacheDir(), 'tasks', key);
+
+  try {
+    if (await pathExists(join(localPath, '.git'))) {
+      await git(localPath).fetch(['origin', '--tags', '--prune']);
+    } else {
+      await mkdir(dirname(localPath), { recursive: true });
+      // Partial clone: history for every ref, file contents only for what gets checked out
+      await git().clone(url, localPath, { filter: 'blob:none' });
+    }
+  } catch (error) {
+    throw new GitError(`Failed to fetch task repository ${url}: ${error}`);
+  }
+
+  const revision = await resolveRevision(localPath, ref);
+  try {
+    await git(localPath).checkout(['--force', '--detach', revision]);
+  } catch (error) {
+    throw new GitError(`Failed to checkout '${ref ?? 'HEAD'}' in ${url}: ${error}`);
+  }
+
+  return localPath;
+}
+
+/**
+ * Resolve a ref to a commit, preferring the remote-tracking branch so updates are picked up.
+ */
+async function resolveRevision(localPath: string, ref: string | null): Promise<string> {
+  const repo = git(localPath);
+  const candidates = ref ? [`origin/${ref}`, ref] : ['origin/HEAD', 'HEAD'];
+
+  for (const candidate of candidates) {
+    try {
+      return await repo.revParse(`${candidate}^{commit}`);
+    } catch {
+      // Try the next candidate
+    }
+  }
+
+  throw new GitError(`Unknown ref '${ref}' in task repository`);
+}
+
+/**
+ * Check whether a path exists.
+ */
+async function pathExists(path: string): Promise<boolean> {
+  try {
+    await stat(path);
+    return true;
+  } catch {
+    return false;
+  }
+}
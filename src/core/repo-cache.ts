@@ -0,0 +1,160 @@
+/**
+ * Local mirrors of task source repositories.
+ *
+ * Workspaces are cloned from a bare mirror in the cache directory instead of
+ * the network, so repeated runs (and CI images with a prewarmed cache) only
+ * fetch what changed.
+ */
+
+import { mkdir, readdir, rm, stat } from 'fs/promises';
+import { dirname, join } from 'path';
+import { createHash } from 'crypto';
+import { getCacheDir } from './config.js';
+import { git } from './git.js';
+import { diskUsage } from '../utils/disk.js';
+import { GitError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+
+/**
+ * Refs that move, so the mirror is refreshed before use.
+ */
+const MOVING_REFS = ['main', 'master', 'HEAD'];
+
+/**
+ * How long a mirror this process just cloned or fetched counts as current for moving refs
+ * (so a suite's prefetch is not repeated by every task).
+ */
+const MIRROR_FRESH_MS = 10 * 60 * 1000;
+
+/**
+ * When this process last cloned or fetched each mirror, by URL.
+ */
+const refreshedAt = new Map<string, number>();
+
+/**
+ * A cached repository mirror.
+ */
+export interface CachedRepo {
+  url: string;
+  path: string;
+  sizeBytes: number;
+  updatedAt: string;
+}
+
+/**
+ * Directory holding the repository mirrors.
+ */
+export function getRepoCacheDir(): string {
+  return join(getCacheDir(), 'repos');
+}
+
+/**
+ * Mirror path for a repository URL.
+ */
+export function getMirrorPath(url: string): string {
+  const key = createHash('sha256').update(url).digest('hex').substring(0, 16);
+  return join(getRepoCacheDir(), `${key}.git`);
+}
+
+/**
+ * Create or refresh the mirror of a repository.
+ * An existing mirror is only fetched when `commit` is missing from it, or is a moving ref
+ * and the mirror was not refreshed in the last few minutes.
+ * @param commit Commit or ref the caller needs (omit to always refresh)
+ * @returns Path of the mirror
+ */
+export async function ensureMirror(url: string, commit?: string): Promise<string> {
+  const mirrorPath = getMirrorPath(url);
+
+  try {
+    if (!(await pathExists(mirrorPath))) {
+      logger.debug(`Mirroring ${url} into ${mirrorPath}`);
+      await mkdir(dirname(mirrorPath), { recursive: true });
+      await git().clone(url, mirrorPath, { mirror: true });
+      refreshedAt.set(url, Date.now());
+    } else if (!commit || (MOVING_REFS.includes(commit) && !isFresh(url)) || !(await hasCommit(mirrorPath, commit))) {
+      logger.debug(`Updating mirror of ${url}`);
+      await git(mirrorPath).fetch(['--prune']);
+      refreshedAt.set(url, Date.now());
+    }
+  } catch (error) {
+    throw new GitError(`Failed to mirror ${url}: ${error}`);
+  }
+
+  return mirrorPath;
+}
+
+/**
+ * Whether this process refreshed a mirror recently.
+ */
+function isFresh(url: string): boolean {
+  return Date.now() - (refreshedAt.get(url) ?? 0) < MIRROR_FRESH_MS;
+}
+
+/**
+ * Check whether a mirror contains a commit or ref.
+ */
+async function hasCommit(mirrorPath: string, commit: string): Promise<boolean> {
+  try {
+    await git(mirrorPath).raw(['cat-file', '-e', `${commit}^{commit}`]);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * List cached mirrors with their origin URL, size, and last update time.
+ */
+export async function listCachedRepos(): Promise<CachedRepo[]> {
+  let entries: string[];
+  try {
+    entries = await readdir(getRepoCacheDir());
+  } catch {
+    return [];
+  }
+
+  const repos: CachedRepo[] = [];
+  for (const entry of entries.filter(name => name.endsWith('.git'))) {
+    const path = join(getRepoCacheDir(), entry);
+    let url = '';
+    try {
+      url = (await git(path).raw(['config', '--get', 'remote.origin.url'])).trim();
+    } catch {
+      // Not a usable mirror; still listed so it can be cleared
+    }
+    const updated = (await pathExists(join(path, 'FETCH_HEAD'))) ? join(path, 'FETCH_HEAD') : path;
+    repos.push({
+      url,
+      path,
+      sizeBytes: await diskUsage(path),
+      updatedAt: (await stat(updated)).mtime.toISOString(),
+    });
+  }
+
+  return repos.sort((a, b) => a.url.localeCompare(b.url));
+}
+
+/**
+ * Remove cached mirrors: all of them, or those whose URL contains `match`.
+ * @returns The removed mirrors
+ */
+export async function clearRepoCache(match?: string): Promise<CachedRepo[]> {
+  const removed = (await listCachedRepos()).filter(repo => !match || repo.url.includes(match));
+  for (const repo of removed) {
+    await rm(repo.path, { recursive: true, force: true });
+  }
+  return removed;
+}
+
+/**
+ * Check whether a path exists.
+ */
+async function pathExists(path: string): Promise<boolean> {
+  try {
+    await stat(path);
+    return true;
+  } catch {
+    return false;
+  }
+}
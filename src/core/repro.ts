@@ -0,0 +1,114 @@
+/**
+ * Per-task reproduction manifest: exactly what the agent was given for a task run, so it
+ * can be repeated later or by someone else. Written to
+ * `<results-dir>/<run-id>/<task-id>/repro.json` before the agent starts.
+ */
+
+import { createHash } from 'crypto';
+import { mkdir, readFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import { getSources, type Task, type WorkspaceSource } from './task.js';
+import type { AgentInvocation } from '../agents/types.js';
+import { resolveCapabilities, type Capabilities } from '../agents/permissions.js';
+import type { RunnerConfig, SandboxName } from './config.js';
+import { writeFileAtomic } from '../utils/files.js';
+
+/**
+ * Contents of a reproduction manifest.
+ */
+export interface ReproManifest {
+  run_id: string;
+  task_id: string;
+  created_at: string;
+  /** Task YAML the run loaded, and the SHA-256 of its contents */
+  task_file: string | null;
+  task_sha256: string | null;
+  sources: WorkspaceSource[];
+  /** Workspace the prompts were rendered for (`{{workspace}}`) */
+  workspace: string;
+  agent: string;
+  /** Command line and model parameters the agent reported, if it describes them */
+  invocation: AgentInvocation | null;
+  permissions: Capabilities;
+  /** Rendered prompts, one per step */
+  prompts: string[];
+  agent_timeout_secs: number | null;
+  verification_timeout_secs: number;
+  sandbox: { backend: SandboxName; writable: string[] };
+  /** Environment variables allowed through to the agent besides the base set */
+  agent_env: string[];
+}
+
+/**
+ * Reproduction manifest path for a task in a run.
+ */
+export function getReproManifestPath(resultsDir: string, runId: string, taskId: string): string {
+  return join(resultsDir, runId, taskId, 'repro.json');
+}
+
+/**
+ * SHA-256 of a task's YAML file, or null if it has none.
+ */
+async function hashTaskFile(task: Task): Promise<string | null> {
+  if (!task.file_path) {
+    return null;
+  }
+  try {
+    return createHash('sha256').update(await readFile(task.file_path)).digest('hex');
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Describe a task run about to start.
+ */
+export async function createReproManifest(
+  runId: string,
+  task: Task,
+  workspace: string,
+  agent: string,
+  invocation: AgentInvocation | null,
+  prompts: string[],
+  config: Pick<RunnerConfig, 'sandbox' | 'sandboxWritable' | 'agentEnv'>
+): Promise<ReproManifest> {
+  return {
+    run_id: runId,
+    task_id: task.id,
+    created_at: new Date().toISOString(),
+    task_file: task.file_path ?? null,
+    task_sha256: await hashTaskFile(task),
+    sources: getSources(task),
+    workspace,
+    agent,
+    invocation,
+    permissions: resolveCapabilities(task.permissions),
+    prompts,
+    agent_timeout_secs: task.agent_timeout ?? null,
+    verification_timeout_secs: task.verification.timeout,
+    sandbox: { backend: config.sandbox, writable: config.sandboxWritable },
+    agent_env: config.agentEnv,
+  };
+}
+
+/**
+ * Write a reproduction manifest.
+ * @returns Path of the manifest
+ */
+export async function writeReproManifest(resultsDir: string, manifest: ReproManifest): Promise<string> {
+  const path = getReproManifestPath(resultsDir, manifest.run_id, manifest.task_id);
+  await mkdir(dirname(path), { recursive: true });
+  await writeFileAtomic(path, JSON.stringify(manifest, null, 2));
+  return path;
+}
+
+/**
+ * Read a task's reproduction manifest from a run, or null if it has none.
+ */
+export async function readReproManifest(resultsDir: string, runId: string, taskId: string): Promise<ReproManifest | null> {
+  try {
+    return JSON.parse(await readFile(getReproManifestPath(resultsDir, runId, taskId), 'utf-8')) as ReproManifest;
+  } catch {
+    return null;
+  }
+}
@@ -2,182 +2,796 @@
  * Task runner for executing benchmarks.
  */
 
+import { createReadStream, createWriteStream } from 'fs';
+import { mkdir } from 'fs/promises';
+import { dirname } from 'path';
+import { once } from 'events';
 import { TaskLoader } from './loader.js';
-import { WorkspaceManager } from './workspace.js';
-import { Task } from './task.js';
-import type { Agent } from '../agents/types.js';
-import { Verifier } from '../evaluator/verifier.js';
-import type { BenchmarkResult } from '../evaluator/results.js';
+import { WorkspaceManager, type Workspace } from './workspace.js';
+import { setGitBackend } from './git.js';
+import { setSandbox } from '../sandbox/index.js';
+import { setAgentEnv, withAgentVariables } from '../agents/env.js';
+import { setCustomAgent } from '../agents/custom.js';
+import { setAgentProfiles } from '../agents/profiles.js';
+import { currentCredential, getCredentialPool, isRateLimitError, setCredentials, withCredential, type CredentialLease } from '../agents/credentials.js';
+import { SOFT_CAPABILITIES, Task, TaskStep, getRequiredCapabilities, getVerificationType, type AgentCapability } from './task.js';
+import { checkRequirements } from './prerequisites.js';
+import { prepareToolchain, toolchainVariables, type ToolchainEnvironment } from './toolchain.js';
+import { applyLockfile, readLockfile } from './lock.js';
+import type { TaskFilterOptions } from './filter.js';
+import { estimateDuration, planAgent, type AgentPlan } from './plan.js';
+import { findDangerousTasks, readRunManifest, writeRunManifest } from './manifest.js';
+import { RunStateTracker, readRunState, type RunState, type TaskState } from './state.js';
+import { EventBus, type RunEventListener } from './events.js';
+import { DiskQuotaMonitor } from './quota.js';
+import { openAuditLog } from './audit.js';
+import { createReproManifest, writeReproManifest } from './repro.js';
+import { prefetchRepositories } from './prefetch.js';
+import { PhaseClock, type Phase } from './phases.js';
+import { buildTemplateVariables, renderPrompt, renderTemplate } from './template.js';
+import type { Agent, AgentResult } from '../agents/types.js';
+import { Verifier, type TaskVerifier, type VerificationResult } from '../evaluator/verifier.js';
+import { PLUGIN_API_VERSION, readPluginFiles, resolvePluginPath, runScorerPlugin, runVerifierPlugin } from '../plugins/wasm.js';
+import type { PluginInput } from '../plugins/wasm.js';
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import { priceUsage } from '../evaluator/pricing.js';
 import {
   createSuccess,
   createFailure,
+  createSkipped,
   withAgentOutput,
+  withWorkspaceChanges,
+  withLeakedProcesses,
+  withSetupTime,
+  withCredentialName,
+  withPhaseTimings,
+  withCost,
   withVerificationOutput,
-  saveResult,
+  withVerificationLog,
+  withExpectations,
+  withCheckpoints,
+  withTags,
+  withRunLog,
+  withFailureKind,
+  withFailureFromError,
+  withScore,
+  getOutcome,
   createSuiteResults,
-  saveSuiteResults,
+  findRegressions,
+  createRunId,
+  getTaskLogPath,
+  getRunLogPath,
+  getVerificationLogPath,
+  writeSuiteRecord,
+  readSuiteRecord,
 } from '../evaluator/results.js';
 import type { RunnerConfig } from './config.js';
+import { createResultStore, type ResultStore } from '../storage/store.js';
 import { logger } from '../utils/logger.js';
-import { AgentError } from '../utils/errors.js';
+import { sendNotification } from '../utils/notify.js';
+import { disposeSpoolFiles } from '../utils/spool.js';
+import { findProcessesIn, terminateProcesses } from '../utils/processes.js';
+import { AgentError, PermissionAcknowledgmentError, RunStateError, type DiskQuotaExceededError } from '../utils/errors.js';
+
+/**
+ * Callbacks invoked as a run progresses. Errors thrown by a callback fail the run.
+ */
+export interface RunnerHooks {
+  /**
+   * Before a task's preconditions are checked.
+   */
+  onTaskStart?(task: Task, agent: Agent): void | Promise<void>;
+  /**
+   * After a task's result (passed, failed, or skipped) has been stored.
+   */
+  onTaskComplete?(result: BenchmarkResult, task: Task): void | Promise<void>;
+  /**
+   * After suite results have been stored.
+   */
+  onSuiteComplete?(suite: SuiteResults): void | Promise<void>;
+}
+
+/**
+ * Components a runner can be given instead of the ones derived from its config.
+ */
+export interface RunnerOptions {
+  agent?: Agent;
+  verifier?: TaskVerifier;
+  store?: ResultStore;
+  hooks?: RunnerHooks;
+  listeners?: RunEventListener[];
+  /** Continue an earlier run instead of starting a new one (see `resumeRun`) */
+  runId?: string;
+}
+
+/**
+ * Timed phase each run state begins.
+ */
+const STATE_PHASES: Partial<Record<TaskState, Phase>> = {
+  preparing: 'workspace',
+  agent_running: 'agent',
+  verifying: 'verification',
+};
 
 /**
  * Task runner for executing benchmarks.
  */
 export class TaskRunner {
+  readonly runId: string;
+  /**
+   * Structured events for this run; subscribe reporters here.
+   */
+  readonly events: EventBus;
   private config: RunnerConfig;
   private loader: TaskLoader;
   private workspace: WorkspaceManager;
+  private store: ResultStore;
+  private verifier: TaskVerifier;
+  private hooks: RunnerHooks;
+  private defaultAgent: Agent | null;
+  private state: RunStateTracker | null = null;
+  /** When the current suite's time budget runs out, and the history its tasks are estimated from */
+  private budget: { deadline: number; history: BenchmarkResult[] } | null = null;
+  /** Phase timing of the tasks being executed, by task ID */
+  private clocks = new Map<string, PhaseClock>();
+  /** Variables putting the toolchain of the tasks being executed first on PATH, by task ID */
+  private toolchains = new Map<string, Record<string, string>>();
 
-  constructor(config: RunnerConfig) {
+  constructor(config: RunnerConfig, options: RunnerOptions = {}) {
+    this.runId = options.runId ?? createRunId();
+    this.events = new EventBus(this.runId);
+    for (const listener of options.listeners ?? []) {
+      this.events.on(listener);
+    }
     this.config = config;
     this.loader = new TaskLoader(config.tasksDir);
-    this.workspace = new WorkspaceManager(config.workspaceDir);
+    this.workspace = new WorkspaceManager(config.workspaceDir, config.keepWorkspace);
+    setGitBackend(config.gitBackend);
+    setSandbox(config.sandbox, config.sandboxWritable);
+    setAgentEnv(config.agentEnv);
+    setCredentials(config.agentCredentials);
+    setCustomAgent(config.customAgent);
+    setAgentProfiles(config.agentProfiles);
+    this.store = options.store ?? createResultStore(config);
+    this.verifier = options.verifier ?? Verifier;
+    this.hooks = options.hooks ?? {};
+    this.defaultAgent = options.agent ?? null;
+  }
+
+  /**
+   * Start building a runner with injected components.
+   */
+  static builder(config: RunnerConfig): TaskRunnerBuilder {
+    return new TaskRunnerBuilder(config);
+  }
+
+  /**
+   * Run a single task with the specified agent (or the runner's agent).
+   */
+  async runTask(taskId: string, agent?: Agent, skipVerify: boolean = false): Promise<BenchmarkResult> {
+    const runAgent = this.resolveAgent(agent);
+    return await this.inRun(runAgent, async () => {
+      const [task] = await this.enforceLock([await this.loader.loadById(taskId)]);
+      await this.startRun([task], runAgent);
+      const result = await this.executeTask(task, runAgent, skipVerify);
+      await this.notifyTaskFailure(task, result);
+      return result;
+    });
   }
 
   /**
-   * Run a single task with the specified agent.
+   * Re-run verification and scoring against an existing workspace without invoking an agent.
+   * Agent metrics and output are carried over from the original result when given.
+   * @param save Persist the replayed result like a regular run
    */
-  async runTask(taskId: string, agent: Agent, skipVerify: boolean = false): Promise<BenchmarkResult> {
+  async replayTask(
+    taskId: string,
+    workspacePath: string,
+    original: BenchmarkResult | null = null,
+    save: boolean = false
+  ): Promise<BenchmarkResult> {
     const task = await this.loader.loadById(taskId);
-    return await this.executeTask(task, agent, skipVerify);
+    const agentResult: AgentResult = {
+      success: true,
+      output: original?.agent_output ?? '',
+      iterations: original?.iterations ?? 0,
+      tokensUsed: original?.tokens_used ?? null,
+      usage: original?.token_usage ?? null,
+      cost: original?.cost_usd ?? null,
+      durationSecs: original?.duration_secs ?? 0,
+      agentVersion: original?.agent_version ?? null,
+      modelName: original?.model_name ?? null,
+    };
+
+    const agentName = original?.agent ?? 'replay';
+    const closeLog = save ? await this.openTaskLog(task, agentName) : async () => {};
+    let result: BenchmarkResult;
+    try {
+      result = await this.verifyAndScore(task, agentName, agentResult, workspacePath);
+    } finally {
+      await closeLog();
+    }
+    result = this.withAgentCost(withAgentOutput(result, agentResult.output), agentResult);
+
+    if (save) {
+      return await this.saveTaskResult(task, result);
+    }
+    return withTags(
+      withExpectations(result, task.expected_duration_secs ?? null, task.human_baseline ?? null),
+      task.metadata?.tags || []
+    );
+  }
+
+  /**
+   * Run all tasks, optionally narrowed by a filter.
+   */
+  async runAll(agent?: Agent, skipVerify: boolean = false, filter: TaskFilterOptions = {}): Promise<SuiteResults> {
+    const runAgent = this.resolveAgent(agent);
+    const tasks = await this.loader.loadAll(filter);
+
+    return await this.inRun(runAgent, async () => {
+      logger.info(`Running ${tasks.length} tasks with agent: ${runAgent.name()}`);
+      return await this.runSuite(tasks, runAgent, skipVerify);
+    });
   }
 
   /**
-   * Run all tasks.
+   * Run tasks by category, optionally narrowed by a filter.
    */
-  async runAll(agent: Agent, skipVerify: boolean = false): Promise<void> {
-    const tasks = await this.loader.loadAll();
+  async runCategory(
+    category: string,
+    agent?: Agent,
+    skipVerify: boolean = false,
+    filter: TaskFilterOptions = {}
+  ): Promise<SuiteResults | null> {
+    const runAgent = this.resolveAgent(agent);
+    const tasks = await this.loader.loadAll({ ...filter, category });
 
-    logger.info(`Running ${tasks.length} tasks with agent: ${agent.name()}`);
+    if (tasks.length === 0) {
+      logger.warn(`No tasks found for category: ${category}`);
+      return null;
+    }
 
-    const results: BenchmarkResult[] = [];
+    logger.info(`Running ${tasks.length} tasks in category "${category}"`);
 
-    for (const task of tasks) {
-      logger.taskHeader(task.id, task.title);
+    return await this.inRun(runAgent, () => this.runSuite(tasks, runAgent, skipVerify));
+  }
 
-      const result = await this.executeTask(task, agent, skipVerify);
-      results.push(result);
+  /**
+   * Continue this runner's run (built with its `runId`) after an interruption: run the tasks
+   * its state file has not scored, then save suite results covering the whole run.
+   * @throws RunStateError If the run has no state file, is still running, or used another agent
+   */
+  async resumeRun(agent?: Agent, skipVerify: boolean = false): Promise<SuiteResults> {
+    const runAgent = this.resolveAgent(agent);
+    const previousState = await readRunState(this.config.resultsDir, this.runId);
+    return await this.inRun(runAgent, () => this.resumeSuite(runAgent, previousState, skipVerify));
+  }
 
-      logger.taskResult(
-        result.success,
-        result.score,
-        result.iterations,
-        result.duration_secs,
-        result.tokens_used || undefined
-      );
+  /**
+   * Body of `resumeRun`, inside the run (the agent's name is only known once it is prepared).
+   */
+  private async resumeSuite(runAgent: Agent, previousState: RunState, skipVerify: boolean): Promise<SuiteResults> {
+    if (previousState.agent !== runAgent.name()) {
+      throw new RunStateError(`Run ${this.runId} used agent ${previousState.agent}, not ${runAgent.name()}`);
     }
+    this.state = await RunStateTracker.resume(this.config.resultsDir, this.runId);
 
-    // Save suite results
-    const suite = createSuiteResults(agent.name(), results);
-    const suitePath = await saveSuiteResults(suite, this.config.resultsDir);
-
-    logger.suiteSummary(
-      suite.total_tasks,
-      suite.passed,
-      suite.failed,
-      suite.pass_rate,
-      suite.total_duration_secs
+    // Carry over the results of tasks scored before the interruption: from the suite record,
+    // else the result store (which has no skips)
+    const record = await readSuiteRecord(this.config.resultsDir, this.runId);
+    const stored = new Map(
+      [...(await this.loadHistory()).filter(result => result.run_id === this.runId), ...(record?.results ?? [])]
+        .map(result => [result.task_id, result])
     );
+    const previous: BenchmarkResult[] = [];
+    for (const taskId of this.state.scoredTasks()) {
+      const entry = previousState.tasks[taskId];
+      const result = stored.get(taskId)
+        ?? (entry.outcome === 'skipped' ? createSkipped(taskId, runAgent.name(), entry.error || 'Skipped') : null);
+      if (result) {
+        previous.push(result);
+      } else {
+        logger.warn(`No stored result for ${taskId} (scored ${entry.outcome} before the interruption)`);
+      }
+    }
 
-    logger.success(`Suite results saved to: ${suitePath}`);
+    const tasks: Task[] = [];
+    for (const taskId of this.state.unfinishedTasks()) {
+      tasks.push(await this.loader.loadById(taskId));
+    }
+    logger.info(`Resuming run ${this.runId}: ${tasks.length} task(s) left, ${previous.length} already scored`);
+
+    return await this.runSuite(tasks, runAgent, skipVerify, previous);
   }
 
   /**
-   * Run tasks by category.
+   * Prepare the agent and run `fn` with the run's log context (run ID, agent), its combined
+   * log at `<results-dir>/<run>/run.log`, and its command audit log at
+   * `<results-dir>/<run>/audit.jsonl` (which covers the agent's preparation).
    */
-  async runCategory(category: string, agent: Agent, skipVerify: boolean = false): Promise<void> {
-    const tasks = await this.loader.filterByCategory(category);
+  private async inRun<T>(agent: Agent, fn: () => Promise<T>): Promise<T> {
+    const closeLog = await this.openLog(getRunLogPath(this.config.resultsDir, this.runId), true);
+    const closeAudit = await openAuditLog(this.config.resultsDir, this.runId);
+    try {
+      await logger.withContext({ run_id: this.runId }, async () => await agent.prepare?.());
+      return await logger.withContext({ run_id: this.runId, agent: agent.name() }, fn);
+    } finally {
+      await closeAudit();
+      await closeLog();
+    }
+  }
 
-    if (tasks.length === 0) {
-      logger.warn(`No tasks found for category: ${category}`);
-      return;
+  /**
+   * The agent for a run: the one passed in, else the one the runner was built with.
+   * @throws AgentError If neither is set
+   */
+  private resolveAgent(agent?: Agent): Agent {
+    const resolved = agent ?? this.defaultAgent;
+    if (!resolved) {
+      throw new AgentError('No agent given and the runner was not built with one');
     }
+    return resolved;
+  }
 
-    logger.info(`Running ${tasks.length} tasks in category "${category}"`);
+  /**
+   * Resolve the tasks a run would select: one task by ID, or a suite (`all` or a category) narrowed by a filter.
+   */
+  async selectTasks(selection: { taskId?: string; suite?: string }, filter: TaskFilterOptions = {}): Promise<Task[]> {
+    if (selection.taskId) {
+      return [await this.loader.loadById(selection.taskId)];
+    }
+    return selection.suite === 'all'
+      ? await this.loader.loadAll(filter)
+      : await this.loader.loadAll({ ...filter, category: selection.suite });
+  }
 
-    const results: BenchmarkResult[] = [];
+  /**
+   * Plan a run without executing it: lock pinning, predicted skips, and duration estimates from history.
+   * @param model Model the agent would use (history is matched on it)
+   */
+  async planTasks(tasks: Task[], agent: Agent, model: string): Promise<AgentPlan> {
+    await agent.prepare?.();
+    const locked = await this.enforceLock(tasks);
+    const skipReasons = new Map<string, string>();
+    for (const task of locked) {
+      const reason = task.disabled
+        ? task.disabled_reason || 'Task is disabled'
+        : this.checkCompatibility(task, agent) ?? (await checkRequirements(task));
+      if (reason) {
+        skipReasons.set(task.id, reason);
+      }
+    }
+    return planAgent(locked, agent.name(), model, await this.loadHistory(), skipReasons);
+  }
 
-    for (const task of tasks) {
-      logger.taskHeader(task.id, task.title);
+  /**
+   * Run a list of tasks sequentially and save the suite results.
+   * @param resumed Results of a resumed run's already-scored tasks (null for a new run)
+   */
+  private async runSuite(
+    suiteTasks: Task[],
+    agent: Agent,
+    skipVerify: boolean,
+    resumed: BenchmarkResult[] | null = null
+  ): Promise<SuiteResults> {
+    const tasks = await this.enforceLock(suiteTasks);
+    await this.startRun(tasks, agent, resumed !== null);
+    await this.events.emit({ type: 'suite_started', agent: agent.name(), task_ids: tasks.map(task => task.id) });
+    const results: BenchmarkResult[] = [...(resumed ?? [])];
+    // The budget covers the whole suite, prefetch included
+    if (this.config.timeBudget !== null) {
+      this.budget = { deadline: Date.now() + this.config.timeBudget * 1000, history: await this.loadHistory() };
+    }
+    const prefetchSecs = this.config.prefetch ? await this.prefetch(tasks) : null;
 
-      const result = await this.executeTask(task, agent, skipVerify);
-      results.push(result);
+    try {
+      for (const task of tasks) {
+        const result = await this.executeTask(task, agent, skipVerify);
+        results.push(result);
+        await this.saveSuiteRecord({ ...createSuiteResults(agent.name(), results), prefetch_secs: prefetchSecs, complete: false });
+        await this.notifyTaskFailure(task, result);
 
-      logger.taskResult(
-        result.success,
-        result.score,
-        result.iterations,
-        result.duration_secs,
-        result.tokens_used || undefined
-      );
+        if (this.config.failFast && getOutcome(result) === 'failed') {
+          logger.warn(`Stopping suite after ${task.id} failed (--fail-fast)`);
+          break;
+        }
+      }
+    } finally {
+      this.budget = null;
     }
 
     // Save suite results
     const suite = createSuiteResults(agent.name(), results);
-    const suitePath = await saveSuiteResults(suite, this.config.resultsDir);
-
-    logger.suiteSummary(
-      suite.total_tasks,
-      suite.passed,
-      suite.failed,
-      suite.pass_rate,
-      suite.total_duration_secs
-    );
+    suite.regressions = findRegressions(results, await this.loadHistory());
+    suite.prefetch_secs = prefetchSecs;
+    await this.saveSuiteRecord(suite);
+    const suitePath = await this.store.saveSuite(suite);
+
+    await this.events.emit({ type: 'suite_finished', suite });
 
     logger.success(`Suite results saved to: ${suitePath}`);
+    await this.hooks.onSuiteComplete?.(suite);
+
+    if (this.config.notify) {
+      const skipped = suite.skipped > 0 ? `, ${suite.skipped} skipped` : '';
+      await sendNotification(
+        `agent-bench: suite finished (${suite.agent})`,
+        `${suite.passed}/${suite.passed + suite.failed} passed (${(suite.pass_rate * 100).toFixed(1)}%)${skipped} ` +
+          `in ${Math.round(suite.total_duration_secs / 60)} min`,
+        suite.failed > 0 ? 'critical' : 'normal'
+      );
+    }
+
+    return suite;
+  }
+
+  /**
+   * Refuse dangerous permissions nobody acknowledged (only needed without a sandbox), then write the run manifest and
+   * start checkpointing task states. A resumed run keeps its manifest and state, and its
+   * original acknowledgment still applies.
+   * @throws PermissionAcknowledgmentError If a task needs acknowledgment and `config.permissionAck` is unset
+   */
+  private async startRun(tasks: Task[], agent: Agent, resuming: boolean = false): Promise<void> {
+    const dangerous = findDangerousTasks(tasks);
+    const acknowledgment = this.config.permissionAck
+      ?? (resuming ? (await readRunManifest(this.config.resultsDir, this.runId))?.acknowledgment ?? null : null);
+    if (dangerous.length > 0 && this.config.sandbox === 'off' && !acknowledgment) {
+      throw new PermissionAcknowledgmentError(
+        `Tasks grant the agent unsandboxed access to this machine (${dangerous.map(entry => entry.task_id).join(', ')}); ` +
+          'confirm at the prompt or pass --yes'
+      );
+    }
+    if (resuming) {
+      return;
+    }
+
+    const path = await writeRunManifest(this.config.resultsDir, {
+      run_id: this.runId,
+      created_at: new Date().toISOString(),
+      agent: agent.name(),
+      tasks: tasks.map(task => task.id),
+      dangerous_permissions: dangerous,
+      acknowledgment: dangerous.length > 0 ? this.config.permissionAck : null,
+    });
+    logger.debug(`Run manifest: ${path}`);
+    this.state = await RunStateTracker.create(this.config.resultsDir, this.runId, agent.name(), tasks.map(task => task.id));
+  }
+
+  /**
+   * Record a task's state transition (`result` is given when it is scored), and start
+   * timing the phase the state begins.
+   * State tracking is diagnostic, so failures are only logged.
+   */
+  private async enterState(task: Task, state: TaskState, result?: BenchmarkResult): Promise<void> {
+    const phase = STATE_PHASES[state];
+    if (phase) {
+      this.clocks.get(task.id)?.enter(phase);
+    }
+    try {
+      if (result) {
+        await this.state?.finish(task.id, result);
+      } else {
+        await this.state?.transition(task.id, state);
+      }
+    } catch (error) {
+      logger.warn(`Could not record ${task.id} state ${state}: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+
+  /**
+   * Send a desktop notification for a failed task when notifications are enabled.
+   */
+  private async notifyTaskFailure(task: Task, result: BenchmarkResult): Promise<void> {
+    if (!this.config.notify || getOutcome(result) !== 'failed') {
+      return;
+    }
+    const reason = result.error ? `: ${result.error.split('\n')[0].substring(0, 200)}` : '';
+    await sendNotification(
+      `agent-bench: ${task.id} failed`,
+      `${result.failure_kind || 'verification'} failure${reason}`,
+      'critical'
+    );
+  }
+
+  /**
+   * Load previously stored results for regression detection.
+   */
+  private async loadHistory(): Promise<BenchmarkResult[]> {
+    try {
+      return await this.store.loadResults();
+    } catch (error) {
+      logger.debug(`Could not load previous results: ${error}`);
+      return [];
+    }
+  }
+
+  /**
+   * When running locked, verify tasks against the lockfile and pin their sources.
+   */
+  private async enforceLock(tasks: Task[]): Promise<Task[]> {
+    if (!this.config.locked) {
+      return tasks;
+    }
+    const lockfile = await readLockfile(await this.loader.getTasksDir());
+    return await applyLockfile(lockfile, tasks);
   }
 
   /**
    * Execute a single task.
    */
   private async executeTask(task: Task, agent: Agent, skipVerify: boolean): Promise<BenchmarkResult> {
+    return await logger.withContext({ task_id: task.id }, () => this.executeTaskInContext(task, agent, skipVerify));
+  }
+
+  /**
+   * Execute a single task, emitting its events and calling hooks around it.
+   */
+  private async executeTaskInContext(task: Task, agent: Agent, skipVerify: boolean): Promise<BenchmarkResult> {
+    await this.events.emit({
+      type: 'task_started',
+      task_id: task.id,
+      title: task.title,
+      agent: agent.name(),
+      file_path: task.file_path ?? null,
+    });
+    await this.hooks.onTaskStart?.(task, agent);
+    const closeLog = await this.openTaskLog(task, agent.name());
+    const clock = new PhaseClock();
+    this.clocks.set(task.id, clock);
+    agent.setIterationListener?.(progress => {
+      clock.iteration();
+      void this.events.emit({
+        type: 'agent_iteration_finished',
+        task_id: task.id,
+        iteration: progress.iteration,
+        tokens_used: progress.tokensUsed,
+        cost: progress.cost,
+      });
+    });
+    let result: BenchmarkResult;
+    try {
+      result = await this.executeChecked(this.applyTimeoutOverrides(task), agent, skipVerify);
+    } finally {
+      agent.setIterationListener?.(null);
+      this.clocks.delete(task.id);
+      await closeLog();
+    }
+    await this.enterState(task, 'scored', result);
+    await this.hooks.onTaskComplete?.(result, task);
+    await this.events.emit({ type: 'task_finished', task_id: task.id, result });
+    return result;
+  }
+
+  /**
+   * Apply run-wide agent and verification timeout overrides (`--agent-timeout`, `--verify-timeout`).
+   */
+  private applyTimeoutOverrides(task: Task): Task {
+    const { agentTimeout, verifyTimeout } = this.config;
+    if (agentTimeout === null && verifyTimeout === null) {
+      return task;
+    }
+    if (agentTimeout !== null) {
+      logger.debug(`Agent timeout for ${task.id}: ${agentTimeout}s (task: ${task.agent_timeout ?? 'none'})`);
+    }
+    if (verifyTimeout !== null) {
+      logger.debug(`Verification timeout for ${task.id}: ${verifyTimeout}s (task: ${task.verification.timeout}s)`);
+    }
+
+    return {
+      ...task,
+      agent_timeout: agentTimeout ?? task.agent_timeout,
+      verification: { ...task.verification, timeout: verifyTimeout ?? task.verification.timeout },
+      steps: task.steps?.map(step => ({
+        ...step,
+        verification: { ...step.verification, timeout: verifyTimeout ?? step.verification.timeout },
+      })),
+    };
+  }
+
+  /**
+   * Tee the full log (including debug lines) into `<results-dir>/<run>/<task>/run.log`.
+   * @returns A function that detaches and closes the log
+   */
+  private async openTaskLog(task: Task, agentName: string): Promise<() => Promise<void>> {
+    const closeLog = await this.openLog(getTaskLogPath(this.config.resultsDir, this.runId, task.id), false);
+    logger.debug(`Run ${this.runId}: task ${task.id} with agent ${agentName}`);
+    return closeLog;
+  }
+
+  /**
+   * Append every log line to a file, as JSON records with `--log-format json`, else as timestamped text.
+   * @param tagTask Prefix text lines with their task ID (for logs spanning several tasks)
+   * @returns A function that detaches and closes the log
+   */
+  private async openLog(logPath: string, tagTask: boolean): Promise<() => Promise<void>> {
+    await mkdir(dirname(logPath), { recursive: true });
+
+    const json = logger.getFormat() === 'json';
+    const stream = createWriteStream(logPath, { flags: 'a' });
+    const detach = logger.addSink((line, record) => {
+      if (json) {
+        stream.write(`${JSON.stringify(record)}\n`);
+      } else {
+        const tag = tagTask && record.task_id ? `[${record.task_id}] ` : '';
+        stream.write(`${record.timestamp} ${tag}${line}\n`);
+      }
+    });
+
+    return async () => {
+      detach();
+      await new Promise<void>(resolve => stream.end(resolve));
+    };
+  }
+
+  /**
+   * Check a task's preconditions, then execute it.
+   */
+  private async executeChecked(task: Task, agent: Agent, skipVerify: boolean): Promise<BenchmarkResult> {
+    // Disabled tasks are reported as skipped without touching the workspace
+    if (task.disabled) {
+      return this.skipTask(task, agent, task.disabled_reason || 'Task is disabled');
+    }
+
+    const incompatibility = this.checkCompatibility(task, agent);
+    if (incompatibility) {
+      return this.skipTask(task, agent, incompatibility);
+    }
+    const overBudget = this.checkTimeBudget(task, agent);
+    if (overBudget) {
+      return this.skipTask(task, agent, overBudget);
+    }
+    const degraded = this.missingCapabilities(task, agent);
+    if (degraded.length > 0) {
+      logger.warn(`Agent ${agent.name()} lacks ${degraded.join(', ')}; ${task.id} will run with incomplete results`);
+    }
+
+    if ((task.plugins?.verifier || task.plugins?.scorer) && !this.config.wasmPlugins) {
+      return this.skipTask(task, agent, 'Task uses WebAssembly plugins, which are disabled (set [plugins] enabled = true)');
+    }
+
+    const unmetRequirements = await checkRequirements(task);
+    if (unmetRequirements) {
+      return this.skipTask(task, agent, unmetRequirements);
+    }
+
+    let toolchain: ToolchainEnvironment;
+    if (task.toolchain && Object.keys(task.toolchain).length > 0) {
+      this.clocks.get(task.id)?.enter('toolchain');
+    }
+    try {
+      toolchain = await prepareToolchain(task);
+    } catch (error) {
+      return this.skipTask(task, agent, error instanceof Error ? error.message : String(error));
+    }
+
+    // The agent's processes and verification get the task's toolchain through their environment
+    const variables = toolchainVariables(toolchain);
+    this.toolchains.set(task.id, variables);
+    try {
+      return await withAgentVariables(variables, () =>
+        this.executeWithCredential(task, agent, () => this.executePrepared(task, agent, skipVerify))
+      );
+    } finally {
+      this.toolchains.delete(task.id);
+    }
+  }
+
+  /**
+   * Run an execution with a credential leased from the pool, when `[[agent.credentials]]`
+   * are configured. A rate-limited execution puts its credential into cooldown.
+   */
+  private async executeWithCredential(
+    task: Task,
+    agent: Agent,
+    execute: () => Promise<BenchmarkResult>
+  ): Promise<BenchmarkResult> {
+    const pool = getCredentialPool();
+    if (!pool) {
+      return execute();
+    }
+
+    let lease: CredentialLease;
+    try {
+      lease = await pool.acquire();
+    } catch (error) {
+      return this.skipTask(task, agent, error instanceof Error ? error.message : String(error));
+    }
+    logger.debug(`Using credential ${lease.name}`);
+    let result: BenchmarkResult | null = null;
+    try {
+      result = await withCredential(lease, execute);
+      return result;
+    } finally {
+      lease.release(isRateLimitError(result?.error ?? null));
+    }
+  }
+
+  /**
+   * Execute a task whose pre-checks passed: prepare the workspace, run the agent, and verify.
+   * The workspace is released (removed unless kept) however the task ends.
+   */
+  private async executePrepared(task: Task, agent: Agent, skipVerify: boolean): Promise<BenchmarkResult> {
     const startTime = Date.now();
 
     // Prepare workspace
+    await this.enterState(task, 'preparing');
     logger.info('Preparing workspace...');
-    let workspacePath: string;
+    let workspace: Workspace;
     try {
-      workspacePath = await this.workspace.prepare(task);
-      logger.success(`Workspace ready: ${workspacePath}`);
+      workspace = await this.workspace.create(task);
+      logger.success(`Workspace ready: ${workspace.path}`);
     } catch (error) {
       const duration = (Date.now() - startTime) / 1000;
-      const result = createFailure(
-        task.id,
-        agent.name(),
-        0,
-        null,
-        duration,
-        `Failed to prepare workspace: ${error}`,
-        null,
-        null
+      const result = withFailureFromError(
+        createFailure(task.id, agent.name(), 0, null, duration, `Failed to prepare workspace: ${error}`, null, null),
+        error,
+        'setup',
+        'workspace'
       );
-      await saveResult(result, this.config.resultsDir);
-      return result;
+      return await this.saveTaskResult(task, result);
+    }
+
+    // Durations measure the agent and verification, not cloning (reported as setup_secs)
+    try {
+      return await this.executeInWorkspace(task, agent, workspace, skipVerify, Date.now());
+    } finally {
+      await workspace.release();
+    }
+  }
+
+  /**
+   * Run the agent in a prepared workspace and verify its changes.
+   */
+  private async executeInWorkspace(
+    task: Task,
+    agent: Agent,
+    workspace: Workspace,
+    skipVerify: boolean,
+    startTime: number
+  ): Promise<BenchmarkResult> {
+    const workspacePath = workspace.path;
+    await this.enterState(task, 'agent_running');
+    if (task.steps) {
+      return await this.executeSteps(task, task.steps, agent, workspace, skipVerify, startTime);
     }
 
-    // Execute agent
+    // Execute agent with the rendered prompt
     logger.info('Executing agent...');
+    const renderedTask: Task = { ...task, prompt: renderPrompt(task, workspacePath) };
+    await this.writeRepro(task, agent, workspacePath, [renderedTask.prompt]);
+    const quota = this.startQuotaMonitor(task, agent, workspacePath);
     let agentResult;
     try {
-      agentResult = await agent.execute(task, workspacePath);
+      agentResult = await agent.execute(renderedTask, workspacePath);
       logger.success(`Agent execution completed: ${agentResult.iterations} iterations`);
     } catch (error) {
       const duration = (Date.now() - startTime) / 1000;
-      const result = createFailure(
-        task.id,
-        agent.name(),
-        0,
-        null,
-        duration,
-        `Agent execution failed: ${error}`,
-        null,
-        null
+      // An agent cancelled for exceeding the quota fails with the quota, not its own error
+      const overQuota = await this.stopQuotaMonitor(quota);
+      const result = withFailureFromError(
+        createFailure(task.id, agent.name(), 0, null, duration, overQuota?.message ?? `Agent execution failed: ${error}`, null, null),
+        overQuota ?? error,
+        'agent',
+        'agent'
       );
-      await saveResult(result, this.config.resultsDir);
-      return result;
+      return await this.saveTaskResult(task, result, workspace);
     }
 
+    const overQuota = await this.stopQuotaMonitor(quota);
+    if (overQuota) {
+      return await this.saveTaskResult(task, this.quotaFailure(task, agent, agentResult, overQuota), workspace);
+    }
+
+    // Record what the agent changed before hidden tests are copied in
+    const changes = await workspace.changes();
+    const listed = [
+      ...changes.added.map(file => `+ ${file}`),
+      ...changes.modified.map(file => `M ${file}`),
+      ...changes.deleted.map(file => `- ${file}`),
+    ];
+    logger.debug(`Workspace changes: ${changes.total} file(s)${listed.map(line => `\n  ${line}`).join('')}`);
+
     // Run verification (unless skipped)
     let result: BenchmarkResult;
     if (skipVerify) {
@@ -192,63 +806,547 @@ export class TaskRunner {
         agentResult.modelName
       );
     } else {
-      logger.info('Running verification...');
-      try {
-        const verification = await Verifier.verify(task, workspacePath);
-
-        if (verification.passed) {
-          logger.success('Verification passed');
-          result = createSuccess(
-            task.id,
-            agent.name(),
-            agentResult.iterations,
-            agentResult.tokensUsed,
-            agentResult.durationSecs,
-            agentResult.agentVersion,
-            agentResult.modelName
-          );
+      result = await this.verifyAndScore(task, agent.name(), agentResult, workspacePath);
+    }
+
+    // Add agent output
+    result = withWorkspaceChanges(this.withAgentCost(withAgentOutput(result, agentResult.output), agentResult), changes);
+
+    return await this.saveTaskResult(task, result, workspace);
+  }
+
+  /**
+   * Apply hidden tests, run verification, and score the outcome (honoring `expect: fail`).
+   */
+  private async verifyAndScore(
+    task: Task,
+    agentName: string,
+    agentResult: AgentResult,
+    workspacePath: string
+  ): Promise<BenchmarkResult> {
+    let result: BenchmarkResult;
+
+    await this.enterState(task, 'verifying');
+    logger.info('Running verification...');
+    try {
+      await this.workspace.applyHiddenTests(task, workspacePath);
+      logger.debug(
+        `Verification (${getVerificationType(task.verification)}): ${task.verification.command ?? task.verification.url ?? ''} (cwd: ${workspacePath})`
+      );
+      const verification = await this.verifier.verify(task, workspacePath, this.toolchains.get(task.id));
+      logger.debug(
+        `Verification exited with ${verification.exitCode} after ${verification.durationSecs.toFixed(2)}s\n` +
+        `STDOUT:\n${verification.stdout}\nSTDERR:\n${verification.stderr}`
+      );
+      await this.emitVerification(task, null, verification);
+      const log = await this.saveVerificationLog(task, 'Verification', verification);
+      const expectFailure = task.expect === 'fail';
+
+      let passed = verification.passed;
+      const pluginNotes: string[] = [];
+      const pluginInput = task.plugins ? await this.buildPluginInput(task, verification, agentResult, workspacePath) : null;
+      if (pluginInput && task.plugins?.verifier) {
+        const verdict = await runVerifierPlugin(
+          resolvePluginPath(task, task.plugins.verifier),
+          pluginInput,
+          this.config.pluginTimeout
+        );
+        passed = verdict.passed;
+        pluginNotes.push(`Verifier plugin: ${verdict.passed ? 'passed' : 'failed'}${verdict.message ? ` (${verdict.message})` : ''}`);
+        logger.debug(pluginNotes[pluginNotes.length - 1]);
+      }
+
+      if (expectFailure && passed) {
+        logger.error('Verification passed on an expected-failure task: possible harness bug');
+        result = withFailureKind(createFailure(
+          task.id,
+          agentName,
+          agentResult.iterations,
+          agentResult.tokensUsed,
+          agentResult.durationSecs,
+          'Harness bug: verification passed on a task marked expect: fail',
+          agentResult.agentVersion,
+          agentResult.modelName
+        ), 'harness', { phase: 'verification', command: task.verification.command });
+      } else if (passed !== expectFailure) {
+        logger.success(expectFailure ? 'Verification failed as expected' : 'Verification passed');
+        result = createSuccess(
+          task.id,
+          agentName,
+          agentResult.iterations,
+          agentResult.tokensUsed,
+          agentResult.durationSecs,
+          agentResult.agentVersion,
+          agentResult.modelName
+        );
+      } else {
+        logger.error(`Verification failed with exit code: ${verification.exitCode}`);
+        result = withFailureKind(createFailure(
+          task.id,
+          agentName,
+          agentResult.iterations,
+          agentResult.tokensUsed,
+          agentResult.durationSecs,
+          'Verification tests failed',
+          agentResult.agentVersion,
+          agentResult.modelName
+        ), 'verification', { phase: 'verification', command: task.verification.command });
+      }
+
+      if (pluginInput && task.plugins?.scorer && result.failure_kind !== 'harness') {
+        const scored = await runScorerPlugin(resolvePluginPath(task, task.plugins.scorer), pluginInput, this.config.pluginTimeout);
+        result = withScore(result, scored.score);
+        pluginNotes.push(`Scorer plugin: ${scored.score}${scored.message ? ` (${scored.message})` : ''}`);
+        logger.debug(pluginNotes[pluginNotes.length - 1]);
+      }
+
+      // Add verification output
+      result = withVerificationOutput(
+        result,
+        `Exit code: ${verification.exitCode}\n\nSTDOUT:\n${verification.stdout}\n\nSTDERR:\n${verification.stderr}` +
+          (pluginNotes.length > 0 ? `\n\nPLUGINS:\n${pluginNotes.join('\n')}` : '')
+      );
+      if (log) {
+        result = withVerificationLog(result, log);
+      }
+    } catch (error) {
+      logger.error(`Verification error: ${error}`);
+      result = withFailureFromError(createFailure(
+        task.id,
+        agentName,
+        agentResult.iterations,
+        agentResult.tokensUsed,
+        agentResult.durationSecs,
+        `Verification error: ${error}`,
+        agentResult.agentVersion,
+        agentResult.modelName
+      ), error, 'harness', 'verification');
+    }
+
+    return result;
+  }
+
+  /**
+   * Append verification output that was too large to keep in memory to the task's
+   * `verification.log`, from the spool files the verifier left, and remove them.
+   * @returns The log path, or null if the output fit in memory
+   */
+  private async saveVerificationLog(task: Task, label: string, verification: VerificationResult): Promise<string | null> {
+    if (!verification.stdoutFile && !verification.stderrFile) {
+      return null;
+    }
+    const path = getVerificationLogPath(this.config.resultsDir, this.runId, task.id);
+    try {
+      await mkdir(dirname(path), { recursive: true });
+      const log = createWriteStream(path, { flags: 'a' });
+      const append = async (text: string, file: string | null | undefined) => {
+        if (file) {
+          for await (const chunk of createReadStream(file)) {
+            if (!log.write(chunk)) {
+              await once(log, 'drain');
+            }
+          }
         } else {
-          logger.error(`Verification failed with exit code: ${verification.exitCode}`);
-          result = createFailure(
-            task.id,
-            agent.name(),
-            agentResult.iterations,
-            agentResult.tokensUsed,
-            agentResult.durationSecs,
-            'Verification tests failed',
-            agentResult.agentVersion,
-            agentResult.modelName
+          log.write(text);
+        }
+      };
+      log.write(`${label}\nExit code: ${verification.exitCode}\n\nSTDOUT:\n`);
+      await append(verification.stdout, verification.stdoutFile);
+      log.write('\n\nSTDERR:\n');
+      await append(verification.stderr, verification.stderrFile);
+      log.write('\n\n');
+      await new Promise<void>(resolve => log.end(resolve));
+      logger.debug(`Full verification output saved to ${path}`);
+      return path;
+    } catch (error) {
+      logger.warn(`Could not save the full verification output: ${error}`);
+      return null;
+    } finally {
+      await disposeSpoolFiles(verification.stdoutFile, verification.stderrFile);
+    }
+  }
+
+  /**
+   * Emit a verification result (`checkpoint` is null for final verification).
+   */
+  private async emitVerification(task: Task, checkpoint: number | null, verification: VerificationResult): Promise<void> {
+    await this.events.emit({
+      type: 'verification_finished',
+      task_id: task.id,
+      checkpoint,
+      passed: verification.passed,
+      exit_code: verification.exitCode,
+      duration_secs: verification.durationSecs,
+    });
+  }
+
+  /**
+   * Document handed to a task's WebAssembly plugins.
+   */
+  private async buildPluginInput(
+    task: Task,
+    verification: VerificationResult,
+    agentResult: AgentResult,
+    workspacePath: string
+  ): Promise<PluginInput> {
+    return {
+      api_version: PLUGIN_API_VERSION,
+      task: { id: task.id, category: task.category, difficulty: task.difficulty, tags: task.metadata?.tags || [] },
+      verification: {
+        passed: verification.passed,
+        exit_code: verification.exitCode,
+        stdout: verification.stdout,
+        stderr: verification.stderr,
+        duration_secs: verification.durationSecs,
+      },
+      agent: {
+        iterations: agentResult.iterations,
+        tokens_used: agentResult.tokensUsed,
+        duration_secs: agentResult.durationSecs,
+      },
+      files: await readPluginFiles(workspacePath, task.plugins?.files || []),
+    };
+  }
+
+  /**
+   * Check whether a task fits in what is left of the suite's time budget: its estimated
+   * duration (see `estimateDuration`) must not exceed the remaining time. Tasks without an
+   * estimate run while any time is left.
+   * @returns Why the task is skipped, or null if it fits (or there is no budget)
+   */
+  private checkTimeBudget(task: Task, agent: Agent): string | null {
+    if (!this.budget) {
+      return null;
+    }
+    const remainingSecs = (this.budget.deadline - Date.now()) / 1000;
+    const estimate = estimateDuration(task, agent.name(), null, this.budget.history).secs;
+    if (remainingSecs <= 0) {
+      return 'Time budget exhausted';
+    }
+    if (estimate !== null && estimate > remainingSecs) {
+      return `Time budget: estimated ${Math.round(estimate)}s, ${Math.round(remainingSecs)}s left`;
+    }
+    return null;
+  }
+
+  /**
+   * Check a task's agent constraints.
+   * @returns The reason the agent cannot run the task, or null if compatible
+   */
+  private checkCompatibility(task: Task, agent: Agent): string | null {
+    if (task.agents && task.agents.length > 0 && !task.agents.includes(agent.name())) {
+      return `Task requires one of agents: ${task.agents.join(', ')}`;
+    }
+
+    const missing = this.missingCapabilities(task, agent).filter(capability => !SOFT_CAPABILITIES.includes(capability));
+    if (missing.length > 0) {
+      return `Agent ${agent.name()} lacks required capabilities: ${missing.join(', ')}`;
+    }
+
+    return null;
+  }
+
+  /**
+   * Capabilities the task requires that the agent does not declare.
+   */
+  private missingCapabilities(task: Task, agent: Agent): AgentCapability[] {
+    const supported = agent.capabilities();
+    return getRequiredCapabilities(task).filter(capability => !supported.includes(capability));
+  }
+
+  /**
+   * Execute a multi-step task, verifying each step as a checkpoint before the agent continues.
+   * The score reflects the fraction of checkpoints reached.
+   */
+  private async executeSteps(
+    task: Task,
+    steps: TaskStep[],
+    agent: Agent,
+    workspace: Workspace,
+    skipVerify: boolean,
+    startTime: number
+  ): Promise<BenchmarkResult> {
+    if (!agent.executeSteps) {
+      return this.skipTask(task, agent, `Agent ${agent.name()} does not support multi-step tasks`);
+    }
+
+    const workspacePath = workspace.path;
+    const variables = buildTemplateVariables(task, workspacePath);
+    const prompts = steps.map(step => renderTemplate(step.prompt, variables));
+    await this.writeRepro(task, agent, workspacePath, prompts);
+    const verificationOutputs: string[] = [];
+    let verificationLog: string | null = null;
+    let reached = 0;
+
+    logger.info(`Executing agent over ${steps.length} steps...`);
+    const quota = this.startQuotaMonitor(task, agent, workspacePath);
+    let agentResult: AgentResult;
+    try {
+      agentResult = await agent.executeSteps(task, workspacePath, prompts, async (index) => {
+        const step = steps[index];
+        const label = `Checkpoint ${index + 1}/${steps.length}${step.title ? ` (${step.title})` : ''}`;
+
+        if (skipVerify) {
+          reached = index + 1;
+          return true;
+        }
+
+        // Checkpoints must not pass on a workspace that is already over its quota
+        if (quota && await quota.check()) {
+          return false;
+        }
+
+        logger.info(`Verifying ${label}...`);
+        await this.enterState(task, 'verifying');
+        try {
+          await this.workspace.applyHiddenTests(task, workspacePath);
+          const verification = await this.verifier.verify(
+            { ...task, verification: step.verification },
+            workspacePath,
+            this.toolchains.get(task.id)
+          );
+          await this.emitVerification(task, index + 1, verification);
+          verificationOutputs.push(
+            `${label}\nExit code: ${verification.exitCode}\n\nSTDOUT:\n${verification.stdout}\n\nSTDERR:\n${verification.stderr}`
           );
+          logger.debug(verificationOutputs[verificationOutputs.length - 1]);
+          verificationLog = (await this.saveVerificationLog(task, label, verification)) ?? verificationLog;
+          if (!verification.passed) {
+            logger.error(`${label} failed with exit code: ${verification.exitCode}`);
+            return false;
+          }
+        } catch (error) {
+          logger.error(`${label} verification error: ${error}`);
+          verificationOutputs.push(`${label}\nVerification error: ${error}`);
+          return false;
         }
 
-        // Add verification output
-        result = withVerificationOutput(
-          result,
-          `Exit code: ${verification.exitCode}\n\nSTDOUT:\n${verification.stdout}\n\nSTDERR:\n${verification.stderr}`
-        );
-      } catch (error) {
-        logger.error(`Verification error: ${error}`);
-        result = createFailure(
+        logger.success(`${label} passed`);
+        reached = index + 1;
+        if (reached < steps.length) {
+          await this.enterState(task, 'agent_running');
+        }
+        return true;
+      });
+      logger.success(`Agent execution completed: ${agentResult.iterations} iterations`);
+    } catch (error) {
+      const duration = (Date.now() - startTime) / 1000;
+      const overQuota = await this.stopQuotaMonitor(quota);
+      const result = withFailureFromError(
+        createFailure(task.id, agent.name(), 0, null, duration, overQuota?.message ?? `Agent execution failed: ${error}`, null, null),
+        overQuota ?? error,
+        'agent',
+        'agent'
+      );
+      return await this.saveTaskResult(task, withCheckpoints(result, reached, steps.length), workspace);
+    }
+
+    const overQuota = await this.stopQuotaMonitor(quota);
+    if (overQuota) {
+      const result = this.quotaFailure(task, agent, agentResult, overQuota);
+      return await this.saveTaskResult(task, withCheckpoints(result, reached, steps.length), workspace);
+    }
+
+    let result = reached === steps.length
+      ? createSuccess(
           task.id,
           agent.name(),
           agentResult.iterations,
           agentResult.tokensUsed,
           agentResult.durationSecs,
-          `Verification error: ${error}`,
           agentResult.agentVersion,
           agentResult.modelName
-        );
+        )
+      : withFailureKind(createFailure(
+          task.id,
+          agent.name(),
+          agentResult.iterations,
+          agentResult.tokensUsed,
+          agentResult.durationSecs,
+          `Reached ${reached}/${steps.length} checkpoints`,
+          agentResult.agentVersion,
+          agentResult.modelName
+        ), 'verification', { phase: 'verification' });
+
+    result = withCheckpoints(result, reached, steps.length);
+    if (verificationOutputs.length > 0) {
+      result = withVerificationOutput(result, verificationOutputs.join('\n\n'));
+    }
+    if (verificationLog) {
+      result = withVerificationLog(result, verificationLog);
+    }
+    result = this.withAgentCost(withAgentOutput(result, agentResult.output), agentResult);
+
+    return await this.saveTaskResult(task, result, workspace);
+  }
+
+  /**
+   * Record the rendered prompts, agent invocation, and permissions in the task's
+   * `repro.json`. The manifest is diagnostic, so failures are only logged.
+   */
+  private async writeRepro(task: Task, agent: Agent, workspacePath: string, prompts: string[]): Promise<void> {
+    try {
+      const manifest = await createReproManifest(
+        this.runId,
+        task,
+        workspacePath,
+        agent.name(),
+        (await agent.describeInvocation?.(task, workspacePath)) ?? null,
+        prompts,
+        this.config
+      );
+      const path = await writeReproManifest(this.config.resultsDir, manifest);
+      logger.debug(`Reproduction manifest: ${path}`);
+    } catch (error) {
+      logger.warn(`Failed to write the reproduction manifest for ${task.id}: ${error}`);
+    }
+  }
+
+  /**
+   * Watch the workspace against `workspace_quota` while the agent runs, cancelling the
+   * agent once it is exceeded (null when no quota is configured).
+   */
+  private startQuotaMonitor(task: Task, agent: Agent, workspacePath: string): DiskQuotaMonitor | null {
+    if (this.config.workspaceQuota === null) {
+      return null;
+    }
+    const monitor = new DiskQuotaMonitor(workspacePath, this.config.workspaceQuota, { taskId: task.id });
+    monitor.start(async (error) => {
+      if (!agent.cancel) {
+        logger.warn(`${error.message}; agent ${agent.name()} cannot be cancelled, the task fails once it finishes`);
+        return;
       }
+      logger.warn(`${error.message}, cancelling agent...`);
+      await agent.cancel();
+    });
+    return monitor;
+  }
+
+  /**
+   * Stop watching the workspace and measure it a final time.
+   * @returns The quota error if the workspace exceeded its quota at any point, else null
+   */
+  private async stopQuotaMonitor(monitor: DiskQuotaMonitor | null): Promise<DiskQuotaExceededError | null> {
+    if (!monitor) {
+      return null;
+    }
+    monitor.stop();
+    const error = await monitor.check();
+    if (error) {
+      logger.error(error.message);
     }
+    return error;
+  }
 
-    // Add agent output
-    result = withAgentOutput(result, agentResult.output);
+  /**
+   * Build the failure for an agent run that left its workspace over the disk quota.
+   */
+  private quotaFailure(task: Task, agent: Agent, agentResult: AgentResult, error: DiskQuotaExceededError): BenchmarkResult {
+    const result = createFailure(
+      task.id,
+      agent.name(),
+      agentResult.iterations,
+      agentResult.tokensUsed,
+      agentResult.durationSecs,
+      error.message,
+      agentResult.agentVersion,
+      agentResult.modelName
+    );
+    return this.withAgentCost(withAgentOutput(withFailureFromError(result, error, 'disk_quota', 'agent'), agentResult.output), agentResult);
+  }
+
+  /**
+   * Record the agent's token usage and price it with `[pricing]` (falling back to the cost the agent reported).
+   */
+  private withAgentCost(result: BenchmarkResult, agentResult: AgentResult): BenchmarkResult {
+    const usage = agentResult.usage ?? null;
+    return withCost(result, usage, priceUsage(this.config.pricing, agentResult.modelName, usage, agentResult.cost));
+  }
+
+  /**
+   * Build a skipped result for a task (skipped results are not saved individually).
+   */
+  private skipTask(task: Task, agent: Agent, reason: string): BenchmarkResult {
+    logger.debug(`Skipping ${task.id}: ${reason}`);
+    return withRunLog(
+      withTags(createSkipped(task.id, agent.name(), reason), task.metadata?.tags || []),
+      this.runId,
+      getTaskLogPath(this.config.resultsDir, this.runId, task.id)
+    );
+  }
+
+  /**
+   * Rewrite the run's suite record (`<run>/suite.json`) so the results so far survive a crash.
+   */
+  private async saveSuiteRecord(suite: SuiteResults): Promise<void> {
+    try {
+      await writeSuiteRecord(suite, this.config.resultsDir, this.runId);
+    } catch (error) {
+      logger.warn(`Failed to update the suite record: ${error}`);
+    }
+  }
+
+  /**
+   * Fetch the suite's repositories into the cache before any task is timed.
+   * @returns Seconds the prefetch took
+   */
+  private async prefetch(tasks: Task[]): Promise<number> {
+    const report = await prefetchRepositories(tasks);
+    for (const failure of report.failed) {
+      logger.warn(`Could not prefetch ${failure.url}: ${failure.error}`);
+    }
+    if (report.fetched.length > 0) {
+      logger.success(`Prefetched ${report.fetched.length} repositories in ${report.durationSecs.toFixed(1)}s`);
+    }
+    return report.durationSecs;
+  }
+
+  /**
+   * Attach task-derived fields to a result and save it.
+   * @param workspace Workspace the task ran in, checked for processes it left running
+   */
+  private async saveTaskResult(task: Task, result: BenchmarkResult, workspace?: Workspace): Promise<BenchmarkResult> {
+    if (workspace) {
+      result = withSetupTime(await this.reapProcesses(result, workspace.path), workspace.setupSecs);
+    }
+    const clock = this.clocks.get(task.id);
+    if (clock) {
+      result = withPhaseTimings(result, clock.timings());
+    }
+    const credential = currentCredential();
+    if (credential) {
+      result = withCredentialName(result, credential.name);
+    }
+    const finalResult = withRunLog(
+      withTags(
+        withExpectations(result, task.expected_duration_secs ?? null, task.human_baseline ?? null),
+        task.metadata?.tags || []
+      ),
+      this.runId,
+      getTaskLogPath(this.config.resultsDir, this.runId, task.id)
+    );
 
-    // Save result
-    const resultPath = await saveResult(result, this.config.resultsDir);
+    const resultPath = await this.store.saveResult(finalResult);
     logger.debug(`Result saved to: ${resultPath}`);
 
-    return result;
+    return finalResult;
+  }
+
+  /**
+   * Kill background processes still running in the workspace (e.g. a dev server the
+   * agent started with `&`) so they cannot interfere with later tasks, and record them.
+   */
+  private async reapProcesses(result: BenchmarkResult, workspacePath: string): Promise<BenchmarkResult> {
+    const leaked = await findProcessesIn(workspacePath);
+    if (leaked.length === 0) {
+      return result;
+    }
+    logger.warn(
+      `Killing ${leaked.length} process(es) left running in the workspace:` +
+        leaked.map(entry => `\n  ${entry.pid} ${entry.command}`).join('')
+    );
+    await terminateProcesses(leaked.map(entry => entry.pid));
+    return withLeakedProcesses(result, leaked);
   }
 
   /**
@@ -257,4 +1355,100 @@ export class TaskRunner {
   async listTasks(): Promise<Task[]> {
     return await this.loader.loadAll();
   }
+
+  /**
+   * Release the result store (needed by long-lived processes that create many runners).
+   */
+  async close(): Promise<void> {
+    await this.store.close();
+  }
+}
+
+/**
+ * Fluent construction of a TaskRunner with injected components.
+ *
+ * ```ts
+ * const runner = TaskRunner.builder(config)
+ *   .agent(myAgent)
+ *   .store(myStore)
+ *   .onTaskComplete(result => console.log(result.task_id, result.score))
+ *   .build();
+ * ```
+ */
+export class TaskRunnerBuilder {
+  private config: RunnerConfig;
+  private options: RunnerOptions = {};
+  private hooks: RunnerHooks = {};
+  private listeners: RunEventListener[] = [];
+
+  constructor(config: RunnerConfig) {
+    this.config = { ...config };
+  }
+
+  /**
+   * Continue an earlier run (call `resumeRun` on the built runner).
+   */
+  runId(runId: string): this {
+    this.options.runId = runId;
+    return this;
+  }
+
+  /**
+   * Agent used when run methods are called without one.
+   */
+  agent(agent: Agent): this {
+    this.options.agent = agent;
+    return this;
+  }
+
+  /**
+   * Verification implementation (default: subprocess `Verifier`).
+   */
+  verifier(verifier: TaskVerifier): this {
+    this.options.verifier = verifier;
+    return this;
+  }
+
+  /**
+   * Result store (default: the backend selected by the config).
+   */
+  store(store: ResultStore): this {
+    this.options.store = store;
+    return this;
+  }
+
+  onTaskStart(callback: NonNullable<RunnerHooks['onTaskStart']>): this {
+    this.hooks.onTaskStart = callback;
+    return this;
+  }
+
+  onTaskComplete(callback: NonNullable<RunnerHooks['onTaskComplete']>): this {
+    this.hooks.onTaskComplete = callback;
+    return this;
+  }
+
+  onSuiteComplete(callback: NonNullable<RunnerHooks['onSuiteComplete']>): this {
+    this.hooks.onSuiteComplete = callback;
+    return this;
+  }
+
+  /**
+   * Subscribe to run events (see `RunEvent`).
+   */
+  on(listener: RunEventListener): this {
+    this.listeners.push(listener);
+    return this;
+  }
+
+  /**
+   * Override config fields (e.g. `{ failFast: true, verifyTimeout: 120 }`).
+   */
+  configure(overrides: Partial<RunnerConfig>): this {
+    this.config = { ...this.config, ...overrides };
+    return this;
+  }
+
+  build(): TaskRunner {
+    return new TaskRunner(this.config, { ...this.options, hooks: { ...this.hooks }, listeners: [...this.listeners] });
+  }
 }
@@ -0,0 +1,144 @@
+/**
+ * Benchmark project scaffolding for `agent-bench init`.
+ */
+
+import { mkdir, stat, writeFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import { CONFIG_FILE_NAME } from './config.js';
+import { DEFAULT_MAX_ITERATIONS } from '../agents/types.js';
+
+/**
+ * Options for a new project.
+ */
+export interface ScaffoldOptions {
+  model: string;
+  force?: boolean;
+}
+
+/**
+ * Files written (or left alone because they already existed).
+ */
+export interface ScaffoldResult {
+  created: string[];
+  skipped: string[];
+}
+
+/**
+ * Example task: a one-character bug in a shell script, verified by a hidden shell check (no extra tooling needed).
+ */
+const EXAMPLE_TASK = `schema: 1
+id: EXAMPLE-001
+title: "Fix the greeting script"
+category: bug-fix
+difficulty: easy
+source:
+  repository: none   # Workspace starts from the fixture files below
+  commit: none
+prompt: |
+  The script greet.sh should print exactly "Hello, world!" but prints a misspelled greeting.
+  Fix greet.sh. Do not add any other output.
+verification:
+  type: command
+  command: "sh check_greeting.sh" # Copied in from hidden_tests/ before verification
+  timeout: 10
+permissions:
+  approval: "auto"
+  read: true
+  write: true
+  execute: true
+  network: false
+metadata:
+  tags:
+    - suite:smoke
+    - lang:shell
+`;
+
+/**
+ * Starting state of the example workspace (verification fails until the agent fixes it).
+ */
+const EXAMPLE_FIXTURE = `#!/bin/sh
+echo "Helo, world!"
+`;
+
+/**
+ * Hidden check run by the example task's verification command.
+ */
+const EXAMPLE_CHECK = `#!/bin/sh
+output="$(sh greet.sh)"
+if [ "$output" != "Hello, world!" ]; then
+  echo "expected 'Hello, world!', got '$output'"
+  exit 1
+fi
+`;
+
+/**
+ * Explains how suites are defined (suite membership lives in task tags).
+ */
+const SUITES_README = `# Suites
+
+A suite is a named group of tasks. Add a \`suite:<name>\` tag to a task's
+\`metadata.tags\` to make it a member, then run the suite with:
+
+    agent-bench run --filter "tag=suite:<name>"
+
+The example task belongs to \`suite:smoke\`. \`agent-bench tasks graph\` shows
+every suite and its tasks.
+`;
+
+/**
+ * Render the project config file.
+ */
+function renderConfig(model: string): string {
+  return [
+    '# agent-bench project configuration',
+    'tasks_dir = "tasks"',
+    'results_dir = "results"',
+    '',
+    '[agent]',
+    `model = "${model}"`,
+    `max_iterations = ${DEFAULT_MAX_ITERATIONS}`,
+    '',
+  ].join('\n');
+}
+
+/**
+ * Create `tasks/`, `suites/`, `results/`, `agent-bench.toml`, and an example task with a fixture.
+ * Existing files are kept unless `force` is set.
+ */
+export async function scaffoldProject(dir: string, options: ScaffoldOptions): Promise<ScaffoldResult> {
+  const files: [string, string][] = [
+    [CONFIG_FILE_NAME, renderConfig(options.model)],
+    [join('tasks', 'bug-fix', 'example-001.yaml'), EXAMPLE_TASK],
+    [join('tasks', 'bug-fix', 'EXAMPLE-001', 'fixtures', 'greet.sh'), EXAMPLE_FIXTURE],
+    [join('tasks', 'bug-fix', 'EXAMPLE-001', 'hidden_tests', 'check_greeting.sh'), EXAMPLE_CHECK],
+    [join('suites', 'README.md'), SUITES_README],
+  ];
+  const result: ScaffoldResult = { created: [], skipped: [] };
+
+  await mkdir(join(dir, 'results'), { recursive: true });
+
+  for (const [relativePath, content] of files) {
+    const path = join(dir, relativePath);
+    if (!options.force && (await exists(path))) {
+      result.skipped.push(relativePath);
+      continue;
+    }
+    await mkdir(dirname(path), { recursive: true });
+    await writeFile(path, content, 'utf-8');
+    result.created.push(relativePath);
+  }
+
+  return result;
+}
+
+/**
+ * Check whether a path exists.
+ */
+async function exists(path: string): Promise<boolean> {
+  try {
+    await stat(path);
+    return true;
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,87 @@
+/**
+ * Full-text task search.
+ */
+
+import { Task } from './task.js';
+
+/**
+ * A task matching a search query.
+ */
+export interface SearchMatch {
+  task: Task;
+  score: number;
+  fields: string[];
+  snippet: string | null;
+}
+
+/**
+ * Relative weight of a term match in each field.
+ */
+const FIELD_WEIGHTS: Record<string, number> = {
+  id: 8,
+  title: 4,
+  tags: 3,
+  prompt: 1,
+};
+
+/**
+ * Search tasks by ID, title, prompt, and tags.
+ * Every whitespace-separated term must match some field (case-insensitive).
+ * @returns Matches sorted by relevance
+ */
+export function searchTasks(tasks: Task[], query: string): SearchMatch[] {
+  const terms = query.toLowerCase().split(/\s+/).filter(Boolean);
+  if (terms.length === 0) {
+    return [];
+  }
+
+  const matches: SearchMatch[] = [];
+
+  for (const task of tasks) {
+    const fields: Record<string, string> = {
+      id: task.id.toLowerCase(),
+      title: task.title.toLowerCase(),
+      tags: (task.metadata?.tags || []).join(' ').toLowerCase(),
+      prompt: task.prompt.toLowerCase(),
+    };
+
+    let score = 0;
+    const matchedFields = new Set<string>();
+    const allTermsMatch = terms.every(term => {
+      let found = false;
+      for (const [field, text] of Object.entries(fields)) {
+        if (text.includes(term)) {
+          score += FIELD_WEIGHTS[field];
+          matchedFields.add(field);
+          found = true;
+        }
+      }
+      return found;
+    });
+
+    if (allTermsMatch) {
+      matches.push({
+        task,
+        score,
+        fields: [...matchedFields],
+        snippet: matchedFields.has('prompt') ? promptSnippet(task.prompt, terms[0]) : null,
+      });
+    }
+  }
+
+  return matches.sort((a, b) => b.score - a.score || a.task.id.localeCompare(b.task.id));
+}
+
+/**
+ * Extract a one-line excerpt of the prompt around the first occurrence of a term.
+ */
+function promptSnippet(prompt: string, term: string, radius: number = 40): string | null {
+  const index = prompt.toLowerCase().indexOf(term);
+  if (index === -1) {
+    return null;
+  }
+  const start = Math.max(0, index - radius);
+  const end = Math.min(prompt.length, index + term.length + radius);
+  const excerpt = prompt.substring(start, end).replace(/\s+/g, ' ').trim();
+  return `${start > 0 ? '…' : ''}${excerpt}${end < prompt.length ? '…' : ''}`;
+}
@@ -0,0 +1,264 @@
+/**
+ * Per-task execution state, checkpointed to `<results-dir>/<run>/state.json`.
+ *
+ * Each task moves through pending → preparing → agent_running → verifying → scored
+ * (multi-step tasks go back to agent_running after each checkpoint; any state may end in
+ * scored when the task is skipped or fails). The file is rewritten atomically on every
+ * transition, so after a crash it shows where each task stopped, `run --resume` picks up
+ * the tasks that were not scored, and `status` can follow a run from another terminal.
+ */
+
+import { mkdir, readdir, readFile, stat } from 'fs/promises';
+import { dirname, join } from 'path';
+import { hostname } from 'os';
+import type { BenchmarkResult, ResultOutcome } from '../evaluator/results.js';
+import { getOutcome } from '../evaluator/results.js';
+import { RunStateError } from '../utils/errors.js';
+import { isProcessAlive, writeFileAtomic } from '../utils/files.js';
+
+export const TASK_STATES = ['pending', 'preparing', 'agent_running', 'verifying', 'scored'] as const;
+export type TaskState = (typeof TASK_STATES)[number];
+
+/**
+ * States each state may move to.
+ */
+const TRANSITIONS: Record<TaskState, readonly TaskState[]> = {
+  pending: ['preparing', 'scored'],
+  preparing: ['agent_running', 'scored'],
+  agent_running: ['verifying', 'scored'],
+  verifying: ['agent_running', 'scored'],
+  scored: [],
+};
+
+/**
+ * State file format version.
+ */
+const RUN_STATE_VERSION = 1;
+
+export interface TaskStateEntry {
+  state: TaskState;
+  /** When the task entered its current state */
+  since: string;
+  /** Every state the task entered, in order */
+  history: { state: TaskState; at: string; note?: string }[];
+  /** Set once scored */
+  outcome: ResultOutcome | null;
+  error: string | null;
+}
+
+/**
+ * Checkpointed state of a run.
+ */
+export interface RunState {
+  version: number;
+  run_id: string;
+  agent: string;
+  /** Process executing the run (the last one, after a resume) */
+  pid: number;
+  host: string;
+  started_at: string;
+  updated_at: string;
+  /** Task IDs in execution order */
+  task_ids: string[];
+  tasks: Record<string, TaskStateEntry>;
+}
+
+/**
+ * How a run stands, derived from its state file.
+ */
+export type RunStatus = 'running' | 'interrupted' | 'finished';
+
+export function getRunStatePath(resultsDir: string, runId: string): string {
+  return join(resultsDir, runId, 'state.json');
+}
+
+/**
+ * Read a run's state file.
+ * @throws RunStateError If the run has no state file or it is unreadable
+ */
+export async function readRunState(resultsDir: string, runId: string): Promise<RunState> {
+  const path = getRunStatePath(resultsDir, runId);
+  let content: string;
+  try {
+    content = await readFile(path, 'utf-8');
+  } catch {
+    throw new RunStateError(`Run ${runId} has no state file (${path})`);
+  }
+
+  const state = JSON.parse(content) as RunState;
+  if (state.version !== RUN_STATE_VERSION) {
+    throw new RunStateError(`State file ${path} has unsupported version ${state.version}`);
+  }
+  return state;
+}
+
+/**
+ * ID of the most recently updated run with a state file, or null if there is none.
+ */
+export async function findLatestRun(resultsDir: string): Promise<string | null> {
+  let entries: string[];
+  try {
+    entries = await readdir(resultsDir);
+  } catch {
+    return null;
+  }
+
+  let latest: { runId: string; mtime: number } | null = null;
+  for (const entry of entries) {
+    try {
+      const { mtimeMs } = await stat(getRunStatePath(resultsDir, entry));
+      if (!latest || mtimeMs > latest.mtime) {
+        latest = { runId: entry, mtime: mtimeMs };
+      }
+    } catch {
+      // Not a run directory, or a run from before state files
+    }
+  }
+  return latest?.runId ?? null;
+}
+
+/**
+ * Whether a run is still executing, stopped before scoring every task, or done.
+ * A run whose process is gone (on this host) with unscored tasks was interrupted.
+ */
+export function getRunStatus(state: RunState): RunStatus {
+  if (Object.values(state.tasks).every(entry => entry.state === 'scored')) {
+    return 'finished';
+  }
+  if (state.host === hostname() && !isProcessAlive(state.pid)) {
+    return 'interrupted';
+  }
+  return 'running';
+}
+
+/**
+ * Records task state transitions for one run and checkpoints them to disk.
+ */
+export class RunStateTracker {
+  private path: string;
+  private state: RunState;
+  private writes: Promise<void> = Promise.resolve();
+
+  private constructor(path: string, state: RunState) {
+    this.path = path;
+    this.state = state;
+  }
+
+  /**
+   * Start tracking a new run with every task pending.
+   */
+  static async create(resultsDir: string, runId: string, agent: string, taskIds: string[]): Promise<RunStateTracker> {
+    const now = new Date().toISOString();
+    const tasks: Record<string, TaskStateEntry> = {};
+    for (const taskId of taskIds) {
+      tasks[taskId] = { state: 'pending', since: now, history: [{ state: 'pending', at: now }], outcome: null, error: null };
+    }
+
+    const path = getRunStatePath(resultsDir, runId);
+    await mkdir(dirname(path), { recursive: true });
+    const tracker = new RunStateTracker(path, {
+      version: RUN_STATE_VERSION,
+      run_id: runId,
+      agent,
+      pid: process.pid,
+      host: hostname(),
+      started_at: now,
+      updated_at: now,
+      task_ids: taskIds,
+      tasks,
+    });
+    await tracker.checkpoint();
+    return tracker;
+  }
+
+  /**
+   * Continue tracking an earlier run in this process. Tasks it left unscored go back to
+   * pending, with the state they were interrupted in noted in their history.
+   * @throws RunStateError If the run is still executing in another process
+   */
+  static async resume(resultsDir: string, runId: string): Promise<RunStateTracker> {
+    const state = await readRunState(resultsDir, runId);
+    if (getRunStatus(state) === 'running' && state.pid !== process.pid) {
+      throw new RunStateError(`Run ${runId} is still running (pid ${state.pid} on ${state.host})`);
+    }
+
+    const now = new Date().toISOString();
+    for (const entry of Object.values(state.tasks)) {
+      if (entry.state !== 'scored' && entry.state !== 'pending') {
+        entry.history.push({ state: 'pending', at: now, note: `resumed after interruption in ${entry.state}` });
+        entry.state = 'pending';
+        entry.since = now;
+      }
+    }
+    state.pid = process.pid;
+    state.host = hostname();
+
+    const tracker = new RunStateTracker(getRunStatePath(resultsDir, runId), state);
+    await tracker.checkpoint();
+    return tracker;
+  }
+
+  get runId(): string {
+    return this.state.run_id;
+  }
+
+  get agent(): string {
+    return this.state.agent;
+  }
+
+  /**
+   * IDs of tasks not yet scored, in execution order.
+   */
+  unfinishedTasks(): string[] {
+    return this.state.task_ids.filter(taskId => this.state.tasks[taskId]?.state !== 'scored');
+  }
+
+  /**
+   * IDs of scored tasks.
+   */
+  scoredTasks(): string[] {
+    return this.state.task_ids.filter(taskId => this.state.tasks[taskId]?.state === 'scored');
+  }
+
+  /**
+   * Move a task to a new state.
+   * @throws RunStateError If the task is unknown or the transition is not allowed
+   */
+  async transition(taskId: string, next: TaskState): Promise<void> {
+    const entry = this.state.tasks[taskId];
+    if (!entry) {
+      throw new RunStateError(`Task ${taskId} is not part of run ${this.state.run_id}`, { taskId });
+    }
+    if (!TRANSITIONS[entry.state].includes(next)) {
+      throw new RunStateError(`Task ${taskId} cannot move from ${entry.state} to ${next}`, { taskId });
+    }
+
+    const now = new Date().toISOString();
+    entry.state = next;
+    entry.since = now;
+    entry.history.push({ state: next, at: now });
+    await this.checkpoint();
+  }
+
+  /**
+   * Mark a task scored with its result (from whatever state it reached).
+   */
+  async finish(taskId: string, result: BenchmarkResult): Promise<void> {
+    const entry = this.state.tasks[taskId];
+    if (entry) {
+      entry.outcome = getOutcome(result);
+      entry.error = result.error || result.skip_reason || null;
+    }
+    await this.transition(taskId, 'scored');
+  }
+
+  /**
+   * Write the state file (writes are serialized; the latest state always lands last).
+   */
+  private async checkpoint(): Promise<void> {
+    this.state.updated_at = new Date().toISOString();
+    const content = JSON.stringify(this.state, null, 2);
+    this.writes = this.writes.catch(() => {}).then(() => writeFileAtomic(this.path, content));
+    await this.writes;
+  }
+}
@@ -0,0 +1,70 @@
+/**
+ * Namespaced task tags (`namespace:value`, e.g. `lang:python`, `suite:smoke`).
+ */
+
+/**
+ * Tags without a namespace are grouped under this name in reports.
+ */
+export const DEFAULT_TAG_NAMESPACE = 'tag';
+
+/**
+ * A tag split into namespace and value.
+ */
+export interface ParsedTag {
+  namespace: string | null;
+  value: string;
+}
+
+/**
+ * Split a tag on its first colon.
+ */
+export function parseTag(tag: string): ParsedTag {
+  const index = tag.indexOf(':');
+  if (index === -1) {
+    return { namespace: null, value: tag };
+  }
+  return { namespace: tag.substring(0, index), value: tag.substring(index + 1) };
+}
+
+/**
+ * Check whether a tag matches a filter.
+ * - `lang:python` matches exactly that tag
+ * - `lang:` or `lang:*` matches any tag in the `lang` namespace
+ * - `python` matches the plain tag `python` or any namespaced tag with value `python`
+ */
+export function matchesTag(filter: string, tag: string): boolean {
+  const wanted = parseTag(filter);
+  const actual = parseTag(tag);
+
+  if (wanted.namespace === null) {
+    return actual.value === wanted.value;
+  }
+  if (wanted.namespace !== actual.namespace) {
+    return false;
+  }
+  return wanted.value === '' || wanted.value === '*' || wanted.value === actual.value;
+}
+
+/**
+ * Group items by tag namespace and value.
+ * @returns namespace → value → items carrying that tag
+ */
+export function groupByTagNamespace<T>(
+  items: T[],
+  tagsOf: (item: T) => string[]
+): Map<string, Map<string, T[]>> {
+  const groups = new Map<string, Map<string, T[]>>();
+
+  for (const item of items) {
+    for (const tag of tagsOf(item)) {
+      const { namespace, value } = parseTag(tag);
+      const key = namespace ?? DEFAULT_TAG_NAMESPACE;
+      if (!groups.has(key)) groups.set(key, new Map());
+      const values = groups.get(key)!;
+      if (!values.has(value)) values.set(value, []);
+      values.get(value)!.push(item);
+    }
+  }
+
+  return groups;
+}
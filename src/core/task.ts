@@ -3,11 +3,22 @@
  */
 
 import { z } from 'zod';
+import { TOOL_CAPABILITIES } from '../agents/permissions.js';
 
 /**
  * Task category classification.
  */
-export const TaskCategorySchema = z.enum(['bug-fix', 'feature', 'refactor', 'tools']);
+export const TaskCategorySchema = z.enum([
+  'bug-fix',
+  'feature',
+  'refactor',
+  'tools',
+  'security',
+  'performance',
+  'testing',
+  'documentation',
+  'migration',
+]);
 export type TaskCategory = z.infer<typeof TaskCategorySchema>;
 
 /**
@@ -26,64 +37,306 @@ export const SourceConfigSchema = z.object({
 export type SourceConfig = z.infer<typeof SourceConfigSchema>;
 
 /**
- * Verification configuration.
+ * Repository checked out into a named subdirectory of a multi-repository workspace.
+ */
+export const NamedSourceConfigSchema = SourceConfigSchema.extend({
+  path: z
+    .string()
+    .min(1, 'Source path cannot be empty')
+    .refine(path => !/^([\\/]|[a-zA-Z]:)/.test(path) && !path.split(/[\\/]/).includes('..'), {
+      message: 'Source path must be relative to the workspace',
+    }),
+});
+export type NamedSourceConfig = z.infer<typeof NamedSourceConfigSchema>;
+
+/**
+ * Task source: a single repository, or a list of repositories in named subdirectories.
+ */
+export const TaskSourceSchema = z.union([
+  SourceConfigSchema,
+  z.array(NamedSourceConfigSchema).min(1, 'Source list cannot be empty'),
+]);
+export type TaskSource = z.infer<typeof TaskSourceSchema>;
+
+/**
+ * Verification types with their own verifier (see evaluator/verifiers). Any other
+ * `type` (e.g. `python`, `shell`) names the command's language and runs as `command`.
+ */
+export const VERIFICATION_TYPES = ['command', 'junit', 'http', 'file-assert', 'llm-judge'] as const;
+export type VerificationType = (typeof VERIFICATION_TYPES)[number];
+
+/**
+ * A check on a workspace file for `file-assert` verification.
  */
-export const VerificationConfigSchema = z.object({
-  type: z.string(),
-  command: z.string().min(1, 'Verification command cannot be empty'),
-  timeout: z.number().int().positive().default(60),
+export const FileAssertionSchema = z.object({
+  path: z.string().min(1, 'Assertion path cannot be empty'),
+  exists: z.boolean().default(true),
+  contains: z.string().optional(),
+  matches: z.string().optional(),
+  equals: z.string().optional(),
 });
+export type FileAssertion = z.infer<typeof FileAssertionSchema>;
+
+/**
+ * Verification configuration.
+ * - command: `command` exits 0
+ * - junit: `command` writes a JUnit XML `report` with tests and no failures or errors, and exits 0 or with one of `allowed_exit_codes`
+ * - http: `url` answers with `expect_status` (default 200) and contains `expect_body`; `command` optionally starts the server
+ * - file-assert: every entry in `assertions` holds
+ * - llm-judge: a model (`model`, default from AGENT_BENCH_JUDGE_MODEL) grades the workspace diff and `files` against `rubric`
+ */
+export const VerificationConfigSchema = z
+  .object({
+    type: z.string(),
+    command: z.string().optional(),
+    timeout: z.number().int().positive().default(60),
+    report: z.string().optional(),
+    allowed_exit_codes: z.array(z.number().int()).optional(),
+    url: z.string().optional(),
+    expect_status: z.number().int().optional(),
+    expect_body: z.string().optional(),
+    assertions: z.array(FileAssertionSchema).optional(),
+    rubric: z.string().optional(),
+    model: z.string().optional(),
+    files: z.array(z.string()).optional(),
+  })
+  .superRefine((config, ctx) => {
+    const require = (field: keyof typeof config, message: string) => {
+      const value = config[field];
+      if (value === undefined || value === '' || (Array.isArray(value) && value.length === 0)) {
+        ctx.addIssue({ code: z.ZodIssueCode.custom, path: [field], message });
+      }
+    };
+    switch (getVerificationType(config)) {
+      case 'command':
+        require('command', 'Verification command cannot be empty');
+        break;
+      case 'junit':
+        require('command', 'junit verification needs a command');
+        require('report', 'junit verification needs a report path');
+        break;
+      case 'http':
+        require('url', 'http verification needs a url');
+        break;
+      case 'file-assert':
+        require('assertions', 'file-assert verification needs assertions');
+        break;
+      case 'llm-judge':
+        require('rubric', 'llm-judge verification needs a rubric');
+        break;
+    }
+  });
 export type VerificationConfig = z.infer<typeof VerificationConfigSchema>;
 
+/**
+ * The verifier a verification config selects.
+ */
+export function getVerificationType(config: Pick<VerificationConfig, 'type'>): VerificationType {
+  return (VERIFICATION_TYPES as readonly string[]).includes(config.type)
+    ? (config.type as VerificationType)
+    : 'command';
+}
+
+/**
+ * A single step of a multi-step task, verified as a checkpoint before the next step.
+ */
+export const TaskStepSchema = z.object({
+  title: z.string().optional(),
+  prompt: z.string().min(1, 'Step prompt cannot be empty'),
+  prompt_file: z.string().optional(),
+  verification: VerificationConfigSchema,
+});
+export type TaskStep = z.infer<typeof TaskStepSchema>;
+
 /**
  * Agent permissions configuration.
+ * `execute`, `network`, and `approval` are agent-neutral; `bash`, `web_fetch`,
+ * and `mode` are the legacy equivalents (see agents/permissions.ts).
  */
 export const PermissionsConfigSchema = z.object({
   mode: z.enum(['dontAsk', 'bypassPermissions', 'default']).optional(),
+  approval: z.enum(['auto', 'bypass', 'ask']).optional(),
   write: z.boolean().default(false),
   read: z.boolean().default(true),
+  execute: z.boolean().optional(),
+  network: z.boolean().optional(),
   bash: z.boolean().default(false),
   web_fetch: z.boolean().default(false),
 });
 export type PermissionsConfig = z.infer<typeof PermissionsConfigSchema>;
 
 /**
- * Task metadata.
+ * Toolchain versions a task runs under, keyed by tool (`python: "3.11"`, `node: "20"`).
+ */
+export const ToolchainSpecSchema = z.record(
+  z.string(),
+  z.union([z.string(), z.number()]).transform(version => String(version))
+);
+export type ToolchainSpec = z.infer<typeof ToolchainSpecSchema>;
+
+/**
+ * Tool kinds a task may require from an agent.
+ */
+export const AgentToolSchema = z.enum(['read', 'write', 'bash', 'web']);
+export type AgentTool = z.infer<typeof AgentToolSchema>;
+
+/**
+ * A `requires_tools` entry: a tool, or the capability it exercises (`execute` for `bash`,
+ * `network` for `web`).
+ */
+const RequiredToolSchema = z.preprocess(
+  value => Object.entries(TOOL_CAPABILITIES).find(([, capability]) => capability === value)?.[0] ?? value,
+  AgentToolSchema
+);
+
+/**
+ * What an agent can do: its tools, plus continuing a session across turns (`multi_turn`),
+ * using MCP servers (`mcp`), and reporting token usage (`token_reporting`).
+ */
+export const AgentCapabilitySchema = z.enum([...AgentToolSchema.options, 'multi_turn', 'mcp', 'token_reporting']);
+export type AgentCapability = z.infer<typeof AgentCapabilitySchema>;
+
+/**
+ * Capabilities whose absence only degrades a result (a warning) instead of making the task unrunnable.
+ */
+export const SOFT_CAPABILITIES: readonly AgentCapability[] = ['token_reporting'];
+
+/**
+ * Runtime prerequisite: a binary name, or a binary with a minimum version.
+ */
+export const RequirementSchema = z.union([
+  z.string().min(1, 'Requirement cannot be empty'),
+  z.object({
+    binary: z.string().min(1, 'Requirement binary cannot be empty'),
+    min_version: z.string().optional(),
+    version_command: z.string().optional(),
+  }),
+]);
+export type Requirement = z.infer<typeof RequirementSchema>;
+
+/**
+ * Task metadata: tags plus provenance (author, creation date, license, upstream
+ * issue/PR, reference solution).
  */
 export const TaskMetadataSchema = z.object({
   tags: z.array(z.string()).optional().default([]),
+  author: z.string().min(1, 'Metadata author cannot be empty').optional(),
+  // YAML parses unquoted dates into Date objects; normalize to YYYY-MM-DD
+  created: z
+    .union([
+      z.string().regex(/^\d{4}-\d{2}-\d{2}$/, 'Metadata created must be a YYYY-MM-DD date'),
+      z.date().transform(date => date.toISOString().substring(0, 10)),
+    ])
+    .optional(),
+  license: z.string().min(1, 'Metadata license cannot be empty').optional(),
+  upstream: z.string().url('Metadata upstream must be an issue or PR URL').optional(),
+  solution: z.string().min(1, 'Metadata solution cannot be empty').optional(),
 }).passthrough(); // Allow extra fields
 export type TaskMetadata = z.infer<typeof TaskMetadataSchema>;
 
+/**
+ * WebAssembly plugins evaluating the task after its verification command (see plugins/wasm.ts).
+ * Paths are relative to the task YAML; `files` are workspace files passed to the plugins.
+ */
+export const PluginsConfigSchema = z.object({
+  verifier: z.string().optional(),
+  scorer: z.string().optional(),
+  files: z.array(z.string()).default([]),
+});
+export type PluginsConfig = z.infer<typeof PluginsConfigSchema>;
+
+/**
+ * Task schema version written by and understood by this harness (the `schema` field).
+ * Older tasks are migrated on load (see migrate.ts); newer ones are refused.
+ */
+export const TASK_SCHEMA_VERSION = 1;
+
 /**
  * A benchmark task definition.
  */
 export const TaskSchema = z.object({
+  schema: z.number().int().positive().default(TASK_SCHEMA_VERSION),
   id: z.string().min(1, 'Task ID cannot be empty'),
   title: z.string().min(1, 'Task title cannot be empty'),
   category: TaskCategorySchema,
   difficulty: DifficultySchema,
-  source: SourceConfigSchema,
+  source: TaskSourceSchema,
   prompt: z.string().min(1, 'Task prompt cannot be empty'),
+  prompt_file: z.string().optional(),
   verification: VerificationConfigSchema,
   permissions: PermissionsConfigSchema.default({}),
   metadata: TaskMetadataSchema.optional().default({ tags: [] }),
   max_iterations: z.number().int().positive().optional(),
+  agent_timeout: z.number().int().positive().optional(),
+  steps: z.array(TaskStepSchema).min(1, 'Task steps cannot be empty').optional(),
+  expected_duration_secs: z.number().positive().optional(),
+  human_baseline: z.number().positive().optional(),
+  agents: z.array(z.string()).optional(),
+  requires_tools: z.array(RequiredToolSchema).optional(),
+  requires_capabilities: z.array(AgentCapabilitySchema).optional(),
+  requires: z.array(RequirementSchema).optional(),
+  toolchain: ToolchainSpecSchema.optional(),
+  plugins: PluginsConfigSchema.optional(),
+  expect: z.enum(['pass', 'fail']).default('pass'),
+  disabled: z.boolean().default(false),
+  disabled_reason: z.string().optional(),
+  file_path: z.string().optional(), // Set by the loader to the task's YAML path
 });
 export type Task = z.infer<typeof TaskSchema>;
 
+/**
+ * A repository to check out, with its subdirectory (null for the workspace root).
+ */
+export interface WorkspaceSource extends SourceConfig {
+  path: string | null;
+}
+
+/**
+ * Normalize a task's source into the list of repositories to check out.
+ */
+export function getSources(task: Task): WorkspaceSource[] {
+  if (Array.isArray(task.source)) {
+    return task.source.map(source => ({ ...source }));
+  }
+  return [{ ...task.source, path: null }];
+}
+
+/**
+ * Capabilities a task needs from its agent: `requires_tools`, `requires_capabilities`,
+ * and `multi_turn` for multi-step tasks.
+ */
+export function getRequiredCapabilities(task: Task): AgentCapability[] {
+  const required = new Set<AgentCapability>([...(task.requires_tools || []), ...(task.requires_capabilities || [])]);
+  if (task.steps) {
+    required.add('multi_turn');
+  }
+  return [...required];
+}
+
+/**
+ * Machine-readable catalog entry: the full task definition with normalized sources and tags.
+ */
+export function toCatalogEntry(task: Task): Record<string, unknown> {
+  return {
+    ...task,
+    sources: getSources(task),
+    tags: task.metadata?.tags || [],
+    disabled: task.disabled ?? false,
+    disabled_reason: task.disabled_reason ?? null,
+  };
+}
+
 /**
  * Validate a task configuration.
  * @throws InvalidTaskFormatError if validation fails
  */
 export function validateTask(task: Task): void {
-  if (!task.source.repository) {
-    throw new Error('Source repository cannot be empty');
-  }
-  if (!task.source.commit) {
-    throw new Error('Source commit cannot be empty');
-  }
-  if (!task.verification.command) {
-    throw new Error('Verification command cannot be empty');
+  for (const source of getSources(task)) {
+    if (!source.repository) {
+      throw new Error('Source repository cannot be empty');
+    }
+    if (!source.commit) {
+      throw new Error('Source commit cannot be empty');
+    }
   }
 }
@@ -0,0 +1,64 @@
+/**
+ * Prompt templating with task variables.
+ */
+
+import { Task, getSources } from './task.js';
+
+/**
+ * Placeholder syntax: `{{name}}`, with optional surrounding whitespace.
+ */
+const PLACEHOLDER_PATTERN = /\{\{\s*([\w.-]+)\s*\}\}/g;
+
+/**
+ * Build the variables available to a task prompt.
+ * Scalar metadata keys are exposed by name; built-in task variables take precedence.
+ * @param task The task being rendered
+ * @param workspace The prepared workspace path
+ */
+export function buildTemplateVariables(task: Task, workspace: string): Record<string, string> {
+  const variables: Record<string, string> = {};
+
+  for (const [key, value] of Object.entries(task.metadata || {})) {
+    if (typeof value === 'string' || typeof value === 'number' || typeof value === 'boolean') {
+      variables[key] = String(value);
+    }
+  }
+
+  // Multi-repository tasks expose each source as `repository.<path>` / `commit.<path>`;
+  // plain `repository` / `commit` refer to the first source
+  const sources = getSources(task);
+  for (const source of sources) {
+    if (source.path) {
+      variables[`repository.${source.path}`] = source.repository;
+      variables[`commit.${source.path}`] = source.commit;
+    }
+  }
+
+  return {
+    ...variables,
+    id: task.id,
+    title: task.title,
+    category: task.category,
+    difficulty: task.difficulty,
+    repository: sources[0].repository,
+    commit: sources[0].commit,
+    workspace,
+  };
+}
+
+/**
+ * Substitute `{{name}}` placeholders in a template.
+ * Unknown placeholders are left untouched so literal braces in prompts survive.
+ */
+export function renderTemplate(template: string, variables: Record<string, string>): string {
+  return template.replace(PLACEHOLDER_PATTERN, (match, name: string) =>
+    Object.prototype.hasOwnProperty.call(variables, name) ? variables[name] : match
+  );
+}
+
+/**
+ * Render a task's prompt for execution in the given workspace.
+ */
+export function renderPrompt(task: Task, workspace: string): string {
+  return renderTemplate(task.prompt, buildTemplateVariables(task, workspace));
+}
@@ -0,0 +1,107 @@
+/**
+ * Per-task toolchain provisioning.
+ *
+ * Tasks declare toolchain versions (`python: "3.11"`, `node: "20"`). When mise
+ * is available the versions are installed and put first on PATH for the agent
+ * and verification; otherwise the system binaries must already match.
+ */
+
+import { delimiter, join } from 'path';
+import { Task } from './task.js';
+import { probeBinary } from './prerequisites.js';
+import { ToolchainError } from '../utils/errors.js';
+import { exec } from '../utils/exec.js';
+
+/**
+ * Binary and version command used to check a tool on the system PATH.
+ */
+const SYSTEM_PROBES: Record<string, { binary: string; versionCommand?: string }> = {
+  python: { binary: 'python' },
+  node: { binary: 'node' },
+  rust: { binary: 'rustc' },
+  go: { binary: 'go', versionCommand: 'go version' },
+  java: { binary: 'java', versionCommand: 'java -version' },
+  ruby: { binary: 'ruby' },
+  bun: { binary: 'bun' },
+};
+
+/**
+ * Directories to prepend to PATH so the task's toolchain takes precedence.
+ */
+export interface ToolchainEnvironment {
+  pathEntries: string[];
+}
+
+/**
+ * Provision a task's toolchain.
+ * @throws ToolchainError If a requested version cannot be provided
+ */
+export async function prepareToolchain(task: Task): Promise<ToolchainEnvironment> {
+  const entries = Object.entries(task.toolchain || {});
+  if (entries.length === 0) {
+    return { pathEntries: [] };
+  }
+
+  const useMise = (await probeBinary('mise')).path !== null;
+  const pathEntries: string[] = [];
+  const unmet: string[] = [];
+
+  for (const [tool, version] of entries) {
+    if (useMise) {
+      const spec = `${tool}@${version}`;
+      const install = await runCommand('mise', ['install', spec]);
+      const where = install.code === 0 ? await runCommand('mise', ['where', spec]) : install;
+      if (where.code !== 0) {
+        unmet.push(`${spec} (mise: ${where.output.trim().split('\n').pop()})`);
+      } else {
+        pathEntries.push(join(where.output.trim(), 'bin'));
+      }
+      continue;
+    }
+
+    const probe = SYSTEM_PROBES[tool] ?? { binary: tool };
+    const { path, version: found } = await probeBinary(probe.binary, probe.versionCommand);
+    if (!path) {
+      unmet.push(`${tool} ${version} (not installed)`);
+    } else if (!found || !versionMatches(found, version)) {
+      unmet.push(`${tool} ${version} (found ${found ?? 'unknown version'})`);
+    }
+  }
+
+  if (unmet.length > 0) {
+    throw new ToolchainError(`Toolchain unavailable: ${unmet.join(', ')}`);
+  }
+
+  return { pathEntries };
+}
+
+/**
+ * Variables that put a toolchain first on PATH, for the agent and verification processes
+ * of its task (empty if the task declares none).
+ */
+export function toolchainVariables(environment: ToolchainEnvironment): Record<string, string> {
+  if (environment.pathEntries.length === 0) {
+    return {};
+  }
+  return { PATH: [...environment.pathEntries, process.env.PATH || ''].join(delimiter) };
+}
+
+/**
+ * Whether a detected version satisfies a requested version prefix ("3.11" matches "3.11.9").
+ */
+export function versionMatches(actual: string, requested: string): boolean {
+  const actualParts = actual.split('.');
+  return requested.split('.').every((part, index) => actualParts[index] === part);
+}
+
+/**
+ * Run a command and capture its combined output.
+ */
+async function runCommand(program: string, args: string[]): Promise<{ code: number | null; output: string }> {
+  try {
+    const result = await exec({ program, args });
+    return { code: result.exitCode, output: result.stdout + result.stderr };
+  } catch (error) {
+    return { code: null, output: String(error) };
+  }
+}
@@ -2,60 +2,300 @@
  * Workspace management for task execution.
  */
 
-import { rm, mkdir } from 'fs/promises';
-import { join } from 'path';
-import simpleGit, { SimpleGit } from 'simple-git';
-import { Task } from './task.js';
-import { GitError } from '../utils/errors.js';
+import { rmSync } from 'fs';
+import { cp, rm, mkdir, mkdtemp, readdir, stat, lstat, writeFile } from 'fs/promises';
+import { dirname, join, relative, resolve, sep } from 'path';
+import { tmpdir } from 'os';
+import { createHash } from 'crypto';
+import { Task, WorkspaceSource, getSources } from './task.js';
+import { ensureMirror } from './repo-cache.js';
+import { git } from './git.js';
+import { GitError, WorkspaceError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+import { exec, formatCommand } from '../utils/exec.js';
+import { copyTree, supportsReflink } from '../utils/reflink.js';
+
+/**
+ * Archives extracted into the workspace instead of cloned.
+ */
+const TARBALL_PATTERN = /\.(tar|tar\.gz|tgz|tar\.bz2|tar\.xz)$/;
+
+/**
+ * Files listed per change kind in a result; the counts stay exact.
+ */
+const MAX_LISTED_CHANGES = 200;
+
+/**
+ * Pristine copies of populated workspaces this process made, by task and sources.
+ * They are removed when the process exits.
+ */
+const templates = new Map<string, string>();
+
+/**
+ * Files added, modified, and deleted in a workspace since its snapshot (paths relative to the workspace).
+ */
+export interface WorkspaceChanges {
+  added: string[];
+  modified: string[];
+  deleted: string[];
+  /** Total changed files, including any beyond the listed ones */
+  total: number;
+}
+
+/**
+ * File path → size and mtime, excluding `.git` directories.
+ */
+export type FileManifest = Map<string, string>;
+
+/**
+ * A task workspace. Owns its directory from creation until `release()`, which removes
+ * it unless the workspace is kept; release is safe to call more than once.
+ */
+export class Workspace {
+  readonly path: string;
+  readonly task: Task;
+  /** Seconds spent cloning and copying sources and fixtures into the workspace */
+  setupSecs = 0;
+  private readonly keep: boolean;
+  private baseline: FileManifest | null = null;
+  private released = false;
+
+  constructor(path: string, task: Task, keep: boolean) {
+    this.path = path;
+    this.task = task;
+    this.keep = keep;
+  }
+
+  /**
+   * Copy the task's hidden tests into the workspace (after the agent, before verification).
+   */
+  async applyHiddenTests(): Promise<void> {
+    await copyTaskAssets(this.task, 'hidden_tests', this.path);
+  }
+
+  /**
+   * Record the current file state; `changes()` compares against it.
+   */
+  async snapshot(): Promise<void> {
+    this.baseline = await readFileManifest(this.path);
+  }
+
+  /**
+   * Files changed since `snapshot()`.
+   * @throws WorkspaceError If no snapshot was taken
+   */
+  async changes(): Promise<WorkspaceChanges> {
+    if (!this.baseline) {
+      throw new WorkspaceError(`No snapshot taken of workspace ${this.path}`, { taskId: this.task.id });
+    }
+
+    const { added, modified, deleted, total } = diffManifests(this.baseline, await readFileManifest(this.path));
+    const limit = (files: string[]) => files.slice(0, MAX_LISTED_CHANGES);
+    return { added: limit(added), modified: limit(modified), deleted: limit(deleted), total };
+  }
+
+  /**
+   * Remove the workspace directory, unless it is kept for inspection or `replay`.
+   */
+  async release(): Promise<void> {
+    if (this.released) {
+      return;
+    }
+    this.released = true;
+
+    if (this.keep) {
+      logger.info(`Keeping workspace: ${this.path}`);
+      return;
+    }
+    await removeDir(this.path);
+  }
+}
 
 /**
  * Workspace manager for preparing and managing task workspaces.
  */
 export class WorkspaceManager {
   private workspaceDir: string;
+  private keep: boolean;
+  private reuse: boolean;
 
-  constructor(workspaceDir: string) {
+  /**
+   * @param keep Leave workspaces in place when they are released
+   * @param reuse Keep a template of each populated workspace even when it has to be copied
+   *   in full (the caller creates the same task's workspace repeatedly, e.g. trials); on
+   *   copy-on-write filesystems templates are always kept
+   */
+  constructor(workspaceDir: string, keep = false, reuse = false) {
     this.workspaceDir = workspaceDir;
+    this.keep = keep;
+    this.reuse = reuse;
   }
 
   /**
-   * Prepare a workspace for task execution.
-   * @param task The task to prepare workspace for
-   * @returns The path to the prepared workspace
+   * Create and populate a fresh workspace for a task: its sources (git repositories, local
+   * directories, or tarballs) and fixtures, then a snapshot of the result.
+   * A task populated before in this process is copied from its template instead (reflinked
+   * where the filesystem supports it).
+   * A workspace that fails to populate is removed before the error propagates.
    */
-  async prepare(task: Task): Promise<string> {
-    const workspace = join(this.workspaceDir, task.id);
+  async create(task: Task): Promise<Workspace> {
+    const startTime = Date.now();
+    const workspace = new Workspace(this.getPath(task), task, this.keep);
+
+    await removeDir(workspace.path);
+    await mkdir(workspace.path, { recursive: true });
 
-    // Clean up existing workspace if it exists
     try {
-      await rm(workspace, { recursive: true, force: true });
+      const key = getTemplateKey(task);
+      const template = templates.get(key);
+      if (template) {
+        const method = await copyTree(template, workspace.path);
+        logger.debug(`Workspace copied from template (${method})`);
+      } else {
+        for (const source of getSources(task)) {
+          const target = source.path ? join(workspace.path, source.path) : workspace.path;
+          await mkdir(target, { recursive: true });
+          await this.populateSource(task, source, target);
+        }
+
+        await copyTaskAssets(task, 'fixtures', workspace.path);
+        await this.saveTemplate(key, workspace.path);
+      }
+      await workspace.snapshot();
     } catch (error) {
-      // Ignore errors if directory doesn't exist
+      await removeDir(workspace.path);
+      throw error;
     }
 
-    // Create workspace directory
-    await mkdir(workspace, { recursive: true });
+    workspace.setupSecs = (Date.now() - startTime) / 1000;
+    return workspace;
+  }
 
-    // Clone repository if not "none"
-    if (task.source.repository !== 'none' && task.source.repository) {
-      await this.cloneRepo(task.source.repository, task.source.commit, workspace);
+  /**
+   * Run `fn` in a fresh workspace, releasing it however `fn` exits.
+   */
+  async withWorkspace<T>(task: Task, fn: (workspace: Workspace) => Promise<T>): Promise<T> {
+    const workspace = await this.create(task);
+    try {
+      return await fn(workspace);
+    } finally {
+      await workspace.release();
     }
+  }
 
-    return workspace;
+  /**
+   * Keep a copy of a freshly populated workspace for later workspaces of the same task,
+   * if copies are cheap here or reuse was requested. Failures only lose the speedup.
+   */
+  private async saveTemplate(key: string, workspace: string): Promise<void> {
+    const templatesDir = join(this.workspaceDir, '.templates', String(process.pid));
+    try {
+      if (!this.reuse && !(await supportsReflink(this.workspaceDir))) {
+        return;
+      }
+      if (templates.size === 0) {
+        process.once('exit', () => rmSync(templatesDir, { recursive: true, force: true }));
+      }
+      await mkdir(templatesDir, { recursive: true });
+      const template = await mkdtemp(join(templatesDir, 'template-'));
+      const method = await copyTree(workspace, template);
+      // A concurrent creation of the same task may have saved one first
+      if (templates.has(key)) {
+        await removeDir(template);
+      } else {
+        templates.set(key, template);
+        logger.debug(`Saved workspace template (${method}): ${template}`);
+      }
+    } catch (error) {
+      logger.debug(`Could not save workspace template: ${error}`);
+    }
+  }
+
+  /**
+   * Copy the task's hidden tests into an existing workspace directory (e.g. one given to `verify`).
+   * @param task The task being verified
+   * @param workspace Workspace path
+   */
+  async applyHiddenTests(task: Task, workspace: string): Promise<void> {
+    await copyTaskAssets(task, 'hidden_tests', workspace);
+  }
+
+  /**
+   * Populate a source target: extract a tarball, copy a plain local directory, or clone a git repository.
+   * Tarball and directory paths are relative to the task YAML; the commit is ignored for them.
+   */
+  private async populateSource(task: Task, source: WorkspaceSource, target: string): Promise<void> {
+    const taskDir = getTaskDir(task);
+
+    switch (await getSourceKind(task, source)) {
+      case 'tarball':
+        await this.extractTarball(source.repository, taskDir, target);
+        break;
+      case 'directory':
+        await cp(resolve(taskDir, source.repository), target, { recursive: true, force: true });
+        break;
+      case 'git':
+        await this.cloneRepo(source.repository, source.commit, target);
+        break;
+      case 'none':
+        break;
+    }
   }
 
   /**
-   * Clone a repository to the workspace.
+   * Extract a local or downloaded tarball into the target directory.
+   */
+  private async extractTarball(location: string, taskDir: string, target: string): Promise<void> {
+    let archive = resolve(taskDir, location);
+    let downloadDir: string | null = null;
+
+    try {
+      if (/^https?:\/\//.test(location)) {
+        const response = await fetch(location);
+        if (!response.ok) {
+          throw new WorkspaceError(`Failed to download ${location}: HTTP ${response.status}`);
+        }
+        downloadDir = await mkdtemp(join(tmpdir(), 'agent-bench-tarball-'));
+        archive = join(downloadDir, location.split('/').pop() || 'source.tar');
+        await writeFile(archive, Buffer.from(await response.arrayBuffer()));
+      }
+
+      const command = { program: 'tar', args: ['-xf', archive, '-C', target] };
+      const result = await exec(command);
+      if (result.exitCode !== 0) {
+        throw new WorkspaceError(`Failed to extract ${location}: ${result.stderr.trim()}`, {
+          command: formatCommand(command),
+        });
+      }
+    } finally {
+      if (downloadDir) {
+        await removeDir(downloadDir);
+      }
+    }
+  }
+
+  /**
+   * Clone a repository to the workspace, through the local mirror cache when possible.
    * @param repoUrl Repository URL
    * @param commit Commit hash or branch name
    * @param workspace Workspace path
    */
   private async cloneRepo(repoUrl: string, commit: string, workspace: string): Promise<void> {
     try {
-      const git: SimpleGit = simpleGit();
-
-      // Clone the repository
-      await git.clone(repoUrl, workspace);
+      // Clone from the cached mirror, keeping the real URL as origin; fall back to the network
+      let mirrorPath: string | null = null;
+      try {
+        mirrorPath = await ensureMirror(repoUrl, commit);
+      } catch (error) {
+        logger.warn(`Repository cache unavailable, cloning directly: ${error}`);
+      }
+      if (mirrorPath) {
+        await git().clone(mirrorPath, workspace);
+        await git(workspace).setRemoteUrl('origin', repoUrl);
+      } else {
+        // Partial clone: blobs are fetched only for the checked-out commit
+        await git().clone(repoUrl, workspace, { filter: 'blob:none' });
+      }
 
       // If commit is "main", "master", or "HEAD", stay on default branch
       if (commit === 'main' || commit === 'master' || commit === 'HEAD') {
@@ -63,9 +303,8 @@ export class WorkspaceManager {
       }
 
       // Checkout specific commit or branch
-      const repoGit = simpleGit(workspace);
       try {
-        await repoGit.checkout(commit);
+        await git(workspace).checkout(commit);
       } catch (error) {
         throw new GitError(`Failed to checkout '${commit}': ${error}`);
       }
@@ -78,35 +317,11 @@ export class WorkspaceManager {
   }
 
   /**
-   * Clean up a workspace after task execution.
+   * Remove a task's workspace, whether or not it was kept.
    * @param task The task whose workspace to clean up
    */
   async cleanup(task: Task): Promise<void> {
-    const workspace = join(this.workspaceDir, task.id);
-    try {
-      await rm(workspace, { recursive: true, force: true });
-    } catch (error) {
-      // Ignore errors if directory doesn't exist
-      console.warn(`Warning: Failed to cleanup workspace ${workspace}:`, error);
-    }
-  }
-
-  /**
-   * Check if a workspace exists for a task.
-   * @param task The task to check
-   * @returns True if workspace exists
-   */
-  async exists(task: Task): Promise<boolean> {
-    const workspace = join(this.workspaceDir, task.id);
-    try {
-      await mkdir(workspace, { recursive: false });
-      // If we can create it, it didn't exist
-      await rm(workspace, { recursive: true });
-      return false;
-    } catch {
-      // If mkdir fails, directory exists
-      return true;
-    }
+    await removeDir(this.getPath(task));
   }
 
   /**
@@ -118,3 +333,159 @@ export class WorkspaceManager {
     return join(this.workspaceDir, task.id);
   }
 }
+
+/**
+ * How a source populates its workspace directory.
+ */
+export type SourceKind = 'git' | 'directory' | 'tarball' | 'none';
+
+/**
+ * Classify a source: `none`, a tarball (by extension), a local directory that is not a git
+ * repository, or otherwise a git repository to clone.
+ */
+export async function getSourceKind(task: Task, source: WorkspaceSource): Promise<SourceKind> {
+  if (!source.repository || source.repository === 'none') {
+    return 'none';
+  }
+  if (TARBALL_PATTERN.test(source.repository)) {
+    return 'tarball';
+  }
+  if (!isRemote(source.repository)) {
+    const localPath = resolve(getTaskDir(task), source.repository);
+    if ((await isDirectory(localPath)) && !(await exists(join(localPath, '.git')))) {
+      return 'directory';
+    }
+  }
+  return 'git';
+}
+
+/**
+ * Identity of a task's populated workspace: the task, its YAML file, and its sources.
+ */
+function getTemplateKey(task: Task): string {
+  return createHash('sha256')
+    .update(JSON.stringify([task.id, task.file_path ?? null, getSources(task)]))
+    .digest('hex');
+}
+
+/**
+ * Directory that task-relative source paths resolve against.
+ */
+function getTaskDir(task: Task): string {
+  return task.file_path ? dirname(task.file_path) : process.cwd();
+}
+
+/**
+ * Copy a conventional asset directory (`<task-dir>/<ID>/<kind>/`) over the workspace root.
+ * @returns True if the asset directory exists and was copied
+ */
+async function copyTaskAssets(task: Task, kind: 'fixtures' | 'hidden_tests', workspace: string): Promise<boolean> {
+  if (!task.file_path) {
+    return false;
+  }
+
+  const assetsDir = join(dirname(task.file_path), task.id, kind);
+  if (!(await isDirectory(assetsDir))) {
+    return false;
+  }
+
+  await cp(assetsDir, workspace, { recursive: true, force: true });
+  return true;
+}
+
+/**
+ * Record size and mtime of every file under a directory, skipping `.git`.
+ * Paths are relative and `/`-separated on every platform.
+ */
+export async function readFileManifest(root: string): Promise<FileManifest> {
+  const manifest: FileManifest = new Map();
+
+  const walk = async (directory: string): Promise<void> => {
+    let entries;
+    try {
+      entries = await readdir(directory, { withFileTypes: true });
+    } catch {
+      // Removed or unreadable: its files count as deleted
+      return;
+    }
+    for (const entry of entries) {
+      const fullPath = join(directory, entry.name);
+      if (entry.isDirectory()) {
+        if (entry.name !== '.git') {
+          await walk(fullPath);
+        }
+      } else {
+        const info = await lstat(fullPath);
+        manifest.set(relative(root, fullPath).split(sep).join('/'), `${info.size}:${info.mtimeMs}`);
+      }
+    }
+  };
+
+  await walk(root);
+  return manifest;
+}
+
+/**
+ * Files added, modified, and deleted between two manifests (sorted, unlimited).
+ */
+export function diffManifests(before: FileManifest, after: FileManifest): WorkspaceChanges {
+  const added: string[] = [];
+  const modified: string[] = [];
+  const deleted: string[] = [];
+  for (const [file, signature] of after) {
+    const previous = before.get(file);
+    if (previous === undefined) {
+      added.push(file);
+    } else if (previous !== signature) {
+      modified.push(file);
+    }
+  }
+  for (const file of before.keys()) {
+    if (!after.has(file)) {
+      deleted.push(file);
+    }
+  }
+
+  return {
+    added: added.sort(),
+    modified: modified.sort(),
+    deleted: deleted.sort(),
+    total: added.length + modified.length + deleted.length,
+  };
+}
+
+/**
+ * Remove a directory, warning instead of failing.
+ */
+async function removeDir(path: string): Promise<void> {
+  try {
+    // Retries cover files briefly held open on Windows (virus scanners, exiting agents)
+    await rm(path, { recursive: true, force: true, maxRetries: 3 });
+  } catch (error) {
+    logger.warn(`Failed to remove ${path}: ${error}`);
+  }
+}
+
+/**
+ * Repository URLs that git fetches over the network.
+ */
+function isRemote(repository: string): boolean {
+  return /^[a-z][a-z0-9+.-]*:\/\//i.test(repository) || /^[^/]+@[^:]+:/.test(repository);
+}
+
+async function isDirectory(path: string): Promise<boolean> {
+  try {
+    return (await stat(path)).isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+async function exists(path: string): Promise<boolean> {
+  try {
+    await stat(path);
+    return true;
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,84 @@
+/**
+ * Difficulty calibration from historical results.
+ */
+
+import type { Difficulty, Task } from '../core/task.js';
+import type { BenchmarkResult } from './results.js';
+import { getOutcome } from './results.js';
+
+/**
+ * Ordered difficulty levels.
+ */
+const DIFFICULTY_LEVELS: Difficulty[] = ['easy', 'medium', 'hard'];
+
+/**
+ * Pass-rate thresholds: at or above `easy` is easy, at or above `medium` is medium, else hard.
+ */
+export const PASS_RATE_THRESHOLDS = { easy: 0.7, medium: 0.3 };
+
+/**
+ * Calibration of a single task.
+ */
+export interface TaskCalibration {
+  taskId: string;
+  declared: Difficulty;
+  observed: Difficulty;
+  passRate: number;
+  runs: number;
+  agents: number;
+  flagged: boolean;
+}
+
+/**
+ * Map a pass rate to the difficulty it suggests.
+ */
+export function difficultyFromPassRate(passRate: number): Difficulty {
+  if (passRate >= PASS_RATE_THRESHOLDS.easy) return 'easy';
+  if (passRate >= PASS_RATE_THRESHOLDS.medium) return 'medium';
+  return 'hard';
+}
+
+/**
+ * Recompute effective difficulty per task from historical pass rates.
+ * Each agent/model contributes its own pass rate equally, so heavily re-run agents do not dominate.
+ * A task is flagged when declared and observed difficulty are two levels apart, or one level apart
+ * with at least `minRuns` runs.
+ */
+export function calibrateDifficulty(
+  tasks: Task[],
+  results: BenchmarkResult[],
+  minRuns: number = 5
+): TaskCalibration[] {
+  const calibrations: TaskCalibration[] = [];
+
+  for (const task of tasks) {
+    const taskResults = results.filter(r => r.task_id === task.id && getOutcome(r) !== 'skipped');
+    if (taskResults.length === 0) continue;
+
+    const byAgent = new Map<string, BenchmarkResult[]>();
+    for (const result of taskResults) {
+      const key = `${result.agent}/${result.model_name || ''}`;
+      if (!byAgent.has(key)) byAgent.set(key, []);
+      byAgent.get(key)!.push(result);
+    }
+
+    const agentRates = [...byAgent.values()].map(
+      runs => runs.filter(r => r.success).length / runs.length
+    );
+    const passRate = agentRates.reduce((sum, rate) => sum + rate, 0) / agentRates.length;
+    const observed = difficultyFromPassRate(passRate);
+    const distance = Math.abs(DIFFICULTY_LEVELS.indexOf(observed) - DIFFICULTY_LEVELS.indexOf(task.difficulty));
+
+    calibrations.push({
+      taskId: task.id,
+      declared: task.difficulty,
+      observed,
+      passRate,
+      runs: taskResults.length,
+      agents: byAgent.size,
+      flagged: distance >= 2 || (distance === 1 && taskResults.length >= minRuns),
+    });
+  }
+
+  return calibrations.sort((a, b) => Number(b.flagged) - Number(a.flagged) || a.taskId.localeCompare(b.taskId));
+}
@@ -0,0 +1,141 @@
+/**
+ * Cost accounting from per-model prices (`[pricing."<provider>/<model>"]`).
+ *
+ * Agents report token usage by kind. A result's cost is priced from the table when its
+ * model has an entry, else it is what the agent reported (if anything). Prices live in
+ * the config file, so updating them needs no rebuild, and `stats` re-prices stored
+ * results from their token usage with the current table.
+ */
+
+import { getOutcome, type BenchmarkResult } from './results.js';
+
+/**
+ * Tokens an execution used, by kind.
+ */
+export interface TokenUsage {
+  input: number;
+  output: number;
+  /** Input tokens served from the provider's prompt cache */
+  cache_read: number;
+  /** Input tokens written to the provider's prompt cache */
+  cache_write: number;
+}
+
+/**
+ * Prices of a model in USD per million tokens.
+ */
+export interface ModelPrice {
+  input: number;
+  output: number;
+  /** null: billed as input */
+  cacheRead: number | null;
+  /** null: billed as input */
+  cacheWrite: number | null;
+}
+
+/**
+ * Prices by model (`provider/model`, or a bare model ID matching any provider).
+ */
+export type PricingTable = Record<string, ModelPrice>;
+
+/**
+ * Cost of a group of results (one agent and model).
+ */
+export interface CostSummary {
+  agent: string;
+  model: string | null;
+  runs: number;
+  usage: TokenUsage;
+  /** Total USD over the runs with a known cost */
+  costUsd: number;
+  /** Runs whose cost is unknown (no price and none reported) */
+  unpriced: number;
+}
+
+/**
+ * An empty token count.
+ */
+export function emptyUsage(): TokenUsage {
+  return { input: 0, output: 0, cache_read: 0, cache_write: 0 };
+}
+
+/**
+ * The price of a model: an exact `provider/model` entry, else one for the bare model ID.
+ */
+export function findPrice(pricing: PricingTable, model: string | null): ModelPrice | null {
+  if (!model) {
+    return null;
+  }
+  return pricing[model] ?? pricing[model.slice(model.indexOf('/') + 1)] ?? null;
+}
+
+/**
+ * USD cost of some token usage at a model's prices.
+ */
+export function computeCost(usage: TokenUsage, price: ModelPrice): number {
+  return (
+    usage.input * price.input +
+    usage.output * price.output +
+    usage.cache_read * (price.cacheRead ?? price.input) +
+    usage.cache_write * (price.cacheWrite ?? price.input)
+  ) / 1_000_000;
+}
+
+/**
+ * The cost of an execution: priced from the table when possible, else as reported by the agent.
+ * @returns USD, or null if unknown
+ */
+export function priceUsage(
+  pricing: PricingTable,
+  model: string | null,
+  usage: TokenUsage | null,
+  reportedCost: number | null
+): number | null {
+  const price = findPrice(pricing, model);
+  return usage && price ? computeCost(usage, price) : reportedCost;
+}
+
+/**
+ * Total usage and cost per agent and model, sorted by cost (highest first).
+ * @param pricing Re-price results from their token usage (stored costs are used otherwise)
+ */
+export function summarizeCosts(results: BenchmarkResult[], pricing: PricingTable = {}): CostSummary[] {
+  const groups = new Map<string, CostSummary>();
+  for (const result of results) {
+    if (getOutcome(result) === 'skipped') continue;
+
+    const key = `${result.agent}\0${result.model_name ?? ''}`;
+    const group = groups.get(key) ?? {
+      agent: result.agent,
+      model: result.model_name ?? null,
+      runs: 0,
+      usage: emptyUsage(),
+      costUsd: 0,
+      unpriced: 0,
+    };
+    groups.set(key, group);
+
+    group.runs++;
+    const usage = result.token_usage ?? null;
+    if (usage) {
+      group.usage.input += usage.input;
+      group.usage.output += usage.output;
+      group.usage.cache_read += usage.cache_read;
+      group.usage.cache_write += usage.cache_write;
+    }
+    const cost = priceUsage(pricing, result.model_name ?? null, usage, result.cost_usd ?? null);
+    if (cost === null) {
+      group.unpriced++;
+    } else {
+      group.costUsd += cost;
+    }
+  }
+  return [...groups.values()].sort((a, b) => b.costUsd - a.costUsd || a.agent.localeCompare(b.agent));
+}
+
+/**
+ * Format a USD amount (more decimals for small amounts).
+ */
+export function formatCost(usd: number): string {
+  return `$${usd < 1 ? usd.toFixed(4) : usd.toFixed(2)}`;
+}
@@ -2,8 +2,38 @@
  * Benchmark results and result persistence.
  */
 
-import { writeFile, mkdir } from 'fs/promises';
-import { join } from 'path';
+import { mkdir, readFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import type { WorkspaceChanges } from '../core/workspace.js';
+import type { PhaseTimings } from '../core/phases.js';
+import type { TokenUsage } from './pricing.js';
+import type { WorkspaceProcess } from '../utils/processes.js';
+import { createUniqueFile, writeFileAtomic } from '../utils/files.js';
+import { categorizeError, getErrorContext, type ErrorCategory, type ErrorContext } from '../utils/errors.js';
+
+/**
+ * Outcome of a single task run.
+ */
+export type ResultOutcome = 'passed' | 'failed' | 'skipped';
+
+/**
+ * What caused a failed task run: task setup (workspace, toolchain), the agent, the verification
+ * tests, the harness itself, a timeout, or the workspace outgrowing its disk quota.
+ */
+export type FailureKind = ErrorCategory;
+
+/**
+ * Process exit codes for `run`; when several tasks fail, the highest code wins.
+ */
+export const EXIT_CODES = {
+  passed: 0,
+  verification: 1,
+  agent: 2,
+  harness: 3,
+  timeout: 4,
+  setup: 5,
+  disk_quota: 6,
+} as const;
 
 /**
  * Benchmark result for a single task run.
@@ -11,17 +41,88 @@ import { join } from 'path';
 export interface BenchmarkResult {
   task_id: string;
   agent: string;
+  outcome: ResultOutcome;
   success: boolean;
   score: number;
   iterations: number;
   tokens_used: number | null;
+  /** Tokens by kind (input, output, cache reads and writes), if the agent reports them */
+  token_usage: TokenUsage | null;
+  /** USD cost, priced from `[pricing]` when the model has an entry, else as reported by the agent */
+  cost_usd: number | null;
   duration_secs: number;
+  /** Seconds spent preparing the workspace (cloning, fixtures), not included in duration_secs */
+  setup_secs: number | null;
+  /** Seconds per phase: toolchain, workspace, agent (and each iteration), verification */
+  phases: PhaseTimings | null;
   verification_output: string | null;
+  /** Full verification output, when it was too large for `verification_output` */
+  verification_log: string | null;
   agent_output: string | null;
   timestamp: string;
   error: string | null;
+  failure_kind: FailureKind | null;
+  failure_phase: string | null;
+  failure_command: string | null;
   agent_version: string | null;
   model_name: string | null;
+  /** Pooled credential the agent ran with (`[[agent.credentials]]`) */
+  credential: string | null;
+  /** Worker (URL) that executed the task in a distributed run (`coordinate`) */
+  worker: string | null;
+  skip_reason: string | null;
+  expected_duration_secs: number | null;
+  human_baseline_secs: number | null;
+  checkpoints_reached: number | null;
+  total_checkpoints: number | null;
+  workspace_changes: WorkspaceChanges | null;
+  /** Background processes still running in the workspace after the task, which were killed */
+  leaked_processes: WorkspaceProcess[] | null;
+  tags: string[];
+  run_id: string | null;
+  log_path: string | null;
+}
+
+/**
+ * Build a result with every optional field unset.
+ */
+function baseResult(taskId: string, agent: string): BenchmarkResult {
+  return {
+    task_id: taskId,
+    agent,
+    outcome: 'failed',
+    success: false,
+    score: 0,
+    iterations: 0,
+    tokens_used: null,
+    token_usage: null,
+    cost_usd: null,
+    duration_secs: 0,
+    setup_secs: null,
+    phases: null,
+    verification_output: null,
+    verification_log: null,
+    agent_output: null,
+    timestamp: new Date().toISOString(),
+    error: null,
+    failure_kind: null,
+    failure_phase: null,
+    failure_command: null,
+    agent_version: null,
+    model_name: null,
+    credential: null,
+    worker: null,
+    skip_reason: null,
+    expected_duration_secs: null,
+    human_baseline_secs: null,
+    checkpoints_reached: null,
+    total_checkpoints: null,
+    workspace_changes: null,
+    leaked_processes: null,
+    tags: [],
+    run_id: null,
+    log_path: null,
+  };
 }
 
 /**
@@ -37,17 +138,13 @@ export function createSuccess(
   modelName: string | null = null
 ): BenchmarkResult {
   return {
-    task_id: taskId,
-    agent,
+    ...baseResult(taskId, agent),
+    outcome: 'passed',
     success: true,
     score: 100,
     iterations,
     tokens_used: tokensUsed,
     duration_secs: durationSecs,
-    verification_output: null,
-    agent_output: null,
-    timestamp: new Date().toISOString(),
-    error: null,
     agent_version: agentVersion,
     model_name: modelName,
   };
@@ -67,22 +164,89 @@ export function createFailure(
   modelName: string | null = null
 ): BenchmarkResult {
   return {
-    task_id: taskId,
-    agent,
+    ...baseResult(taskId, agent),
+    outcome: 'failed',
     success: false,
     score: 0,
     iterations,
     tokens_used: tokensUsed,
     duration_secs: durationSecs,
-    verification_output: null,
-    agent_output: null,
-    timestamp: new Date().toISOString(),
     error,
     agent_version: agentVersion,
     model_name: modelName,
   };
 }
 
+/**
+ * Create a skipped benchmark result.
+ */
+export function createSkipped(taskId: string, agent: string, reason: string): BenchmarkResult {
+  return {
+    ...baseResult(taskId, agent),
+    outcome: 'skipped',
+    skip_reason: reason,
+  };
+}
+
+/**
+ * Get the outcome of a result, deriving it for results saved before outcomes were recorded.
+ */
+export function getOutcome(result: BenchmarkResult): ResultOutcome {
+  return result.outcome ?? (result.success ? 'passed' : 'failed');
+}
+
+/**
+ * Exit code summarizing a set of results (skipped tasks do not count as failures).
+ */
+export function getExitCode(results: BenchmarkResult[]): number {
+  return results
+    .filter(result => getOutcome(result) === 'failed')
+    .reduce((code, result) => Math.max(code, EXIT_CODES[result.failure_kind ?? 'verification']), EXIT_CODES.passed);
+}
+
+/**
+ * Exit code for an error that aborted a run before results were produced.
+ */
+export function getErrorExitCode(error: unknown): number {
+  return EXIT_CODES[categorizeError(error)];
+}
+
+/**
+ * Record what caused a failure and where (phase and command).
+ */
+export function withFailureKind(result: BenchmarkResult, kind: FailureKind, context: ErrorContext = {}): BenchmarkResult {
+  return {
+    ...result,
+    failure_kind: kind,
+    failure_phase: context.phase ?? null,
+    failure_command: context.command ?? null,
+  };
+}
+
+/**
+ * Record a failure caused by a thrown error, using its category and context when it has them.
+ * @param fallback Category for errors without one
+ * @param phase Phase the error was raised in, unless the error says otherwise
+ */
+export function withFailureFromError(
+  result: BenchmarkResult,
+  error: unknown,
+  fallback: FailureKind,
+  phase: string
+): BenchmarkResult {
+  return withFailureKind(result, categorizeError(error, fallback), { phase, ...getErrorContext(error) });
+}
+
+/**
+ * Replace a result's score (clamped to 0-100), e.g. with a scorer plugin's.
+ */
+export function withScore(result: BenchmarkResult, score: number): BenchmarkResult {
+  return {
+    ...result,
+    score: Math.min(100, Math.max(0, score)),
+  };
+}
+
 /**
  * Add verification output to a result.
  */
@@ -93,6 +257,135 @@ export function withVerificationOutput(result: BenchmarkResult, output: string):
   };
 }
 
+/**
+ * Record where the full verification output was saved.
+ */
+export function withVerificationLog(result: BenchmarkResult, path: string): BenchmarkResult {
+  return {
+    ...result,
+    verification_log: path,
+  };
+}
+
+/**
+ * Record the task's expected and human baseline durations on a result.
+ */
+export function withExpectations(
+  result: BenchmarkResult,
+  expectedDurationSecs: number | null,
+  humanBaselineSecs: number | null
+): BenchmarkResult {
+  return {
+    ...result,
+    expected_duration_secs: expectedDurationSecs,
+    human_baseline_secs: humanBaselineSecs,
+  };
+}
+
+/**
+ * Record multi-step checkpoint progress; the score reflects the fraction of checkpoints reached.
+ */
+export function withCheckpoints(result: BenchmarkResult, reached: number, total: number): BenchmarkResult {
+  return {
+    ...result,
+    score: total > 0 ? Math.round((reached / total) * 100) : result.score,
+    checkpoints_reached: reached,
+    total_checkpoints: total,
+  };
+}
+
+/**
+ * Record the files the agent added, modified, and deleted in its workspace.
+ */
+export function withWorkspaceChanges(result: BenchmarkResult, changes: WorkspaceChanges): BenchmarkResult {
+  return {
+    ...result,
+    workspace_changes: changes,
+  };
+}
+
+/**
+ * Record how long the task's workspace took to prepare.
+ */
+export function withSetupTime(result: BenchmarkResult, setupSecs: number): BenchmarkResult {
+  return {
+    ...result,
+    setup_secs: setupSecs,
+  };
+}
+
+/**
+ * Record the agent's token usage and the execution's cost.
+ */
+export function withCost(result: BenchmarkResult, usage: TokenUsage | null, costUsd: number | null): BenchmarkResult {
+  return {
+    ...result,
+    token_usage: usage,
+    cost_usd: costUsd,
+  };
+}
+
+/**
+ * Record the time the task spent in each phase.
+ */
+export function withPhaseTimings(result: BenchmarkResult, phases: PhaseTimings): BenchmarkResult {
+  return {
+    ...result,
+    phases,
+  };
+}
+
+/**
+ * Record which pooled credential the agent ran with.
+ */
+export function withCredentialName(result: BenchmarkResult, credential: string): BenchmarkResult {
+  return {
+    ...result,
+    credential,
+  };
+}
+
+/**
+ * Record the worker that executed a task of a distributed run.
+ */
+export function withWorker(result: BenchmarkResult, worker: string): BenchmarkResult {
+  return {
+    ...result,
+    worker,
+  };
+}
+
+/**
+ * Record the background processes the task left running in its workspace.
+ */
+export function withLeakedProcesses(result: BenchmarkResult, processes: WorkspaceProcess[]): BenchmarkResult {
+  return {
+    ...result,
+    leaked_processes: processes,
+  };
+}
+
+/**
+ * Record the task's tags on a result.
+ */
+export function withTags(result: BenchmarkResult, tags: string[]): BenchmarkResult {
+  return {
+    ...result,
+    tags,
+  };
+}
+
+/**
+ * Record the run a result belongs to and its per-task log file.
+ */
+export function withRunLog(result: BenchmarkResult, runId: string, logPath: string | null): BenchmarkResult {
+  return {
+    ...result,
+    run_id: runId,
+    log_path: logPath,
+  };
+}
+
 /**
  * Add agent output to a result.
  */
@@ -104,7 +397,8 @@ export function withAgentOutput(result: BenchmarkResult, output: string): Benchm
 }
 
 /**
- * Save a benchmark result to a JSON file.
+ * Save a benchmark result to a JSON file. The file appears complete or not at all, and a
+ * concurrent save of the same task and agent in the same second gets a `_2` suffix.
  * @returns The path to the saved file
  */
 export async function saveResult(result: BenchmarkResult, resultsDir: string): Promise<string> {
@@ -113,10 +407,8 @@ export async function saveResult(result: BenchmarkResult, resultsDir: string): P
   const timestamp = new Date(result.timestamp).toISOString().replace(/[:.]/g, '-').split('T')[0] +
                     '_' + new Date(result.timestamp).toISOString().replace(/[:.]/g, '-').split('T')[1].split('Z')[0].substring(0, 6);
   const status = result.success ? 'pass' : 'fail';
-  const filename = `${result.task_id}_${result.agent}_${timestamp}_${status}.json`;
-  const path = join(resultsDir, filename);
-
-  await writeFile(path, JSON.stringify(result, null, 2), 'utf-8');
+  const filename = `${result.task_id}_${result.agent}_${timestamp}_${status}`;
+  const path = await createUniqueFile(join(resultsDir, filename), '.json', JSON.stringify(result, null, 2));
 
   // Auto-append to summary CSV
   try {
@@ -131,6 +423,36 @@ export async function saveResult(result: BenchmarkResult, resultsDir: string): P
   return path;
 }
 
+/**
+ * Create a run ID (`YYYYMMDD-HHMMSS-xxxx`) naming the per-run results directory.
+ */
+export function createRunId(date: Date = new Date()): string {
+  const stamp = date.toISOString().replace(/[-:]/g, '').replace('T', '-').substring(0, 15);
+  const suffix = Math.floor(Math.random() * 0x10000).toString(16).padStart(4, '0');
+  return `${stamp}-${suffix}`;
+}
+
+/**
+ * Path of a run's combined log file: `<results-dir>/<run>/run.log`.
+ */
+export function getRunLogPath(resultsDir: string, runId: string): string {
+  return join(resultsDir, runId, 'run.log');
+}
+
+/**
+ * Path of a task's log file within a run: `<results-dir>/<run>/<task>/run.log`.
+ */
+export function getTaskLogPath(resultsDir: string, runId: string, taskId: string): string {
+  return join(resultsDir, runId, taskId, 'run.log');
+}
+
+/**
+ * Path of a task's full verification output within a run: `<results-dir>/<run>/<task>/verification.log`.
+ */
+export function getVerificationLogPath(resultsDir: string, runId: string, taskId: string): string {
+  return join(resultsDir, runId, taskId, 'verification.log');
+}
+
 /**
  * Suite results for multiple tasks.
  */
@@ -141,17 +463,30 @@ export interface SuiteResults {
   total_tasks: number;
   passed: number;
   failed: number;
+  skipped: number;
   pass_rate: number;
   total_duration_secs: number;
+  /** Seconds spent fetching the suite's repositories before its tasks ran (null: no prefetch) */
+  prefetch_secs: number | null;
+  /** USD cost of the tasks with a known cost (null: none) */
+  cost_usd: number | null;
+  expected_duration_ratio: number | null;
+  human_baseline_ratio: number | null;
+  regressions: string[];
+  /** False while the run is in progress, or if it was interrupted before the last task */
+  complete: boolean;
 }
 
 /**
  * Create suite results from individual benchmark results.
  */
 export function createSuiteResults(agent: string, results: BenchmarkResult[]): SuiteResults {
-  const passed = results.filter(r => r.success).length;
-  const failed = results.filter(r => !r.success).length;
+  const passed = results.filter(r => getOutcome(r) === 'passed').length;
+  const failed = results.filter(r => getOutcome(r) === 'failed').length;
+  const skipped = results.filter(r => getOutcome(r) === 'skipped').length;
+  const executed = passed + failed;
   const totalDuration = results.reduce((sum, r) => sum + r.duration_secs, 0);
+  const costs = results.flatMap(r => (r.cost_usd !== null && r.cost_usd !== undefined ? [r.cost_usd] : []));
 
   return {
     agent,
@@ -160,13 +495,65 @@ export function createSuiteResults(agent: string, results: BenchmarkResult[]): S
     total_tasks: results.length,
     passed,
     failed,
-    pass_rate: results.length > 0 ? passed / results.length : 0,
+    skipped,
+    pass_rate: executed > 0 ? passed / executed : 0,
     total_duration_secs: totalDuration,
+    prefetch_secs: null,
+    cost_usd: costs.length > 0 ? costs.reduce((sum, cost) => sum + cost, 0) : null,
+    expected_duration_ratio: durationRatio(results, r => r.expected_duration_secs),
+    human_baseline_ratio: durationRatio(results, r => r.human_baseline_secs),
+    regressions: [],
+    complete: true,
   };
 }
 
 /**
- * Save suite results to a JSON file.
+ * IDs of tasks that failed in `results` but passed in the agent's most recent earlier run.
+ * @param history Previously stored results (any agent, any order)
+ */
+export function findRegressions(results: BenchmarkResult[], history: BenchmarkResult[]): string[] {
+  const regressions: string[] = [];
+  for (const result of results) {
+    if (getOutcome(result) !== 'failed') {
+      continue;
+    }
+    const previous = history
+      .filter(r =>
+        r.task_id === result.task_id &&
+        r.agent === result.agent &&
+        r.timestamp < result.timestamp &&
+        (result.run_id === null || r.run_id !== result.run_id) &&
+        getOutcome(r) !== 'skipped'
+      )
+      .sort((a, b) => b.timestamp.localeCompare(a.timestamp))[0];
+    if (previous && getOutcome(previous) === 'passed') {
+      regressions.push(result.task_id);
+    }
+  }
+  return regressions;
+}
+
+/**
+ * Ratio of agent time to a reference time, over the executed results that declare the reference.
+ */
+function durationRatio(
+  results: BenchmarkResult[],
+  reference: (result: BenchmarkResult) => number | null | undefined
+): number | null {
+  let actual = 0;
+  let expected = 0;
+  for (const result of results) {
+    const referenceSecs = reference(result);
+    if (referenceSecs && getOutcome(result) !== 'skipped') {
+      actual += result.duration_secs;
+      expected += referenceSecs;
+    }
+  }
+  return expected > 0 ? actual / expected : null;
+}
+
+/**
+ * Save suite results to a JSON file (atomically, never replacing another suite's file).
  * @returns The path to the saved file
  */
 export async function saveSuiteResults(suite: SuiteResults, resultsDir: string): Promise<string> {
@@ -174,10 +561,35 @@ export async function saveSuiteResults(suite: SuiteResults, resultsDir: string):
 
   const timestamp = new Date(suite.timestamp).toISOString().replace(/[:.]/g, '-').split('T')[0] +
                     '_' + new Date(suite.timestamp).toISOString().replace(/[:.]/g, '-').split('T')[1].split('Z')[0].substring(0, 6);
-  const filename = `suite_${suite.agent}_${timestamp}.json`;
-  const path = join(resultsDir, filename);
+  const filename = `suite_${suite.agent}_${timestamp}`;
+  return await createUniqueFile(join(resultsDir, filename), '.json', JSON.stringify(suite, null, 2));
+}
 
-  await writeFile(path, JSON.stringify(suite, null, 2), 'utf-8');
+/**
+ * Path of a run's suite record: `<results-dir>/<run>/suite.json`, rewritten after every task.
+ */
+export function getSuiteRecordPath(resultsDir: string, runId: string): string {
+  return join(resultsDir, runId, 'suite.json');
+}
 
+/**
+ * Replace a run's suite record, atomically so a crash leaves the previous version intact.
+ * @returns Path of the record
+ */
+export async function writeSuiteRecord(suite: SuiteResults, resultsDir: string, runId: string): Promise<string> {
+  const path = getSuiteRecordPath(resultsDir, runId);
+  await mkdir(dirname(path), { recursive: true });
+  await writeFileAtomic(path, JSON.stringify(suite, null, 2));
   return path;
 }
+
+/**
+ * Read a run's suite record, or null if the run has none.
+ */
+export async function readSuiteRecord(resultsDir: string, runId: string): Promise<SuiteResults | null> {
+  try {
+    return JSON.parse(await readFile(getSuiteRecordPath(resultsDir, runId), 'utf-8')) as SuiteResults;
+  } catch {
+    return null;
+  }
+}
@@ -0,0 +1,129 @@
+/**
+ * Summary statistics over stored results.
+ */
+
+import type { BenchmarkResult } from './results.js';
+import { getOutcome } from './results.js';
+import { getOverheadSecs } from '../core/phases.js';
+import { summarizeCosts, type CostSummary, type PricingTable } from './pricing.js';
+
+/**
+ * Aggregate statistics for a group of results (all results, one agent, or one category).
+ */
+export interface GroupStats {
+  name: string;
+  runs: number;
+  passed: number;
+  failed: number;
+  skipped: number;
+  passRate: number;
+  medianDurationSecs: number | null;
+  /** Median time in the agent, over runs with phase timings */
+  medianAgentSecs: number | null;
+  /** Median harness time (toolchain, workspace, verification), over runs with phase timings */
+  medianOverheadSecs: number | null;
+  totalTokens: number;
+}
+
+/**
+ * A task that failed in at least one stored run.
+ */
+export interface FailedTask {
+  taskId: string;
+  failures: number;
+  runs: number;
+}
+
+/**
+ * Health overview of the results directory.
+ */
+export interface ResultStats {
+  overall: GroupStats;
+  byAgent: GroupStats[];
+  byCategory: GroupStats[];
+  mostFailed: FailedTask[];
+  /** Token usage and cost per agent and model */
+  costs: CostSummary[];
+}
+
+/**
+ * Median of a list of numbers, or null when empty.
+ */
+function median(values: number[]): number | null {
+  if (values.length === 0) return null;
+  const sorted = [...values].sort((a, b) => a - b);
+  const middle = Math.floor(sorted.length / 2);
+  return sorted.length % 2 === 1 ? sorted[middle] : (sorted[middle - 1] + sorted[middle]) / 2;
+}
+
+/**
+ * Aggregate a group of results. Pass rates and durations only count executed (non-skipped) runs.
+ */
+function groupStats(name: string, results: BenchmarkResult[]): GroupStats {
+  const executed = results.filter(r => getOutcome(r) !== 'skipped');
+  const passed = executed.filter(r => getOutcome(r) === 'passed').length;
+  const timed = executed.flatMap(r => (r.phases ? [r.phases] : []));
+
+  return {
+    name,
+    runs: results.length,
+    passed,
+    failed: executed.length - passed,
+    skipped: results.length - executed.length,
+    passRate: executed.length > 0 ? passed / executed.length : 0,
+    medianDurationSecs: median(executed.map(r => r.duration_secs)),
+    medianAgentSecs: median(timed.map(phases => phases.agent_secs ?? 0)),
+    medianOverheadSecs: median(timed.map(getOverheadSecs)),
+    totalTokens: results.reduce((sum, r) => sum + (r.tokens_used || 0), 0),
+  };
+}
+
+/**
+ * Group results by a key and aggregate each group, sorted by name.
+ */
+function groupBy(results: BenchmarkResult[], key: (result: BenchmarkResult) => string): GroupStats[] {
+  const groups = new Map<string, BenchmarkResult[]>();
+  for (const result of results) {
+    const name = key(result);
+    if (!groups.has(name)) groups.set(name, []);
+    groups.get(name)!.push(result);
+  }
+  return [...groups.entries()]
+    .map(([name, group]) => groupStats(name, group))
+    .sort((a, b) => a.name.localeCompare(b.name));
+}
+
+/**
+ * Summarize stored results per agent and per category, and list the most-failed tasks.
+ * @param categoryOf Category of a task ID (tasks no longer in the tasks directory map to "unknown")
+ * @param top Number of most-failed tasks to report
+ * @param pricing Prices to re-price results with (results keep their stored cost otherwise)
+ */
+export function computeStats(
+  results: BenchmarkResult[],
+  categoryOf: (taskId: string) => string,
+  top: number = 5,
+  pricing: PricingTable = {}
+): ResultStats {
+  const failures = new Map<string, FailedTask>();
+  for (const result of results) {
+    const outcome = getOutcome(result);
+    if (outcome === 'skipped') continue;
+
+    const entry = failures.get(result.task_id) || { taskId: result.task_id, failures: 0, runs: 0 };
+    entry.runs++;
+    if (outcome === 'failed') entry.failures++;
+    failures.set(result.task_id, entry);
+  }
+
+  return {
+    overall: groupStats('all', results),
+    byAgent: groupBy(results, r => (r.model_name ? `${r.agent} (${r.model_name})` : r.agent)),
+    byCategory: groupBy(results, r => categoryOf(r.task_id)),
+    mostFailed: [...failures.values()]
+      .filter(entry => entry.failures > 0)
+      .sort((a, b) => b.failures - a.failures || b.failures / b.runs - a.failures / a.runs || a.taskId.localeCompare(b.taskId))
+      .slice(0, top),
+    costs: summarizeCosts(results, pricing),
+  };
+}
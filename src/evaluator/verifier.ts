@@ -2,9 +2,8 @@
  * Verification for task execution.
  */
 
-import { spawn } from 'child_process';
-import { Task } from '../core/task.js';
-import { VerificationError } from '../utils/errors.js';
+import type { Task } from '../core/task.js';
+import { getVerifier } from './verifiers/index.js';
 
 /**
  * Verification result.
@@ -12,91 +11,38 @@ import { VerificationError } from '../utils/errors.js';
 export interface VerificationResult {
   passed: boolean;
   exitCode: number | null;
+  /** Output, or its start and end if the full output was spooled to a file */
   stdout: string;
   stderr: string;
+  /** Temp files with the full output of streams too large to keep in memory (removed once the runner saved them) */
+  stdoutFile?: string | null;
+  stderrFile?: string | null;
   durationSecs: number;
 }
 
 /**
- * Verifier for running task verification commands.
+ * Runs a task's verification in a workspace. `Verifier` is the default;
+ * embedders can supply their own through `TaskRunnerBuilder.verifier()`.
+ */
+export interface TaskVerifier {
+  /**
+   * @param env Variables for the processes verification starts (the task's toolchain)
+   */
+  verify(task: Task, workspace: string, env?: Record<string, string>): Promise<VerificationResult>;
+}
+
+/**
+ * Default verifier: dispatches on `verification.type` to the implementations in verifiers/.
  */
 export class Verifier {
   /**
    * Run verification for a task in the given workspace.
    * @param task The task to verify
    * @param workspace The workspace path
+   * @param env Variables for the processes verification starts (the task's toolchain)
    * @returns Verification result
    */
-  static async verify(task: Task, workspace: string): Promise<VerificationResult> {
-    const startTime = Date.now();
-
-    // Parse the command
-    const commandParts = task.verification.command.match(/(?:[^\s"]+|"[^"]*")+/g) || [];
-    if (commandParts.length === 0) {
-      throw new VerificationError('Empty verification command');
-    }
-
-    const program = commandParts[0].replace(/"/g, '');
-    const args = commandParts.slice(1).map(arg => arg.replace(/"/g, ''));
-
-    // Execute command with timeout
-    return new Promise((resolve, reject) => {
-      const proc = spawn(program, args, {
-        cwd: workspace,
-        timeout: task.verification.timeout * 1000, // Convert to milliseconds
-      });
-
-      let stdout = '';
-      let stderr = '';
-
-      proc.stdout?.on('data', (data) => {
-        stdout += data.toString();
-      });
-
-      proc.stderr?.on('data', (data) => {
-        stderr += data.toString();
-      });
-
-      proc.on('close', (code) => {
-        const durationSecs = (Date.now() - startTime) / 1000;
-        resolve({
-          passed: code === 0,
-          exitCode: code,
-          stdout,
-          stderr,
-          durationSecs,
-        });
-      });
-
-      proc.on('error', (error) => {
-        const durationSecs = (Date.now() - startTime) / 1000;
-
-        // Check if it's a timeout error
-        if ((error as any).code === 'ETIMEDOUT') {
-          reject(
-            new VerificationError(
-              `Verification command timed out after ${task.verification.timeout} seconds`
-            )
-          );
-        } else {
-          reject(new VerificationError(`Failed to execute verification command: ${error.message}`));
-        }
-      });
-
-      // Additional timeout handling
-      const timeoutId = setTimeout(() => {
-        proc.kill('SIGTERM');
-        const durationSecs = (Date.now() - startTime) / 1000;
-        reject(
-          new VerificationError(
-            `Verification command timed out after ${task.verification.timeout} seconds`
-          )
-        );
-      }, task.verification.timeout * 1000);
-
-      proc.on('close', () => {
-        clearTimeout(timeoutId);
-      });
-    });
+  static async verify(task: Task, workspace: string, env: Record<string, string> = {}): Promise<VerificationResult> {
+    return await getVerifier(task.verification).verify(task, workspace, env);
   }
 }
@@ -0,0 +1,52 @@
+/**
+ * `command` verification: the command exits 0.
+ */
+
+import type { Task } from '../../core/task.js';
+import { exec } from '../../utils/exec.js';
+import { sandboxCommand } from '../../sandbox/index.js';
+import { ExecError, TimeoutError, VerificationError } from '../../utils/errors.js';
+import type { TaskVerifier, VerificationResult } from '../verifier.js';
+
+/**
+ * Run a verification command in the workspace (in the configured sandbox), collecting its output.
+ * @throws SandboxError If the configured sandbox is unavailable
+ * @throws VerificationError If the command cannot be parsed or started
+ * @throws TimeoutError If the command exceeds the timeout
+ */
+export async function runCommand(
+  command: string,
+  cwd: string,
+  timeoutSecs: number,
+  env: Record<string, string> = {}
+): Promise<VerificationResult> {
+  const context = { phase: 'verification', command };
+  let result;
+  try {
+    result = await exec(await sandboxCommand(command, cwd), { cwd, env, timeoutSecs });
+  } catch (error) {
+    if (error instanceof ExecError) {
+      throw new VerificationError(`Failed to execute verification command: ${error.message}`, context);
+    }
+    throw error;
+  }
+
+  if (result.timedOut) {
+    throw new TimeoutError(timeoutSecs, context);
+  }
+  return {
+    passed: result.exitCode === 0,
+    exitCode: result.exitCode,
+    stdout: result.stdout,
+    stderr: result.stderr,
+    stdoutFile: result.stdoutFile,
+    stderrFile: result.stderrFile,
+    durationSecs: result.durationSecs,
+  };
+}
+
+export const commandVerifier: TaskVerifier = {
+  verify(task: Task, workspace: string, env?: Record<string, string>): Promise<VerificationResult> {
+    return runCommand(task.verification.command || '', workspace, task.verification.timeout, env);
+  },
+};
@@ -0,0 +1,65 @@
+/**
+ * `file-assert` verification: assertions about workspace files (existence, content, pattern).
+ */
+
+import { readFile } from 'fs/promises';
+import { join, relative } from 'path';
+import type { FileAssertion, Task } from '../../core/task.js';
+import type { TaskVerifier, VerificationResult } from '../verifier.js';
+
+/**
+ * Check one assertion.
+ * @returns Why it does not hold, or null if it does
+ */
+async function check(assertion: FileAssertion, workspace: string): Promise<string | null> {
+  const path = join(workspace, assertion.path);
+  if (relative(workspace, path).startsWith('..')) {
+    return 'path is outside the workspace';
+  }
+
+  let content: string | null;
+  try {
+    content = await readFile(path, 'utf-8');
+  } catch {
+    content = null;
+  }
+
+  if (!assertion.exists) {
+    return content === null ? null : 'exists but should not';
+  }
+  if (content === null) {
+    return 'does not exist';
+  }
+  if (assertion.equals !== undefined && content.trimEnd() !== assertion.equals.trimEnd()) {
+    return 'content differs from expected';
+  }
+  if (assertion.contains !== undefined && !content.includes(assertion.contains)) {
+    return `does not contain "${assertion.contains}"`;
+  }
+  if (assertion.matches !== undefined && !new RegExp(assertion.matches, 'm').test(content)) {
+    return `does not match /${assertion.matches}/`;
+  }
+  return null;
+}
+
+export const fileAssertVerifier: TaskVerifier = {
+  async verify(task: Task, workspace: string): Promise<VerificationResult> {
+    const startTime = Date.now();
+    const lines: string[] = [];
+    let failures = 0;
+
+    for (const assertion of task.verification.assertions || []) {
+      const problem = await check(assertion, workspace);
+      if (problem) failures++;
+      lines.push(`${problem ? 'FAIL' : 'ok  '} ${assertion.path}${problem ? `: ${problem}` : ''}`);
+    }
+
+    return {
+      passed: failures === 0,
+      exitCode: failures === 0 ? 0 : 1,
+      stdout: lines.join('\n'),
+      stderr: '',
+      durationSecs: (Date.now() - startTime) / 1000,
+    };
+  },
+};
@@ -0,0 +1,68 @@
+/**
+ * `http` verification: a URL answers with the expected status and body.
+ * When `command` is set it is started first (e.g. the server under test, in the configured sandbox) and stopped afterwards;
+ * the URL is polled until it matches or the timeout expires.
+ */
+
+import type { Task } from '../../core/task.js';
+import type { TaskVerifier, VerificationResult } from '../verifier.js';
+import { startProcess, type RunningProcess } from '../../utils/exec.js';
+import { disposeSpoolFiles } from '../../utils/spool.js';
+import { sandboxCommand } from '../../sandbox/index.js';
+
+/**
+ * Delay between polls of the URL.
+ */
+const POLL_INTERVAL_MS = 500;
+
+export const httpVerifier: TaskVerifier = {
+  async verify(task: Task, workspace: string, env?: Record<string, string>): Promise<VerificationResult> {
+    const verification = task.verification;
+    const startTime = Date.now();
+    const deadline = startTime + verification.timeout * 1000;
+    const expectedStatus = verification.expect_status ?? 200;
+
+    let serverOutput = '';
+    let server: RunningProcess | null = null;
+    if (verification.command) {
+      server = startProcess(await sandboxCommand(verification.command, workspace), {
+        cwd: workspace,
+        env,
+        onOutput: (chunk) => { serverOutput += chunk; },
+      });
+      server.done.catch((error) => { serverOutput += `\nFailed to start server: ${error.message}`; });
+    }
+
+    let last = 'no response';
+    try {
+      while (Date.now() < deadline) {
+        try {
+          const response = await fetch(verification.url || '', {
+            signal: AbortSignal.timeout(Math.max(1, deadline - Date.now())),
+          });
+          const body = await response.text();
+          const bodyMatches = !verification.expect_body || body.includes(verification.expect_body);
+          last = `HTTP ${response.status} (${body.length} bytes)${bodyMatches ? '' : `, body lacks "${verification.expect_body}"`}`;
+          if (response.status === expectedStatus && bodyMatches) {
+            return result(true, `${last}\n${body.substring(0, 2000)}`, serverOutput, startTime);
+          }
+        } catch (error) {
+          last = error instanceof Error ? error.message : String(error);
+        }
+        if (server && server.exitCode !== null) {
+          last = `server exited with code ${server.exitCode} (${last})`;
+          break;
+        }
+        await Bun.sleep(POLL_INTERVAL_MS);
+      }
+      return result(false, `Expected HTTP ${expectedStatus} from ${verification.url}; last: ${last}`, serverOutput, startTime);
+    } finally {
+      await server?.kill();
+      await server?.done.then(done => disposeSpoolFiles(done.stdoutFile, done.stderrFile), () => undefined);
+    }
+  },
+};
+
+function result(passed: boolean, stdout: string, stderr: string, startTime: number): VerificationResult {
+  return { passed, exitCode: passed ? 0 : 1, stdout, stderr, durationSecs: (Date.now() - startTime) / 1000 };
+}
@@ -0,0 +1,27 @@
+/**
+ * Verifier implementations by verification type.
+ */
+
+import { getVerificationType } from '../../core/task.js';
+import type { VerificationConfig, VerificationType } from '../../core/task.js';
+import type { TaskVerifier } from '../verifier.js';
+import { commandVerifier } from './command.js';
+import { junitVerifier } from './junit.js';
+import { httpVerifier } from './http.js';
+import { fileAssertVerifier } from './file-assert.js';
+import { llmJudgeVerifier } from './llm-judge.js';
+
+export const VERIFIERS: Record<VerificationType, TaskVerifier> = {
+  command: commandVerifier,
+  junit: junitVerifier,
+  http: httpVerifier,
+  'file-assert': fileAssertVerifier,
+  'llm-judge': llmJudgeVerifier,
+};
+
+/**
+ * The verifier for a verification config (unknown types run as commands).
+ */
+export function getVerifier(config: VerificationConfig): TaskVerifier {
+  return VERIFIERS[getVerificationType(config)];
+}
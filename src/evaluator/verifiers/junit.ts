@@ -0,0 +1,81 @@
+/**
+ * `junit` verification: the command writes a JUnit XML report with at least one test and no failures or errors.
+ * The report is deleted before the command runs, so only one the command writes counts. The command must exit 0
+ * or with one of `allowed_exit_codes` (test runners that exit non-zero on failures the report already records).
+ */
+
+import { readFile, rm } from 'fs/promises';
+import { join, relative } from 'path';
+import type { Task } from '../../core/task.js';
+import type { TaskVerifier, VerificationResult } from '../verifier.js';
+import { runCommand } from './command.js';
+import { VerificationError } from '../../utils/errors.js';
+
+/**
+ * Test counts from a JUnit report.
+ */
+export interface JUnitSummary {
+  tests: number;
+  failures: number;
+  errors: number;
+  skipped: number;
+  failed: string[];
+}
+
+/**
+ * Summarize a JUnit XML report by its test cases (suite-level counts are not always present).
+ */
+export function summarizeJUnit(xml: string): JUnitSummary {
+  const summary: JUnitSummary = { tests: 0, failures: 0, errors: 0, skipped: 0, failed: [] };
+  const testcase = /<testcase\b([^>]*?)(?:\/>|>([\s\S]*?)<\/testcase>)/g;
+  let match: RegExpExecArray | null;
+  while ((match = testcase.exec(xml))) {
+    summary.tests++;
+    const body = match[2] || '';
+    const name = /\bname="([^"]*)"/.exec(match[1])?.[1] ?? `#${summary.tests}`;
+    if (/<failure\b/.test(body)) {
+      summary.failures++;
+      summary.failed.push(name);
+    } else if (/<error\b/.test(body)) {
+      summary.errors++;
+      summary.failed.push(name);
+    } else if (/<skipped\b/.test(body)) {
+      summary.skipped++;
+    }
+  }
+  return summary;
+}
+
+export const junitVerifier: TaskVerifier = {
+  async verify(task: Task, workspace: string, env?: Record<string, string>): Promise<VerificationResult> {
+    const verification = task.verification;
+    const reportPath = join(workspace, verification.report || '');
+    if (relative(workspace, reportPath).startsWith('..')) {
+      throw new VerificationError(`JUnit report ${verification.report} is outside the workspace`, { phase: 'verification' });
+    }
+    // A report already in the workspace (committed, or written by the agent) must not count
+    await rm(reportPath, { force: true });
+    const run = await runCommand(verification.command || '', workspace, verification.timeout, env);
+
+    let xml: string;
+    try {
+      xml = await readFile(reportPath, 'utf-8');
+    } catch {
+      return { ...run, passed: false, stderr: `${run.stderr}\nThe command did not write the JUnit report: ${verification.report}` };
+    }
+
+    const allowedExitCodes = [0, ...(verification.allowed_exit_codes ?? [])];
+    const exitAllowed = run.exitCode !== null && allowedExitCodes.includes(run.exitCode);
+    const summary = summarizeJUnit(xml);
+    const lines = [
+      `JUnit: ${summary.tests} tests, ${summary.failures} failures, ${summary.errors} errors, ${summary.skipped} skipped`,
+      ...summary.failed.map(name => `  failed: ${name}`),
+      ...(exitAllowed ? [] : [`Command exited with ${run.exitCode} (allowed: ${allowedExitCodes.join(', ')})`]),
+    ];
+    return {
+      ...run,
+      passed: exitAllowed && summary.tests > 0 && summary.failures === 0 && summary.errors === 0,
+      stdout: `${lines.join('\n')}\n\n${run.stdout}`,
+    };
+  },
+};
@@ -0,0 +1,159 @@
+/**
+ * `llm-judge` verification: a model grades the agent's changes against a rubric.
+ *
+ * The judge sees the task prompt, the rubric, the workspace diff against HEAD,
+ * and the contents of `files`, and must answer with JSON
+ * `{"passed": bool, "reasoning": string}`. Supported providers: anthropic,
+ * openai (OPENAI_BASE_URL for compatible endpoints), and openrouter.
+ */
+
+import { readFile } from 'fs/promises';
+import { join } from 'path';
+import type { Task } from '../../core/task.js';
+import { git } from '../../core/git.js';
+import { parseModel } from '../../agents/types.js';
+import { VerificationError } from '../../utils/errors.js';
+import type { TaskVerifier, VerificationResult } from '../verifier.js';
+
+/**
+ * Judge model when neither the task nor AGENT_BENCH_JUDGE_MODEL names one.
+ */
+const DEFAULT_JUDGE_MODEL = 'anthropic/claude-sonnet-4-5';
+
+/**
+ * Largest diff or file excerpt sent to the judge.
+ */
+const MAX_CONTEXT_CHARS = 100_000;
+
+const SYSTEM_PROMPT =
+  'You grade changes made by a coding agent. Judge only against the rubric. ' +
+  'Reply with a single JSON object: {"passed": true|false, "reasoning": "<one paragraph>"}.';
+
+function truncate(text: string): string {
+  return text.length > MAX_CONTEXT_CHARS ? `${text.substring(0, MAX_CONTEXT_CHARS)}\n[truncated]` : text;
+}
+
+/**
+ * Collect the workspace diff (tracked changes and new files) and the requested files.
+ */
+async function buildContext(task: Task, workspace: string): Promise<string> {
+  const repo = git(workspace);
+  let diff: string;
+  try {
+    diff = await repo.diff(['HEAD']);
+    const untracked = await repo.untrackedFiles();
+    if (untracked.length > 0) {
+      diff += `\nNew files: ${untracked.join(', ')}`;
+    }
+  } catch {
+    diff = '(workspace is not a git repository)';
+  }
+
+  const sections = [`## Diff\n${truncate(diff) || '(no changes)'}`];
+  for (const path of task.verification.files || []) {
+    let content: string;
+    try {
+      content = truncate(await readFile(join(workspace, path), 'utf-8'));
+    } catch {
+      content = '(missing)';
+    }
+    sections.push(`## ${path}\n${content}`);
+  }
+  return sections.join('\n\n');
+}
+
+/**
+ * Send the grading request to the judge's provider and return its reply text.
+ */
+async function complete(model: string, prompt: string, timeoutSecs: number): Promise<string> {
+  const { providerID, modelID } = parseModel(model);
+  const signal = AbortSignal.timeout(timeoutSecs * 1000);
+  const requireKey = (name: string): string => {
+    const key = process.env[name];
+    if (!key) throw new VerificationError(`llm-judge with ${providerID} needs ${name}`);
+    return key;
+  };
+
+  if (providerID === 'anthropic') {
+    const response = await fetch('https://api.anthropic.com/v1/messages', {
+      method: 'POST',
+      signal,
+      headers: {
+        'content-type': 'application/json',
+        'x-api-key': requireKey('ANTHROPIC_API_KEY'),
+        'anthropic-version': '2023-06-01',
+      },
+      body: JSON.stringify({
+        model: modelID,
+        max_tokens: 1024,
+        system: SYSTEM_PROMPT,
+        messages: [{ role: 'user', content: prompt }],
+      }),
+    });
+    const body: any = await response.json();
+    if (!response.ok) throw new VerificationError(`Judge request failed: HTTP ${response.status} ${JSON.stringify(body)}`);
+    return (body.content || []).map((block: any) => block.text || '').join('');
+  }
+
+  if (providerID === 'openai' || providerID === 'openrouter') {
+    const baseUrl = providerID === 'openrouter'
+      ? 'https://openrouter.ai/api/v1'
+      : process.env.OPENAI_BASE_URL || 'https://api.openai.com/v1';
+    const key = requireKey(providerID === 'openrouter' ? 'OPENROUTER_API_KEY' : 'OPENAI_API_KEY');
+    const response = await fetch(`${baseUrl}/chat/completions`, {
+      method: 'POST',
+      signal,
+      headers: { 'content-type': 'application/json', authorization: `Bearer ${key}` },
+      body: JSON.stringify({
+        model: modelID,
+        messages: [
+          { role: 'system', content: SYSTEM_PROMPT },
+          { role: 'user', content: prompt },
+        ],
+      }),
+    });
+    const body: any = await response.json();
+    if (!response.ok) throw new VerificationError(`Judge request failed: HTTP ${response.status} ${JSON.stringify(body)}`);
+    return body.choices?.[0]?.message?.content || '';
+  }
+
+  throw new VerificationError(`llm-judge does not support provider "${providerID}" (anthropic, openai, openrouter)`);
+}
+
+/**
+ * Extract the verdict from the judge's reply (the first JSON object in it).
+ */
+export function parseVerdict(reply: string): { passed: boolean; reasoning: string } {
+  const json = reply.match(/\{[\s\S]*\}/)?.[0];
+  try {
+    const verdict = JSON.parse(json || '');
+    if (typeof verdict.passed === 'boolean') {
+      return { passed: verdict.passed, reasoning: String(verdict.reasoning ?? '') };
+    }
+  } catch {
+    // Fall through to the error below
+  }
+  throw new VerificationError(`Judge reply has no verdict: ${reply.substring(0, 500)}`);
+}
+
+export const llmJudgeVerifier: TaskVerifier = {
+  async verify(task: Task, workspace: string): Promise<VerificationResult> {
+    const startTime = Date.now();
+    const model = task.verification.model || process.env.AGENT_BENCH_JUDGE_MODEL || DEFAULT_JUDGE_MODEL;
+    const prompt = [
+      `# Task given to the agent\n${task.prompt}`,
+      `# Rubric\n${task.verification.rubric}`,
+      await buildContext(task, workspace),
+    ].join('\n\n');
+
+    const reply = await complete(model, prompt, task.verification.timeout);
+    const verdict = parseVerdict(reply);
+    return {
+      passed: verdict.passed,
+      exitCode: verdict.passed ? 0 : 1,
+      stdout: `Judge (${model}): ${verdict.passed ? 'passed' : 'failed'}\n${verdict.reasoning}`,
+      stderr: '',
+      durationSecs: (Date.now() - startTime) / 1000,
+    };
+  },
+};
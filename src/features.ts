@@ -0,0 +1,49 @@
+/**
+ * Optional features, chosen at build time.
+ *
+ * `scripts/build.ts --features ...` defines AGENT_BENCH_FEATURES and replaces the modules of
+ * disabled features with empty stubs, so a slim build (local JSON workflow only) leaves out
+ * their code. Running from source enables every feature. Feature modules are only loaded
+ * through dynamic imports guarded by `requireFeature`.
+ */
+
+import { FeatureError } from './utils/errors.js';
+
+/**
+ * `sql`: SQLite and Postgres result backends. `server`: the REST API (`serve-api`) and
+ * distributed runs (`worker`, `coordinate`).
+ */
+export const FEATURES = ['sql', 'server'] as const;
+export type Feature = (typeof FEATURES)[number];
+
+/**
+ * Comma-separated features, defined by the build script (undefined when running from source).
+ */
+declare const AGENT_BENCH_FEATURES: string | undefined;
+
+const enabled: readonly string[] =
+  typeof AGENT_BENCH_FEATURES === 'string' ? AGENT_BENCH_FEATURES.split(',').filter(Boolean) : FEATURES;
+
+/**
+ * Whether this build includes a feature.
+ */
+export function hasFeature(feature: Feature): boolean {
+  return enabled.includes(feature);
+}
+
+/**
+ * Features included in this build.
+ */
+export function getEnabledFeatures(): Feature[] {
+  return FEATURES.filter(hasFeature);
+}
+
+/**
+ * @param purpose What needs the feature, for the error message (e.g. "The sqlite results backend")
+ * @throws FeatureError If this build does not include the feature
+ */
+export function requireFeature(feature: Feature, purpose: string): void {
+  if (!hasFeature(feature)) {
+    throw new FeatureError(`${purpose} requires the "${feature}" feature, which this build leaves out (rebuild with --features ${feature})`);
+  }
+}
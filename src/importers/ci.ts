@@ -0,0 +1,89 @@
+/**
+ * Import bug-fix tasks from failing CI runs.
+ */
+
+import { readFile } from 'fs/promises';
+import { TASK_SCHEMA_VERSION } from '../core/task.js';
+
+/**
+ * A CI failure to turn into a task.
+ */
+export interface CiFailure {
+  repository: string;
+  commit: string;
+  command: string;
+  log: string | null;
+  id?: string;
+  title?: string;
+  timeout?: number;
+}
+
+/**
+ * Maximum number of log lines embedded in the generated prompt.
+ */
+const MAX_LOG_LINES = 60;
+
+/**
+ * Read a CI failure log, keeping only the tail that usually contains the failure.
+ */
+export async function readFailureLog(path: string): Promise<string> {
+  const content = await readFile(path, 'utf-8');
+  return tailLines(content, MAX_LOG_LINES);
+}
+
+/**
+ * Build a bug-fix task definition (ready to serialize as YAML) from a CI failure.
+ * The failing command becomes the verification command. The prompt describes the failure
+ * through the log without quoting the command (lint's `solution-leak` rule would flag it),
+ * so lines of the log that echo it are replaced with a placeholder.
+ */
+export function taskFromCiFailure(failure: CiFailure): Record<string, unknown> {
+  const id = failure.id || `CI-${failure.commit.substring(0, 8).toUpperCase()}`;
+  const repoName = failure.repository.replace(/\.git$/, '').split('/').pop() || failure.repository;
+
+  const promptLines = [
+    `The CI pipeline for ${repoName} fails at commit ${failure.commit}: one of its test commands exits with an error.`,
+  ];
+  if (failure.log) {
+    const tail = tailLines(failure.log, MAX_LOG_LINES);
+    const log = failure.command ? tail.split(failure.command).join('<failing CI command>') : tail;
+    promptLines.push('', 'Failure log (tail):', '', '```', log, '```');
+  }
+  promptLines.push('', 'Find the root cause and fix the code so that the failing tests pass. Do not modify or skip the failing tests.');
+
+  return {
+    schema: TASK_SCHEMA_VERSION,
+    id,
+    title: failure.title || `Fix failing CI: ${failure.command}`.substring(0, 80),
+    category: 'bug-fix',
+    difficulty: 'medium',
+    source: {
+      repository: failure.repository,
+      commit: failure.commit,
+    },
+    prompt: promptLines.join('\n') + '\n',
+    verification: {
+      type: 'command',
+      command: failure.command,
+      timeout: failure.timeout ?? 300,
+    },
+    permissions: {
+      approval: 'auto',
+      read: true,
+      write: true,
+      execute: true,
+      network: false,
+    },
+    metadata: {
+      tags: ['source:ci'],
+    },
+  };
+}
+
+/**
+ * Keep the last `count` lines of a text.
+ */
+function tailLines(text: string, count: number): string {
+  const lines = text.trimEnd().split('\n');
+  return lines.slice(-count).join('\n');
+}
@@ -0,0 +1,169 @@
+/**
+ * Import function-completion problems (HumanEval, MBPP) as tasks.
+ */
+
+import { readFile } from 'fs/promises';
+import { TASK_SCHEMA_VERSION } from '../core/task.js';
+
+/**
+ * Supported problem set formats.
+ */
+export type CodegenFormat = 'humaneval' | 'mbpp';
+
+/**
+ * A generated task together with its conventional asset files.
+ */
+export interface ImportedTask {
+  task: Record<string, unknown>;
+  /** Files copied into the workspace before the agent runs (`<ID>/fixtures/`). */
+  fixtures: Record<string, string>;
+  /** Files copied into the workspace only for verification (`<ID>/hidden_tests/`). */
+  hiddenTests: Record<string, string>;
+}
+
+/**
+ * Module the agent implements and the tests import from.
+ */
+const SOLUTION_FILE = 'solution.py';
+const TEST_FILE = 'test_solution.py';
+
+/**
+ * Read problems from a JSONL (one problem per line) or JSON array file.
+ */
+export async function readProblems(path: string): Promise<Record<string, unknown>[]> {
+  const content = (await readFile(path, 'utf-8')).trim();
+  if (content.startsWith('[')) {
+    return JSON.parse(content);
+  }
+  return content
+    .split('\n')
+    .filter(line => line.trim().length > 0)
+    .map(line => JSON.parse(line));
+}
+
+/**
+ * Detect the format of a problem record from its fields.
+ */
+export function detectFormat(problem: Record<string, unknown>): CodegenFormat {
+  if ('entry_point' in problem) {
+    return 'humaneval';
+  }
+  if ('test_list' in problem) {
+    return 'mbpp';
+  }
+  throw new Error(`Unrecognized problem format (task_id: ${problem.task_id ?? 'unknown'})`);
+}
+
+/**
+ * Convert a problem record into a task with `repository: none`, a starter
+ * `solution.py` fixture, and a generated pytest verification.
+ */
+export function taskFromProblem(problem: Record<string, unknown>, format: CodegenFormat = detectFormat(problem)): ImportedTask {
+  return format === 'humaneval' ? fromHumanEval(problem) : fromMbpp(problem);
+}
+
+function fromHumanEval(problem: Record<string, unknown>): ImportedTask {
+  const entryPoint = String(problem.entry_point);
+  const starter = String(problem.prompt);
+  const number = String(problem.task_id).split('/').pop() ?? '';
+  const id = `HUMANEVAL-${number.padStart(3, '0')}`;
+
+  const tests = [
+    `from solution import ${entryPoint}`,
+    '',
+    String(problem.test).trim(),
+    '',
+    '',
+    `def test_${entryPoint}():`,
+    `    check(${entryPoint})`,
+    '',
+  ].join('\n');
+
+  return buildTask(id, `HumanEval ${number}: ${entryPoint}`, 'humaneval', entryPoint, starter, tests);
+}
+
+function fromMbpp(problem: Record<string, unknown>): ImportedTask {
+  const code = String(problem.code);
+  const testList = (problem.test_list as string[]) ?? [];
+  // The entry point is the function defined by the reference solution that the tests call
+  const defined = [...code.matchAll(/def\s+(\w+)\s*\(/g)].map(match => match[1]);
+  const entryPoint = defined.find(name => testList.some(assertion => assertion.includes(`${name}(`)));
+  if (!entryPoint) {
+    throw new Error(`Cannot determine entry point for MBPP problem ${problem.task_id}`);
+  }
+
+  const signature = code.match(new RegExp(`def\\s+${entryPoint}\\s*\\([^)]*\\)\\s*:`))?.[0] ?? `def ${entryPoint}(*args):`;
+  const description = String(problem.text).trim();
+  const starter = [
+    signature,
+    `    """${description.replace(/"""/g, "'''")}"""`,
+    '    raise NotImplementedError',
+    '',
+  ].join('\n');
+
+  const setup = String(problem.test_setup_code ?? '').trim();
+  const tests = [
+    'from solution import *',
+    '',
+    ...(setup ? [setup, ''] : []),
+    '',
+    `def test_${entryPoint}():`,
+    ...testList.map(assertion => `    ${assertion.trim()}`),
+    '',
+  ].join('\n');
+
+  const id = `MBPP-${String(problem.task_id).padStart(3, '0')}`;
+  return buildTask(id, `MBPP ${problem.task_id}: ${entryPoint}`, 'mbpp', entryPoint, starter, tests);
+}
+
+function buildTask(
+  id: string,
+  title: string,
+  format: CodegenFormat,
+  entryPoint: string,
+  starter: string,
+  tests: string
+): ImportedTask {
+  const prompt = [
+    `Implement the function \`${entryPoint}\` in \`${SOLUTION_FILE}\` according to its signature and docstring.`,
+    '',
+    '```python',
+    starter.trimEnd(),
+    '```',
+    '',
+    `Keep the function name and signature unchanged. Only edit \`${SOLUTION_FILE}\`.`,
+    '',
+  ].join('\n');
+
+  return {
+    task: {
+      schema: TASK_SCHEMA_VERSION,
+      id,
+      title,
+      category: 'feature',
+      difficulty: 'easy',
+      source: {
+        repository: 'none',
+        commit: 'none',
+      },
+      prompt,
+      verification: {
+        type: 'command',
+        command: `python -m pytest -q ${TEST_FILE}`,
+        timeout: 60,
+      },
+      permissions: {
+        approval: 'auto',
+        read: true,
+        write: true,
+        execute: true,
+        network: false,
+      },
+      metadata: {
+        tags: [`source:${format}`, 'lang:python'],
+      },
+    },
+    fixtures: { [SOLUTION_FILE]: starter },
+    hiddenTests: { [TEST_FILE]: tests },
+  };
+}
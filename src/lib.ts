@@ -0,0 +1,212 @@
+/**
+ * Agent Bench library entry point.
+ *
+ * Embedders import from `agent-bench` instead of reaching into src/; the CLI
+ * (src/index.ts) is a thin layer over the same modules. Anything not exported
+ * here is internal and may change without notice. Exported names and fields
+ * follow semver: removals and incompatible changes only in a major release;
+ * new optional fields and hooks may be added in minor releases.
+ *
+ * ```ts
+ * import { TaskRunner, loadConfig, createAgentFromSpec } from 'agent-bench';
+ *
+ * const config = await loadConfig();
+ * const runner = new TaskRunner(config);
+ * const result = await runner.runTask('BUG-001', createAgentFromSpec({ type: 'opencode', model: null }));
+ * await runner.close();
+ *
+ * // Or inject components and observe progress
+ * const custom = TaskRunner.builder(config)
+ *   .agent(myAgent)
+ *   .verifier(myVerifier)
+ *   .store(myStore)
+ *   .onTaskComplete(result => report(result))
+ *   .on(event => { if (event.type === 'agent_iteration_finished') progress(event.iteration); })
+ *   .build();
+ * ```
+ */
+
+// Runner
+export { TaskRunner, TaskRunnerBuilder } from './core/runner.js';
+export type { RunnerHooks, RunnerOptions } from './core/runner.js';
+export type { AgentPlan, PlannedTask, RunPlan, EstimateSource } from './core/plan.js';
+export type { RunManifest, PermissionAcknowledgment, DangerousTask } from './core/manifest.js';
+export { getAuditLogPath, readAuditLog } from './core/audit.js';
+export type { AuditEntry } from './core/audit.js';
+export { getReproManifestPath, readReproManifest } from './core/repro.js';
+export type { ReproManifest } from './core/repro.js';
+export { TASK_STATES, RunStateTracker, readRunState, findLatestRun, getRunStatus } from './core/state.js';
+export type { TaskState, TaskStateEntry, RunState, RunStatus } from './core/state.js';
+export { EventBus } from './core/events.js';
+export { DiskQuotaMonitor } from './core/quota.js';
+export { prefetchRepositories, collectRepositories, PREFETCH_CONCURRENCY } from './core/prefetch.js';
+export type { PrefetchReport } from './core/prefetch.js';
+export { PhaseClock, getOverheadSecs } from './core/phases.js';
+export type { Phase, PhaseTimings } from './core/phases.js';
+export type {
+  RunEvent,
+  RunEventType,
+  RunEventListener,
+  SuiteStartedEvent,
+  TaskStartedEvent,
+  AgentIterationFinishedEvent,
+  VerificationFinishedEvent,
+  TaskFinishedEvent,
+  SuiteFinishedEvent,
+} from './core/events.js';
+export { consoleReporter } from './utils/reporter.js';
+export { createGithubReporter, formatWorkflowCommand, formatJobSummary, type GithubReporterOptions } from './utils/github.js';
+export { createTapReporter, formatTestPoint, type TapReporter } from './utils/tap.js';
+
+// Configuration
+export {
+  createDefaultConfig,
+  loadConfig,
+  resolveConfig,
+  applyConfigLayer,
+  applyEnvConfig,
+  applyFileConfig,
+  loadFileConfig,
+  parseQuota,
+  RESULTS_BACKENDS,
+  RECORD_MODES,
+  GIT_BACKENDS,
+  SANDBOXES,
+  ENV_VARS,
+} from './core/config.js';
+export type {
+  RunnerConfig,
+  ResultsBackend,
+  RecordMode,
+  GitBackendName,
+  SandboxName,
+  FileConfig,
+  ResolvedConfig,
+  ConfigSources,
+} from './core/config.js';
+
+// Task model
+export { TaskLoader } from './core/loader.js';
+export {
+  TaskSchema,
+  VERIFICATION_TYPES,
+  SOFT_CAPABILITIES,
+  TASK_SCHEMA_VERSION,
+  getVerificationType,
+  getSources,
+  getRequiredCapabilities,
+  toCatalogEntry,
+  validateTask,
+} from './core/task.js';
+export type {
+  Task,
+  TaskStep,
+  TaskCategory,
+  Difficulty,
+  SourceConfig,
+  VerificationConfig,
+  VerificationType,
+  FileAssertion,
+  PermissionsConfig,
+  AgentTool,
+  AgentCapability,
+  Requirement,
+  TaskMetadata,
+  WorkspaceSource,
+} from './core/task.js';
+export { getSchemaVersion, upgradeTask, migrateTaskFile } from './core/migrate.js';
+export type { TaskMigration } from './core/migrate.js';
+export { Git, git, createGitBackend, setGitBackend, getGitBackend } from './core/git.js';
+export type { GitBackend, CloneOptions } from './core/git.js';
+export { filterTasks, parseFilterExpression, evaluateFilter } from './core/filter.js';
+export type { TaskFilterOptions, FilterExpression } from './core/filter.js';
+
+// Agents
+export { AGENT_TYPES, parseAgentSpec, parseRemoteAgentSpec, parseAgentList, formatAgentSpec, createAgentFromSpec } from './agents/factory.js';
+export type { AgentSpec, AgentType, BuiltinAgentType } from './agents/factory.js';
+export { OpencodeAgent } from './agents/opencode.js';
+export { CliAgent, parseTokenCount } from './agents/cli.js';
+export type { CliCommand, CliOutput } from './agents/cli.js';
+export { AiderAgent, parseAiderOutput } from './agents/aider.js';
+export { CodexAgent, parseCodexEvents } from './agents/codex.js';
+export { CustomAgent, setCustomAgent, getCustomAgent, validateCommandTemplate, expandCommandTemplate, DEFAULT_CUSTOM_CAPABILITIES } from './agents/custom.js';
+export type { CustomAgentConfig } from './agents/custom.js';
+export { OpenHandsAgent, OPENHANDS_VERSION, parseOpenHandsTrajectory } from './agents/openhands.js';
+export { GooseAgent } from './agents/goose.js';
+export { ApiAgent, API_BASE_URLS, COMMAND_TIMEOUT_SECS, resolveApiEndpoint } from './agents/api.js';
+export { QwenAgent, parseQwenReport } from './agents/qwen.js';
+export { PluginAgent, PLUGIN_PROTOCOL_VERSION, PluginDescriptionSchema, PluginAnswerSchema, parsePluginAnswer } from './agents/plugin.js';
+export type { PluginDescription } from './agents/plugin.js';
+export { ProfileAgent, setAgentProfiles, getAgentProfiles, getAgentProfile, resolveProfileEnv } from './agents/profiles.js';
+export type { AgentProfile } from './agents/profiles.js';
+export { parseModel, DEFAULT_MODEL, DEFAULT_MAX_ITERATIONS } from './agents/types.js';
+export type { Agent, AgentResult, AgentInvocation, AgentIteration, IterationListener, StepCallback, ModelConfig } from './agents/types.js';
+export { resolveCapabilities, describeDangerousPermissions } from './agents/permissions.js';
+export type { Capabilities, ApprovalPolicy } from './agents/permissions.js';
+export { RecordingAgent, withRecording, getRecordingKey } from './agents/recording.js';
+export type { AgentRecording, RecordingOptions } from './agents/recording.js';
+export { discoverAgents } from './agents/discovery.js';
+export { BASE_AGENT_ENV, PROVIDER_ENV_VARS, setAgentEnv, getAgentEnv, agentEnvironment, providerEnvVars, withAgentVariables } from './agents/env.js';
+export {
+  CredentialPool,
+  RATE_LIMIT_COOLDOWN_MS,
+  setCredentials,
+  getCredentialPool,
+  withCredential,
+  currentCredential,
+  isRateLimitError,
+  type CredentialConfig,
+  type CredentialLease,
+  type CredentialStats,
+} from './agents/credentials.js';
+export type { AgentStatus } from './agents/discovery.js';
+
+// Evaluation
+export { Verifier } from './evaluator/verifier.js';
+export type { VerificationResult, TaskVerifier } from './evaluator/verifier.js';
+export { VERIFIERS, getVerifier } from './evaluator/verifiers/index.js';
+export {
+  EXIT_CODES,
+  getOutcome,
+  getExitCode,
+  getErrorExitCode,
+  createSuiteResults,
+  findRegressions,
+  getSuiteRecordPath,
+  readSuiteRecord,
+} from './evaluator/results.js';
+export type { BenchmarkResult, SuiteResults, ResultOutcome, FailureKind } from './evaluator/results.js';
+export { computeStats } from './evaluator/stats.js';
+export type { ResultStats, GroupStats, FailedTask } from './evaluator/stats.js';
+export { findPrice, computeCost, priceUsage, summarizeCosts, formatCost } from './evaluator/pricing.js';
+export type { TokenUsage, ModelPrice, PricingTable, CostSummary } from './evaluator/pricing.js';
+
+// Result storage and export
+export { createResultStore, loadStoredResults } from './storage/store.js';
+export type { ResultStore } from './storage/store.js';
+export { EXPORTERS, getExporter } from './collectors/export.js';
+export type { ResultExporter } from './collectors/export.js';
+
+// Sandboxing (for custom verifiers running agent-written code)
+export { createSandbox, setSandbox, getSandbox, sandboxCommand } from './sandbox/index.js';
+export type { Sandbox, SandboxPolicy, SandboxCommandOptions } from './sandbox/index.js';
+export { LandlockSandbox } from './sandbox/landlock.js';
+export { BubblewrapSandbox, FirejailSandbox } from './sandbox/bubblewrap.js';
+
+// Subprocesses (for custom agents and verifiers)
+export { exec, startProcess, splitCommand, addCommandListener, reportCommand } from './utils/exec.js';
+export type { CommandSpec, ExecOptions, ExecResult, RunningProcess, CommandRecord, CommandListener } from './utils/exec.js';
+export { OutputSpool, SPOOL_MEMORY_BYTES, disposeSpoolFiles } from './utils/spool.js';
+export { findProcessesIn, terminateProcesses } from './utils/processes.js';
+export type { WorkspaceProcess } from './utils/processes.js';
+
+// Test doubles (hermetic runs without an agent, verification subprocess, or result files)
+export { FakeAgent, FakeVerifier, MemoryResultStore } from './testing/fakes.js';
+export type { FakeAgentOptions, FakeAgentCall } from './testing/fakes.js';
+
+// Build features
+export { FEATURES, hasFeature, getEnabledFeatures } from './features.js';
+export type { Feature } from './features.js';
+
+// Errors
+export * from './utils/errors.js';
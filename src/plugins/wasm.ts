@@ -0,0 +1,222 @@
+/**
+ * WebAssembly plugin host for custom verifiers and scorers.
+ *
+ * A task's `plugins` section names `.wasm` modules (paths relative to the task
+ * YAML) that run after the verification command:
+ *
+ * - a verifier plugin decides pass/fail from the command's output and selected workspace files
+ * - a scorer plugin assigns a 0-100 score to the result
+ *
+ * Plugin ABI (version 1), JSON in and out over linear memory:
+ *
+ *   export memory
+ *   export agent_bench_api_version() -> i32     must return 1
+ *   export alloc(len: i32) -> i32               buffer for the input document
+ *   export verify(ptr: i32, len: i32) -> i64    verifier plugins
+ *   export score(ptr: i32, len: i32) -> i64     scorer plugins
+ *
+ * `verify` and `score` return `(ptr << 32) | len` of a UTF-8 JSON document:
+ * `{"passed": bool, "message"?: string}` or `{"score": number, "message"?: string}`.
+ * Modules get no imports (no WASI), so a plugin can only compute over its input.
+ * Each call runs in a worker thread that is terminated after `[plugins] timeout` seconds.
+ */
+
+import { readFile } from 'fs/promises';
+import { Worker } from 'worker_threads';
+import { dirname, isAbsolute, join, relative, resolve } from 'path';
+import type { Task } from '../core/task.js';
+import { PluginError } from '../utils/errors.js';
+
+/**
+ * ABI version this host implements.
+ */
+export const PLUGIN_API_VERSION = 1;
+
+/**
+ * Seconds a plugin call may run unless `[plugins] timeout` is set.
+ */
+export const DEFAULT_PLUGIN_TIMEOUT_SECS = 10;
+
+/**
+ * Largest workspace file passed to a plugin.
+ */
+const MAX_FILE_BYTES = 1024 * 1024;
+
+/**
+ * Document passed to a plugin.
+ */
+export interface PluginInput {
+  api_version: number;
+  task: { id: string; category: string; difficulty: string; tags: string[] };
+  verification: { passed: boolean; exit_code: number | null; stdout: string; stderr: string; duration_secs: number };
+  agent: { iterations: number; tokens_used: number | null; duration_secs: number };
+  files: Record<string, string | null>;
+}
+
+export interface VerifierOutput {
+  passed: boolean;
+  message: string | null;
+}
+
+export interface ScorerOutput {
+  score: number;
+  message: string | null;
+}
+
+/**
+ * Worker thread that instantiates a plugin, checks its ABI version and exports, and calls
+ * an entry point: `workerData` is `{ path, entry, version, bytes, input }`, and it posts
+ * `{ output }` (the returned text) or `{ error }`.
+ */
+const CALL_WORKER_SOURCE = `
+const { parentPort, workerData } = require('worker_threads');
+const { path, entry, version, bytes, input } = workerData;
+function run() {
+  let module;
+  try {
+    module = new WebAssembly.Module(bytes);
+  } catch (error) {
+    return { error: 'Cannot load plugin ' + path + ': ' + error.message };
+  }
+  const imports = WebAssembly.Module.imports(module);
+  if (imports.length > 0) {
+    const names = imports.map(item => item.module + '.' + item.name).join(', ');
+    return { error: 'Plugin ' + path + ' requires imports (' + names + '); plugins must be self-contained' };
+  }
+  const exports = new WebAssembly.Instance(module, {}).exports;
+  for (const name of ['memory', 'alloc', 'agent_bench_api_version', entry]) {
+    if (!(name in exports)) {
+      return { error: 'Plugin ' + path + ' does not export "' + name + '"' };
+    }
+  }
+  const found = exports.agent_bench_api_version();
+  if (found !== version) {
+    return { error: 'Plugin ' + path + ' targets API version ' + found + '; this harness implements ' + version };
+  }
+
+  const encoded = new TextEncoder().encode(input);
+  let packed;
+  try {
+    const ptr = exports.alloc(encoded.length);
+    new Uint8Array(exports.memory.buffer, ptr, encoded.length).set(encoded);
+    packed = BigInt(exports[entry](ptr, encoded.length));
+  } catch (error) {
+    return { error: 'Plugin ' + path + ' trapped in ' + entry + ': ' + (error instanceof Error ? error.message : error) };
+  }
+  const outPtr = Number(packed >> 32n);
+  const outLen = Number(packed & 0xffffffffn);
+  if (outPtr + outLen > exports.memory.buffer.byteLength) {
+    return { error: 'Plugin ' + path + ' returned an out-of-bounds result' };
+  }
+  return { output: new TextDecoder().decode(new Uint8Array(exports.memory.buffer, outPtr, outLen)) };
+}
+parentPort.postMessage(run());
+`;
+
+/**
+ * Call a plugin entry point with a JSON document and parse the JSON it returns. The plugin
+ * runs in a worker thread, which is terminated if the call does not return in time.
+ * @param timeoutSecs Seconds the call may run
+ */
+async function call(
+  path: string,
+  entry: 'verify' | 'score',
+  input: PluginInput,
+  timeoutSecs: number
+): Promise<Record<string, unknown>> {
+  let bytes: Buffer;
+  try {
+    bytes = await readFile(path);
+  } catch (error) {
+    throw new PluginError(`Cannot load plugin ${path}: ${error instanceof Error ? error.message : error}`);
+  }
+
+  const worker = new Worker(CALL_WORKER_SOURCE, {
+    eval: true,
+    workerData: { path, entry, version: PLUGIN_API_VERSION, bytes, input: JSON.stringify(input) },
+  });
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  let reply: { output?: string; error?: string };
+  try {
+    reply = await new Promise((resolve, reject) => {
+      timer = setTimeout(
+        () => reject(new PluginError(`Plugin ${path} did not return from ${entry} within ${timeoutSecs}s`)),
+        timeoutSecs * 1000
+      );
+      worker.once('message', resolve);
+      worker.once('error', error => reject(new PluginError(`Plugin ${path} failed in ${entry}: ${error.message}`)));
+      worker.once('exit', code => reject(new PluginError(`Plugin ${path} exited with code ${code} in ${entry}`)));
+    });
+  } finally {
+    clearTimeout(timer);
+    await worker.terminate();
+  }
+  if (reply.error !== undefined) {
+    throw new PluginError(reply.error);
+  }
+
+  try {
+    const output = JSON.parse(reply.output ?? '');
+    if (!output || typeof output !== 'object') {
+      throw new Error('not an object');
+    }
+    return output;
+  } catch (error) {
+    throw new PluginError(`Plugin ${path} returned invalid JSON: ${error instanceof Error ? error.message : error}`);
+  }
+}
+
+/**
+ * Run a verifier plugin.
+ * @param timeoutSecs Seconds the plugin may run (`[plugins] timeout`)
+ */
+export async function runVerifierPlugin(
+  path: string,
+  input: PluginInput,
+  timeoutSecs: number = DEFAULT_PLUGIN_TIMEOUT_SECS
+): Promise<VerifierOutput> {
+  const output = await call(path, 'verify', input, timeoutSecs);
+  if (typeof output.passed !== 'boolean') {
+    throw new PluginError(`Verifier plugin ${path} did not return a boolean "passed"`);
+  }
+  return { passed: output.passed, message: typeof output.message === 'string' ? output.message : null };
+}
+
+/**
+ * Run a scorer plugin. Scores are clamped to 0-100.
+ * @param timeoutSecs Seconds the plugin may run (`[plugins] timeout`)
+ */
+export async function runScorerPlugin(
+  path: string,
+  input: PluginInput,
+  timeoutSecs: number = DEFAULT_PLUGIN_TIMEOUT_SECS
+): Promise<ScorerOutput> {
+  const output = await call(path, 'score', input, timeoutSecs);
+  if (typeof output.score !== 'number' || !Number.isFinite(output.score)) {
+    throw new PluginError(`Scorer plugin ${path} did not return a numeric "score"`);
+  }
+  return {
+    score: Math.min(100, Math.max(0, output.score)),
+    message: typeof output.message === 'string' ? output.message : null,
+  };
+}
+
+/**
+ * Read the workspace files a task hands to its plugins (missing or oversized files are null).
+ */
+export async function readPluginFiles(workspacePath: string, paths: string[]): Promise<Record<string, string | null>> {
+  const files: Record<string, string | null> = {};
+  for (const path of paths) {
+    const fullPath = join(workspacePath, path);
+    if (relative(workspacePath, fullPath).startsWith('..')) {
+      throw new PluginError(`Plugin file ${path} is outside the workspace`);
+    }
+    try {
+      const content = await readFile(fullPath);
+      files[path] = content.length > MAX_FILE_BYTES ? null : content.toString('utf-8');
+    } catch {
+      files[path] = null;
+    }
+  }
+  return files;
+}
@@ -0,0 +1,113 @@
+/**
+ * Sandboxes built on setuid-free Linux isolation tools, for hosts where containers are
+ * not allowed: bubblewrap (`bwrap`, unprivileged user namespaces) and firejail.
+ */
+
+import { tmpdir, homedir } from 'os';
+import { existsSync } from 'fs';
+import { findExecutable, exec, formatCommand, type CommandSpec } from '../utils/exec.js';
+import { SandboxError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+import type { Sandbox, SandboxPolicy } from './index.js';
+
+/**
+ * Find a sandbox tool and check that it can start a process.
+ * @throws SandboxError If the tool is missing or fails
+ */
+async function probeTool(name: string, binary: string, probe: string[]): Promise<string> {
+  if (process.platform !== 'linux') {
+    throw new SandboxError(`The ${name} sandbox is only available on Linux`);
+  }
+  const path = findExecutable(binary);
+  if (!path) {
+    throw new SandboxError(`The ${name} sandbox needs ${binary} on PATH`);
+  }
+
+  const command = { program: path, args: probe };
+  const result = await exec(command, { timeoutSecs: 10 });
+  if (result.exitCode !== 0) {
+    throw new SandboxError(`${name} sandbox unavailable (${formatCommand(command)}): ${result.stderr.trim() || `exit ${result.exitCode}`}`);
+  }
+  logger.debug(`${name} sandbox: ${path}`);
+  return path;
+}
+
+/**
+ * Bubblewrap: the whole filesystem bind-mounted read-only, a private temp directory,
+ * minimal /dev and /proc, and the writable paths bound read-write on top. Processes get
+ * their own PID, IPC, and UTS namespaces and session and die with the harness. The
+ * network is shared (agents need their model API; servers under test listen on localhost).
+ */
+export class BubblewrapSandbox implements Sandbox {
+  readonly name = 'bubblewrap' as const;
+  private binary = 'bwrap';
+  private available: Promise<void> | null = null;
+
+  check(): Promise<void> {
+    this.available ??= probeTool('bubblewrap', 'bwrap', ['--ro-bind', '/', '/', '--unshare-pid', '--', 'true'])
+      .then(path => { this.binary = path; });
+    return this.available;
+  }
+
+  wrap(program: string, args: string[], policy: SandboxPolicy): CommandSpec {
+    const mounts = ['--ro-bind', '/', '/', '--dev', '/dev', '--proc', '/proc', '--tmpfs', tmpdir()];
+    // Bound after the private temp directory so workspaces under it stay visible
+    for (const path of [policy.workspace, ...policy.writable.filter(path => existsSync(path))]) {
+      mounts.push('--bind', path, path);
+    }
+    return {
+      program: this.binary,
+      args: [
+        ...mounts,
+        '--unshare-pid',
+        '--unshare-ipc',
+        '--unshare-uts',
+        '--unshare-cgroup-try',
+        '--new-session',
+        '--die-with-parent',
+        '--chdir',
+        policy.workspace,
+        '--',
+        program,
+        ...args,
+      ],
+    };
+  }
+}
+
+/**
+ * Firejail: the home directory read-only except the workspace and writable paths (system
+ * directories are already read-only to an unprivileged user), firejail's default seccomp
+ * filter, no capabilities, and no new privileges. The temp directory stays shared because
+ * workspaces usually live in it.
+ */
+export class FirejailSandbox implements Sandbox {
+  readonly name = 'firejail' as const;
+  private binary = 'firejail';
+  private available: Promise<void> | null = null;
+
+  check(): Promise<void> {
+    this.available ??= probeTool('firejail', 'firejail', ['--quiet', '--noprofile', '--', 'true'])
+      .then(path => { this.binary = path; });
+    return this.available;
+  }
+
+  wrap(program: string, args: string[], policy: SandboxPolicy): CommandSpec {
+    const writable = [policy.workspace, ...policy.writable.filter(path => existsSync(path))];
+    return {
+      program: this.binary,
+      args: [
+        '--quiet',
+        '--noprofile',
+        `--read-only=${homedir()}`,
+        ...writable.map(path => `--read-write=${path}`),
+        '--seccomp',
+        '--caps.drop=all',
+        '--nonewprivs',
+        '--',
+        program,
+        ...args,
+      ],
+    };
+  }
+}
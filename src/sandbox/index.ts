@@ -0,0 +1,107 @@
+/**
+ * Sandboxes for agents and for commands run against agent-written code (`[sandbox] backend`).
+ *
+ * Agent processes and verification commands (including `http` servers under test) go
+ * through `sandboxCommand()`, which wraps them in the selected sandbox: writes are
+ * confined to the workspace and `[sandbox] writable` paths (plus whatever scratch space
+ * the backend provides), so hostile or buggy code cannot modify the rest of the machine.
+ */
+
+import { homedir } from 'os';
+import { resolve } from 'path';
+import type { SandboxName } from '../core/config.js';
+import { splitCommand, type CommandSpec } from '../utils/exec.js';
+import { LandlockSandbox } from './landlock.js';
+import { BubblewrapSandbox, FirejailSandbox } from './bubblewrap.js';
+
+/**
+ * What a sandboxed command may write to (everything stays readable).
+ */
+export interface SandboxPolicy {
+  workspace: string;
+  /** Further writable paths */
+  writable: string[];
+}
+
+/**
+ * Wraps commands so they run confined to a policy.
+ */
+export interface Sandbox {
+  readonly name: Exclude<SandboxName, 'off'>;
+
+  /**
+   * Check that the sandbox works on this machine (cached after the first call).
+   * @throws SandboxError If it does not
+   */
+  check(): Promise<void>;
+
+  /**
+   * The command that runs `program` with `args` inside the sandbox.
+   */
+  wrap(program: string, args: string[], policy: SandboxPolicy): CommandSpec;
+}
+
+/**
+ * Create a sandbox by name (null for `off`).
+ */
+export function createSandbox(name: SandboxName): Sandbox | null {
+  switch (name) {
+    case 'off':
+      return null;
+    case 'landlock':
+      return new LandlockSandbox();
+    case 'bubblewrap':
+      return new BubblewrapSandbox();
+    case 'firejail':
+      return new FirejailSandbox();
+  }
+}
+
+let activeSandbox: Sandbox | null = null;
+let extraWritable: string[] = [];
+
+/**
+ * Select the sandbox used by `sandboxCommand()` (the CLI and TaskRunner set it from config).
+ * @param writable Paths writable besides the workspace (`~` is expanded)
+ */
+export function setSandbox(name: SandboxName, writable: string[] = []): void {
+  if ((activeSandbox?.name ?? 'off') !== name) {
+    activeSandbox = createSandbox(name);
+  }
+  extraWritable = writable.map(path => resolve(path.replace(/^~(?=[\\/]|$)/, homedir())));
+}
+
+export function getSandbox(): Sandbox | null {
+  return activeSandbox;
+}
+
+/**
+ * Options for `sandboxCommand()`.
+ */
+export interface SandboxCommandOptions {
+  /** Whether a string command is meant for `sh -c` rather than lexed */
+  shell?: boolean;
+  /** Paths this command needs to write besides the configured ones (e.g. an agent's state directory) */
+  writable?: string[];
+}
+
+/**
+ * Wrap a command run in a workspace with the selected sandbox (unchanged when sandboxing is off).
+ * @throws SandboxError If the sandbox is unavailable on this machine
+ */
+export async function sandboxCommand(
+  command: CommandSpec,
+  workspace: string,
+  options: SandboxCommandOptions = {}
+): Promise<CommandSpec> {
+  const sandbox = activeSandbox;
+  if (!sandbox) {
+    return command;
+  }
+  await sandbox.check();
+
+  const [program, args] = typeof command === 'string'
+    ? (options.shell ? ['sh', ['-c', command]] as [string, string[]] : splitCommand(command))
+    : [command.program, command.args];
+  return sandbox.wrap(program, args, { workspace, writable: [...extraWritable, ...(options.writable ?? [])] });
+}
@@ -0,0 +1,184 @@
+/**
+ * Landlock + seccomp sandbox (Linux 5.13+).
+ *
+ * Bun has no bindings for landlock(7) or seccomp(2), so commands are started through a
+ * small Python helper that uses ctypes to restrict itself and then execs the command:
+ * the filesystem becomes read-only except the policy's writable paths, the temp
+ * directory, and /dev (Landlock), and syscalls that reach beyond the process (mount,
+ * ptrace, kernel modules, namespaces, bpf, reboot, clock changes) fail with EPERM
+ * (seccomp). Both restrictions are inherited by every child and cannot be lifted.
+ */
+
+import { tmpdir } from 'os';
+import { findExecutable, exec, type CommandSpec } from '../utils/exec.js';
+import { SandboxError } from '../utils/errors.js';
+import { logger } from '../utils/logger.js';
+import type { Sandbox, SandboxPolicy } from './index.js';
+
+/**
+ * Exit code of the helper when it cannot set up the sandbox.
+ */
+export const SANDBOX_SETUP_EXIT_CODE = 125;
+
+/**
+ * The helper. argv: `--probe` (print the Landlock ABI version), or a JSON policy
+ * (`{"read": [...], "write": [...]}`) followed by the program and its arguments.
+ */
+const HELPER = String.raw`
+import ctypes, json, os, platform, struct, sys
+
+def fail(message, code=${SANDBOX_SETUP_EXIT_CODE}):
+    sys.stderr.write('agent-bench sandbox: %s\n' % message)
+    sys.exit(code)
+
+libc = ctypes.CDLL(None, use_errno=True)
+libc.syscall.restype = ctypes.c_long
+libc.prctl.argtypes = [ctypes.c_int, ctypes.c_ulong, ctypes.c_ulong, ctypes.c_ulong, ctypes.c_ulong]
+
+def check(result, what):
+    if result < 0:
+        errno = ctypes.get_errno()
+        fail('%s failed: %s' % (what, os.strerror(errno)))
+    return result
+
+# landlock_create_ruleset, landlock_add_rule, landlock_restrict_self (same number on every architecture)
+CREATE_RULESET, ADD_RULE, RESTRICT_SELF = 444, 445, 446
+
+# Denied syscalls per architecture: (AUDIT_ARCH, {name: number})
+ARCHES = {
+    'x86_64': (0xC000003E, {
+        'ptrace': 101, 'mount': 165, 'umount2': 166, 'pivot_root': 155, 'chroot': 161, 'reboot': 169,
+        'kexec_load': 246, 'kexec_file_load': 320, 'init_module': 175, 'finit_module': 313,
+        'delete_module': 176, 'swapon': 167, 'swapoff': 168, 'bpf': 321, 'perf_event_open': 298,
+        'keyctl': 250, 'add_key': 248, 'request_key': 249, 'setns': 308, 'unshare': 272,
+        'process_vm_readv': 310, 'process_vm_writev': 311, 'open_by_handle_at': 304, 'userfaultfd': 323,
+        'acct': 163, 'settimeofday': 164, 'clock_settime': 227, 'adjtimex': 159, 'sethostname': 170,
+        'setdomainname': 171, 'iopl': 172, 'ioperm': 173,
+    }),
+    'aarch64': (0xC00000B7, {
+        'ptrace': 117, 'mount': 40, 'umount2': 39, 'pivot_root': 41, 'chroot': 51, 'reboot': 142,
+        'kexec_load': 104, 'kexec_file_load': 294, 'init_module': 105, 'finit_module': 273,
+        'delete_module': 106, 'swapon': 224, 'swapoff': 225, 'bpf': 280, 'perf_event_open': 241,
+        'keyctl': 219, 'add_key': 217, 'request_key': 218, 'setns': 268, 'unshare': 97,
+        'process_vm_readv': 270, 'process_vm_writev': 271, 'open_by_handle_at': 265, 'userfaultfd': 282,
+        'acct': 89, 'settimeofday': 170, 'clock_settime': 112, 'adjtimex': 171, 'sethostname': 161,
+        'setdomainname': 162,
+    }),
+}
+# New mount API, numbered alike everywhere
+MOUNT_API = {'open_tree': 428, 'move_mount': 429, 'fsopen': 430, 'fsconfig': 431, 'fsmount': 432, 'fspick': 433}
+
+machine = platform.machine()
+if machine not in ARCHES:
+    fail('no seccomp filter for architecture %s' % machine)
+abi = libc.syscall(CREATE_RULESET, None, ctypes.c_size_t(0), ctypes.c_uint32(1))
+if abi < 1:
+    fail('Landlock is not enabled in this kernel (needs Linux 5.13+ with landlock in the lsm= list)')
+if sys.argv[1] == '--probe':
+    print(abi)
+    sys.exit(0)
+
+policy = json.loads(sys.argv[1])
+argv = sys.argv[2:]
+
+# Filesystem access rights: execute, read_file, read_dir, then every write right this ABI knows
+READ = 1 | 4 | 8
+FILE_RIGHTS = 1 | 2 | 4 | (1 << 14) | (1 << 15)
+ALL = (1 << 13) - 1
+if abi >= 2:
+    ALL |= 1 << 13  # refer (rename/link across directories)
+if abi >= 3:
+    ALL |= 1 << 14  # truncate
+if abi >= 5:
+    ALL |= 1 << 15  # ioctl on devices
+
+class PathBeneath(ctypes.Structure):
+    _pack_ = 1
+    _fields_ = [('allowed_access', ctypes.c_uint64), ('parent_fd', ctypes.c_int32)]
+
+attr = ctypes.c_uint64(ALL)
+ruleset = check(libc.syscall(CREATE_RULESET, ctypes.byref(attr), ctypes.c_size_t(8), ctypes.c_uint32(0)), 'landlock_create_ruleset')
+
+def allow(path, access):
+    try:
+        fd = os.open(path, os.O_PATH | os.O_CLOEXEC)
+    except OSError:
+        return  # Missing paths grant nothing
+    if not os.path.isdir(path):
+        access &= FILE_RIGHTS
+    rule = PathBeneath(access & ALL, fd)
+    check(libc.syscall(ADD_RULE, ctypes.c_int(ruleset), ctypes.c_int(1), ctypes.byref(rule), ctypes.c_uint32(0)), 'landlock_add_rule %s' % path)
+    os.close(fd)
+
+for path in policy['read']:
+    allow(path, READ)
+for path in policy['write']:
+    allow(path, ALL)
+
+PR_SET_NO_NEW_PRIVS, PR_SET_SECCOMP, SECCOMP_MODE_FILTER = 38, 22, 2
+check(libc.prctl(PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0), 'prctl(PR_SET_NO_NEW_PRIVS)')
+check(libc.syscall(RESTRICT_SELF, ctypes.c_int(ruleset), ctypes.c_uint32(0)), 'landlock_restrict_self')
+os.close(ruleset)
+
+# seccomp: EPERM for denied syscalls, other ABIs (i386, x32) and other architectures
+arch, numbers = ARCHES[machine]
+denied = sorted(set(numbers.values()) | set(MOUNT_API.values()))
+LD, JEQ, JGE, RET = 0x20, 0x15, 0x35, 0x06
+ALLOW, DENY = 0x7FFF0000, 0x00050000 | 1
+program = [(LD, 0, 0, 4), (JEQ, 1, 0, arch), (RET, 0, 0, DENY), (LD, 0, 0, 0)]
+checks = [(JGE, 0x40000000)] + [(JEQ, number) for number in denied]
+deny_at = len(program) + len(checks) + 1
+for code, value in checks:
+    program.append((code, deny_at - len(program) - 1, 0, value))
+program += [(RET, 0, 0, ALLOW), (RET, 0, 0, DENY)]
+code = b''.join(struct.pack('=HBBI', *instruction) for instruction in program)
+buffer = ctypes.create_string_buffer(code, len(code))
+
+class SockFprog(ctypes.Structure):
+    _fields_ = [('len', ctypes.c_ushort), ('filter', ctypes.c_void_p)]
+
+fprog = SockFprog(len(program), ctypes.addressof(buffer))
+check(libc.prctl(PR_SET_SECCOMP, SECCOMP_MODE_FILTER, ctypes.addressof(fprog), 0, 0), 'prctl(PR_SET_SECCOMP)')
+
+try:
+    os.execvp(argv[0], argv)
+except FileNotFoundError:
+    fail('%s: command not found' % argv[0], 127)
+except OSError as error:
+    fail('%s: %s' % (argv[0], error.strerror), 126)
+`;
+
+export class LandlockSandbox implements Sandbox {
+  readonly name = 'landlock' as const;
+  private python: string | null = null;
+  private available: Promise<void> | null = null;
+
+  check(): Promise<void> {
+    this.available ??= this.probe();
+    return this.available;
+  }
+
+  wrap(program: string, args: string[], policy: SandboxPolicy): CommandSpec {
+    const rules = JSON.stringify({ read: ['/'], write: [policy.workspace, tmpdir(), '/dev', ...policy.writable] });
+    return { program: this.python ?? 'python3', args: ['-I', '-c', HELPER, rules, program, ...args] };
+  }
+
+  /**
+   * Check that the helper can run and the kernel supports Landlock.
+   */
+  private async probe(): Promise<void> {
+    if (process.platform !== 'linux') {
+      throw new SandboxError('The landlock sandbox is only available on Linux');
+    }
+    this.python = findExecutable('python3');
+    if (!this.python) {
+      throw new SandboxError('The landlock sandbox needs python3 on PATH');
+    }
+
+    const result = await exec({ program: this.python, args: ['-I', '-c', HELPER, '--probe'] }, { timeoutSecs: 10 });
+    if (result.exitCode !== 0) {
+      throw new SandboxError(`Landlock sandbox unavailable: ${result.stderr.trim() || `exit ${result.exitCode}`}`);
+    }
+    logger.debug(`Landlock ABI version ${result.stdout.trim()}`);
+  }
+}
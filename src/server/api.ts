@@ -0,0 +1,296 @@
+/**
+ * REST API behind `agent-bench serve-api`.
+ *
+ * Runs are queued and executed one at a time in the background (tasks share
+ * the workspace directory and the machine), and clients poll for status.
+ *
+ *   GET  /api/health          Liveness check
+ *   GET  /api/tasks           Task catalog (same entries as `--format json list`)
+ *   POST /api/runs            Queue a task or suite run; returns 202 with the run
+ *   GET  /api/runs            All runs started by this server, newest first
+ *   GET  /api/runs/:id        Run status, with results once finished
+ *   GET  /api/results         Stored results (?task=, ?agent=, ?run_id=, ?limit=)
+ *
+ * Without a sandbox, tasks with unrestricted shell access only run when the request
+ * sets `"yes": true` (the API equivalent of `run --yes`); otherwise the run fails.
+ * Requests cannot select a `plugin:<path>` agent, only the config's named agents.
+ */
+
+import { z } from 'zod';
+import { randomUUID } from 'crypto';
+import { TaskRunner } from '../core/runner.js';
+import { TaskLoader } from '../core/loader.js';
+import { toCatalogEntry } from '../core/task.js';
+import { findDangerousTasks } from '../core/manifest.js';
+import { parseFilterExpression } from '../core/filter.js';
+import type { TaskFilterOptions } from '../core/filter.js';
+import type { RunnerConfig } from '../core/config.js';
+import { createAgentFromSpec, formatAgentSpec, parseAgentSpec, parseRemoteAgentSpec } from '../agents/factory.js';
+import { withRecording } from '../agents/recording.js';
+import type { AgentSpec } from '../agents/factory.js';
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import { loadStoredResults } from '../storage/store.js';
+import { logger } from '../utils/logger.js';
+import { consoleReporter } from '../utils/reporter.js';
+import { authorize, HttpError, json, readJson, withErrorResponses } from './http.js';
+
+/**
+ * Body of `POST /api/runs`. Exactly one of `task` or `suite` is required.
+ */
+const RunRequestSchema = z
+  .object({
+    task: z.string().min(1).optional(),
+    suite: z.string().min(1).optional(),
+    filter: z.string().optional(),
+    agent: z.string().optional(),
+    model: z.string().optional(),
+    skip_verify: z.boolean().default(false),
+    fail_fast: z.boolean().optional(),
+    yes: z.boolean().default(false),
+  })
+  .strict()
+  .refine(request => Boolean(request.task) !== Boolean(request.suite), {
+    message: 'Specify exactly one of "task" or "suite"',
+  });
+export type RunRequest = z.infer<typeof RunRequestSchema>;
+
+export type RunStatus = 'queued' | 'running' | 'completed' | 'failed';
+
+/**
+ * A run started through the API.
+ */
+export interface ApiRun {
+  id: string;
+  status: RunStatus;
+  request: RunRequest;
+  agent: string;
+  run_id: string | null;
+  created_at: string;
+  started_at: string | null;
+  finished_at: string | null;
+  error: string | null;
+  results: BenchmarkResult[] | null;
+  suite: SuiteResults | null;
+}
+
+/**
+ * Server options.
+ */
+export interface ApiServerOptions {
+  host: string;
+  port: number;
+  token: string | null;
+}
+
+/**
+ * Queues and executes runs for the API.
+ */
+export class RunQueue {
+  private config: RunnerConfig;
+  private runs = new Map<string, ApiRun>();
+  private pending: string[] = [];
+  private active = false;
+
+  constructor(config: RunnerConfig) {
+    this.config = config;
+  }
+
+  /**
+   * Validate a request and queue it.
+   * @throws HttpError 400 If the request is invalid
+   */
+  submit(body: unknown): ApiRun {
+    const parsed = RunRequestSchema.safeParse(body);
+    if (!parsed.success) {
+      throw new HttpError(400, parsed.error.issues.map(issue => issue.message).join('; '));
+    }
+    const request = parsed.data;
+
+    let agent: string;
+    try {
+      if (request.filter) parseFilterExpression(request.filter);
+      agent = formatAgentSpec(this.resolveSpec(request), this.config.defaultModel);
+    } catch (error) {
+      throw new HttpError(400, error instanceof Error ? error.message : String(error));
+    }
+
+    const run: ApiRun = {
+      id: randomUUID(),
+      status: 'queued',
+      request,
+      agent,
+      run_id: null,
+      created_at: new Date().toISOString(),
+      started_at: null,
+      finished_at: null,
+      error: null,
+      results: null,
+      suite: null,
+    };
+    this.runs.set(run.id, run);
+    this.pending.push(run.id);
+    void this.drain();
+    return run;
+  }
+
+  get(id: string): ApiRun | undefined {
+    return this.runs.get(id);
+  }
+
+  list(): ApiRun[] {
+    return [...this.runs.values()].reverse();
+  }
+
+  /**
+   * Agent selection for a request: its agent, else the configured default agent, with the request's model.
+   */
+  private resolveSpec(request: RunRequest): AgentSpec {
+    const spec = request.agent
+      ? parseRemoteAgentSpec(request.agent)
+      : parseAgentSpec(this.config.defaultAgent || 'opencode');
+    return { ...spec, model: spec.model || request.model || null };
+  }
+
+  /**
+   * Execute queued runs one at a time.
+   */
+  private async drain(): Promise<void> {
+    if (this.active) return;
+    this.active = true;
+    try {
+      let id: string | undefined;
+      while ((id = this.pending.shift())) {
+        await this.execute(this.runs.get(id)!);
+      }
+    } finally {
+      this.active = false;
+    }
+  }
+
+  private async execute(run: ApiRun): Promise<void> {
+    const { request } = run;
+    const config: RunnerConfig = { ...this.config, failFast: request.fail_fast ?? this.config.failFast };
+    const runner = new TaskRunner(config, { listeners: [consoleReporter] });
+    const spec = this.resolveSpec(request);
+    const agent = withRecording(createAgentFromSpec(spec, config.defaultModel, config.maxIterations), {
+      mode: config.recordMode,
+      dir: config.recordingsDir,
+      model: spec.model || config.defaultModel,
+    });
+
+    run.status = 'running';
+    run.run_id = runner.runId;
+    run.started_at = new Date().toISOString();
+    logger.info(`API run ${run.id} started (${request.task ? `task ${request.task}` : `suite ${request.suite}`})`);
+
+    try {
+      if (request.yes) {
+        const tasks = await runner.selectTasks({ taskId: request.task, suite: request.suite }, {
+          expression: request.filter ? parseFilterExpression(request.filter) : undefined,
+        });
+        config.permissionAck = {
+          method: 'api',
+          acknowledged_at: new Date().toISOString(),
+          tasks: findDangerousTasks(tasks),
+        };
+      }
+
+      if (request.task) {
+        run.results = [await runner.runTask(request.task, agent, request.skip_verify)];
+      } else {
+        const filter: TaskFilterOptions = {
+          expression: request.filter ? parseFilterExpression(request.filter) : undefined,
+        };
+        const suite = request.suite === 'all'
+          ? await runner.runAll(agent, request.skip_verify, filter)
+          : await runner.runCategory(request.suite!, agent, request.skip_verify, filter);
+        if (!suite) {
+          throw new Error(`No tasks found for category: ${request.suite}`);
+        }
+        run.suite = suite;
+        run.results = suite.results;
+      }
+      run.status = 'completed';
+    } catch (error) {
+      run.status = 'failed';
+      run.error = error instanceof Error ? error.message : String(error);
+      logger.error(`API run ${run.id} failed: ${run.error}`);
+    } finally {
+      run.finished_at = new Date().toISOString();
+      await runner.close();
+    }
+  }
+}
+
+/**
+ * Build the request handler. Exported separately from the server so it can be mounted elsewhere.
+ */
+export function createApiHandler(config: RunnerConfig, options: Pick<ApiServerOptions, 'token'>) {
+  const queue = new RunQueue(config);
+
+  const route = async (request: Request): Promise<Response> => {
+    const url = new URL(request.url);
+    const path = url.pathname.replace(/\/+$/, '') || '/';
+
+    if (path === '/api/health' && request.method === 'GET') {
+      return json({ status: 'ok' });
+    }
+
+    authorize(request, options.token);
+
+    if (path === '/api/tasks' && request.method === 'GET') {
+      const tasks = await new TaskLoader(config.tasksDir).loadAll();
+      return json(tasks.map(toCatalogEntry));
+    }
+
+    if (path === '/api/runs') {
+      if (request.method === 'GET') {
+        return json(queue.list());
+      }
+      if (request.method === 'POST') {
+        return json(queue.submit(await readJson(request)), 202);
+      }
+      throw new HttpError(405, `Method ${request.method} not allowed`);
+    }
+
+    const runMatch = path.match(/^\/api\/runs\/([^/]+)$/);
+    if (runMatch && request.method === 'GET') {
+      const run = queue.get(runMatch[1]);
+      if (!run) {
+        throw new HttpError(404, `Unknown run: ${runMatch[1]}`);
+      }
+      return json(run);
+    }
+
+    if (path === '/api/results' && request.method === 'GET') {
+      const task = url.searchParams.get('task');
+      const agent = url.searchParams.get('agent');
+      const runId = url.searchParams.get('run_id');
+      const limit = Number(url.searchParams.get('limit') || 0);
+      let results = (await loadStoredResults(config)).filter(result =>
+        (!task || result.task_id === task) &&
+        (!agent || result.agent === agent) &&
+        (!runId || result.run_id === runId)
+      );
+      if (Number.isInteger(limit) && limit > 0) {
+        results = results.slice(-limit);
+      }
+      return json(results);
+    }
+
+    throw new HttpError(404, `Not found: ${request.method} ${path}`);
+  };
+
+  return withErrorResponses('API', route);
+}
+
+/**
+ * Start the API server.
+ */
+export function startApiServer(config: RunnerConfig, options: ApiServerOptions) {
+  return Bun.serve({
+    hostname: options.host,
+    port: options.port,
+    fetch: createApiHandler(config, options),
+  });
+}
@@ -0,0 +1,301 @@
+/**
+ * Coordinator of a distributed run (`agent-bench coordinate`).
+ *
+ * The coordinator selects a suite's tasks from its own task checkout and hands them out to
+ * workers (`agent-bench worker`, see `worker.ts`) as they become free, each worker
+ * executing one task at a time. Results come back over HTTP and are stored under the
+ * coordinator's run ID, with the worker that produced them, by the coordinator's result
+ * store; the suite is then saved and reported like a local one.
+ *
+ * A worker that cannot be reached or answers with a server error is dropped and its task
+ * handed to another worker. A busy worker is asked again, and a task a worker rejects fails
+ * with a `setup` error. When no worker is left, the remaining tasks fail with a `harness`
+ * error.
+ */
+
+import { EventBus, type RunEventListener } from '../core/events.js';
+import type { RunnerConfig } from '../core/config.js';
+import { hashTask } from '../core/lock.js';
+import type { Task } from '../core/task.js';
+import {
+  createFailure,
+  createRunId,
+  createSuiteResults,
+  findRegressions,
+  getOutcome,
+  withFailureKind,
+  withRunLog,
+  withWorker,
+  type BenchmarkResult,
+  type SuiteResults,
+} from '../evaluator/results.js';
+import { createResultStore, type ResultStore } from '../storage/store.js';
+import { logger } from '../utils/logger.js';
+import { WorkerError } from '../utils/errors.js';
+import type { ExecutionRequest, WorkerExecution } from './worker.js';
+
+/**
+ * Coordinator options.
+ */
+export interface CoordinatorOptions {
+  /** Worker base URLs, e.g. `http://build-01:8788` */
+  workers: string[];
+  /** Bearer token the workers require */
+  token: string | null;
+  /** Seconds between execution status polls (default 5) */
+  pollSecs?: number;
+  /** Result store (default: the configured one) */
+  store?: ResultStore;
+  listeners?: RunEventListener[];
+}
+
+/**
+ * A suite to distribute.
+ */
+export interface DistributedSuite {
+  tasks: Task[];
+  /** Agent as `<type>[@provider/model]`, created by each worker */
+  agent: string;
+  skipVerify: boolean;
+  /** Dangerous permissions were acknowledged on the coordinator */
+  yes: boolean;
+}
+
+/**
+ * HTTP client for one worker.
+ */
+export class WorkerClient {
+  readonly url: string;
+  private token: string | null;
+
+  constructor(url: string, token: string | null) {
+    this.url = url.replace(/\/+$/, '');
+    this.token = token;
+  }
+
+  /**
+   * Whether the worker is up.
+   */
+  async healthy(): Promise<boolean> {
+    try {
+      return (await this.request<{ status: string }>('GET', '/api/health')).status === 'ok';
+    } catch {
+      return false;
+    }
+  }
+
+  /**
+   * Start an execution.
+   * @throws WorkerError If the worker is unreachable, busy (409), or rejects the request
+   */
+  async start(request: ExecutionRequest): Promise<WorkerExecution> {
+    return await this.request<WorkerExecution>('POST', '/api/executions', request);
+  }
+
+  /**
+   * Poll an execution until it finishes.
+   * @throws WorkerError If the worker stops responding
+   */
+  async wait(id: string, pollSecs: number): Promise<WorkerExecution> {
+    for (;;) {
+      const execution = await this.request<WorkerExecution>('GET', `/api/executions/${id}`);
+      if (execution.status !== 'running') {
+        return execution;
+      }
+      await new Promise(resolve => setTimeout(resolve, pollSecs * 1000));
+    }
+  }
+
+  private async request<T>(method: string, path: string, body?: unknown): Promise<T> {
+    const headers: Record<string, string> = { Accept: 'application/json' };
+    if (body !== undefined) {
+      headers['Content-Type'] = 'application/json';
+    }
+    if (this.token) {
+      headers.Authorization = `Bearer ${this.token}`;
+    }
+
+    let response: Response;
+    try {
+      response = await fetch(this.url + path, {
+        method,
+        headers,
+        body: body === undefined ? undefined : JSON.stringify(body),
+      });
+    } catch (error) {
+      throw new WorkerError(`Cannot reach worker ${this.url}: ${error instanceof Error ? error.message : error}`);
+    }
+    const data = await response.json().catch(() => null) as { error?: string } | null;
+    if (!response.ok) {
+      throw new WorkerError(`Worker ${this.url}: ${method} ${path}: ${data?.error ?? response.statusText}`, response.status);
+    }
+    return data as T;
+  }
+}
+
+/**
+ * Dispatches a suite's tasks to workers and aggregates their results.
+ */
+export class Coordinator {
+  readonly runId: string;
+  readonly events: EventBus;
+  private config: RunnerConfig;
+  private workers: WorkerClient[];
+  private pollSecs: number;
+  private store: ResultStore;
+
+  constructor(config: RunnerConfig, options: CoordinatorOptions) {
+    this.runId = createRunId();
+    this.events = new EventBus(this.runId);
+    for (const listener of options.listeners ?? []) {
+      this.events.on(listener);
+    }
+    this.config = config;
+    this.workers = options.workers.map(url => new WorkerClient(url, options.token));
+    this.pollSecs = options.pollSecs ?? 5;
+    this.store = options.store ?? createResultStore(config);
+  }
+
+  /**
+   * Run a suite across the workers and save its results.
+   * @throws WorkerError If no worker is reachable
+   */
+  async run(suite: DistributedSuite): Promise<SuiteResults> {
+    const workers: WorkerClient[] = [];
+    for (const worker of this.workers) {
+      if (await worker.healthy()) {
+        workers.push(worker);
+      } else {
+        logger.warn(`Worker ${worker.url} is not reachable; leaving it out`);
+      }
+    }
+    if (workers.length === 0) {
+      throw new WorkerError('No reachable workers');
+    }
+    logger.info(`Distributing ${suite.tasks.length} tasks over ${workers.length} worker(s)`);
+
+    await this.events.emit({ type: 'suite_started', agent: suite.agent, task_ids: suite.tasks.map(task => task.id) });
+    const pending = [...suite.tasks];
+    const results = new Map<string, BenchmarkResult>();
+    let stopped = false;
+
+    const serve = async (worker: WorkerClient) => {
+      let task: Task | undefined;
+      while (!stopped && (task = pending.shift())) {
+        await this.events.emit({
+          type: 'task_started',
+          task_id: task.id,
+          title: task.title,
+          agent: suite.agent,
+          file_path: task.file_path ?? null,
+        });
+        let result: BenchmarkResult;
+        try {
+          result = await this.execute(worker, task, suite);
+        } catch (error) {
+          logger.warn(`${error instanceof Error ? error.message : error}; handing ${task.id} to another worker`);
+          pending.unshift(task);
+          return;
+        }
+        results.set(task.id, result);
+        await this.events.emit({ type: 'task_finished', task_id: task.id, result });
+        if (this.config.failFast && getOutcome(result) === 'failed') {
+          logger.warn(`Stopping suite after ${task.id} failed (--fail-fast)`);
+          stopped = true;
+        }
+      }
+    };
+    await Promise.all(workers.map(serve));
+
+    if (!stopped) {
+      for (const task of pending) {
+        const result = await this.save(withFailureKind(
+          createFailure(task.id, suite.agent, 0, null, 0, 'No reachable worker left to execute the task'),
+          'harness',
+          { phase: 'dispatch' }
+        ));
+        results.set(task.id, result);
+        await this.events.emit({ type: 'task_finished', task_id: task.id, result });
+      }
+    }
+
+    const ordered = suite.tasks.flatMap(task => results.get(task.id) ?? []);
+    const summary = createSuiteResults(ordered.find(result => result.agent)?.agent ?? suite.agent, ordered);
+    summary.regressions = findRegressions(ordered, await this.store.loadResults());
+    const suitePath = await this.store.saveSuite(summary);
+    await this.events.emit({ type: 'suite_finished', suite: summary });
+    logger.success(`Suite results saved to: ${suitePath}`);
+    return summary;
+  }
+
+  /**
+   * Release the result store.
+   */
+  async close(): Promise<void> {
+    await this.store.close();
+  }
+
+  /**
+   * Execute a task on a worker and store its result. A task the worker rejects or could
+   * not run at all (unknown task, a copy that differs from ours, invalid agent) becomes a
+   * `setup` failure.
+   * @throws WorkerError If the worker is unreachable or answers with a server error
+   */
+  private async execute(worker: WorkerClient, task: Task, suite: DistributedSuite): Promise<BenchmarkResult> {
+    let error: string;
+    try {
+      const execution = await this.dispatch(worker, task, suite);
+      if (execution.result) {
+        return await this.save(withWorker(execution.result, worker.url));
+      }
+      error = execution.error ?? 'Execution failed';
+    } catch (caught) {
+      if (!(caught instanceof WorkerError) || caught.status === null || caught.status >= 500) {
+        throw caught;
+      }
+      error = caught.message;
+    }
+
+    const result = withFailureKind(createFailure(task.id, suite.agent, 0, null, 0, error), 'setup', { phase: 'dispatch' });
+    return await this.save(withWorker(result, worker.url));
+  }
+
+  /**
+   * Start a task on a worker, asking again while it is busy, and wait for it to finish.
+   * @throws WorkerError If the worker is unreachable or does not accept the task
+   */
+  private async dispatch(worker: WorkerClient, task: Task, suite: DistributedSuite): Promise<WorkerExecution> {
+    const request: ExecutionRequest = {
+      task_id: task.id,
+      task_sha256: await hashTask(task),
+      agent: suite.agent,
+      run_id: this.runId,
+      skip_verify: suite.skipVerify,
+      yes: suite.yes,
+    };
+    let started: WorkerExecution;
+    for (;;) {
+      try {
+        started = await worker.start(request);
+        break;
+      } catch (error) {
+        if (!(error instanceof WorkerError) || error.status !== 409) {
+          throw error;
+        }
+        logger.debug(`${worker.url} is busy; asking again for ${task.id}`);
+        await new Promise(resolve => setTimeout(resolve, this.pollSecs * 1000));
+      }
+    }
+    logger.debug(`${task.id} is executing on ${worker.url} (${started.id})`);
+    return await worker.wait(started.id, this.pollSecs);
+  }
+
+  /**
+   * Store a result under the coordinator's run (its log stays on the worker).
+   */
+  private async save(result: BenchmarkResult): Promise<BenchmarkResult> {
+    const stored = withRunLog(result, this.runId, result.log_path);
+    await this.store.saveResult(stored);
+    return stored;
+  }
+}
@@ -0,0 +1,94 @@
+/**
+ * HTTP helpers shared by the servers (`serve-api`, `worker`).
+ */
+
+import { randomBytes, timingSafeEqual } from 'crypto';
+import { logger } from '../utils/logger.js';
+
+/**
+ * Error mapped to an HTTP status.
+ */
+export class HttpError extends Error {
+  status: number;
+
+  constructor(status: number, message: string) {
+    super(message);
+    this.status = status;
+  }
+}
+
+/**
+ * Serialize a JSON response.
+ */
+export function json(data: unknown, status: number = 200): Response {
+  return new Response(JSON.stringify(data, null, 2), {
+    status,
+    headers: { 'Content-Type': 'application/json' },
+  });
+}
+
+/**
+ * Parse a JSON request body. Requiring the JSON content type keeps browsers from posting
+ * to the server from other sites without a CORS preflight.
+ * @throws HttpError 415 If the body is not declared as `application/json`
+ * @throws HttpError 400 If the body is not JSON
+ */
+export async function readJson(request: Request): Promise<unknown> {
+  const contentType = (request.headers.get('content-type') ?? '').split(';')[0].trim().toLowerCase();
+  if (contentType !== 'application/json') {
+    throw new HttpError(415, 'Content-Type must be application/json');
+  }
+  try {
+    return await request.json();
+  } catch {
+    throw new HttpError(400, 'Request body must be JSON');
+  }
+}
+
+/**
+ * Require `Authorization: Bearer <token>` when a token is set. Without one, only reads
+ * are allowed: requests that start work need a token.
+ * @throws HttpError 401 If the header is missing or wrong, or no token is set for a POST
+ */
+export function authorize(request: Request, token: string | null): void {
+  if (!token) {
+    if (request.method !== 'GET' && request.method !== 'HEAD') {
+      throw new HttpError(401, `${request.method} requires a bearer token, but the server has none configured`);
+    }
+    return;
+  }
+  const given = Buffer.from(request.headers.get('authorization') ?? '');
+  const expected = Buffer.from(`Bearer ${token}`);
+  // Constant-time comparison, so response timing does not reveal the token
+  if (given.length !== expected.length || !timingSafeEqual(given, expected)) {
+    throw new HttpError(401, 'Missing or invalid bearer token');
+  }
+}
+
+/**
+ * A random bearer token, for servers started without one.
+ */
+export function generateToken(): string {
+  return randomBytes(24).toString('hex');
+}
+
+/**
+ * Wrap a router so thrown HttpErrors become JSON error responses (other errors are 500s).
+ * @param label Prefix for logged internal errors
+ */
+export function withErrorResponses(
+  label: string,
+  route: (request: Request) => Promise<Response>
+): (request: Request) => Promise<Response> {
+  return async (request: Request): Promise<Response> => {
+    try {
+      return await route(request);
+    } catch (error) {
+      if (error instanceof HttpError) {
+        return json({ error: error.message }, error.status);
+      }
+      logger.error(`${label} error: ${error}`);
+      return json({ error: String(error) }, 500);
+    }
+  };
+}
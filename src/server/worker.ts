@@ -0,0 +1,244 @@
+/**
+ * Worker node of a distributed run (`agent-bench worker`).
+ *
+ * A worker executes tasks dispatched by a coordinator (`agent-bench coordinate`), one at a
+ * time, with its own workspaces, agent CLIs, and task checkout, and hands back each result.
+ * Executions can outlast an HTTP request, so the coordinator polls for them:
+ *
+ *   GET  /api/health            Liveness and whether an execution is in progress
+ *   POST /api/executions        Start a task; 202 with the execution, 409 while busy
+ *   GET  /api/executions/:id    Execution status, with the result once finished
+ *
+ * A finished execution is forgotten once it has been fetched. Results are also stored by
+ * the worker's own result store, like a local `run --task`. Requests carry the checksum of
+ * the coordinator's copy of the task (`hashTask`), and a worker whose copy differs refuses
+ * to execute it.
+ */
+
+import { z } from 'zod';
+import { randomUUID } from 'crypto';
+import { TaskRunner } from '../core/runner.js';
+import type { RunnerConfig } from '../core/config.js';
+import { hashTask } from '../core/lock.js';
+import { findDangerousTasks } from '../core/manifest.js';
+import { createAgentFromSpec, parseAgentSpec, parseRemoteAgentSpec } from '../agents/factory.js';
+import { withRecording } from '../agents/recording.js';
+import type { BenchmarkResult } from '../evaluator/results.js';
+import { logger } from '../utils/logger.js';
+import { consoleReporter } from '../utils/reporter.js';
+import { authorize, HttpError, json, readJson, withErrorResponses } from './http.js';
+
+/**
+ * Body of `POST /api/executions`.
+ */
+const ExecutionRequestSchema = z
+  .object({
+    task_id: z.string().min(1),
+    /** Checksum of the coordinator's copy of the task (see hashTask) */
+    task_sha256: z.string().regex(/^[0-9a-f]{64}$/, 'task_sha256 must be a SHA-256 hex digest'),
+    /** Agent as `<type>[@provider/model]` or a named agent (not `plugin:<path>`) */
+    agent: z.string().min(1),
+    /** Coordinator's run, for the worker's logs */
+    run_id: z.string().nullable().default(null),
+    skip_verify: z.boolean().default(false),
+    /** The coordinator acknowledged the task's dangerous permissions */
+    yes: z.boolean().default(false),
+  })
+  .strict();
+export type ExecutionRequest = z.infer<typeof ExecutionRequestSchema>;
+
+/**
+ * A task execution on a worker.
+ */
+export interface WorkerExecution {
+  id: string;
+  status: 'running' | 'completed' | 'failed';
+  request: ExecutionRequest;
+  started_at: string;
+  finished_at: string | null;
+  /** Why the task could not be executed (status `failed`); task failures are results */
+  error: string | null;
+  result: BenchmarkResult | null;
+}
+
+/**
+ * Worker server options.
+ */
+export interface WorkerServerOptions {
+  host: string;
+  port: number;
+  token: string | null;
+}
+
+export interface WorkerHandlerOptions {
+  token: string | null;
+  /**
+   * Create the runner for an execution, with the requested agent as its agent
+   * (default: the agent from the spec, with recording as configured)
+   */
+  createRunner?: (config: RunnerConfig, agent: string) => TaskRunner;
+}
+
+/**
+ * Runner for an execution with the agent built from its spec.
+ */
+function createDefaultRunner(config: RunnerConfig, agentSpec: string): TaskRunner {
+  const spec = parseAgentSpec(agentSpec);
+  const agent = withRecording(createAgentFromSpec(spec, config.defaultModel, config.maxIterations), {
+    mode: config.recordMode,
+    dir: config.recordingsDir,
+    model: spec.model || config.defaultModel,
+  });
+  return new TaskRunner(config, { agent, listeners: [consoleReporter] });
+}
+
+/**
+ * Executes dispatched tasks one at a time.
+ */
+export class ExecutionSlot {
+  private config: RunnerConfig;
+  private createRunner: (config: RunnerConfig, agent: string) => TaskRunner;
+  private executions = new Map<string, WorkerExecution>();
+  private current: string | null = null;
+
+  constructor(config: RunnerConfig, createRunner = createDefaultRunner) {
+    this.config = config;
+    this.createRunner = createRunner;
+  }
+
+  get busy(): boolean {
+    return this.current !== null;
+  }
+
+  /**
+   * Validate a request and start executing it.
+   * @throws HttpError 400 If the request is invalid, 409 If a task is already executing
+   */
+  start(body: unknown): WorkerExecution {
+    const parsed = ExecutionRequestSchema.safeParse(body);
+    if (!parsed.success) {
+      throw new HttpError(400, parsed.error.issues.map(issue => issue.message).join('; '));
+    }
+    if (this.current) {
+      throw new HttpError(409, `Busy executing ${this.executions.get(this.current)?.request.task_id}`);
+    }
+    const request = parsed.data;
+    try {
+      parseRemoteAgentSpec(request.agent);
+    } catch (error) {
+      throw new HttpError(400, error instanceof Error ? error.message : String(error));
+    }
+
+    const execution: WorkerExecution = {
+      id: randomUUID(),
+      status: 'running',
+      request,
+      started_at: new Date().toISOString(),
+      finished_at: null,
+      error: null,
+      result: null,
+    };
+    this.executions.set(execution.id, execution);
+    this.current = execution.id;
+    void this.execute(execution).finally(() => {
+      this.current = null;
+    });
+    return execution;
+  }
+
+  /**
+   * An execution; a finished one is forgotten after this returns it.
+   */
+  take(id: string): WorkerExecution | undefined {
+    const execution = this.executions.get(id);
+    if (execution && execution.status !== 'running') {
+      this.executions.delete(id);
+    }
+    return execution;
+  }
+
+  private async execute(execution: WorkerExecution): Promise<void> {
+    const { request } = execution;
+    const config: RunnerConfig = { ...this.config, permissionAck: null };
+    let runner: TaskRunner | null = null;
+    logger.info(`Executing ${request.task_id} for run ${request.run_id ?? '(unknown)'}`);
+
+    try {
+      runner = this.createRunner(config, request.agent);
+      const tasks = await runner.selectTasks({ taskId: request.task_id });
+      const checksum = await hashTask(tasks[0]);
+      if (checksum !== request.task_sha256) {
+        throw new Error(
+          `Task ${request.task_id} differs from the coordinator's copy (sha256 ${checksum}, expected ${request.task_sha256}); ` +
+            'sync the worker\'s tasks'
+        );
+      }
+      if (request.yes) {
+        config.permissionAck = {
+          method: 'coordinator',
+          acknowledged_at: new Date().toISOString(),
+          tasks: findDangerousTasks(tasks),
+        };
+      }
+      execution.result = await runner.runTask(request.task_id, undefined, request.skip_verify);
+      execution.status = 'completed';
+    } catch (error) {
+      execution.status = 'failed';
+      execution.error = error instanceof Error ? error.message : String(error);
+      logger.error(`Execution of ${request.task_id} failed: ${execution.error}`);
+    } finally {
+      execution.finished_at = new Date().toISOString();
+      await runner?.close();
+    }
+  }
+}
+
+/**
+ * Build the worker's request handler.
+ */
+export function createWorkerHandler(config: RunnerConfig, options: WorkerHandlerOptions) {
+  const slot = new ExecutionSlot(config, options.createRunner);
+
+  const route = async (request: Request): Promise<Response> => {
+    const url = new URL(request.url);
+    const path = url.pathname.replace(/\/+$/, '') || '/';
+
+    if (path === '/api/health' && request.method === 'GET') {
+      return json({ status: 'ok', busy: slot.busy });
+    }
+
+    authorize(request, options.token);
+
+    if (path === '/api/executions' && request.method === 'POST') {
+      return json(slot.start(await readJson(request)), 202);
+    }
+
+    const executionMatch = path.match(/^\/api\/executions\/([^/]+)$/);
+    if (executionMatch && request.method === 'GET') {
+      const execution = slot.take(executionMatch[1]);
+      if (!execution) {
+        throw new HttpError(404, `Unknown execution: ${executionMatch[1]}`);
+      }
+      return json(execution);
+    }
+
+    throw new HttpError(404, `Not found: ${request.method} ${path}`);
+  };
+
+  return withErrorResponses('Worker', route);
+}
+
+/**
+ * Start the worker server.
+ */
+export function startWorkerServer(
+  config: RunnerConfig,
+  options: WorkerServerOptions,
+  handlerOptions: Omit<WorkerHandlerOptions, 'token'> = {}
+) {
+  return Bun.serve({
+    hostname: options.host,
+    port: options.port,
+    fetch: createWorkerHandler(config, { ...handlerOptions, token: options.token }),
+  });
+}
@@ -0,0 +1,42 @@
+/**
+ * JSON file result storage (one file per result, the default).
+ */
+
+import { collectResults } from '../collectors/csv.js';
+import { saveResult, saveSuiteResults } from '../evaluator/results.js';
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import type { ResultStore } from './store.js';
+
+/**
+ * Stores results as JSON files in the results directory (plus the auto-appended summary CSV).
+ */
+export class JsonResultStore implements ResultStore {
+  readonly backend = 'json' as const;
+  private resultsDir: string;
+
+  constructor(resultsDir: string) {
+    this.resultsDir = resultsDir;
+  }
+
+  async saveResult(result: BenchmarkResult): Promise<string> {
+    return await saveResult(result, this.resultsDir);
+  }
+
+  async saveSuite(suite: SuiteResults): Promise<string> {
+    return await saveSuiteResults(suite, this.resultsDir);
+  }
+
+  async loadResults(): Promise<BenchmarkResult[]> {
+    try {
+      return await collectResults(this.resultsDir);
+    } catch (error) {
+      // A missing results directory just means nothing has been stored yet
+      if ((error as NodeJS.ErrnoException).code === 'ENOENT') {
+        return [];
+      }
+      throw error;
+    }
+  }
+
+  async close(): Promise<void> {}
+}
@@ -0,0 +1,102 @@
+/**
+ * PostgreSQL result storage (Bun's built-in SQL client).
+ */
+
+import { SQL } from 'bun';
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import { getOutcome } from '../evaluator/results.js';
+import { StorageError } from '../utils/errors.js';
+import type { ResultStore } from './store.js';
+
+/**
+ * Stores results in PostgreSQL, e.g. a database shared by several benchmark machines.
+ */
+export class PostgresResultStore implements ResultStore {
+  readonly backend = 'postgres' as const;
+  private url: string;
+  private sql: SQL | null = null;
+  private ready: Promise<void> | null = null;
+
+  /**
+   * @param url Connection URL (defaults to DATABASE_URL)
+   */
+  constructor(url: string | null) {
+    const resolved = url || process.env.DATABASE_URL;
+    if (!resolved) {
+      throw new StorageError('The postgres results backend needs a connection URL ([results] url or DATABASE_URL)');
+    }
+    this.url = resolved;
+  }
+
+  async saveResult(result: BenchmarkResult): Promise<string> {
+    const sql = await this.open();
+    const [row] = await sql`
+      INSERT INTO results (task_id, agent, run_id, outcome, timestamp, data)
+      VALUES (${result.task_id}, ${result.agent}, ${result.run_id}, ${getOutcome(result)}, ${result.timestamp}, ${JSON.stringify(result)}::jsonb)
+      RETURNING id`;
+    return `postgres:results/${row.id}`;
+  }
+
+  async saveSuite(suite: SuiteResults): Promise<string> {
+    const sql = await this.open();
+    const [row] = await sql`
+      INSERT INTO suites (agent, timestamp, data)
+      VALUES (${suite.agent}, ${suite.timestamp}, ${JSON.stringify(suite)}::jsonb)
+      RETURNING id`;
+    return `postgres:suites/${row.id}`;
+  }
+
+  async loadResults(): Promise<BenchmarkResult[]> {
+    const sql = await this.open();
+    const rows = await sql`SELECT data FROM results ORDER BY timestamp, id`;
+    return rows.map((row: { data: unknown }) =>
+      typeof row.data === 'string' ? JSON.parse(row.data) : row.data
+    );
+  }
+
+  async close(): Promise<void> {
+    if (this.sql) {
+      await this.sql.close();
+      this.sql = null;
+      this.ready = null;
+    }
+  }
+
+  /**
+   * Connect and create the schema on first use.
+   */
+  private async open(): Promise<SQL> {
+    if (!this.sql) {
+      this.sql = new SQL(this.url);
+    }
+    const sql = this.sql;
+    this.ready ??= (async () => {
+      try {
+        await sql`
+          CREATE TABLE IF NOT EXISTS results (
+            id BIGSERIAL PRIMARY KEY,
+            task_id TEXT NOT NULL,
+            agent TEXT NOT NULL,
+            run_id TEXT,
+            outcome TEXT NOT NULL,
+            timestamp TEXT NOT NULL,
+            data JSONB NOT NULL
+          )`;
+        await sql`CREATE INDEX IF NOT EXISTS results_task ON results (task_id, agent)`;
+        await sql`CREATE INDEX IF NOT EXISTS results_run ON results (run_id)`;
+        await sql`
+          CREATE TABLE IF NOT EXISTS suites (
+            id BIGSERIAL PRIMARY KEY,
+            agent TEXT NOT NULL,
+            timestamp TEXT NOT NULL,
+            data JSONB NOT NULL
+          )`;
+      } catch (error) {
+        this.ready = null;
+        throw new StorageError(`Failed to initialize postgres results database: ${error}`);
+      }
+    })();
+    await this.ready;
+    return sql;
+  }
+}
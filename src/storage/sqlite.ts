@@ -0,0 +1,95 @@
+/**
+ * SQLite result storage (bun:sqlite).
+ */
+
+import { Database } from 'bun:sqlite';
+import { mkdirSync } from 'fs';
+import { dirname } from 'path';
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import { getOutcome } from '../evaluator/results.js';
+import { StorageError } from '../utils/errors.js';
+import type { ResultStore } from './store.js';
+
+/**
+ * Schema: indexed columns for common queries, the full record as JSON.
+ */
+const SCHEMA = `
+CREATE TABLE IF NOT EXISTS results (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  task_id TEXT NOT NULL,
+  agent TEXT NOT NULL,
+  run_id TEXT,
+  outcome TEXT NOT NULL,
+  timestamp TEXT NOT NULL,
+  data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS results_task ON results (task_id, agent);
+CREATE INDEX IF NOT EXISTS results_run ON results (run_id);
+CREATE TABLE IF NOT EXISTS suites (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  agent TEXT NOT NULL,
+  timestamp TEXT NOT NULL,
+  data TEXT NOT NULL
+);
+`;
+
+/**
+ * How long a write waits for another connection's write to finish.
+ */
+const BUSY_TIMEOUT_MS = 30_000;
+
+/**
+ * Stores results in a local SQLite database file. Safe to share between processes.
+ */
+export class SqliteResultStore implements ResultStore {
+  readonly backend = 'sqlite' as const;
+  private path: string;
+  private db: Database | null = null;
+
+  constructor(path: string) {
+    this.path = path;
+  }
+
+  async saveResult(result: BenchmarkResult): Promise<string> {
+    const { lastInsertRowid } = this.open()
+      .query('INSERT INTO results (task_id, agent, run_id, outcome, timestamp, data) VALUES (?, ?, ?, ?, ?, ?)')
+      .run(result.task_id, result.agent, result.run_id, getOutcome(result), result.timestamp, JSON.stringify(result));
+    return `${this.path}#results/${lastInsertRowid}`;
+  }
+
+  async saveSuite(suite: SuiteResults): Promise<string> {
+    const { lastInsertRowid } = this.open()
+      .query('INSERT INTO suites (agent, timestamp, data) VALUES (?, ?, ?)')
+      .run(suite.agent, suite.timestamp, JSON.stringify(suite));
+    return `${this.path}#suites/${lastInsertRowid}`;
+  }
+
+  async loadResults(): Promise<BenchmarkResult[]> {
+    const rows = this.open().query('SELECT data FROM results ORDER BY timestamp, id').all() as { data: string }[];
+    return rows.map(row => JSON.parse(row.data));
+  }
+
+  async close(): Promise<void> {
+    this.db?.close();
+    this.db = null;
+  }
+
+  /**
+   * Open the database and create the schema on first use.
+   */
+  private open(): Database {
+    if (!this.db) {
+      try {
+        mkdirSync(dirname(this.path), { recursive: true });
+        this.db = new Database(this.path, { create: true });
+        // WAL lets readers and concurrent shards' writers share the file; writers wait for each other
+        this.db.exec('PRAGMA journal_mode = WAL');
+        this.db.exec(`PRAGMA busy_timeout = ${BUSY_TIMEOUT_MS}`);
+        this.db.exec(SCHEMA);
+      } catch (error) {
+        throw new StorageError(`Failed to open SQLite results database ${this.path}: ${error}`);
+      }
+    }
+    return this.db;
+  }
+}
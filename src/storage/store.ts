@@ -0,0 +1,122 @@
+/**
+ * Result storage abstraction.
+ *
+ * The runner and the reporting commands go through a ResultStore instead of
+ * reading and writing result files directly, so the backend is a config choice.
+ */
+
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import type { ResultsBackend, RunnerConfig } from '../core/config.js';
+import { join } from 'path';
+import { JsonResultStore } from './json.js';
+import { requireFeature } from '../features.js';
+
+/**
+ * Persists and loads benchmark results.
+ */
+export interface ResultStore {
+  /**
+   * Backend name.
+   */
+  readonly backend: ResultsBackend;
+
+  /**
+   * Persist a task result.
+   * @returns Where the result was stored (file path or database reference)
+   */
+  saveResult(result: BenchmarkResult): Promise<string>;
+
+  /**
+   * Persist suite results.
+   * @returns Where the suite was stored
+   */
+  saveSuite(suite: SuiteResults): Promise<string>;
+
+  /**
+   * Load all stored task results, oldest first.
+   */
+  loadResults(): Promise<BenchmarkResult[]>;
+
+  /**
+   * Release connections.
+   */
+  close(): Promise<void>;
+}
+
+/**
+ * Create the result store selected by the configuration.
+ * SQLite defaults to `<results-dir>/results.db`; Postgres uses `resultsUrl` or DATABASE_URL.
+ * The SQL backends (the `sql` feature) are loaded on first use.
+ */
+export function createResultStore(config: RunnerConfig): ResultStore {
+  switch (config.resultsBackend) {
+    case 'json':
+      return new JsonResultStore(config.resultsDir);
+    case 'sqlite':
+      return new LazyResultStore('sqlite', async () => {
+        const { SqliteResultStore } = await import('./sqlite.js');
+        return new SqliteResultStore(config.resultsUrl || join(config.resultsDir, 'results.db'));
+      });
+    case 'postgres':
+      return new LazyResultStore('postgres', async () => {
+        const { PostgresResultStore } = await import('./postgres.js');
+        return new PostgresResultStore(config.resultsUrl);
+      });
+  }
+}
+
+/**
+ * A SQL result store whose module is imported on first use, so builds without the `sql`
+ * feature (and runs that never touch results) do not load it.
+ */
+class LazyResultStore implements ResultStore {
+  readonly backend: ResultsBackend;
+  private load: () => Promise<ResultStore>;
+  private store: Promise<ResultStore> | null = null;
+
+  constructor(backend: ResultsBackend, load: () => Promise<ResultStore>) {
+    this.backend = backend;
+    this.load = load;
+  }
+
+  private open(): Promise<ResultStore> {
+    if (!this.store) {
+      this.store = (async () => {
+        requireFeature('sql', `The ${this.backend} results backend`);
+        return await this.load();
+      })();
+    }
+    return this.store;
+  }
+
+  async saveResult(result: BenchmarkResult): Promise<string> {
+    return await (await this.open()).saveResult(result);
+  }
+
+  async saveSuite(suite: SuiteResults): Promise<string> {
+    return await (await this.open()).saveSuite(suite);
+  }
+
+  async loadResults(): Promise<BenchmarkResult[]> {
+    return await (await this.open()).loadResults();
+  }
+
+  async close(): Promise<void> {
+    // Nothing to release if the store was never opened
+    if (this.store) {
+      await (await this.store).close();
+    }
+  }
+}
+
+/**
+ * Load all stored results from the configured backend.
+ */
+export async function loadStoredResults(config: RunnerConfig): Promise<BenchmarkResult[]> {
+  const store = createResultStore(config);
+  try {
+    return await store.loadResults();
+  } finally {
+    await store.close();
+  }
+}
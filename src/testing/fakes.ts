@@ -0,0 +1,165 @@
+/**
+ * In-process fakes for exercising the run pipeline without a real agent, verification
+ * subprocess, or result files: inject them with `TaskRunner.builder(config)`.
+ *
+ * ```ts
+ * const agent = new FakeAgent({ files: { 'fix.txt': 'done' } });
+ * const runner = TaskRunner.builder(config)
+ *   .agent(agent)
+ *   .verifier(new FakeVerifier(async (_task, workspace) => existsSync(join(workspace, 'fix.txt'))))
+ *   .store(new MemoryResultStore())
+ *   .build();
+ * ```
+ */
+
+import { mkdir, writeFile } from 'fs/promises';
+import { dirname, join } from 'path';
+import type { Agent, AgentResult, IterationListener, StepCallback } from '../agents/types.js';
+import { AgentCapabilitySchema, type AgentCapability, type Task } from '../core/task.js';
+import type { TaskVerifier, VerificationResult } from '../evaluator/verifier.js';
+import type { BenchmarkResult, SuiteResults } from '../evaluator/results.js';
+import type { ResultStore } from '../storage/store.js';
+
+export interface FakeAgentOptions {
+  /** Agent name (default `fake`) */
+  name?: string;
+  /** Declared capabilities (default: all) */
+  capabilities?: AgentCapability[];
+  /** Files to write into the workspace on each execution, relative path → content */
+  files?: Record<string, string>;
+  /** Custom behavior run after `files` are written, e.g. to edit existing files */
+  act?: (task: Task, workspace: string) => void | Promise<void>;
+  /** Fields overriding the returned result */
+  result?: Partial<AgentResult>;
+  /** Throw this instead of returning a result */
+  error?: Error;
+}
+
+/**
+ * A call made to a fake agent.
+ */
+export interface FakeAgentCall {
+  task: Task;
+  workspace: string;
+  prompts: string[];
+}
+
+/**
+ * Agent that edits the workspace as scripted and reports fixed metrics.
+ */
+export class FakeAgent implements Agent {
+  /** Every execution, in order */
+  readonly calls: FakeAgentCall[] = [];
+  private options: FakeAgentOptions;
+  private listener: IterationListener | null = null;
+
+  constructor(options: FakeAgentOptions = {}) {
+    this.options = options;
+  }
+
+  name(): string {
+    return this.options.name ?? 'fake';
+  }
+
+  capabilities(): AgentCapability[] {
+    return this.options.capabilities ?? [...AgentCapabilitySchema.options];
+  }
+
+  setIterationListener(listener: IterationListener | null): void {
+    this.listener = listener;
+  }
+
+  async execute(task: Task, workspace: string): Promise<AgentResult> {
+    this.calls.push({ task, workspace, prompts: [task.prompt] });
+    await this.act(task, workspace);
+    return this.result();
+  }
+
+  async executeSteps(task: Task, workspace: string, prompts: string[], onStep: StepCallback): Promise<AgentResult> {
+    this.calls.push({ task, workspace, prompts });
+    for (let index = 0; index < prompts.length; index++) {
+      await this.act(task, workspace);
+      if (!(await onStep(index, this.result()))) {
+        break;
+      }
+    }
+    return this.result();
+  }
+
+  private async act(task: Task, workspace: string): Promise<void> {
+    if (this.options.error) {
+      throw this.options.error;
+    }
+    for (const [file, content] of Object.entries(this.options.files ?? {})) {
+      const path = join(workspace, file);
+      await mkdir(dirname(path), { recursive: true });
+      await writeFile(path, content, 'utf-8');
+    }
+    await this.options.act?.(task, workspace);
+    this.listener?.({ iteration: 1, tokensUsed: this.options.result?.tokensUsed ?? 0, cost: null });
+  }
+
+  private result(): AgentResult {
+    return {
+      success: true,
+      output: 'fake agent output',
+      iterations: 1,
+      tokensUsed: 0,
+      usage: null,
+      cost: null,
+      durationSecs: 0,
+      agentVersion: null,
+      modelName: null,
+      ...this.options.result,
+    };
+  }
+}
+
+/**
+ * Verifier returning scripted verdicts instead of running the task's verification.
+ */
+export class FakeVerifier implements TaskVerifier {
+  /** Every verification, in order */
+  readonly calls: { task: Task; workspace: string }[] = [];
+  private decide: (task: Task, workspace: string) => boolean | Partial<VerificationResult> | Promise<boolean | Partial<VerificationResult>>;
+
+  /**
+   * @param decide Fixed verdict, or a function computing it (a boolean or result fields) from the workspace
+   */
+  constructor(decide: boolean | FakeVerifier['decide'] = true) {
+    this.decide = typeof decide === 'boolean' ? () => decide : decide;
+  }
+
+  async verify(task: Task, workspace: string): Promise<VerificationResult> {
+    this.calls.push({ task, workspace });
+    const verdict = await this.decide(task, workspace);
+    const fields = typeof verdict === 'boolean' ? { passed: verdict } : verdict;
+    const passed = fields.passed ?? true;
+    return { passed, exitCode: passed ? 0 : 1, stdout: '', stderr: '', durationSecs: 0, ...fields };
+  }
+}
+
+/**
+ * Result store keeping everything in memory.
+ */
+export class MemoryResultStore implements ResultStore {
+  readonly backend = 'json' as const;
+  readonly results: BenchmarkResult[] = [];
+  readonly suites: SuiteResults[] = [];
+
+  async saveResult(result: BenchmarkResult): Promise<string> {
+    this.results.push(result);
+    return `memory#results/${this.results.length}`;
+  }
+
+  async saveSuite(suite: SuiteResults): Promise<string> {
+    this.suites.push(suite);
+    return `memory#suites/${this.suites.length}`;
+  }
+
+  async loadResults(): Promise<BenchmarkResult[]> {
+    return [...this.results];
+  }
+
+  async close(): Promise<void> {}
+}
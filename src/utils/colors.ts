@@ -0,0 +1,50 @@
+/**
+ * Terminal color helpers.
+ *
+ * Chalk detects whether the terminal supports color; `--no-color` and the
+ * NO_COLOR environment variable force plain output (e.g. for CI logs).
+ */
+
+import chalk, { type ChalkInstance } from 'chalk';
+import type { ResultOutcome } from '../evaluator/results.js';
+
+/**
+ * Disable colors when requested by flag or by a non-empty NO_COLOR variable.
+ * @param enabled False when `--no-color` was passed
+ */
+export function configureColors(enabled: boolean = true): void {
+  if (!enabled || (process.env.NO_COLOR ?? '') !== '') {
+    chalk.level = 0;
+  }
+}
+
+/**
+ * Color associated with a task outcome.
+ */
+export function outcomeColor(outcome: ResultOutcome): ChalkInstance {
+  return outcome === 'passed' ? chalk.green : outcome === 'failed' ? chalk.red : chalk.yellow;
+}
+
+/**
+ * Bold PASS/FAIL/SKIP marker for a task outcome.
+ */
+export function outcomeMarker(outcome: ResultOutcome): string {
+  const label = outcome === 'passed' ? 'PASS' : outcome === 'failed' ? 'FAIL' : 'SKIP';
+  return outcomeColor(outcome).bold(label);
+}
+
+/**
+ * Color a difficulty level (easy green, medium yellow, hard red).
+ */
+export function colorDifficulty(difficulty: string, text: string = difficulty): string {
+  switch (difficulty) {
+    case 'easy':
+      return chalk.green(text);
+    case 'medium':
+      return chalk.yellow(text);
+    case 'hard':
+      return chalk.red(text);
+    default:
+      return text;
+  }
+}
@@ -0,0 +1,42 @@
+/**
+ * Bounded-concurrency helpers for I/O-heavy loops.
+ */
+
+/**
+ * Map items with at most `limit` calls in flight, yielding results in input order
+ * as soon as each one (and every one before it) is done.
+ */
+export async function* mapBounded<T, R>(
+  items: readonly T[],
+  limit: number,
+  fn: (item: T) => Promise<R>
+): AsyncGenerator<R> {
+  const pending: Promise<R>[] = [];
+  let next = 0;
+  const fill = () => {
+    while (next < items.length && pending.length < Math.max(1, limit)) {
+      const promise = fn(items[next++]);
+      // Rejections surface when the promise is awaited below; don't report them as unhandled first
+      promise.catch(() => {});
+      pending.push(promise);
+    }
+  };
+
+  fill();
+  while (pending.length > 0) {
+    const result = await pending.shift()!;
+    fill();
+    yield result;
+  }
+}
+
+/**
+ * Map items with at most `limit` calls in flight, returning results in input order.
+ */
+export async function mapConcurrent<T, R>(items: readonly T[], limit: number, fn: (item: T) => Promise<R>): Promise<R[]> {
+  const results: R[] = [];
+  for await (const result of mapBounded(items, limit, fn)) {
+    results.push(result);
+  }
+  return results;
+}
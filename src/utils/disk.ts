@@ -0,0 +1,55 @@
+/**
+ * Disk usage helpers.
+ */
+
+import { lstat, readdir } from 'fs/promises';
+import { join } from 'path';
+
+/**
+ * Total size of a file or directory tree in bytes (symbolic links count as links, not followed).
+ */
+export async function diskUsage(path: string): Promise<number> {
+  try {
+    const info = await lstat(path);
+    if (!info.isDirectory()) {
+      return info.size;
+    }
+    let total = 0;
+    for (const entry of await readdir(path)) {
+      total += await diskUsage(join(path, entry));
+    }
+    return total;
+  } catch {
+    return 0;
+  }
+}
+
+/**
+ * Parse a size such as `500MB`, `10 GB`, `1.5G`, or a plain byte count (units are powers of 1024).
+ * @returns Bytes, or null if the size is malformed
+ */
+export function parseSize(value: string | number): number | null {
+  if (typeof value === 'number') {
+    return Number.isFinite(value) && value > 0 ? Math.floor(value) : null;
+  }
+  const match = /^\s*(\d+(?:\.\d+)?)\s*([KMGT]?)(?:I?B)?\s*$/i.exec(value);
+  if (!match) {
+    return null;
+  }
+  const bytes = Math.floor(Number(match[1]) * 1024 ** ' KMGT'.indexOf(match[2].toUpperCase() || ' '));
+  return bytes > 0 ? bytes : null;
+}
+
+/**
+ * Format a byte count for display.
+ */
+export function formatBytes(bytes: number): string {
+  const units = ['B', 'KB', 'MB', 'GB'];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${value.toFixed(unit === 0 ? 0 : 1)} ${units[unit]}`;
+}
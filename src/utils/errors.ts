@@ -2,23 +2,62 @@
  * Custom error types for Agent Bench.
  */
 
+import { formatBytes } from './disk.js';
+
+/**
+ * What an error is attributed to. Results record it as `failure_kind` and `run`
+ * maps it to an exit code.
+ */
+export const ERROR_CATEGORIES = ['setup', 'agent', 'verification', 'harness', 'timeout', 'disk_quota'] as const;
+export type ErrorCategory = typeof ERROR_CATEGORIES[number];
+
+/**
+ * Where an error happened.
+ */
+export interface ErrorContext {
+  taskId?: string;
+  /** Run phase, e.g. `workspace`, `agent`, `verification` */
+  phase?: string;
+  /** Command being run, if any */
+  command?: string;
+}
+
 /**
  * Base exception for Agent Bench errors.
  */
 export class BenchError extends Error {
-  constructor(message: string) {
+  category: ErrorCategory;
+  context: ErrorContext;
+
+  constructor(message: string, category: ErrorCategory = 'harness', context: ErrorContext = {}) {
     super(message);
     this.name = 'BenchError';
+    this.category = category;
+    this.context = context;
     Error.captureStackTrace(this, this.constructor);
   }
 }
 
+/**
+ * Category of any thrown value: a BenchError's own, otherwise the fallback.
+ */
+export function categorizeError(error: unknown, fallback: ErrorCategory = 'harness'): ErrorCategory {
+  return error instanceof BenchError ? error.category : fallback;
+}
+
+/**
+ * Context of any thrown value (empty unless it is a BenchError).
+ */
+export function getErrorContext(error: unknown): ErrorContext {
+  return error instanceof BenchError ? error.context : {};
+}
+
 /**
  * Raised when a task is not found.
  */
 export class TaskNotFoundError extends BenchError {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
     this.name = 'TaskNotFoundError';
   }
 }
@@ -27,8 +66,8 @@ export class TaskNotFoundError extends BenchError {
  * Raised when a task has an invalid format.
  */
 export class InvalidTaskFormatError extends BenchError {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
     this.name = 'InvalidTaskFormatError';
   }
 }
@@ -37,8 +76,8 @@ export class InvalidTaskFormatError extends BenchError {
  * Raised when a task fails to load.
  */
 export class TaskLoadError extends BenchError {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
     this.name = 'TaskLoadError';
   }
 }
@@ -47,18 +86,18 @@ export class TaskLoadError extends BenchError {
  * Raised when an agent execution fails.
  */
 export class AgentError extends BenchError {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'agent', context);
     this.name = 'AgentError';
   }
 }
 
 /**
- * Raised when verification fails.
+ * Raised when verification cannot run (a harness failure, unlike failing tests).
  */
 export class VerificationError extends BenchError {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'harness', context);
     this.name = 'VerificationError';
   }
 }
@@ -69,19 +108,188 @@ export class VerificationError extends BenchError {
 export class TimeoutError extends BenchError {
   timeoutSecs: number;
 
-  constructor(timeoutSecs: number) {
-    super(`Timeout after ${timeoutSecs} seconds`);
+  constructor(timeoutSecs: number, context: ErrorContext = {}) {
+    super(`Timeout after ${timeoutSecs} seconds${context.phase ? ` during ${context.phase}` : ''}`, 'timeout', context);
     this.name = 'TimeoutError';
     this.timeoutSecs = timeoutSecs;
   }
 }
 
+/**
+ * Raised when a workspace grows past its disk quota.
+ */
+export class DiskQuotaExceededError extends BenchError {
+  usedBytes: number;
+  limitBytes: number;
+
+  constructor(usedBytes: number, limitBytes: number, context: ErrorContext = {}) {
+    super(`Workspace uses ${formatBytes(usedBytes)}, over its ${formatBytes(limitBytes)} disk quota`, 'disk_quota', context);
+    this.name = 'DiskQuotaExceededError';
+    this.usedBytes = usedBytes;
+    this.limitBytes = limitBytes;
+  }
+}
+
 /**
  * Raised when a git operation fails.
  */
 export class GitError extends BenchError {
-  constructor(message: string) {
-    super(message);
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
     this.name = 'GitError';
   }
 }
+
+/**
+ * Raised when a workspace cannot be populated (e.g. a tarball fails to download or extract).
+ */
+export class WorkspaceError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'WorkspaceError';
+  }
+}
+
+/**
+ * Raised when a task's schema version is unsupported or cannot be migrated.
+ */
+export class TaskSchemaError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'TaskSchemaError';
+  }
+}
+
+/**
+ * Raised when a run's checkpointed state cannot be read, resumed, or updated.
+ */
+export class RunStateError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'harness', context);
+    this.name = 'RunStateError';
+  }
+}
+
+/**
+ * Raised when something needs a feature that this build leaves out.
+ */
+export class FeatureError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'FeatureError';
+  }
+}
+
+/**
+ * Raised when a task pack cannot be created or fails verification.
+ */
+export class PackError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'harness', context);
+    this.name = 'PackError';
+  }
+}
+
+/**
+ * Raised when tasks do not match the lockfile.
+ */
+export class LockError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'LockError';
+  }
+}
+
+/**
+ * Raised when a task's toolchain cannot be provisioned.
+ */
+export class ToolchainError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'ToolchainError';
+  }
+}
+
+/**
+ * Raised when a configuration file is invalid.
+ */
+export class ConfigError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'ConfigError';
+  }
+}
+
+/**
+ * Raised when a filter expression cannot be parsed.
+ */
+export class FilterSyntaxError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'FilterSyntaxError';
+  }
+}
+
+/**
+ * Raised when a run needs dangerous permissions that were not acknowledged.
+ */
+export class PermissionAcknowledgmentError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'PermissionAcknowledgmentError';
+  }
+}
+
+/**
+ * Raised when a WebAssembly plugin cannot be loaded or violates the plugin ABI.
+ */
+export class PluginError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'harness', context);
+    this.name = 'PluginError';
+  }
+}
+
+/**
+ * Raised when a subprocess cannot be parsed or started.
+ */
+export class ExecError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'harness', context);
+    this.name = 'ExecError';
+  }
+}
+
+/**
+ * Raised when the configured sandbox cannot be used on this machine.
+ */
+export class SandboxError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'setup', context);
+    this.name = 'SandboxError';
+  }
+}
+
+/**
+ * Raised when results cannot be stored or loaded.
+ */
+export class StorageError extends BenchError {
+  constructor(message: string, context: ErrorContext = {}) {
+    super(message, 'harness', context);
+    this.name = 'StorageError';
+  }
+}
+
+/**
+ * Raised when a distributed run's worker cannot be reached or rejects a task.
+ */
+export class WorkerError extends BenchError {
+  /** HTTP status the worker answered with (null: no answer) */
+  status: number | null;
+
+  constructor(message: string, status: number | null = null, context: ErrorContext = {}) {
+    super(message, 'harness', context);
+    this.name = 'WorkerError';
+    this.status = status;
+  }
+}
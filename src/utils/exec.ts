@@ -0,0 +1,432 @@
+/**
+ * Shared process execution: command lexing or `sh -c`, cwd/env, timeouts,
+ * output streaming (large output spooled to disk), and killing the whole process tree.
+ *
+ * Every subprocess the harness starts (verification commands, servers under
+ * test, toolchain and version probes, tar, notifiers) goes through here so
+ * they agree on quoting, timeouts, and cleanup.
+ *
+ * On Windows, programs are looked up with PATHEXT, `.cmd`/`.bat` shims (npm-installed
+ * agent CLIs) run through `cmd.exe`, shell commands use `cmd.exe /c`, and process
+ * trees are stopped with `taskkill /T`.
+ */
+
+import { spawn, type ChildProcess } from 'child_process';
+import { accessSync, constants, statSync } from 'fs';
+import { delimiter, extname, isAbsolute, join } from 'path';
+import { ExecError } from './errors.js';
+import { OutputSpool } from './spool.js';
+
+/**
+ * A command line (lexed, or run by the shell with `shell: true`) or an explicit program and arguments.
+ */
+export type CommandSpec = string | { program: string; args: string[] };
+
+export interface ExecOptions {
+  cwd?: string;
+  /** Extra environment variables layered over the current environment */
+  env?: Record<string, string | undefined>;
+  /** Start from an empty environment (only `env`) instead of the current one (default true) */
+  inheritEnv?: boolean;
+  /** Kill the process tree after this many seconds */
+  timeoutSecs?: number | null;
+  /** Run a string command with `sh -c` (`cmd.exe /c` on Windows) instead of lexing it */
+  shell?: boolean;
+  /** Called with each chunk of output as it arrives */
+  onOutput?: (chunk: string, stream: 'stdout' | 'stderr') => void;
+  /** Do not collect output (for noisy or fire-and-forget processes) */
+  discardOutput?: boolean;
+  /** Written to the process's stdin, which is then closed (default: no stdin) */
+  input?: string;
+  /**
+   * Output kept in memory per stream; beyond it the full output is spooled to a temp file
+   * and only its start and end are kept (default 1 MiB; Infinity keeps everything)
+   */
+  maxOutputBytes?: number;
+}
+
+export interface ExecResult {
+  exitCode: number | null;
+  signal: NodeJS.Signals | null;
+  /** Output, or its start and end if it outgrew `maxOutputBytes` */
+  stdout: string;
+  stderr: string;
+  /** Temp files with the full output of streams that outgrew `maxOutputBytes` (remove them with `disposeSpoolFiles`) */
+  stdoutFile: string | null;
+  stderrFile: string | null;
+  durationSecs: number;
+  timedOut: boolean;
+}
+
+/**
+ * Grace period between SIGTERM and SIGKILL when stopping a process tree.
+ */
+export const KILL_GRACE_MS = 2000;
+
+/**
+ * Split a command line into program and arguments like a POSIX shell would, without
+ * expansion: single quotes are literal, double quotes allow `\"` `\\` `\$` escapes,
+ * and a backslash outside quotes escapes the next character.
+ * @throws ExecError If the command is empty or has an unterminated quote
+ */
+export function splitCommand(command: string): [string, string[]] {
+  const words: string[] = [];
+  let word = '';
+  let inWord = false;
+
+  for (let i = 0; i < command.length; i++) {
+    const char = command[i];
+    if (char === "'") {
+      const end = command.indexOf("'", i + 1);
+      if (end === -1) {
+        throw new ExecError(`Unterminated ' in command: ${command}`, { command });
+      }
+      word += command.slice(i + 1, end);
+      inWord = true;
+      i = end;
+    } else if (char === '"') {
+      let j = i + 1;
+      for (; j < command.length && command[j] !== '"'; j++) {
+        if (command[j] === '\\' && '"\\$`'.includes(command[j + 1] ?? '')) {
+          j++;
+        }
+        word += command[j];
+      }
+      if (j >= command.length) {
+        throw new ExecError(`Unterminated " in command: ${command}`, { command });
+      }
+      inWord = true;
+      i = j;
+    } else if (char === '\\' && i + 1 < command.length) {
+      word += command[++i];
+      inWord = true;
+    } else if (/\s/.test(char)) {
+      if (inWord) {
+        words.push(word);
+        word = '';
+        inWord = false;
+      }
+    } else {
+      word += char;
+      inWord = true;
+    }
+  }
+  if (inWord) {
+    words.push(word);
+  }
+
+  if (words.length === 0) {
+    throw new ExecError('Empty command', { command });
+  }
+  return [words[0], words.slice(1)];
+}
+
+const isWindows = process.platform === 'win32';
+
+/**
+ * Find an executable: a path is checked as given, a bare name is looked up on PATH
+ * (trying each PATHEXT extension on Windows).
+ * @param searchPath PATH to look in (default: this process's)
+ * @returns The executable's path, or null if there is none
+ */
+export function findExecutable(
+  program: string,
+  searchPath: string | undefined = process.env.PATH || process.env.Path
+): string | null {
+  const isFile = (path: string): boolean => {
+    try {
+      accessSync(path, isWindows ? constants.F_OK : constants.X_OK);
+      return statSync(path).isFile();
+    } catch {
+      return false;
+    }
+  };
+  const extensions = isWindows && !extname(program)
+    ? (process.env.PATHEXT || '.COM;.EXE;.BAT;.CMD').split(';').filter(Boolean)
+    : [''];
+
+  const directories = isAbsolute(program) || /[\\/]/.test(program)
+    ? ['']
+    : (searchPath || '').split(delimiter).filter(Boolean);
+  for (const directory of directories) {
+    for (const extension of extensions) {
+      const candidate = directory ? join(directory, program + extension) : program + extension;
+      if (isFile(candidate)) {
+        return candidate;
+      }
+    }
+  }
+  return null;
+}
+
+/**
+ * Quote an argument for a `cmd.exe /s /c "..."` command line: quoted as Windows programs
+ * parse it, with cmd metacharacters caret-escaped so they reach the program literally.
+ * Arguments of batch files are escaped twice, since shims expand them again (`%*`).
+ */
+export function quoteCmdArgument(arg: string, batchArgument: boolean = false): string {
+  const quoted = `"${arg.replace(/(\\*)"/g, '$1$1\\"').replace(/(\\*)$/, '$1$1')}"`;
+  const escape = (text: string) => text.replace(/[()\][%!^"`<>&|;, *?]/g, '^$&');
+  return batchArgument ? escape(escape(quoted)) : escape(quoted);
+}
+
+/**
+ * Resolve a command spec to the program and arguments to spawn.
+ * @returns Program, arguments, and whether the arguments are a preformatted cmd.exe line
+ */
+function resolveCommand(command: CommandSpec, shell: boolean, env: NodeJS.ProcessEnv): [string, string[], boolean] {
+  if (typeof command === 'string' && shell) {
+    return isWindows ? ['cmd.exe', ['/d', '/s', '/c', command], false] : ['sh', ['-c', command], false];
+  }
+
+  const [program, args] = typeof command === 'string' ? splitCommand(command) : [command.program, command.args];
+  if (!isWindows) {
+    return [program, args, false];
+  }
+
+  // Batch files cannot be spawned directly; run them through cmd.exe with explicit quoting
+  const resolved = findExecutable(program, env.PATH || env.Path) ?? program;
+  if (/\.(cmd|bat)$/i.test(resolved)) {
+    const line = [quoteCmdArgument(resolved), ...args.map(arg => quoteCmdArgument(arg, true))].join(' ');
+    return ['cmd.exe', ['/d', '/s', '/c', `"${line}"`], true];
+  }
+  return [resolved, args, false];
+}
+
+/**
+ * Human-readable form of a command spec (for errors and logs).
+ */
+export function formatCommand(command: CommandSpec): string {
+  return typeof command === 'string' ? command : [command.program, ...command.args].join(' ');
+}
+
+/**
+ * A finished (or failed to start) subprocess, as reported to command listeners.
+ */
+export interface CommandRecord {
+  /** Program and arguments as spawned */
+  program: string;
+  args: string[];
+  cwd: string;
+  /** Variables added or changed relative to the harness environment */
+  envSet: Record<string, string>;
+  /** Harness variables the process did not get */
+  envRemoved: string[];
+  exitCode: number | null;
+  signal: string | null;
+  durationSecs: number;
+  timedOut: boolean;
+  /** Why the process failed when it has no exit code (e.g. it could not start) */
+  error: string | null;
+}
+
+export type CommandListener = (record: CommandRecord) => void;
+
+const commandListeners = new Set<CommandListener>();
+
+/**
+ * Register a listener called after every subprocess the harness runs (e.g. the run audit log).
+ * @returns A function removing the listener
+ */
+export function addCommandListener(listener: CommandListener): () => void {
+  commandListeners.add(listener);
+  return () => {
+    commandListeners.delete(listener);
+  };
+}
+
+/**
+ * Report a command to the listeners; `startProcess` does this itself, and code spawning
+ * processes some other way (a library such as simple-git) calls it.
+ */
+export function reportCommand(record: CommandRecord): void {
+  for (const listener of commandListeners) {
+    try {
+      listener(record);
+    } catch {
+      // A broken listener must not fail the command
+    }
+  }
+}
+
+/**
+ * Compare a child's environment with the harness's.
+ */
+function diffEnvironment(env: NodeJS.ProcessEnv): { envSet: Record<string, string>; envRemoved: string[] } {
+  const envSet: Record<string, string> = {};
+  for (const [name, value] of Object.entries(env)) {
+    if (value !== undefined && process.env[name] !== value) {
+      envSet[name] = value;
+    }
+  }
+  const envRemoved = Object.keys(process.env).filter(name => env[name] === undefined);
+  return { envSet, envRemoved };
+}
+
+/**
+ * A started process.
+ */
+export interface RunningProcess {
+  pid: number | undefined;
+  /** Output collected so far */
+  readonly stdout: string;
+  readonly stderr: string;
+  /** Exit code once the process has exited, else null */
+  readonly exitCode: number | null;
+  /** Resolves when the process exits; rejects with ExecError if it could not start */
+  readonly done: Promise<ExecResult>;
+  /** Stop the process and everything it started (SIGTERM, then SIGKILL after a grace period) */
+  kill(): Promise<void>;
+}
+
+/**
+ * Start a process without waiting for it (e.g. a server under test).
+ * Processes run in their own process group (a `taskkill /T` tree on Windows) so `kill()`
+ * and timeouts reach their children.
+ */
+export function startProcess(command: CommandSpec, options: ExecOptions = {}): RunningProcess {
+  const env = options.inheritEnv === false
+    ? { ...options.env }
+    : options.env ? { ...process.env, ...options.env } : process.env;
+  const [program, args, verbatim] = resolveCommand(command, options.shell ?? false, env);
+  const startTime = Date.now();
+  const groupKill = !isWindows;
+  // Processes that fail to start emit 'error' and then 'close'; report them once
+  let reported = false;
+  const report = (result: Pick<CommandRecord, 'exitCode' | 'signal' | 'error'>) => {
+    if (!reported && commandListeners.size > 0) {
+      reported = true;
+      reportCommand({
+        program,
+        args,
+        cwd: options.cwd ?? process.cwd(),
+        ...diffEnvironment(env),
+        durationSecs: (Date.now() - startTime) / 1000,
+        timedOut,
+        ...result,
+      });
+    }
+  };
+
+  const proc = spawn(program, args, {
+    cwd: options.cwd,
+    env,
+    detached: groupKill,
+    windowsVerbatimArguments: verbatim,
+    windowsHide: true,
+    stdio: [options.input !== undefined ? 'pipe' : 'ignore', options.discardOutput ? 'ignore' : 'pipe', options.discardOutput ? 'ignore' : 'pipe'],
+  });
+  if (options.input !== undefined) {
+    // A process that exits without reading its input is reported by its exit status, not EPIPE
+    proc.stdin?.on('error', () => {});
+    proc.stdin?.end(options.input);
+  }
+
+  const stdout = new OutputSpool(options.maxOutputBytes);
+  const stderr = new OutputSpool(options.maxOutputBytes);
+  let timedOut = false;
+  proc.stdout?.setEncoding('utf-8').on('data', (chunk: string) => {
+    stdout.write(chunk);
+    options.onOutput?.(chunk, 'stdout');
+  });
+  proc.stderr?.setEncoding('utf-8').on('data', (chunk: string) => {
+    stderr.write(chunk);
+    options.onOutput?.(chunk, 'stderr');
+  });
+
+  const kill = () => killTree(proc, groupKill);
+  const timer = options.timeoutSecs
+    ? setTimeout(() => {
+        timedOut = true;
+        void kill();
+      }, options.timeoutSecs * 1000)
+    : null;
+
+  const done = new Promise<ExecResult>((resolve, reject) => {
+    proc.on('error', (error) => {
+      if (timer) clearTimeout(timer);
+      stdout.close();
+      stderr.close();
+      report({ exitCode: null, signal: null, error: error.message });
+      reject(new ExecError(`Failed to run ${formatCommand(command)}: ${error.message}`, { command: formatCommand(command) }));
+    });
+    proc.on('close', (code, signal) => {
+      if (timer) clearTimeout(timer);
+      stdout.close();
+      stderr.close();
+      report({ exitCode: code, signal, error: null });
+      resolve({
+        exitCode: code,
+        signal,
+        stdout: stdout.text,
+        stderr: stderr.text,
+        stdoutFile: stdout.path,
+        stderrFile: stderr.path,
+        durationSecs: (Date.now() - startTime) / 1000,
+        timedOut,
+      });
+    });
+  });
+  // Callers that only kill() must not see the rejection reported as unhandled
+  done.catch(() => {});
+
+  return {
+    pid: proc.pid,
+    get stdout() {
+      return stdout.text;
+    },
+    get stderr() {
+      return stderr.text;
+    },
+    get exitCode() {
+      return proc.exitCode;
+    },
+    done,
+    kill,
+  };
+}
+
+/**
+ * Run a command to completion, collecting its output.
+ * A non-zero exit or a timeout is reported in the result, not thrown.
+ * @throws ExecError If the command cannot be parsed or started
+ */
+export async function exec(command: CommandSpec, options: ExecOptions = {}): Promise<ExecResult> {
+  return await startProcess(command, options).done;
+}
+
+/**
+ * Terminate a process and its descendants, escalating to SIGKILL if they linger.
+ * Windows has no process groups or SIGTERM, so the tree is force-stopped with taskkill.
+ */
+async function killTree(proc: ChildProcess, groupKill: boolean): Promise<void> {
+  if (proc.pid === undefined || proc.exitCode !== null || proc.signalCode !== null) {
+    return;
+  }
+  if (isWindows) {
+    const exited = new Promise<void>(resolve => proc.once('close', () => resolve()));
+    const taskkill = spawn('taskkill', ['/pid', String(proc.pid), '/T', '/F'], { stdio: 'ignore', windowsHide: true });
+    taskkill.on('error', () => proc.kill());
+    await exited;
+    return;
+  }
+  const signal = (name: NodeJS.Signals) => {
+    try {
+      if (groupKill) {
+        process.kill(-proc.pid!, name);
+      } else {
+        proc.kill(name);
+      }
+    } catch {
+      // Already gone
+    }
+  };
+
+  const exited = new Promise<void>(resolve => proc.once('close', () => resolve()));
+  signal('SIGTERM');
+  const timeout = new Promise<'timeout'>(resolve => setTimeout(() => resolve('timeout'), KILL_GRACE_MS));
+  if ((await Promise.race([exited, timeout])) === 'timeout') {
+    signal('SIGKILL');
+    await exited;
+  }
+}
@@ -0,0 +1,220 @@
+/**
+ * Crash- and concurrency-safe file writes: atomic replacement, no-clobber creation,
+ * and advisory lock files shared by processes on one machine (parallel suite shards
+ * writing to the same results directory), plus containment checks for paths an agent or
+ * recording names inside a workspace.
+ */
+
+import { link, lstat, open, readFile, realpath, rename, rm, stat, writeFile } from 'fs/promises';
+import { dirname, isAbsolute, relative, resolve, sep } from 'path';
+import { hostname } from 'os';
+import { randomBytes } from 'crypto';
+import { StorageError } from './errors.js';
+
+/**
+ * How long to wait for a lock before giving up.
+ */
+const LOCK_TIMEOUT_MS = 30_000;
+
+/**
+ * Locks older than this are assumed abandoned (their holder crashed on another host or was reused).
+ */
+const STALE_LOCK_MS = 120_000;
+
+/**
+ * Temporary sibling of a file, unique per writer.
+ */
+function tempPath(path: string): string {
+  return `${path}.${process.pid}.${randomBytes(4).toString('hex')}.tmp`;
+}
+
+/**
+ * Whether `path` is `root` or below it, by path segments (`..config` is inside, `../x` is not).
+ */
+export function isWithin(root: string, path: string): boolean {
+  const rel = relative(root, path);
+  return rel !== '..' && !rel.startsWith(`..${sep}`) && !isAbsolute(rel);
+}
+
+/**
+ * Resolve `path` against `root` and check that it stays inside it after following symlinks:
+ * the real path of the target, or of its nearest existing ancestor if it does not exist
+ * yet, must be inside the real path of `root`. Dangling symlinks are rejected, since
+ * writing through one creates its target.
+ * @returns The resolved path, or null if it leads outside `root`
+ */
+export async function resolveWithin(root: string, path: string): Promise<string | null> {
+  const target = resolve(root, path);
+  if (!isWithin(resolve(root), target)) {
+    return null;
+  }
+  let existing = target;
+  while (!(await lstat(existing).then(() => true, () => false))) {
+    existing = dirname(existing);
+  }
+  try {
+    return isWithin(await realpath(root), await realpath(existing)) ? target : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Write a file atomically: readers see the old content or the new content, never a partial write.
+ */
+export async function writeFileAtomic(path: string, content: string | Buffer): Promise<void> {
+  const temp = tempPath(path);
+  try {
+    await writeFile(temp, content);
+    await rename(temp, path);
+  } catch (error) {
+    await rm(temp, { force: true });
+    throw error;
+  }
+}
+
+/**
+ * Create a file with its full content, failing if it already exists (even when another
+ * process creates it at the same moment).
+ * @returns False if the file already exists
+ */
+export async function createFileExclusive(path: string, content: string | Buffer): Promise<boolean> {
+  const temp = tempPath(path);
+  try {
+    await writeFile(temp, content);
+    // link() never replaces an existing file, unlike rename()
+    await link(temp, path);
+    return true;
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'EEXIST') {
+      return false;
+    }
+    throw error;
+  } finally {
+    await rm(temp, { force: true });
+  }
+}
+
+/**
+ * Create a new file at `<base><ext>`, or `<base>_2<ext>`, `<base>_3<ext>`, ... if taken.
+ * @returns Path of the created file
+ */
+export async function createUniqueFile(base: string, ext: string, content: string | Buffer): Promise<string> {
+  for (let attempt = 1; ; attempt++) {
+    const path = attempt === 1 ? `${base}${ext}` : `${base}_${attempt}${ext}`;
+    if (await createFileExclusive(path, content)) {
+      return path;
+    }
+  }
+}
+
+/**
+ * Holder of a lock file.
+ */
+interface LockOwner {
+  pid: number;
+  host: string;
+  acquired_at: string;
+}
+
+/**
+ * Run `fn` while holding the lock file `<path>.lock`, waiting for other holders in this
+ * or other processes. Locks left behind by dead processes are taken over.
+ * @throws StorageError If the lock is not acquired within the timeout
+ */
+export async function withFileLock<T>(path: string, fn: () => Promise<T>, timeoutMs: number = LOCK_TIMEOUT_MS): Promise<T> {
+  const lockPath = `${path}.lock`;
+  const deadline = Date.now() + timeoutMs;
+
+  for (let delay = 10; ; delay = Math.min(delay * 2, 250)) {
+    try {
+      const handle = await open(lockPath, 'wx');
+      const owner: LockOwner = { pid: process.pid, host: hostname(), acquired_at: new Date().toISOString() };
+      await handle.writeFile(JSON.stringify(owner));
+      await handle.close();
+      break;
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== 'EEXIST') {
+        throw new StorageError(`Failed to create lock ${lockPath}: ${error}`);
+      }
+    }
+    if (await removeStaleLock(lockPath)) {
+      continue;
+    }
+    if (Date.now() >= deadline) {
+      throw new StorageError(`Timed out after ${timeoutMs / 1000}s waiting for lock ${lockPath} (remove it if no agent-bench process holds it)`);
+    }
+    await Bun.sleep(delay);
+  }
+
+  try {
+    return await fn();
+  } finally {
+    await rm(lockPath, { force: true });
+  }
+}
+
+/**
+ * A lock file's content and modification time, identifying one holder's lock.
+ */
+async function readLockSnapshot(path: string): Promise<{ content: string; mtimeMs: number } | null> {
+  try {
+    const { mtimeMs } = await stat(path);
+    return { content: await readFile(path, 'utf-8'), mtimeMs };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Remove a lock whose holder is gone. Another waiter may take over the same stale lock and
+ * a new holder create a fresh one meanwhile, so the lock is first renamed to a name unique
+ * to this waiter and only removed if it is still the stale one; a fresh lock is put back.
+ * @returns True if the lock was removed (or is gone), so acquiring it can be retried
+ */
+async function removeStaleLock(lockPath: string): Promise<boolean> {
+  const snapshot = await readLockSnapshot(lockPath);
+  if (!snapshot) {
+    return false;
+  }
+  let stale = false;
+  try {
+    const owner = JSON.parse(snapshot.content) as LockOwner;
+    stale = owner.host === hostname() && !isProcessAlive(owner.pid);
+  } catch {
+    // Still being written by its holder
+  }
+  if (!stale && Date.now() - snapshot.mtimeMs <= STALE_LOCK_MS) {
+    return false;
+  }
+
+  const claimed = tempPath(lockPath);
+  try {
+    await rename(lockPath, claimed);
+  } catch {
+    // Taken over (or released) by someone else
+    return true;
+  }
+  const renamed = await readLockSnapshot(claimed);
+  if (renamed && (renamed.content !== snapshot.content || renamed.mtimeMs !== snapshot.mtimeMs)) {
+    // A new holder's lock replaced the stale one before the rename: restore it unless yet another exists
+    await link(claimed, lockPath).catch(() => {});
+    await rm(claimed, { force: true });
+    return false;
+  }
+  await rm(claimed, { force: true });
+  return true;
+}
+
+/**
+ * Whether a process on this host is running.
+ */
+export function isProcessAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM: alive, owned by another user
+    return (error as NodeJS.ErrnoException).code === 'EPERM';
+  }
+}
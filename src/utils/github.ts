@@ -0,0 +1,175 @@
+/**
+ * GitHub Actions reporter (`run --output github`).
+ *
+ * Failed tasks and regressions become `::error` workflow commands pointing at the task's
+ * YAML file, so they show up as annotations on the workflow run and in pull request
+ * diffs. When the suite (or single task) finishes, a markdown summary is appended to the
+ * job summary file (`$GITHUB_STEP_SUMMARY`).
+ */
+
+import { appendFileSync } from 'fs';
+import { isAbsolute, relative } from 'path';
+import type { RunEvent, RunEventListener } from '../core/events.js';
+import { getOutcome, type BenchmarkResult, type SuiteResults } from '../evaluator/results.js';
+import { formatCost } from '../evaluator/pricing.js';
+import { logger } from './logger.js';
+
+export interface GithubReporterOptions {
+  /** Job summary file to append to (default: `$GITHUB_STEP_SUMMARY`; null: no summary) */
+  summaryPath?: string | null;
+  /** Directory annotation paths are relative to (default: `$GITHUB_WORKSPACE`, else the working directory) */
+  workspace?: string;
+  /** Where workflow commands are written (default: stdout) */
+  write?: (line: string) => void;
+}
+
+/**
+ * Escape workflow command data (the message).
+ */
+function escapeData(value: string): string {
+  return value.replace(/%/g, '%25').replace(/\r/g, '%0D').replace(/\n/g, '%0A');
+}
+
+/**
+ * Escape a workflow command property value.
+ */
+function escapeProperty(value: string): string {
+  return escapeData(value).replace(/:/g, '%3A').replace(/,/g, '%2C');
+}
+
+/**
+ * Format a workflow command, e.g. `::error file=tasks/BUG-001.yaml,title=BUG-001 failed::Verification tests failed`.
+ */
+export function formatWorkflowCommand(
+  command: 'error' | 'warning' | 'notice',
+  message: string,
+  properties: Record<string, string | undefined> = {}
+): string {
+  const props = Object.entries(properties)
+    .filter((entry): entry is [string, string] => entry[1] !== undefined)
+    .map(([key, value]) => `${key}=${escapeProperty(value)}`)
+    .join(',');
+  return `::${command}${props ? ` ${props}` : ''}::${escapeData(message)}`;
+}
+
+/**
+ * Escape text for a markdown table cell.
+ */
+function cell(value: string): string {
+  return value.replace(/\|/g, '\\|').replace(/\r?\n/g, ' ');
+}
+
+/**
+ * First line of a result's error, for annotations and summaries.
+ */
+function describeFailure(result: BenchmarkResult): string {
+  const error = result.error?.split('\n')[0] ?? 'Task failed';
+  return result.failure_kind ? `${error} (${result.failure_kind})` : error;
+}
+
+/**
+ * Markdown job summary of finished results.
+ * @param suite The suite, or null for a single task run
+ */
+export function formatJobSummary(results: BenchmarkResult[], suite: SuiteResults | null): string {
+  const lines: string[] = [];
+  const agent = suite?.agent ?? results[0]?.agent ?? 'unknown';
+  lines.push(`## agent-bench: ${agent}`, '');
+
+  const passed = results.filter(result => getOutcome(result) === 'passed').length;
+  const failed = results.filter(result => getOutcome(result) === 'failed');
+  const skipped = results.length - passed - failed.length;
+  const executed = passed + failed.length;
+  lines.push('| Passed | Failed | Skipped | Pass Rate | Duration | Cost |');
+  lines.push('| --- | --- | --- | --- | --- | --- |');
+  const cost = suite ? suite.cost_usd : results[0]?.cost_usd ?? null;
+  lines.push(
+    `| ${passed} | ${failed.length} | ${skipped} | ${executed > 0 ? ((passed / executed) * 100).toFixed(1) : '0.0'}% | ` +
+      `${results.reduce((sum, result) => sum + result.duration_secs, 0).toFixed(1)}s | ${cost !== null && cost !== undefined ? formatCost(cost) : '-'} |`
+  );
+
+  const regressions = suite?.regressions ?? [];
+  if (regressions.length > 0) {
+    lines.push('', `### :warning: Regressions (${regressions.length})`, '');
+    lines.push(...regressions.map(id => `- \`${id}\` failed but passed in the previous run`));
+  }
+
+  if (failed.length > 0) {
+    lines.push('', `### Failures (${failed.length})`, '');
+    lines.push('| Task | Kind | Error |', '| --- | --- | --- |');
+    for (const result of failed) {
+      lines.push(`| \`${result.task_id}\` | ${result.failure_kind ?? ''} | ${cell(result.error?.split('\n')[0] ?? '')} |`);
+    }
+  }
+  return `${lines.join('\n')}\n`;
+}
+
+/**
+ * Create a run event listener that reports to GitHub Actions. Use it next to `consoleReporter`.
+ */
+export function createGithubReporter(options: GithubReporterOptions = {}): RunEventListener {
+  const summaryPath = options.summaryPath === undefined ? process.env.GITHUB_STEP_SUMMARY || null : options.summaryPath;
+  const workspace = options.workspace ?? process.env.GITHUB_WORKSPACE ?? process.cwd();
+  const write = options.write ?? ((line: string) => console.log(line));
+  const files = new Map<string, string>();
+  let inSuite = false;
+
+  const fileOf = (taskId: string): string | undefined => {
+    const file = files.get(taskId);
+    if (!file) {
+      return undefined;
+    }
+    const path = relative(workspace, file);
+    // Files outside the repository cannot be annotated
+    return path.startsWith('..') || isAbsolute(path) ? undefined : path.split('\\').join('/');
+  };
+
+  const appendSummary = (markdown: string) => {
+    if (!summaryPath) {
+      return;
+    }
+    try {
+      appendFileSync(summaryPath, markdown);
+    } catch (error) {
+      logger.warn(`Could not write the job summary: ${error}`);
+    }
+  };
+
+  return (event: RunEvent) => {
+    switch (event.type) {
+      case 'suite_started':
+        inSuite = true;
+        break;
+      case 'task_started':
+        if (event.file_path) {
+          files.set(event.task_id, event.file_path);
+        }
+        break;
+      case 'task_finished': {
+        const { result } = event;
+        if (getOutcome(result) === 'failed') {
+          write(formatWorkflowCommand('error', describeFailure(result), {
+            file: fileOf(result.task_id),
+            title: `${result.task_id} failed`,
+          }));
+        }
+        if (!inSuite) {
+          appendSummary(formatJobSummary([result], null));
+        }
+        break;
+      }
+      case 'suite_finished': {
+        const { suite } = event;
+        for (const id of suite.regressions) {
+          write(formatWorkflowCommand('error', 'Failed but passed in the previous run', {
+            file: fileOf(id),
+            title: `Regression: ${id}`,
+          }));
+        }
+        appendSummary(formatJobSummary(suite.results, suite));
+        inSuite = false;
+        break;
+      }
+    }
+  };
+}
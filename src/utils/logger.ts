@@ -3,6 +3,13 @@
  */
 
 import chalk from 'chalk';
+import { AsyncLocalStorage } from 'async_hooks';
+import { format } from 'util';
+import { getOutcome } from '../evaluator/results.js';
+import type { SuiteResults } from '../evaluator/results.js';
+import { formatCost } from '../evaluator/pricing.js';
+import { outcomeMarker } from './colors.js';
+import { groupByTagNamespace } from '../core/tags.js';
 
 /**
  * Log levels.
@@ -15,11 +22,55 @@ export enum LogLevel {
   ERROR = 'error',
 }
 
+/**
+ * Console log formats: colored text, or one JSON record per line.
+ */
+export const LOG_FORMATS = ['text', 'json'] as const;
+export type LogFormat = typeof LOG_FORMATS[number];
+
+/**
+ * Span context attached to every record logged inside `withContext`.
+ */
+export interface LogContext {
+  run_id?: string;
+  task_id?: string;
+  agent?: string;
+}
+
+/**
+ * A structured log line.
+ */
+export interface LogRecord extends LogContext {
+  timestamp: string;
+  level: LogLevel;
+  message: string;
+}
+
+/**
+ * Receives every log line (uncolored) and its record, including debug lines hidden from the console.
+ */
+export type LogSink = (line: string, record: LogRecord) => void;
+
+/**
+ * Parse a log format name.
+ * @throws Error If the format is unknown
+ */
+export function parseLogFormat(value: string): LogFormat {
+  if (!(LOG_FORMATS as readonly string[]).includes(value)) {
+    throw new Error(`Unknown log format: ${value} (expected one of: ${LOG_FORMATS.join(', ')})`);
+  }
+  return value as LogFormat;
+}
+
 /**
  * Logger class for colored console output.
+ * All diagnostics are written to stderr; stdout is reserved for command output.
  */
 export class Logger {
   private debugEnabled: boolean;
+  private logFormat: LogFormat = 'text';
+  private sinks = new Set<LogSink>();
+  private contexts = new AsyncLocalStorage<LogContext>();
 
   constructor(debugEnabled: boolean = false) {
     this.debugEnabled = debugEnabled;
@@ -33,80 +84,205 @@ export class Logger {
   }
 
   /**
-   * Log debug message (only if debug enabled).
+   * Set the console format (`json` writes one LogRecord per line).
+   */
+  setFormat(logFormat: LogFormat): void {
+    this.logFormat = logFormat;
+  }
+
+  getFormat(): LogFormat {
+    return this.logFormat;
+  }
+
+  /**
+   * Run `fn` with span context (run, task, agent) added to every record it logs,
+   * including from async work it starts. Nested contexts extend the outer one.
+   */
+  withContext<T>(context: LogContext, fn: () => T): T {
+    return this.contexts.run({ ...this.contexts.getStore(), ...context }, fn);
+  }
+
+  /**
+   * The span context of the current `withContext` call (empty outside one).
+   */
+  getContext(): LogContext {
+    return { ...this.contexts.getStore() };
+  }
+
+  /**
+   * Register a sink receiving all log lines regardless of the debug setting.
+   * @returns A function removing the sink
+   */
+  addSink(sink: LogSink): () => void {
+    this.sinks.add(sink);
+    return () => {
+      this.sinks.delete(sink);
+    };
+  }
+
+  /**
+   * Log debug message (printed only if debug enabled; always forwarded to sinks).
    */
   debug(message: string, ...args: any[]): void {
-    if (this.debugEnabled) {
-      console.log(chalk.gray(`[DEBUG] ${message}`), ...args);
-    }
+    this.write(chalk.gray(`[DEBUG] ${message}`), args, this.debugEnabled, LogLevel.DEBUG, message);
   }
 
   /**
    * Log info message.
    */
   info(message: string, ...args: any[]): void {
-    console.log(chalk.blue(`[INFO] ${message}`), ...args);
+    this.write(chalk.blue(`[INFO] ${message}`), args, true, LogLevel.INFO, message);
   }
 
   /**
    * Log success message.
    */
   success(message: string, ...args: any[]): void {
-    console.log(chalk.green(`✓ ${message}`), ...args);
+    this.write(chalk.green(`✓ ${message}`), args, true, LogLevel.SUCCESS, message);
   }
 
   /**
    * Log warning message.
    */
   warn(message: string, ...args: any[]): void {
-    console.warn(chalk.yellow(`[WARN] ${message}`), ...args);
+    this.write(chalk.yellow(`[WARN] ${message}`), args, true, LogLevel.WARNING, message);
   }
 
   /**
    * Log error message.
    */
   error(message: string, ...args: any[]): void {
-    console.error(chalk.red(`[ERROR] ${message}`), ...args);
+    this.write(chalk.red(`[ERROR] ${message}`), args, true, LogLevel.ERROR, message);
   }
 
   /**
    * Log task header.
    */
   taskHeader(taskId: string, title: string): void {
-    console.log(chalk.bold.cyan(`\n┌─ Task: ${taskId}`));
-    console.log(chalk.cyan(`└─ ${title}\n`));
+    this.write(chalk.bold.cyan(`\n┌─ Task: ${taskId}`));
+    this.write(chalk.cyan(`└─ ${title}\n`));
   }
 
   /**
    * Log task result.
    */
   taskResult(passed: boolean, score: number, iterations: number, duration: number, tokens?: number): void {
-    const status = passed ? chalk.green.bold('PASS') : chalk.red.bold('FAIL');
-    console.log(`\n${status}`);
-    console.log(`  Score: ${score}/100`);
-    console.log(`  Iterations: ${iterations}`);
-    console.log(`  Duration: ${duration.toFixed(2)}s`);
+    this.write(`\n${outcomeMarker(passed ? 'passed' : 'failed')}`);
+    this.write(`  Score: ${score}/100`);
+    this.write(`  Iterations: ${iterations}`);
+    this.write(`  Duration: ${duration.toFixed(2)}s`);
     if (tokens !== undefined && tokens !== null) {
-      console.log(`  Tokens: ${tokens}`);
+      this.write(`  Tokens: ${tokens}`);
+    }
+  }
+
+  /**
+   * Log skipped task.
+   */
+  taskSkipped(reason: string): void {
+    this.write(`\n${outcomeMarker('skipped')}`);
+    this.write(`  Reason: ${reason}`);
+  }
+
+  /**
+   * Log agent duration relative to the task's expected and human baseline durations.
+   */
+  durationComparison(duration: number, expected: number | null, humanBaseline: number | null): void {
+    if (expected) {
+      this.write(`  Expected: ${expected.toFixed(0)}s (${(duration / expected).toFixed(2)}x)`);
+    }
+    if (humanBaseline) {
+      this.write(`  Human Baseline: ${humanBaseline.toFixed(0)}s (${(duration / humanBaseline).toFixed(2)}x)`);
     }
   }
 
   /**
    * Log suite summary.
    */
-  suiteSummary(totalTasks: number, passed: number, failed: number, passRate: number, duration: number): void {
-    console.log(chalk.bold('\n═══════════════════════════════════════'));
-    console.log(chalk.bold('  Suite Summary'));
-    console.log(chalk.bold('═══════════════════════════════════════'));
-    console.log(`  Total Tasks: ${totalTasks}`);
-    console.log(`  ${chalk.green('Passed')}: ${passed}`);
-    console.log(`  ${chalk.red('Failed')}: ${failed}`);
-    console.log(`  Pass Rate: ${(passRate * 100).toFixed(1)}%`);
-    console.log(`  Total Duration: ${duration.toFixed(2)}s`);
-    console.log(chalk.bold('═══════════════════════════════════════\n'));
+  suiteSummary(suite: SuiteResults): void {
+    this.write(chalk.bold('\n═══════════════════════════════════════'));
+    this.write(chalk.bold('  Suite Summary'));
+    this.write(chalk.bold('═══════════════════════════════════════'));
+    this.write(`  Total Tasks: ${suite.total_tasks}`);
+    this.write(`  ${chalk.green('Passed')}: ${suite.passed}`);
+    this.write(`  ${chalk.red('Failed')}: ${suite.failed}`);
+    if (suite.skipped > 0) {
+      this.write(`  ${chalk.yellow('Skipped')}: ${suite.skipped}`);
+    }
+    this.write(`  Pass Rate: ${(suite.pass_rate * 100).toFixed(1)}%`);
+    this.write(`  Total Duration: ${suite.total_duration_secs.toFixed(2)}s`);
+    if (suite.prefetch_secs) {
+      this.write(`  Prefetch: ${suite.prefetch_secs.toFixed(2)}s`);
+    }
+    if (suite.cost_usd !== null && suite.cost_usd !== undefined) {
+      this.write(`  Cost: ${formatCost(suite.cost_usd)}`);
+    }
+    if (suite.expected_duration_ratio !== null) {
+      this.write(`  Time vs Expected: ${suite.expected_duration_ratio.toFixed(2)}x`);
+    }
+    if (suite.human_baseline_ratio !== null) {
+      this.write(`  Time vs Human Baseline: ${suite.human_baseline_ratio.toFixed(2)}x`);
+    }
+
+    const executed = suite.results.filter(r => getOutcome(r) !== 'skipped');
+    for (const [namespace, values] of groupByTagNamespace(executed, r => r.tags || [])) {
+      const breakdown = [...values.entries()]
+        .map(([value, results]) => `${value} ${results.filter(r => r.success).length}/${results.length}`)
+        .join(', ');
+      this.write(`  By ${namespace}: ${breakdown}`);
+    }
+    if (suite.regressions && suite.regressions.length > 0) {
+      this.write(chalk.red.bold(`  Regressions: ${suite.regressions.join(', ')}`));
+    }
+    this.write(chalk.bold('═══════════════════════════════════════\n'));
+  }
+
+  /**
+   * Write a line to stderr (as text or a JSON record) and forward it to sinks.
+   * @param raw Message without the level prefix, for the record (defaults to the line)
+   */
+  private write(
+    message: string,
+    args: any[] = [],
+    toConsole: boolean = true,
+    level: LogLevel = LogLevel.INFO,
+    raw: string = message
+  ): void {
+    if (!toConsole && this.sinks.size === 0) {
+      return;
+    }
+
+    const record: LogRecord = {
+      timestamp: new Date().toISOString(),
+      level,
+      message: stripAnsi(format(raw, ...args)).trim(),
+      ...this.contexts.getStore(),
+    };
+    if (toConsole) {
+      if (this.logFormat === 'json') {
+        if (record.message) {
+          console.error(JSON.stringify(record));
+        }
+      } else {
+        console.error(message, ...args);
+      }
+    }
+    if (this.sinks.size > 0) {
+      const plain = stripAnsi(format(message, ...args));
+      for (const sink of this.sinks) {
+        sink(plain, record);
+      }
+    }
   }
 }
 
+/**
+ * Remove ANSI color codes.
+ */
+function stripAnsi(text: string): string {
+  return text.replace(/\x1b\[[0-9;]*m/g, '');
+}
+
 /**
  * Global logger instance.
  */
@@ -0,0 +1,58 @@
+/**
+ * Desktop notifications for long-running suites.
+ *
+ * Uses the platform's notifier (`notify-send` on Linux, `osascript` on macOS).
+ * Notifications are best effort: a missing notifier never fails a run.
+ */
+
+import { exec } from './exec.js';
+import { logger } from './logger.js';
+
+/**
+ * Notification urgency; critical notifications stay on screen on Linux.
+ */
+export type NotificationUrgency = 'normal' | 'critical';
+
+/**
+ * Build the notifier command for the current platform.
+ * @returns Program and arguments, or null if the platform has no supported notifier
+ */
+function notifierCommand(title: string, message: string, urgency: NotificationUrgency): [string, string[]] | null {
+  switch (process.platform) {
+    case 'linux':
+    case 'freebsd':
+    case 'openbsd':
+      return ['notify-send', ['--app-name=agent-bench', `--urgency=${urgency}`, title, message]];
+    case 'darwin': {
+      const quote = (value: string) => `"${value.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"`;
+      return ['osascript', ['-e', `display notification ${quote(message)} with title ${quote(title)}`]];
+    }
+    default:
+      return null;
+  }
+}
+
+/**
+ * Show a desktop notification. Resolves once the notifier exits; failures are only logged at debug level.
+ */
+export async function sendNotification(
+  title: string,
+  message: string,
+  urgency: NotificationUrgency = 'normal'
+): Promise<void> {
+  const command = notifierCommand(title, message, urgency);
+  if (!command) {
+    logger.debug(`Desktop notifications are not supported on ${process.platform}`);
+    return;
+  }
+
+  const [program, args] = command;
+  try {
+    const result = await exec({ program, args }, { timeoutSecs: 5, discardOutput: true });
+    if (result.exitCode !== 0) {
+      logger.debug(`${program} exited with code ${result.exitCode}`);
+    }
+  } catch (error) {
+    logger.debug(`Could not send notification with ${program}: ${error instanceof Error ? error.message : error}`);
+  }
+}
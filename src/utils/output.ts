@@ -0,0 +1,90 @@
+/**
+ * Command output in the format selected by the global `--format` flag.
+ *
+ * Command data goes to stdout; diagnostics (the logger) go to stderr, so
+ * `--format json` output can be piped into other tools.
+ */
+
+import chalk from 'chalk';
+import type { Command } from 'commander';
+
+/**
+ * Supported output formats.
+ */
+export const OUTPUT_FORMATS = ['text', 'json', 'table'] as const;
+export type OutputFormat = (typeof OUTPUT_FORMATS)[number];
+
+/**
+ * Tabular view of command data.
+ */
+export interface TableView {
+  columns: string[];
+  rows: (string | number | null)[][];
+}
+
+let currentFormat: OutputFormat = 'text';
+
+/**
+ * The format for a command declaring a `--format` of its own. Global options are parsed
+ * anywhere on the command line, so `--format` after the command name lands in the global
+ * option: an explicitly given global value wins over the command's own (`-f`) value.
+ */
+export function getCommandFormat(command: Command, own: string | undefined): string | undefined {
+  let root = command;
+  while (root.parent) {
+    root = root.parent;
+  }
+  return root.getOptionValueSource('format') === 'default' ? own : root.opts().format;
+}
+
+/**
+ * Select the output format for the current process.
+ * @throws Error If the format is unknown
+ */
+export function setOutputFormat(format: string): void {
+  if (!(OUTPUT_FORMATS as readonly string[]).includes(format)) {
+    throw new Error(`Unknown output format "${format}" (expected ${OUTPUT_FORMATS.join(', ')})`);
+  }
+  currentFormat = format as OutputFormat;
+}
+
+/**
+ * Get the selected output format.
+ */
+export function getOutputFormat(): OutputFormat {
+  return currentFormat;
+}
+
+/**
+ * Write command data to stdout in the selected format.
+ * @param data Machine-readable data (used for json)
+ * @param text Human-readable renderer (used for text; falls back to the table)
+ * @param table Tabular view (used for table; falls back to text)
+ */
+export function emit(data: unknown, text?: () => void, table?: () => TableView): void {
+  if (currentFormat === 'json') {
+    console.log(JSON.stringify(data, null, 2));
+  } else if ((currentFormat === 'table' || !text) && table) {
+    printTable(table());
+  } else if (text) {
+    text();
+  } else {
+    console.log(JSON.stringify(data, null, 2));
+  }
+}
+
+/**
+ * Print a table with padded columns.
+ */
+export function printTable(view: TableView): void {
+  const cells = view.rows.map(row => row.map(cell => (cell === null ? '' : String(cell))));
+  const widths = view.columns.map((column, index) =>
+    Math.max(column.length, ...cells.map(row => (row[index] || '').length))
+  );
+
+  console.log(chalk.bold(view.columns.map((column, index) => column.padEnd(widths[index] + 2)).join('').trimEnd()));
+  console.log('─'.repeat(widths.reduce((sum, width) => sum + width + 2, 0)));
+  for (const row of cells) {
+    console.log(row.map((cell, index) => cell.padEnd(widths[index] + 2)).join('').trimEnd());
+  }
+}
@@ -0,0 +1,110 @@
+/**
+ * Finding and stopping processes left behind in a workspace.
+ *
+ * Agents (and verification commands) can start background processes, e.g. a dev server
+ * launched with `&`, that outlive the command that started them. They are found by
+ * their working directory: any process whose cwd is the workspace or below it. On Linux
+ * that is read from /proc; on macOS it comes from `lsof`. Windows does not expose
+ * other processes' working directories, so nothing is found there.
+ */
+
+import { readdir, readFile, readlink, realpath } from 'fs/promises';
+import { sep } from 'path';
+import { exec, findExecutable, KILL_GRACE_MS } from './exec.js';
+import { logger } from './logger.js';
+
+/**
+ * A process found running in a workspace.
+ */
+export interface WorkspaceProcess {
+  pid: number;
+  /** Command line (or program name when the command line is unavailable) */
+  command: string;
+}
+
+/**
+ * Processes whose working directory is `dir` or below it (never this process or its parent).
+ */
+export async function findProcessesIn(dir: string): Promise<WorkspaceProcess[]> {
+  const root = await realpath(dir).catch(() => dir);
+  const inside = (cwd: string) => cwd === root || cwd.startsWith(root.endsWith(sep) ? root : root + sep);
+  const found = process.platform === 'linux'
+    ? await listLinux(inside)
+    : process.platform === 'darwin'
+      ? await listLsof(inside)
+      : [];
+  return found.filter(entry => entry.pid !== process.pid && entry.pid !== process.ppid);
+}
+
+async function listLinux(inside: (cwd: string) => boolean): Promise<WorkspaceProcess[]> {
+  const found: WorkspaceProcess[] = [];
+  for (const entry of await readdir('/proc')) {
+    if (!/^\d+$/.test(entry)) {
+      continue;
+    }
+    try {
+      // Fails for other users' processes and processes that already exited
+      if (!inside(await readlink(`/proc/${entry}/cwd`))) {
+        continue;
+      }
+      const cmdline = (await readFile(`/proc/${entry}/cmdline`, 'utf-8')).split('\0').filter(Boolean).join(' ');
+      const command = cmdline || (await readFile(`/proc/${entry}/comm`, 'utf-8')).trim();
+      found.push({ pid: Number(entry), command });
+    } catch {
+      continue;
+    }
+  }
+  return found;
+}
+
+async function listLsof(inside: (cwd: string) => boolean): Promise<WorkspaceProcess[]> {
+  const lsof = findExecutable('lsof');
+  if (!lsof) {
+    logger.debug('lsof not found; cannot look for processes left in the workspace');
+    return [];
+  }
+  // One record per process: p<pid>, c<command>, then n<cwd>
+  const result = await exec(
+    { program: lsof, args: ['-w', '-a', '-d', 'cwd', '-F', 'pcn'] },
+    { timeoutSecs: 30, maxOutputBytes: Infinity }
+  );
+  const found: WorkspaceProcess[] = [];
+  let pid = 0;
+  let command = '';
+  for (const line of result.stdout.split('\n')) {
+    const value = line.slice(1);
+    if (line.startsWith('p')) {
+      pid = Number(value);
+      command = '';
+    } else if (line.startsWith('c')) {
+      command = value;
+    } else if (line.startsWith('n') && pid && inside(value)) {
+      found.push({ pid, command });
+    }
+  }
+  return found;
+}
+
+/**
+ * Send SIGTERM to the processes, then SIGKILL to those still running after a grace period.
+ */
+export async function terminateProcesses(pids: number[]): Promise<void> {
+  const signal = (pid: number, name: NodeJS.Signals | 0): boolean => {
+    try {
+      process.kill(pid, name);
+      return true;
+    } catch {
+      return false; // Already gone
+    }
+  };
+
+  let alive = pids.filter(pid => signal(pid, 'SIGTERM'));
+  const deadline = Date.now() + KILL_GRACE_MS;
+  while (alive.length > 0 && Date.now() < deadline) {
+    await new Promise(resolve => setTimeout(resolve, 100));
+    alive = alive.filter(pid => signal(pid, 0));
+  }
+  for (const pid of alive) {
+    signal(pid, 'SIGKILL');
+  }
+}
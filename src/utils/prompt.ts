@@ -0,0 +1,25 @@
+/**
+ * Interactive prompts (written to stderr so stdout stays machine-readable).
+ */
+
+import { createInterface } from 'readline/promises';
+
+/**
+ * Whether prompts can be answered (stdin is a terminal).
+ */
+export function isInteractive(): boolean {
+  return Boolean(process.stdin.isTTY);
+}
+
+/**
+ * Ask a yes/no question; anything other than y/yes counts as no.
+ */
+export async function confirm(question: string): Promise<boolean> {
+  const rl = createInterface({ input: process.stdin, output: process.stderr });
+  try {
+    const answer = await rl.question(`${question} [y/N] `);
+    return ['y', 'yes'].includes(answer.trim().toLowerCase());
+  } finally {
+    rl.close();
+  }
+}
@@ -0,0 +1,80 @@
+/**
+ * Directory copies that share file data with the source where the filesystem allows it.
+ *
+ * On copy-on-write filesystems (Btrfs, XFS with reflink, APFS, ZFS with block cloning)
+ * files are reflinked: the copy is near-instant and takes no extra space until one side
+ * changes. Elsewhere files are copied byte for byte.
+ */
+
+import { constants } from 'fs';
+import { chmod, copyFile, lstat, mkdir, mkdtemp, readdir, readlink, rm, symlink, utimes, writeFile } from 'fs/promises';
+import { dirname, join } from 'path';
+
+/**
+ * How a tree was copied.
+ */
+export type CopyMethod = 'reflink' | 'copy';
+
+/**
+ * Whether reflinks work in each probed directory.
+ */
+const reflinkSupport = new Map<string, boolean>();
+
+/**
+ * Whether files in `dir` can be reflinked to other files in `dir`.
+ */
+export async function supportsReflink(dir: string): Promise<boolean> {
+  const cached = reflinkSupport.get(dir);
+  if (cached !== undefined) {
+    return cached;
+  }
+
+  let supported = false;
+  await mkdir(dir, { recursive: true });
+  const probe = await mkdtemp(join(dir, '.reflink-'));
+  try {
+    await writeFile(join(probe, 'source'), 'reflink probe');
+    await copyFile(join(probe, 'source'), join(probe, 'copy'), constants.COPYFILE_FICLONE_FORCE);
+    supported = true;
+  } catch {
+    supported = false;
+  } finally {
+    await rm(probe, { recursive: true, force: true });
+  }
+  reflinkSupport.set(dir, supported);
+  return supported;
+}
+
+/**
+ * Copy a directory tree into `target` (created if missing), keeping symlinks, file modes and
+ * modification times. Files are reflinked when the filesystem `target` is on supports it.
+ * @returns How the files were copied
+ */
+export async function copyTree(source: string, target: string): Promise<CopyMethod> {
+  await mkdir(target, { recursive: true });
+  const method: CopyMethod = (await supportsReflink(dirname(target))) ? 'reflink' : 'copy';
+  const mode = method === 'reflink' ? constants.COPYFILE_FICLONE : 0;
+
+  const walk = async (from: string, to: string): Promise<void> => {
+    for (const entry of await readdir(from, { withFileTypes: true })) {
+      const fromPath = join(from, entry.name);
+      const toPath = join(to, entry.name);
+      if (entry.isDirectory()) {
+        await mkdir(toPath);
+        await walk(fromPath, toPath);
+        // After the contents, so read-only directories can still be filled
+        await chmod(toPath, (await lstat(fromPath)).mode);
+      } else if (entry.isSymbolicLink()) {
+        await symlink(await readlink(fromPath), toPath);
+      } else if (entry.isFile()) {
+        // copyFile keeps the mode; build tools compare mtimes, so keep those too
+        await copyFile(fromPath, toPath, mode);
+        const info = await lstat(fromPath);
+        await utimes(toPath, info.atime, info.mtime);
+      }
+    }
+  };
+
+  await walk(source, target);
+  return method;
+}
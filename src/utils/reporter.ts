@@ -0,0 +1,36 @@
+/**
+ * Console reporter: renders run events as the familiar per-task and suite output.
+ */
+
+import type { RunEvent } from '../core/events.js';
+import { logger } from './logger.js';
+
+/**
+ * Event listener printing task headers, task results, and the suite summary to stderr.
+ */
+export function consoleReporter(event: RunEvent): void {
+  switch (event.type) {
+    case 'task_started':
+      logger.taskHeader(event.task_id, event.title);
+      break;
+    case 'task_finished': {
+      const { result } = event;
+      if (result.outcome === 'skipped') {
+        logger.taskSkipped(result.skip_reason || 'unknown');
+        break;
+      }
+      logger.taskResult(
+        result.success,
+        result.score,
+        result.iterations,
+        result.duration_secs,
+        result.tokens_used || undefined
+      );
+      logger.durationComparison(result.duration_secs, result.expected_duration_secs, result.human_baseline_secs);
+      break;
+    }
+    case 'suite_finished':
+      logger.suiteSummary(event.suite);
+      break;
+  }
+}
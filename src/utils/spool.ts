@@ -0,0 +1,125 @@
+/**
+ * Bounded capture of subprocess output.
+ *
+ * Output is kept in memory up to a limit. Past it, everything is written to a spool file
+ * in a per-process temp directory, and only the first and last parts stay in memory as
+ * an excerpt. A test suite printing hundreds of megabytes therefore costs disk, not heap.
+ * Callers that keep the full output copy the spool file and then remove it with
+ * `disposeSpoolFiles`; any left over are removed when the process exits.
+ */
+
+import { closeSync, mkdtempSync, openSync, rmSync, writeSync } from 'fs';
+import { rm } from 'fs/promises';
+import { tmpdir } from 'os';
+import { join } from 'path';
+
+/**
+ * Output kept in memory per stream before it is spooled to disk.
+ */
+export const SPOOL_MEMORY_BYTES = 1024 * 1024;
+
+let spoolDir: string | null = null;
+let spoolCount = 0;
+
+/**
+ * The directory holding this process's spool files (created on first use, removed on exit).
+ */
+function getSpoolDir(): string {
+  if (!spoolDir) {
+    const dir = mkdtempSync(join(tmpdir(), 'agent-bench-spool-'));
+    process.once('exit', () => rmSync(dir, { recursive: true, force: true }));
+    spoolDir = dir;
+  }
+  return spoolDir;
+}
+
+/**
+ * Remove spool files once their content has been copied (null entries are skipped).
+ */
+export async function disposeSpoolFiles(...paths: (string | null | undefined)[]): Promise<void> {
+  await Promise.all(paths.map(path => (path ? rm(path, { force: true }) : undefined)));
+}
+
+/**
+ * One output stream of a process.
+ */
+export class OutputSpool {
+  private readonly half: number;
+  private readonly limit: number;
+  private content = '';
+  private head = '';
+  private tail = '';
+  private fd: number | null = null;
+  private file: string | null = null;
+  private size = 0;
+  private closed = false;
+
+  /**
+   * @param limit Bytes kept in memory (half from the start, half from the end once spooled)
+   */
+  constructor(limit: number = SPOOL_MEMORY_BYTES) {
+    this.limit = limit;
+    this.half = Math.floor(limit / 2);
+  }
+
+  /**
+   * Append a chunk.
+   */
+  write(chunk: string): void {
+    if (this.closed) {
+      return;
+    }
+    this.size += Buffer.byteLength(chunk);
+    if (this.fd === null && this.size <= this.limit) {
+      this.content += chunk;
+      return;
+    }
+
+    if (this.fd === null) {
+      this.file = join(getSpoolDir(), `output-${++spoolCount}.log`);
+      this.fd = openSync(this.file, 'w');
+      writeSync(this.fd, this.content);
+      this.head = this.content.slice(0, this.half);
+      this.tail = this.content.slice(this.half);
+      this.content = '';
+    }
+    writeSync(this.fd, chunk);
+    this.tail = (this.tail + chunk).slice(-this.half);
+  }
+
+  /**
+   * Stop writing to the spool file; the output stays readable.
+   */
+  close(): void {
+    this.closed = true;
+    if (this.fd !== null) {
+      closeSync(this.fd);
+      this.fd = null;
+    }
+  }
+
+  /**
+   * Total bytes written.
+   */
+  get bytes(): number {
+    return this.size;
+  }
+
+  /**
+   * Spool file with the full output, or null if it all fit in memory.
+   */
+  get path(): string | null {
+    return this.file;
+  }
+
+  /**
+   * The full output if it fit in memory, else its start and end around an omission marker.
+   */
+  get text(): string {
+    if (this.file === null) {
+      return this.content;
+    }
+    const omitted = this.size - Buffer.byteLength(this.head) - Buffer.byteLength(this.tail);
+    return `${this.head}\n[... ${omitted} bytes omitted ...]\n${this.tail}`;
+  }
+}
@@ -0,0 +1,98 @@
+/**
+ * TAP reporter (`run --output tap`).
+ *
+ * Writes task results to stdout as a Test Anything Protocol (version 14) stream while the
+ * run progresses: one test point per task, a YAML diagnostic block for failures, and
+ * `# SKIP` for skipped tasks. Several suites (one per agent) share one stream, so the plan
+ * (`1..N`) comes at the end, written by `end()`. Console output goes to stderr, so TAP
+ * consumers (`prove`, tap-parser) can read stdout directly.
+ */
+
+import yaml from 'js-yaml';
+import type { RunEvent, RunEventListener } from '../core/events.js';
+import { getOutcome, type BenchmarkResult } from '../evaluator/results.js';
+
+/**
+ * A TAP stream over one or more runs.
+ */
+export interface TapReporter {
+  listener: RunEventListener;
+  /** Write the plan; call once every run has finished */
+  end(): void;
+}
+
+/**
+ * Escape a test point description or directive reason.
+ */
+function escapeDescription(value: string): string {
+  return value.replace(/\\/g, '\\\\').replace(/#/g, '\\#').replace(/\r?\n/g, ' ');
+}
+
+/**
+ * Format a result as a test point, with a YAML diagnostic block for failures.
+ * @param number One-based test number
+ */
+export function formatTestPoint(number: number, result: BenchmarkResult): string {
+  const description = escapeDescription(result.task_id);
+  switch (getOutcome(result)) {
+    case 'passed':
+      return `ok ${number} - ${description}`;
+    case 'skipped':
+      return `ok ${number} - ${description} # SKIP ${escapeDescription(result.skip_reason ?? '')}`.trimEnd();
+    case 'failed': {
+      const diagnostics = {
+        message: result.error ?? 'Task failed',
+        severity: 'fail',
+        data: {
+          failure_kind: result.failure_kind,
+          failure_phase: result.failure_phase,
+          score: result.score,
+          iterations: result.iterations,
+          duration_secs: Number(result.duration_secs.toFixed(2)),
+          log: result.log_path,
+        },
+      };
+      const block = yaml.dump(diagnostics, { lineWidth: -1 }).trimEnd().split('\n').map(line => `  ${line}`);
+      return [`not ok ${number} - ${description}`, '  ---', ...block, '  ...'].join('\n');
+    }
+  }
+}
+
+/**
+ * Create a TAP reporter writing to stdout (or `write`).
+ */
+export function createTapReporter(write: (line: string) => void = line => console.log(line)): TapReporter {
+  let count = 0;
+  let started = false;
+
+  const start = () => {
+    if (!started) {
+      write('TAP version 14');
+      started = true;
+    }
+  };
+
+  return {
+    listener: (event: RunEvent) => {
+      switch (event.type) {
+        case 'suite_started':
+          start();
+          write(`# ${event.agent}: ${event.task_ids.length} tasks`);
+          break;
+        case 'task_finished':
+          start();
+          write(formatTestPoint(++count, event.result));
+          break;
+        case 'suite_finished':
+          if (event.suite.regressions.length > 0) {
+            write(`# Regressions: ${event.suite.regressions.join(', ')}`);
+          }
+          break;
+      }
+    },
+    end: () => {
+      start();
+      write(`1..${count}`);
+    },
+  };
+}
@@ -0,0 +1,1161 @@
+/**
+ * Run pipeline integration tests: real task loading, workspaces, state checkpoints and
+ * scoring, with the agent (and mostly the verifier and store) replaced by in-process fakes.
+ */
+
+import { afterEach, beforeEach, describe, expect, test } from 'bun:test';
+import { spawn } from 'child_process';
+import { chmod, mkdir, mkdtemp, rm, symlink, writeFile } from 'fs/promises';
+import { existsSync, readdirSync, readFileSync, statSync } from 'fs';
+import { tmpdir } from 'os';
+import { join } from 'path';
+import yaml from 'js-yaml';
+import { createDefaultConfig, type RunnerConfig } from '../src/core/config.js';
+import { TaskRunner } from '../src/core/runner.js';
+import { TaskLoader } from '../src/core/loader.js';
+import { WorkspaceManager } from '../src/core/workspace.js';
+import { readRunState } from '../src/core/state.js';
+import { applyLockfile, createLockfile, hashTask } from '../src/core/lock.js';
+import { readAuditLog } from '../src/core/audit.js';
+import { readReproManifest } from '../src/core/repro.js';
+import { getOutcome, readSuiteRecord, type SuiteResults } from '../src/evaluator/results.js';
+import { FakeAgent, FakeVerifier, MemoryResultStore } from '../src/testing/fakes.js';
+import { AiderAgent } from '../src/agents/aider.js';
+import { CodexAgent } from '../src/agents/codex.js';
+import { CustomAgent } from '../src/agents/custom.js';
+import { OpenHandsAgent, OPENHANDS_VERSION } from '../src/agents/openhands.js';
+import { GooseAgent } from '../src/agents/goose.js';
+import { ApiAgent } from '../src/agents/api.js';
+import { QwenAgent } from '../src/agents/qwen.js';
+import { PluginAgent, parsePluginAnswer } from '../src/agents/plugin.js';
+import { createAgentFromSpec, formatAgentSpec, parseAgentSpec } from '../src/agents/factory.js';
+import { setAgentProfiles } from '../src/agents/profiles.js';
+import { RecordingAgent, getRecordingKey } from '../src/agents/recording.js';
+import { createGithubReporter } from '../src/utils/github.js';
+import { createTapReporter } from '../src/utils/tap.js';
+import { createWorkerHandler, startWorkerServer } from '../src/server/worker.js';
+import { Coordinator } from '../src/server/coordinator.js';
+import { junitVerifier } from '../src/evaluator/verifiers/junit.js';
+import { exec, quoteCmdArgument } from '../src/utils/exec.js';
+import { createPack, extractPack } from '../src/core/pack.js';
+import { PLUGIN_API_VERSION, runScorerPlugin, runVerifierPlugin, type PluginInput } from '../src/plugins/wasm.js';
+
+let root: string;
+let config: RunnerConfig;
+let previousCacheHome: string | undefined;
+
+beforeEach(async () => {
+  root = await mkdtemp(join(tmpdir(), 'agent-bench-test-'));
+  previousCacheHome = process.env.XDG_CACHE_HOME;
+  process.env.XDG_CACHE_HOME = join(root, 'cache');
+  config = {
+    ...createDefaultConfig(),
+    tasksDir: join(root, 'tasks'),
+    resultsDir: join(root, 'results'),
+    workspaceDir: join(root, 'workspaces'),
+  };
+  await mkdir(config.tasksDir, { recursive: true });
+});
+
+afterEach(async () => {
+  if (previousCacheHome === undefined) {
+    delete process.env.XDG_CACHE_HOME;
+  } else {
+    process.env.XDG_CACHE_HOME = previousCacheHome;
+  }
+  await rm(root, { recursive: true, force: true });
+});
+
+/**
+ * Write a task with an empty source workspace.
+ */
+async function writeTask(id: string, fields: Record<string, unknown> = {}): Promise<void> {
+  const task = {
+    id,
+    title: `Test task ${id}`,
+    category: 'tools',
+    difficulty: 'easy',
+    source: { repository: 'none', commit: 'none' },
+    prompt: 'Create answer.txt containing 42',
+    verification: { type: 'file-assert', assertions: [{ path: 'answer.txt', contains: '42' }] },
+    ...fields,
+  };
+  await writeFile(join(config.tasksDir, `${id}.yaml`), yaml.dump(task));
+}
+
+describe('TaskRunner with fakes', () => {
+  test('scores a passing task and records its workspace changes', async () => {
+    await writeTask('FAKE-001');
+    const agent = new FakeAgent({ files: { 'answer.txt': '42\n' }, result: { iterations: 3, tokensUsed: 1200 } });
+    const verifier = new FakeVerifier((_task, workspace) => existsSync(join(workspace, 'answer.txt')));
+    const store = new MemoryResultStore();
+    const runner = TaskRunner.builder(config).agent(agent).verifier(verifier).store(store).build();
+
+    const result = await runner.runTask('FAKE-001');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.iterations).toBe(3);
+    expect(result.tokens_used).toBe(1200);
+    expect(result.workspace_changes?.added).toEqual(['answer.txt']);
+    expect(agent.calls).toHaveLength(1);
+    expect(verifier.calls).toHaveLength(1);
+    expect(store.results).toEqual([result]);
+
+    const state = await readRunState(config.resultsDir, runner.runId);
+    expect(state.tasks['FAKE-001'].history.map(entry => entry.state)).toEqual([
+      'pending',
+      'preparing',
+      'agent_running',
+      'verifying',
+      'scored',
+    ]);
+    expect(state.tasks['FAKE-001'].outcome).toBe('passed');
+  });
+
+  test('runs the task verification when no verifier is injected', async () => {
+    await writeTask('FAKE-002');
+    const store = new MemoryResultStore();
+
+    const passing = TaskRunner.builder(config).agent(new FakeAgent({ files: { 'answer.txt': '42' } })).store(store).build();
+    expect(getOutcome(await passing.runTask('FAKE-002'))).toBe('passed');
+
+    const failing = TaskRunner.builder(config).agent(new FakeAgent({ files: { 'answer.txt': '41' } })).store(store).build();
+    const result = await failing.runTask('FAKE-002');
+    expect(getOutcome(result)).toBe('failed');
+    expect(result.failure_kind).toBe('verification');
+  });
+
+  test('reports agent errors as agent failures without verifying', async () => {
+    await writeTask('FAKE-003');
+    const verifier = new FakeVerifier();
+    const runner = TaskRunner.builder(config)
+      .agent(new FakeAgent({ error: new Error('model unavailable') }))
+      .verifier(verifier)
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('FAKE-003');
+
+    expect(getOutcome(result)).toBe('failed');
+    expect(result.failure_kind).toBe('agent');
+    expect(result.error).toContain('model unavailable');
+    expect(verifier.calls).toHaveLength(0);
+  });
+
+  test('skips tasks requiring capabilities the agent lacks', async () => {
+    await writeTask('FAKE-004', { requires_tools: ['web'] });
+    const agent = new FakeAgent({ capabilities: ['read', 'write'] });
+    const store = new MemoryResultStore();
+    const runner = TaskRunner.builder(config).agent(agent).verifier(new FakeVerifier()).store(store).build();
+
+    const result = await runner.runTask('FAKE-004');
+
+    expect(getOutcome(result)).toBe('skipped');
+    expect(result.skip_reason).toContain('web');
+    expect(agent.calls).toHaveLength(0);
+    expect(store.results).toHaveLength(0);
+  });
+
+  test('scores multi-step tasks by checkpoints reached', async () => {
+    const step = (path: string) => ({
+      prompt: `Create ${path}`,
+      verification: { type: 'file-assert', assertions: [{ path }] },
+    });
+    await writeTask('FAKE-005', { steps: [step('one.txt'), step('two.txt')] });
+    const runner = TaskRunner.builder(config)
+      .agent(new FakeAgent({ files: { 'one.txt': '1' } }))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('FAKE-005');
+
+    expect(getOutcome(result)).toBe('failed');
+    expect(result.checkpoints_reached).toBe(1);
+    expect(result.total_checkpoints).toBe(2);
+  });
+
+  test('fails tasks whose workspace exceeds the disk quota', async () => {
+    await writeTask('FAKE-008');
+    const verifier = new FakeVerifier();
+    const runner = TaskRunner.builder(config)
+      .configure({ workspaceQuota: 1024 })
+      .agent(new FakeAgent({ files: { 'answer.txt': '42', 'cache.bin': 'x'.repeat(4096) } }))
+      .verifier(verifier)
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('FAKE-008');
+
+    expect(getOutcome(result)).toBe('failed');
+    expect(result.failure_kind).toBe('disk_quota');
+    expect(result.error).toContain('disk quota');
+    expect(verifier.calls).toHaveLength(0);
+  });
+
+  test.skipIf(process.platform === 'win32')('kills and records processes left running in the workspace', async () => {
+    await writeTask('FAKE-009');
+    let pid = 0;
+    const agent = new FakeAgent({
+      files: { 'answer.txt': '42' },
+      act: (_task, workspace) => {
+        const server = spawn('sleep', ['300'], { cwd: workspace, detached: true, stdio: 'ignore' });
+        server.unref();
+        pid = server.pid!;
+      },
+    });
+    const runner = TaskRunner.builder(config).agent(agent).store(new MemoryResultStore()).build();
+
+    const result = await runner.runTask('FAKE-009');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.leaked_processes?.map(entry => entry.pid)).toEqual([pid]);
+    expect(result.leaked_processes?.[0].command).toStartWith('sleep');
+    expect(() => process.kill(pid, 0)).toThrow();
+  });
+
+  test('records the commands a run executes in its audit log', async () => {
+    await writeTask('FAKE-010', { verification: { type: 'command', command: 'git --version' } });
+    const runner = TaskRunner.builder(config).agent(new FakeAgent()).store(new MemoryResultStore()).build();
+
+    const result = await runner.runTask('FAKE-010');
+
+    expect(getOutcome(result)).toBe('passed');
+    const audit = await readAuditLog(config.resultsDir, runner.runId);
+    const verification = audit.find(entry => entry.args.includes('--version'));
+    expect(verification).toMatchObject({ run_id: runner.runId, task_id: 'FAKE-010', exit_code: 0, timed_out: false });
+    expect(verification?.cwd).toContain('FAKE-010');
+  });
+
+  test('spools large verification output to the task\'s verification log', async () => {
+    const command = `"${process.execPath}" -e "process.stdout.write('x'.repeat(3 * 1024 * 1024))"`;
+    await writeTask('FAKE-012', { verification: { type: 'command', command } });
+    const runner = TaskRunner.builder(config).agent(new FakeAgent()).store(new MemoryResultStore()).build();
+
+    const result = await runner.runTask('FAKE-012');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.verification_output!.length).toBeLessThan(2 * 1024 * 1024);
+    expect(result.verification_output).toContain('bytes omitted');
+    expect(result.verification_log).toBe(join(config.resultsDir, runner.runId, 'FAKE-012', 'verification.log'));
+    expect(statSync(result.verification_log!).size).toBeGreaterThan(3 * 1024 * 1024);
+    const spools = readdirSync(tmpdir())
+      .filter(name => name.startsWith('agent-bench-spool-'))
+      .flatMap(name => readdirSync(join(tmpdir(), name)));
+    expect(spools).toEqual([]);
+  });
+
+  test('writes a reproduction manifest with the rendered prompts', async () => {
+    await writeTask('FAKE-011', { prompt: 'Work in {{workspace}}', agent_timeout: 120 });
+    const agent = new FakeAgent({ files: { 'answer.txt': '42' } });
+    const runner = TaskRunner.builder(config).agent(agent).store(new MemoryResultStore()).build();
+
+    await runner.runTask('FAKE-011');
+
+    const manifest = await readReproManifest(config.resultsDir, runner.runId, 'FAKE-011');
+    expect(manifest?.prompts).toEqual(agent.calls[0].prompts);
+    expect(manifest?.prompts[0]).toBe(`Work in ${agent.calls[0].workspace}`);
+    expect(manifest?.agent_timeout_secs).toBe(120);
+    expect(manifest?.task_sha256).toHaveLength(64);
+  });
+
+  test('runs a suite and stops after a failure with fail-fast', async () => {
+    await writeTask('FAKE-006');
+    await writeTask('FAKE-007');
+    const store = new MemoryResultStore();
+    const runner = TaskRunner.builder(config)
+      .configure({ failFast: true })
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(false))
+      .store(store)
+      .build();
+
+    const suite = await runner.runAll();
+
+    expect(suite.total_tasks).toBe(1);
+    expect(suite.failed).toBe(1);
+    expect(store.suites).toEqual([suite]);
+    const state = JSON.parse(readFileSync(join(config.resultsDir, runner.runId, 'state.json'), 'utf-8'));
+    expect(Object.values(state.tasks).map((entry: any) => entry.state).sort()).toEqual(['pending', 'scored']);
+  });
+
+  test('skips suite tasks estimated to overrun the time budget', async () => {
+    await writeTask('FAKE-015', { expected_duration_secs: 7200 });
+    await writeTask('FAKE-016', { expected_duration_secs: 10 });
+    const runner = TaskRunner.builder(config)
+      .configure({ timeBudget: 3600 })
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .build();
+
+    const suite = await runner.runAll();
+
+    expect(suite.results.map(result => [result.task_id, getOutcome(result)])).toEqual([
+      ['FAKE-015', 'skipped'],
+      ['FAKE-016', 'passed'],
+    ]);
+    expect(suite.results[0].skip_reason).toContain('Time budget');
+  });
+
+  test('balances task executions across the configured credentials', async () => {
+    await writeTask('FAKE-017');
+    await writeTask('FAKE-018');
+    await writeTask('FAKE-019');
+    const runner = TaskRunner.builder(config)
+      .configure({
+        agentCredentials: [
+          { name: 'team-a', env: { ANTHROPIC_API_KEY: 'key-a' } },
+          { name: 'team-b', env: { ANTHROPIC_API_KEY: 'key-b' } },
+        ],
+      })
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .build();
+
+    const suite = await runner.runAll();
+
+    expect(suite.results.map(result => result.credential)).toEqual(['team-a', 'team-b', 'team-a']);
+  });
+
+  test('rewrites the suite record after every task', async () => {
+    await writeTask('FAKE-013');
+    await writeTask('FAKE-014');
+    const recorded: (SuiteResults | null)[] = [];
+    const runner = TaskRunner.builder(config)
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .onTaskStart(async () => {
+        recorded.push(await readSuiteRecord(config.resultsDir, runner.runId));
+      })
+      .build();
+
+    const suite = await runner.runAll();
+
+    expect(recorded[0]).toBeNull();
+    expect(recorded[1]).toMatchObject({ total_tasks: 1, passed: 1, complete: false });
+    expect(await readSuiteRecord(config.resultsDir, runner.runId)).toEqual(suite);
+    expect(suite.complete).toBe(true);
+  });
+
+  test('annotates failed tasks and writes a job summary for GitHub Actions', async () => {
+    await writeTask('FAKE-023');
+    await writeTask('FAKE-024');
+    const lines: string[] = [];
+    const summaryPath = join(root, 'summary.md');
+    const runner = TaskRunner.builder(config)
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(task => task.id === 'FAKE-024'))
+      .store(new MemoryResultStore())
+      .on(createGithubReporter({ summaryPath, workspace: root, write: line => lines.push(line) }))
+      .build();
+
+    await runner.runAll();
+
+    expect(lines).toEqual(['::error file=tasks/FAKE-023.yaml,title=FAKE-023 failed::Verification tests failed (verification)']);
+    const summary = readFileSync(summaryPath, 'utf-8');
+    expect(summary).toContain('| 1 | 1 | 0 | 50.0% |');
+    expect(summary).toContain('| `FAKE-023` | verification | Verification tests failed |');
+  });
+
+  test('streams suite results as TAP', async () => {
+    await writeTask('FAKE-025');
+    await writeTask('FAKE-026');
+    await writeTask('FAKE-027', { disabled: true, disabled_reason: 'Needs a GPU' });
+    const lines: string[] = [];
+    const tap = createTapReporter(line => lines.push(line));
+    const runner = TaskRunner.builder(config)
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(task => task.id === 'FAKE-025'))
+      .store(new MemoryResultStore())
+      .on(tap.listener)
+      .build();
+
+    await runner.runAll();
+    tap.end();
+
+    const output = lines.join('\n').split('\n');
+    expect(output.slice(0, 3)).toEqual(['TAP version 14', '# fake: 3 tasks', 'ok 1 - FAKE-025']);
+    expect(output).toContain('not ok 2 - FAKE-026');
+    expect(output).toContain('  message: Verification tests failed');
+    expect(output.slice(-2)).toEqual(['ok 3 - FAKE-027 # SKIP Needs a GPU', '1..3']);
+  });
+
+  test('prices token usage with the configured model prices', async () => {
+    await writeTask('FAKE-021');
+    await writeTask('FAKE-022');
+    const usage = { input: 1_000_000, output: 100_000, cache_read: 2_000_000, cache_write: 0 };
+    const runner = TaskRunner.builder(config)
+      .configure({ pricing: { 'claude-test': { input: 3, output: 15, cacheRead: 0.3, cacheWrite: null } } })
+      .agent(new FakeAgent({ result: { usage, cost: 99, modelName: 'anthropic/claude-test' } }))
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .build();
+
+    const suite = await runner.runAll();
+
+    // 3 + 1.5 + 0.6: the reported cost is only used for unpriced models
+    expect(suite.results[0].cost_usd).toBeCloseTo(5.1);
+    expect(suite.results[0].token_usage).toEqual(usage);
+    expect(suite.cost_usd).toBeCloseTo(10.2);
+  });
+
+  test('records the time spent in each phase', async () => {
+    await writeTask('FAKE-020');
+    const agent = new FakeAgent({ act: () => new Promise(resolve => setTimeout(resolve, 50)) });
+    const runner = TaskRunner.builder(config)
+      .agent(agent)
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .build();
+
+    const { phases } = await runner.runTask('FAKE-020');
+
+    expect(phases?.toolchain_secs).toBeNull();
+    expect(phases?.workspace_secs).toBeGreaterThanOrEqual(0);
+    expect(phases?.agent_secs).toBeGreaterThanOrEqual(0.04);
+    expect(phases?.iteration_secs).toHaveLength(1);
+    expect(phases?.iteration_secs[0]).toBeGreaterThanOrEqual(0.04);
+    expect(phases?.verification_secs).toBeGreaterThanOrEqual(0);
+  });
+
+  test('reports workspace setup and prefetch time apart from task durations', async () => {
+    await writeTask('FAKE-008');
+    const runner = TaskRunner.builder(config)
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .build();
+
+    const suite = await runner.runAll();
+
+    expect(suite.prefetch_secs).not.toBeNull();
+    expect(suite.results[0].setup_secs).toBeGreaterThanOrEqual(0);
+
+    const unprefetched = await TaskRunner.builder(config)
+      .configure({ prefetch: false })
+      .agent(new FakeAgent())
+      .verifier(new FakeVerifier(true))
+      .store(new MemoryResultStore())
+      .build()
+      .runAll();
+    expect(unprefetched.prefetch_secs).toBeNull();
+  });
+});
+
+describe('CLI agents', () => {
+  let previousEnv: { PATH?: string; HOME?: string };
+
+  beforeEach(() => {
+    previousEnv = { PATH: process.env.PATH, HOME: process.env.HOME };
+    process.env.PATH = `${join(root, 'bin')}:${process.env.PATH}`;
+    process.env.HOME = root;
+  });
+
+  afterEach(() => {
+    process.env.PATH = previousEnv.PATH;
+    process.env.HOME = previousEnv.HOME;
+  });
+
+  /**
+   * Put a fake CLI on PATH: answers `--version`, records its arguments, and runs `script`.
+   */
+  async function writeFakeCli(name: string, version: string, script: string): Promise<void> {
+    const path = join(root, 'bin', name);
+    await mkdir(join(root, 'bin'), { recursive: true });
+    await writeFile(path, [
+      '#!/bin/sh',
+      `if [ "$1" = "--version" ]; then echo "${name} ${version}"; exit 0; fi`,
+      `printf '%s\\n' "$@" > '${join(root, `${name}-args.txt`)}'`,
+      script,
+    ].join('\n'));
+    await chmod(path, 0o755);
+  }
+
+  test.skipIf(process.platform === 'win32')('runs aider with flags for the task permissions and parses its usage', async () => {
+    await writeTask('CLI-001', { permissions: { write: true, approval: 'auto' } });
+    await writeFakeCli('aider', '0.86.1', [
+      'echo 42 > answer.txt',
+      'echo "Applied edit to answer.txt"',
+      'echo "Tokens: 2.5k sent, 1k cache hit, 120 received. Cost: \\$0.01 message, \\$0.01 session."',
+      'echo "Tokens: 3k sent, 80 received. Cost: \\$0.02 message, \\$0.03 session."',
+    ].join('\n'));
+    const runner = TaskRunner.builder(config)
+      .agent(new AiderAgent('anthropic/claude-test'))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('CLI-001');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.iterations).toBe(2);
+    expect(result.token_usage).toEqual({ input: 5500, output: 200, cache_read: 1000, cache_write: 0 });
+    expect(result.cost_usd).toBeCloseTo(0.03);
+    expect(result.agent_version).toBe('aider@0.86.1');
+    expect(result.agent_output).toBe('Applied edit to answer.txt');
+    expect(result.workspace_changes?.added).toEqual(['answer.txt']);
+    const args = readFileSync(join(root, 'aider-args.txt'), 'utf-8').split('\n');
+    expect(args).toContain('--yes-always');
+    expect(args).toContain('--no-suggest-shell-commands');
+    expect(args).toContain('--no-auto-commits');
+    expect(args).not.toContain('--dry-run');
+  });
+
+  test.skipIf(process.platform === 'win32')('parses CLI output that outgrew memory from its spool file', async () => {
+    await writeTask('CLI-009', { permissions: { write: true, approval: 'auto' } });
+    const padding = `head -c ${2 * 1024 * 1024} /dev/zero | tr '\\0' x; echo`;
+    await writeFakeCli('aider', '0.86.1', [
+      'echo 42 > answer.txt',
+      padding,
+      'echo "Tokens: 2.5k sent, 120 received. Cost: \\$0.01 message, \\$0.01 session."',
+      padding,
+      'echo "Tokens: 3k sent, 80 received. Cost: \\$0.02 message, \\$0.03 session."',
+    ].join('\n'));
+    const runner = TaskRunner.builder(config)
+      .agent(new AiderAgent('anthropic/claude-test'))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('CLI-009');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.iterations).toBe(2);
+    expect(result.token_usage).toEqual({ input: 5500, output: 200, cache_read: 0, cache_write: 0 });
+  });
+
+  test.skipIf(process.platform === 'win32')('passes CLI agents the API key of their model\'s provider only', async () => {
+    const previousKeys = { ANTHROPIC_API_KEY: process.env.ANTHROPIC_API_KEY, OPENAI_API_KEY: process.env.OPENAI_API_KEY };
+    process.env.ANTHROPIC_API_KEY = 'anthropic-key';
+    process.env.OPENAI_API_KEY = 'openai-key';
+    await writeTask('CLI-008', { permissions: { write: true, approval: 'auto' } });
+    await writeFakeCli('aider', '0.86.1', `printf '%s,%s' "$ANTHROPIC_API_KEY" "$OPENAI_API_KEY" > '${join(root, 'keys.txt')}'`);
+    const runner = TaskRunner.builder(config)
+      .agent(new AiderAgent('anthropic/claude-test'))
+      .store(new MemoryResultStore())
+      .build();
+
+    try {
+      await runner.runTask('CLI-008');
+    } finally {
+      Object.assign(process.env, previousKeys);
+      for (const [name, value] of Object.entries(previousKeys)) {
+        if (value === undefined) {
+          delete process.env[name];
+        }
+      }
+    }
+
+    expect(readFileSync(join(root, 'keys.txt'), 'utf-8')).toBe('anthropic-key,');
+  });
+
+  test.skipIf(process.platform === 'win32')('runs codex in its sandbox mode for the task permissions and parses its events', async () => {
+    await writeTask('CLI-002', { permissions: { write: true, approval: 'auto' } });
+    await writeFakeCli('codex', '0.46.0', [
+      'echo 42 > answer.txt',
+      `echo '{"type":"thread.started","thread_id":"t-1"}'`,
+      `echo '{"type":"item.completed","item":{"id":"item_0","type":"reasoning","text":"Writing the answer"}}'`,
+      `echo '{"type":"item.completed","item":{"id":"item_1","type":"command_execution","command":"echo 42 > answer.txt"}}'`,
+      `echo '{"type":"item.completed","item":{"id":"item_2","type":"agent_message","text":"Wrote answer.txt"}}'`,
+      `echo '{"type":"turn.completed","usage":{"input_tokens":3000,"cached_input_tokens":1000,"output_tokens":150}}'`,
+    ].join('\n'));
+    const runner = TaskRunner.builder(config)
+      .agent(new CodexAgent('openai/gpt-test'))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('CLI-002');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.iterations).toBe(2);
+    expect(result.token_usage).toEqual({ input: 2000, output: 150, cache_read: 1000, cache_write: 0 });
+    expect(result.agent_version).toBe('codex@0.46.0');
+    expect(result.agent_output).toBe('Wrote answer.txt');
+    const args = readFileSync(join(root, 'codex-args.txt'), 'utf-8').split('\n');
+    expect(args.slice(0, 2)).toEqual(['exec', '--json']);
+    expect(args[args.indexOf('--sandbox') + 1]).toBe('workspace-write');
+    expect(args[args.indexOf('--model') + 1]).toBe('gpt-test');
+    expect(args).not.toContain('--dangerously-bypass-approvals-and-sandbox');
+  });
+
+  test.skipIf(process.platform === 'win32')('runs a custom command template with the task prompt and permission flags', async () => {
+    await writeTask('CLI-003', { permissions: { write: true } });
+    await writeFakeCli('mytool', '1.0.0', 'echo 42 > answer.txt\necho Done');
+    const agent = new CustomAgent('anthropic/claude-test', undefined, {
+      command: 'mytool --prompt {prompt} --model={model} {write?--allow-write:--read-only} {network?--online} "{execute?--shell on:--no-shell}"',
+      env: [],
+      capabilities: ['read', 'write'],
+    });
+    const runner = TaskRunner.builder(config).agent(agent).store(new MemoryResultStore()).build();
+
+    const result = await runner.runTask('CLI-003');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.agent_output).toBe('Done');
+    expect(result.agent_version).toBe('mytool@1.0.0');
+    expect(readFileSync(join(root, 'mytool-args.txt'), 'utf-8').split('\n')).toEqual([
+      '--prompt', 'Create answer.txt containing 42', '--model=anthropic/claude-test', '--allow-write', '--no-shell', '',
+    ]);
+  });
+
+  test.skipIf(process.platform === 'win32')('runs openhands in docker and parses its trajectory', async () => {
+    await writeTask('CLI-004', { permissions: { write: true } });
+    const trajectory = [
+      { source: 'user', action: 'message', args: { content: 'Create answer.txt containing 42' } },
+      { source: 'agent', action: 'run', args: { command: 'echo 42 > answer.txt' } },
+      {
+        source: 'agent',
+        action: 'finish',
+        args: { final_thought: 'Created answer.txt' },
+        llm_metrics: {
+          accumulated_cost: 0.02,
+          accumulated_token_usage: { prompt_tokens: 4000, completion_tokens: 100, cache_read_tokens: 1000, cache_write_tokens: 0 },
+        },
+      },
+    ];
+    await writeFakeCli('docker', '27.0.3', [
+      'for arg in "$@"; do case "$arg" in *:/trajectory:rw) scratch="${arg%:/trajectory:rw}";; esac; done',
+      'echo 42 > answer.txt',
+      `echo '${JSON.stringify(trajectory)}' > "$scratch/trajectory.json"`,
+    ].join('\n'));
+    const runner = TaskRunner.builder(config)
+      .agent(new OpenHandsAgent('anthropic/claude-test'))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('CLI-004');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.agent_output).toBe('Created answer.txt');
+    expect(result.iterations).toBe(2);
+    expect(result.token_usage).toEqual({ input: 3000, output: 100, cache_read: 1000, cache_write: 0 });
+    expect(result.cost_usd).toBeCloseTo(0.02);
+    expect(result.agent_version).toBe(`openhands@${OPENHANDS_VERSION}`);
+    const args = readFileSync(join(root, 'docker-args.txt'), 'utf-8').split('\n');
+    expect(args).toContain('LLM_MODEL=anthropic/claude-test');
+    expect(args).toContain('AGENT_ENABLE_CMD=false');
+    expect(args.find(arg => arg.startsWith('SANDBOX_VOLUMES='))).toMatch(/:\/workspace:rw$/);
+  });
+
+  test.skipIf(process.platform === 'win32')('runs goose with the built-in extensions the task permissions allow', async () => {
+    await writeTask('CLI-005', { permissions: { write: true, bash: true, approval: 'ask' } });
+    await writeFakeCli('goose', '1.9.0', [
+      'echo 42 > answer.txt',
+      `echo "$GOOSE_PROVIDER $GOOSE_MODEL $GOOSE_MODE" > '${join(root, 'goose-env.txt')}'`,
+      'echo "Created answer.txt"',
+    ].join('\n'));
+    const runner = TaskRunner.builder(config)
+      .agent(new GooseAgent('anthropic/claude-test'))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('CLI-005');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.agent_output).toBe('Created answer.txt');
+    expect(result.agent_version).toBe('goose@1.9.0');
+    const args = readFileSync(join(root, 'goose-args.txt'), 'utf-8').split('\n');
+    expect(args[args.indexOf('--with-builtin') + 1]).toBe('developer');
+    expect(args).not.toContain('computercontroller');
+    expect(readFileSync(join(root, 'goose-env.txt'), 'utf-8').trim()).toBe('anthropic claude-test approve');
+  });
+
+  test.skipIf(process.platform === 'win32')('runs qwen in the approval mode for the task permissions and parses its report', async () => {
+    await writeTask('CLI-006', { permissions: { write: true, approval: 'auto' } });
+    const report = {
+      response: 'Created answer.txt',
+      stats: { models: { 'qwen3-coder-plus': { api: { totalRequests: 3 }, tokens: { prompt: 5000, candidates: 200, cached: 2000, thoughts: 50 } } } },
+    };
+    await writeFakeCli('qwen', '0.1.0', `echo 42 > answer.txt\necho '${JSON.stringify(report)}'`);
+    const runner = TaskRunner.builder(config)
+      .agent(new QwenAgent('openai/qwen3-coder-plus'))
+      .store(new MemoryResultStore())
+      .build();
+
+    const result = await runner.runTask('CLI-006');
+
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.agent_output).toBe('Created answer.txt');
+    expect(result.iterations).toBe(3);
+    expect(result.token_usage).toEqual({ input: 3000, output: 250, cache_read: 2000, cache_write: 0 });
+    const args = readFileSync(join(root, 'qwen-args.txt'), 'utf-8').split('\n');
+    expect(args[args.indexOf('--approval-mode') + 1]).toBe('auto-edit');
+    expect(args[args.indexOf('--model') + 1]).toBe('qwen3-coder-plus');
+  });
+
+  test.skipIf(process.platform === 'win32')('runs a named agent with its model, flags, and variables', async () => {
+    await writeTask('CLI-007', { permissions: { write: true, approval: 'auto' } });
+    await writeFakeCli('aider', '0.86.1', `echo 42 > answer.txt\necho "$ANTHROPIC_API_KEY" > '${join(root, 'aider-env.txt')}'`);
+    process.env.TEST_OPUS_KEY = 'sk-opus';
+    config.agentProfiles = {
+      'claude-opus': {
+        adapter: 'aider',
+        model: 'anthropic/claude-opus-test',
+        flags: ['--edit-format', 'diff'],
+        env: { ANTHROPIC_API_KEY: '$TEST_OPUS_KEY' },
+        maxIterations: null,
+      },
+    };
+    setAgentProfiles(config.agentProfiles);
+    try {
+      const spec = parseAgentSpec('claude-opus');
+      expect(spec).toEqual({ type: 'aider', model: 'anthropic/claude-opus-test', profile: 'claude-opus' });
+      expect(formatAgentSpec(spec, config.defaultModel)).toBe('claude-opus@anthropic/claude-opus-test');
+      expect(parseAgentSpec('claude-opus@anthropic/claude-haiku-test').model).toBe('anthropic/claude-haiku-test');
+      const runner = TaskRunner.builder(config)
+        .agent(createAgentFromSpec(spec, config.defaultModel))
+        .store(new MemoryResultStore())
+        .build();
+
+      const result = await runner.runTask('CLI-007');
+
+      expect(getOutcome(result)).toBe('passed');
+      expect(result.agent).toBe('claude-opus');
+      expect(result.agent_version).toBe('aider@0.86.1');
+      const args = readFileSync(join(root, 'aider-args.txt'), 'utf-8').split('\n');
+      expect(args[args.indexOf('--model') + 1]).toBe('anthropic/claude-opus-test');
+      expect(args[args.indexOf('--edit-format') + 1]).toBe('diff');
+      expect(readFileSync(join(root, 'aider-env.txt'), 'utf-8').trim()).toBe('sk-opus');
+    } finally {
+      delete process.env.TEST_OPUS_KEY;
+      setAgentProfiles({});
+    }
+  });
+});
+
+describe('Agent plugins', () => {
+  test.skipIf(process.platform === 'win32')('runs the reference plugin over the stdin/stdout protocol', async () => {
+    await writeTask('PLUGIN-001', {
+      permissions: { write: true },
+      verification: { type: 'file-assert', assertions: [{ path: 'NOTES.md', contains: 'Create answer.txt containing 42' }] },
+    });
+    const spec = parseAgentSpec(`plugin:${join(import.meta.dir, '..', 'examples', 'agent-plugin.ts')}@anthropic/claude-test`);
+    expect(spec.type).toBe('plugin');
+    expect(spec.model).toBe('anthropic/claude-test');
+    const agent = new PluginAgent(spec.plugin!, spec.model!);
+    expect(() => agent.capabilities()).toThrow('has not been described yet');
+    const runner = TaskRunner.builder(config).agent(agent).store(new MemoryResultStore()).build();
+
+    const result = await runner.runTask('PLUGIN-001');
+
+    expect(agent.capabilities()).toEqual(['read', 'write']);
+    expect(result.agent).toBe('reference-plugin');
+    const audit = await readAuditLog(config.resultsDir, runner.runId);
+    expect(audit.filter(entry => entry.program.endsWith('agent-plugin.ts'))).toHaveLength(2);
+    expect(getOutcome(result)).toBe('passed');
+    expect(result.agent_output).toBe('Wrote the task to NOTES.md');
+    expect(result.agent_version).toBe('reference-plugin@1.0.0');
+    expect(result.workspace_changes?.added).toEqual(['NOTES.md']);
+  });
+
+  test('takes the last JSON line of a plugin\'s stdout as its answer', () => {
+    const answer = parsePluginAnswer([
+      'thinking...',
+      '{"type": "log"}',
+      '{"type": "result", "output": "done", "iterations": 4, "usage": {"input": 100, "output": 20}, "cost": 0.5}',
+    ].join('\n'));
+    expect(answer).toEqual({ output: 'done', iterations: 4, usage: { input: 100, output: 20, cache_read: 0, cache_write: 0 }, cost: 0.5 });
+    expect(() => parsePluginAnswer('{"type": "error", "message": "no model"}')).toThrow('Plugin failed: no model');
+    expect(() => parsePluginAnswer('no answer')).toThrow('Plugin exited without an answer');
+    expect(() => parsePluginAnswer('{"type": "result", "iterations": -1}')).toThrow('Invalid plugin answer');
+  });
+});
+
+describe('API agent', () => {
+  test('runs its tool loop against an OpenAI-compatible endpoint', async () => {
+    await writeTask('API-001', { permissions: { write: true, bash: true } });
+    const turns = [
+      { tool_calls: [{ id: 'call-1', type: 'function', function: { name: 'write_file', arguments: JSON.stringify({ path: 'answer.txt', content: '42\n' }) } }] },
+      { tool_calls: [{ id: 'call-2', type: 'function', function: { name: 'run_command', arguments: JSON.stringify({ command: 'cat answer.txt' }) } }] },
+      { content: 'Wrote 42 to answer.txt' },
+    ];
+    const requests: { authorization: string | null; body: any }[] = [];
+    const server = Bun.serve({
+      hostname: '127.0.0.1',
+      port: 0,
+      fetch: async request => {
+        requests.push({ authorization: request.headers.get('authorization'), body: await request.json() });
+        return Response.json({
+          choices: [{ message: { role: 'assistant', content: null, ...turns[requests.length - 1] } }],
+          usage: { prompt_tokens: 1000, completion_tokens: 50, prompt_tokens_details: { cached_tokens: 400 } },
+        });
+      },
+    });
+    process.env.LOCAL_BASE_URL = `http://127.0.0.1:${server.port}/v1`;
+    process.env.LOCAL_API_KEY = 'test-key';
+
+    try {
+      const runner = TaskRunner.builder({ ...config, agentEnv: ['LOCAL_*'] })
+        .agent(new ApiAgent('local/test-model'))
+        .store(new MemoryResultStore())
+        .build();
+      const result = await runner.runTask('API-001');
+
+      expect(getOutcome(result)).toBe('passed');
+      expect(result.iterations).toBe(3);
+      expect(result.agent_output).toBe('Wrote 42 to answer.txt');
+      expect(result.token_usage).toEqual({ input: 1800, output: 150, cache_read: 1200, cache_write: 0 });
+      expect(requests[0].authorization).toBe('Bearer test-key');
+      expect(requests[0].body.model).toBe('test-model');
+      expect(requests[0].body.tools.map((tool: any) => tool.function.name)).toEqual(['read_file', 'write_file', 'run_command']);
+      expect(requests[2].body.messages.at(-1)).toEqual({ role: 'tool', tool_call_id: 'call-2', content: 'exit code 0\n42\n' });
+    } finally {
+      delete process.env.LOCAL_BASE_URL;
+      delete process.env.LOCAL_API_KEY;
+      server.stop(true);
+    }
+  });
+});
+
+describe('API agent file tools', () => {
+  test.skipIf(process.platform === 'win32')('do not follow workspace symlinks outside the workspace', async () => {
+    await writeTask('API-002', { permissions: { write: true, bash: true } });
+    await writeFile(join(root, 'secret.txt'), 'secret');
+    const calls = [
+      { name: 'run_command', arguments: { command: `ln -s '${root}' escape` } },
+      { name: 'read_file', arguments: { path: 'escape/secret.txt' } },
+      { name: 'write_file', arguments: { path: 'escape/planted.txt', content: 'x' } },
+      { name: 'write_file', arguments: { path: '..config', content: '42\n' } },
+    ];
+    const turns = [
+      ...calls.map((call, index) => ({
+        tool_calls: [{ id: `call-${index}`, type: 'function', function: { name: call.name, arguments: JSON.stringify(call.arguments) } }],
+      })),
+      { content: 'Done' },
+    ];
+    const requests: any[] = [];
+    const server = Bun.serve({
+      hostname: '127.0.0.1',
+      port: 0,
+      fetch: async request => {
+        requests.push(await request.json());
+        return Response.json({ choices: [{ message: { role: 'assistant', content: null, ...turns[requests.length - 1] } }] });
+      },
+    });
+    process.env.LOCAL_BASE_URL = `http://127.0.0.1:${server.port}/v1`;
+    process.env.LOCAL_API_KEY = 'test-key';
+
+    try {
+      const runner = TaskRunner.builder({ ...config, agentEnv: ['LOCAL_*'] })
+        .agent(new ApiAgent('local/test-model'))
+        .verifier(new FakeVerifier(() => true))
+        .store(new MemoryResultStore())
+        .build();
+      await runner.runTask('API-002');
+
+      expect(requests[2].messages.at(-1).content).toBe('Error: escape/secret.txt is outside the repository');
+      expect(requests[3].messages.at(-1).content).toBe('Error: escape/planted.txt is outside the repository');
+      expect(requests[4].messages.at(-1).content).toBe('Wrote ..config');
+      expect(existsSync(join(root, 'planted.txt'))).toBe(false);
+    } finally {
+      delete process.env.LOCAL_BASE_URL;
+      delete process.env.LOCAL_API_KEY;
+      server.stop(true);
+    }
+  });
+});
+
+describe('Distributed runs', () => {
+  test('dispatches suite tasks to workers and stores their results centrally', async () => {
+    await writeTask('FAKE-028');
+    await writeTask('FAKE-029');
+    await writeTask('FAKE-030');
+    const workers = [1, 2].map(() => startWorkerServer(config, { host: '127.0.0.1', port: 0, token: 'secret' }, {
+      createRunner: workerConfig => TaskRunner.builder(workerConfig)
+        .agent(new FakeAgent({ act: () => new Promise(resolve => setTimeout(resolve, 20)) }))
+        .verifier(new FakeVerifier(task => task.id !== 'FAKE-029'))
+        .store(new MemoryResultStore())
+        .build(),
+    }));
+    const urls = workers.map(worker => `http://127.0.0.1:${worker.port}`);
+    const store = new MemoryResultStore();
+    const coordinator = new Coordinator(config, {
+      workers: [...urls, 'http://127.0.0.1:1'],
+      token: 'secret',
+      pollSecs: 0.01,
+      store,
+    });
+
+    try {
+      const tasks = await new TaskLoader(config.tasksDir).loadAll();
+      const suite = await coordinator.run({ tasks, agent: 'opencode', skipVerify: false, yes: false });
+
+      expect(suite.results.map(result => result.task_id)).toEqual(['FAKE-028', 'FAKE-029', 'FAKE-030']);
+      expect(suite.passed).toBe(2);
+      expect(suite.failed).toBe(1);
+      expect(new Set(suite.results.map(result => result.worker))).toEqual(new Set(urls));
+      expect(suite.results.every(result => result.run_id === coordinator.runId)).toBe(true);
+      expect(store.results).toHaveLength(3);
+    } finally {
+      await coordinator.close();
+      workers.forEach(worker => worker.stop(true));
+    }
+  });
+
+  test('workers refuse tasks that differ from the coordinator\'s copy', async () => {
+    await writeTask('FAKE-032');
+    const workerTasksDir = join(root, 'worker-tasks');
+    await mkdir(workerTasksDir, { recursive: true });
+    await writeFile(join(workerTasksDir, 'FAKE-032.yaml'), readFileSync(join(config.tasksDir, 'FAKE-032.yaml'), 'utf-8').replace('42', '43'));
+    const worker = startWorkerServer({ ...config, tasksDir: workerTasksDir }, { host: '127.0.0.1', port: 0, token: 'secret' }, {
+      createRunner: workerConfig => TaskRunner.builder(workerConfig).agent(new FakeAgent()).store(new MemoryResultStore()).build(),
+    });
+    const coordinator = new Coordinator(config, {
+      workers: [`http://127.0.0.1:${worker.port}`],
+      token: 'secret',
+      pollSecs: 0.01,
+      store: new MemoryResultStore(),
+    });
+
+    try {
+      const tasks = await new TaskLoader(config.tasksDir).loadAll();
+      const suite = await coordinator.run({ tasks, agent: 'opencode', skipVerify: false, yes: false });
+
+      expect(suite.results[0].failure_kind).toBe('setup');
+      expect(suite.results[0].error).toContain('differs from the coordinator\'s copy');
+    } finally {
+      await coordinator.close();
+      worker.stop(true);
+    }
+  });
+
+  test('fails tasks a worker rejects and waits for a busy worker', async () => {
+    await writeTask('FAKE-035');
+    const worker = startWorkerServer(config, { host: '127.0.0.1', port: 0, token: 'secret' }, {
+      createRunner: workerConfig => TaskRunner.builder(workerConfig)
+        .agent(new FakeAgent({ act: () => new Promise(resolve => setTimeout(resolve, 50)) }))
+        .verifier(new FakeVerifier(true))
+        .store(new MemoryResultStore())
+        .build(),
+    });
+    const url = `http://127.0.0.1:${worker.port}`;
+    const coordinator = new Coordinator(config, { workers: [url], token: 'secret', pollSecs: 0.01, store: new MemoryResultStore() });
+
+    try {
+      const tasks = await new TaskLoader(config.tasksDir).loadAll();
+      const rejected = await coordinator.run({ tasks, agent: 'plugin:/tmp/agent', skipVerify: false, yes: false });
+      expect(rejected.results[0].failure_kind).toBe('setup');
+      expect(rejected.results[0].error).toContain('named agent');
+
+      const busy = await fetch(`${url}/api/executions`, {
+        method: 'POST',
+        headers: { Authorization: 'Bearer secret', 'Content-Type': 'application/json' },
+        body: JSON.stringify({ task_id: 'FAKE-035', task_sha256: await hashTask(tasks[0]), agent: 'opencode' }),
+      });
+      expect(busy.status).toBe(202);
+      const suite = await coordinator.run({ tasks, agent: 'opencode', skipVerify: false, yes: false });
+      expect(suite.passed).toBe(1);
+      expect(suite.results[0].worker).toBe(url);
+    } finally {
+      await coordinator.close();
+      worker.stop(true);
+    }
+  });
+
+  test('workers only accept executions with a token and a JSON body', async () => {
+    const post = (token: string | null, headers: Record<string, string>) =>
+      createWorkerHandler(config, { token })(new Request('http://worker/api/executions', {
+        method: 'POST',
+        headers,
+        body: JSON.stringify({ task_id: 'FAKE-031', agent: 'opencode' }),
+      }));
+
+    expect((await post(null, { 'Content-Type': 'application/json' })).status).toBe(401);
+    expect((await post('secret', { 'Content-Type': 'application/json' })).status).toBe(401);
+    expect((await post('secret', { Authorization: 'Bearer secret', 'Content-Type': 'text/plain' })).status).toBe(415);
+  });
+
+  test('workers refuse plugin agents selected by path', async () => {
+    const response = await createWorkerHandler(config, { token: 'secret' })(new Request('http://worker/api/executions', {
+      method: 'POST',
+      headers: { Authorization: 'Bearer secret', 'Content-Type': 'application/json' },
+      body: JSON.stringify({ task_id: 'FAKE-031', task_sha256: '0'.repeat(64), agent: 'plugin:/tmp/agent' }),
+    }));
+
+    expect(response.status).toBe(400);
+    expect(await response.text()).toContain('named agent');
+  });
+});
+
+describe('WorkspaceManager', () => {
+  test('copies repeated workspaces of a task from its template', async () => {
+    await mkdir(join(config.tasksDir, 'project', 'src'), { recursive: true });
+    await writeFile(join(config.tasksDir, 'project', 'src', 'main.txt'), 'original');
+    await writeTask('FAKE-009', { source: { repository: 'project', commit: 'none' } });
+    const task = await new TaskLoader(config.tasksDir).loadById('FAKE-009');
+    const manager = new WorkspaceManager(config.workspaceDir, false, true);
+
+    const first = await manager.create(task);
+    await first.release();
+    // Later workspaces come from the template, not the source directory
+    await writeFile(join(config.tasksDir, 'project', 'src', 'main.txt'), 'changed');
+    const second = await manager.create(task);
+
+    expect(readFileSync(join(second.path, 'src', 'main.txt'), 'utf-8')).toBe('original');
+    expect((await second.changes()).total).toBe(0);
+    await second.release();
+  });
+});
+
+describe('TaskLoader', () => {
+  test('does not read YAML in task asset directories as tasks', async () => {
+    await writeTask('FAKE-010');
+    await mkdir(join(config.tasksDir, 'FAKE-010', 'fixtures'), { recursive: true });
+    await writeFile(join(config.tasksDir, 'FAKE-010', 'fixtures', 'config.yaml'), 'key: value\n');
+    await mkdir(join(config.tasksDir, 'nested', 'hidden_tests'), { recursive: true });
+    await writeFile(join(config.tasksDir, 'nested', 'task.yaml'), yaml.dump({ id: 'FAKE-011' }));
+    await writeFile(join(config.tasksDir, 'nested', 'hidden_tests', 'cases.yml'), '- 1\n');
+
+    const files = await new TaskLoader(config.tasksDir).listTaskFiles();
+
+    expect(files).toEqual([join(config.tasksDir, 'FAKE-010.yaml'), join(config.tasksDir, 'nested', 'task.yaml')]);
+  });
+});
+
+describe('Lockfile', () => {
+  test('detects changes to a task\'s fixtures since lock', async () => {
+    await writeTask('FAKE-012');
+    await mkdir(join(config.tasksDir, 'FAKE-012', 'fixtures'), { recursive: true });
+    await writeFile(join(config.tasksDir, 'FAKE-012', 'fixtures', 'input.txt'), 'one');
+    const loader = new TaskLoader(config.tasksDir);
+    const lockfile = await createLockfile(loader);
+    expect(await applyLockfile(lockfile, await loader.loadAll())).toHaveLength(1);
+
+    await writeFile(join(config.tasksDir, 'FAKE-012', 'fixtures', 'input.txt'), 'two');
+
+    await expect(applyLockfile(lockfile, await loader.loadAll())).rejects.toThrow('FAKE-012');
+  });
+});
+
+describe('WebAssembly plugins', () => {
+  test('stops a plugin that does not return within the timeout', async () => {
+    // Exports the plugin ABI; `verify` loops forever
+    const path = join(root, 'loop.wasm');
+    await writeFile(path, Buffer.from(
+      '0061736d010000000110036000017f60017f017f60027f7f017e0304030001020503010001073504066d656d6f72790200176167656e745f62656e63685f6170695f76657273696f6e000005616c6c6f6300010676657269667900020a1403040041010b040041000b080003400c000b000b',
+      'hex'
+    ));
+    const input: PluginInput = {
+      api_version: PLUGIN_API_VERSION,
+      task: { id: 'FAKE-013', category: 'bug-fix', difficulty: 'easy', tags: [] },
+      verification: { passed: true, exit_code: 0, stdout: '', stderr: '', duration_secs: 0 },
+      agent: { iterations: 1, tokens_used: null, duration_secs: 0 },
+      files: {},
+    };
+
+    await expect(runVerifierPlugin(path, input, 0.5)).rejects.toThrow('did not return from verify within 0.5s');
+    await expect(runScorerPlugin(path, input, 0.5)).rejects.toThrow('does not export "score"');
+  });
+});
+
+describe('JUnit verification', () => {
+  test('only counts a report the command writes, and fails on exit codes not allowed', async () => {
+    const workspace = join(root, 'junit');
+    await mkdir(workspace, { recursive: true });
+    await writeFile(join(workspace, 'report.xml'), '<testsuite><testcase/></testsuite>');
+    const command = `sh -c "echo '<testsuite><testcase/></testsuite>' > report.xml; exit 1"`;
+    await writeTask('FAKE-014', { verification: { type: 'junit', command: 'true', report: 'report.xml' } });
+    await writeTask('FAKE-015', { verification: { type: 'junit', command, report: 'report.xml' } });
+    await writeTask('FAKE-016', { verification: { type: 'junit', command, report: 'report.xml', allowed_exit_codes: [1] } });
+    const loader = new TaskLoader(config.tasksDir);
+
+    expect((await junitVerifier.verify(await loader.loadById('FAKE-014'), workspace)).passed).toBe(false);
+    expect((await junitVerifier.verify(await loader.loadById('FAKE-015'), workspace)).passed).toBe(false);
+    expect((await junitVerifier.verify(await loader.loadById('FAKE-016'), workspace)).passed).toBe(true);
+  });
+});
+
+describe('Agent recordings', () => {
+  test('refuses to replay files outside the workspace or unreadable recordings', async () => {
+    await writeTask('FAKE-017');
+    const task = await new TaskLoader(config.tasksDir).loadById('FAKE-017');
+    const workspace = join(root, 'replay', 'workspace');
+    const dir = join(root, 'recordings');
+    await mkdir(workspace, { recursive: true });
+    await mkdir(dir, { recursive: true });
+    const agent = new RecordingAgent(new FakeAgent(), { mode: 'replay', dir, model: 'test/model' });
+    const path = join(dir, `${getRecordingKey(task, workspace, agent.name(), 'test/model')}.json`);
+
+    await writeFile(path, JSON.stringify({
+      version: 1,
+      key: 'key',
+      recorded_at: new Date().toISOString(),
+      result: { success: true, iterations: 1, tokensUsed: null, durationSecs: 0, output: '', agentVersion: null, modelName: null },
+      files: { 'answer.txt': { content: Buffer.from('42').toString('base64'), mode: 0o644 }, '../escape.txt': { content: '', mode: 0o644 } },
+    }));
+    await expect(agent.execute(task, workspace)).rejects.toThrow('outside the workspace: ../escape.txt');
+    expect(existsSync(join(workspace, 'answer.txt'))).toBe(false);
+    expect(existsSync(join(root, 'replay', 'escape.txt'))).toBe(false);
+
+    await writeFile(path, '{"version": 1,');
+    await expect(agent.execute(task, workspace)).rejects.toThrow('is not valid JSON');
+  });
+
+  test.skipIf(process.platform === 'win32')('does not create directories through workspace symlinks', async () => {
+    await writeTask('FAKE-034');
+    const task = await new TaskLoader(config.tasksDir).loadById('FAKE-034');
+    const workspace = join(root, 'replay', 'workspace');
+    const dir = join(root, 'recordings');
+    await mkdir(workspace, { recursive: true });
+    await mkdir(join(root, 'outside'), { recursive: true });
+    await mkdir(dir, { recursive: true });
+    await symlink(join(root, 'outside'), join(workspace, 'link'));
+    const agent = new RecordingAgent(new FakeAgent(), { mode: 'replay', dir, model: 'test/model' });
+    const path = join(dir, `${getRecordingKey(task, workspace, agent.name(), 'test/model')}.json`);
+    const record = (files: Record<string, unknown>) => writeFile(path, JSON.stringify({
+      version: 1,
+      key: 'key',
+      recorded_at: new Date().toISOString(),
+      result: { success: true, iterations: 1, tokensUsed: null, durationSecs: 0, output: '', agentVersion: null, modelName: null },
+      files,
+    }));
+
+    await record({ 'link/new/answer.txt': { content: Buffer.from('42').toString('base64'), mode: 0o644 } });
+    await expect(agent.execute(task, workspace)).rejects.toThrow('outside the workspace: link/new/answer.txt');
+    expect(existsSync(join(root, 'outside', 'new'))).toBe(false);
+
+    await record({ '..config': { content: Buffer.from('42').toString('base64'), mode: 0o644 } });
+    await agent.execute(task, workspace);
+    expect(readFileSync(join(workspace, '..config'), 'utf-8')).toBe('42');
+  });
+});
+
+describe('Subprocesses', () => {
+  test('escapes batch file arguments twice for cmd.exe', () => {
+    expect(quoteCmdArgument('fix &calc', true)).toBe('^^^"fix^^^ ^^^&calc^^^"');
+    expect(quoteCmdArgument('C:\\Program Files\\agent.cmd')).toBe('^"C:\\Program^ Files\\agent.cmd^"');
+  });
+
+  test.skipIf(process.platform !== 'win32')('passes a prompt through an npm-style shim as one literal argument', async () => {
+    await writeFile(join(root, 'args.js'), 'console.log(JSON.stringify(process.argv.slice(2)))');
+    await writeFile(join(root, 'agent.cmd'), `@"${process.execPath}" "%~dp0args.js" %*\r\n`);
+    const prompt = 'Fix it &calc | more > out.txt; (echo, `x`)';
+
+    const result = await exec({ program: join(root, 'agent.cmd'), args: [prompt] }, { cwd: root });
+
+    expect(JSON.parse(result.stdout)).toEqual([prompt]);
+    expect(existsSync(join(root, 'out.txt'))).toBe(false);
+  });
+});
+
+describe('Task packs', () => {
+  test('packs task files outside dot directories and unpacks only what the manifest lists', async () => {
+    await writeTask('FAKE-033');
+    await mkdir(join(config.tasksDir, '.git'), { recursive: true });
+    await writeFile(join(config.tasksDir, '.git', 'config'), '[core]\n');
+    await writeFile(join(config.tasksDir, '-notes.md'), 'notes\n');
+    const archive = join(root, 'tasks.tar.gz');
+
+    const manifest = await createPack(config.tasksDir, archive, 'test', '1.0.0');
+    expect(manifest.files.map(entry => entry.path)).toEqual(['-notes.md', 'FAKE-033.yaml']);
+    await extractPack(archive, join(root, 'unpacked'));
+    expect(readFileSync(join(root, 'unpacked', '-notes.md'), 'utf-8')).toBe('notes\n');
+
+    // Repack with a file the manifest does not list
+    await writeFile(join(root, 'unpacked', 'extra.yaml'), 'id: EXTRA\n');
+    const tampered = join(root, 'tampered.tar.gz');
+    await exec({ program: 'tar', args: ['-czf', tampered, '-C', join(root, 'unpacked'), '.'] });
+    await expect(extractPack(tampered, join(root, 'rejected'))).rejects.toThrow('extra.yaml: not in the manifest');
+    expect(existsSync(join(root, 'rejected', 'FAKE-033.yaml'))).toBe(false);
+  });
+});